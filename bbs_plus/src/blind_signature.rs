@@ -0,0 +1,637 @@
+//! Requesting a partially blind BBS or BBS+ signature where the signer does not learn some of the messages.
+//!
+//! The requester commits to the messages it wants to hide from the signer using the same bases as the signature,
+//! i.e. `h_0 * blinding + \sum_{i \in C}(h_i*m_i)` for BBS+ and `\sum_{i \in C}(h_i*m_i)` for BBS where `C` is the set
+//! of indices of the committed messages, and proves knowledge of the opening of the commitment using a Schnorr
+//! protocol. The proof is bound to a nonce chosen by the signer so it can't be replayed. The signer verifies
+//! the proof and creates a blind signature over the commitment and the messages it knows. The requester then
+//! unblinds the signature (only for BBS+) and verifies it.
+//!
+//! As the commitment for BBS has no blinding, it is hiding only when at least one of the committed messages is
+//! random, like a secret key or a blinding. To prove predicates about the committed messages, like equality with a
+//! message in another signature or membership in an accumulator, the requester commits to the messages using
+//! `MessageOrBlinding::BlindMessageWithConcreteBlinding` and uses the same blindings in the other protocols. The
+//! responses for the committed messages are available via `get_resp_for_message` and can be checked for equality
+//! with responses in the other proofs, all of which must use the same challenge.
+//!
+//! # Examples
+//!
+//! ```
+//! use blake2::Blake2b512;
+//! use bbs_plus::blind_signature::BlindSignatureRequestProtocol;
+//!
+//! // Requester commits to `committed_messages`, a map of message index -> `MessageOrBlinding` and creates the request
+//! let protocol = BlindSignatureRequestProtocol::init(&mut rng, blinding, committed_messages, &params).unwrap();
+//! let request = protocol.gen_request_with_nonce::<Blake2b512>(&nonce, &params).unwrap();
+//!
+//! // Signer verifies the request and signs the messages it knows along with the commitment
+//! request.verify_with_nonce::<Blake2b512>(&nonce, &params).unwrap();
+//! let blinded_sig = request.sign(&mut rng, uncommitted_messages, &secret_key, &params).unwrap();
+//!
+//! // Requester unblinds and verifies the signature
+//! let sig = request.unblind(blinded_sig, &blinding, &messages, &public_key, &params).unwrap();
+//! ```
+
+use crate::{
+    error::BBSPlusError,
+    proof::MessageOrBlinding,
+    setup::{
+        MultiMessageSignatureParams, PublicKeyG2, SecretKey, SignatureParams23G1, SignatureParamsG1,
+    },
+    signature::SignatureG1,
+    signature_23::Signature23G1,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    rand::RngCore,
+    vec::Vec,
+};
+use digest::Digest;
+use dock_crypto_utils::{misc::rand, serde_utils::*, try_iter::IndexIsOutOfBounds};
+use schnorr_pok::{compute_random_oracle_challenge, SchnorrCommitment, SchnorrResponse};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Bases of the commitment to the hidden messages
+trait CommitmentBases<E: Pairing> {
+    /// Base for the blinding, if any
+    fn blinding_base(&self) -> Option<&E::G1Affine>;
+    fn message_bases(&self) -> &[E::G1Affine];
+
+    /// Bases of the commitment in the order in which the witnesses are passed to the Schnorr protocol
+    fn bases_for(
+        &self,
+        committed_indices: &BTreeSet<usize>,
+    ) -> Result<Vec<E::G1Affine>, BBSPlusError> {
+        let h = self.message_bases();
+        let mut bases = Vec::with_capacity(committed_indices.len() + 1);
+        bases.extend(self.blinding_base().copied());
+        for i in committed_indices {
+            bases.push(*h.get(*i).ok_or(IndexIsOutOfBounds {
+                index: *i,
+                length: h.len(),
+            })?);
+        }
+        Ok(bases)
+    }
+}
+
+impl<E: Pairing> CommitmentBases<E> for SignatureParamsG1<E> {
+    fn blinding_base(&self) -> Option<&E::G1Affine> {
+        Some(&self.h_0)
+    }
+
+    fn message_bases(&self) -> &[E::G1Affine] {
+        &self.h
+    }
+}
+
+impl<E: Pairing> CommitmentBases<E> for SignatureParams23G1<E> {
+    fn blinding_base(&self) -> Option<&E::G1Affine> {
+        None
+    }
+
+    fn message_bases(&self) -> &[E::G1Affine] {
+        &self.h
+    }
+}
+
+macro_rules! impl_blind_signature_request {
+    ( $protocol:ident, $request:ident, $params:ident, $scheme:literal ) => {
+        #[doc = concat!("Protocol run by the requester of a blind ", $scheme, " signature to commit to the hidden messages and prove knowledge of them")]
+        #[serde_as]
+        #[derive(
+            Clone,
+            PartialEq,
+            Eq,
+            Debug,
+            Zeroize,
+            ZeroizeOnDrop,
+            CanonicalSerialize,
+            CanonicalDeserialize,
+            Serialize,
+            Deserialize,
+        )]
+        pub struct $protocol<E: Pairing> {
+            /// Commitment to the hidden messages
+            #[zeroize(skip)]
+            #[serde_as(as = "ArkObjectBytes")]
+            pub commitment: E::G1Affine,
+            /// Indices of the hidden messages
+            #[zeroize(skip)]
+            pub committed_indices: BTreeSet<usize>,
+            pub sc_comm: SchnorrCommitment<E::G1Affine>,
+            #[serde_as(as = "Vec<ArkObjectBytes>")]
+            sc_wits: Vec<E::ScalarField>,
+        }
+
+        #[doc = concat!("Request for a blind ", $scheme, " signature. Contains the commitment to the hidden messages and the proof of knowledge of its opening")]
+        #[serde_as]
+        #[derive(
+            Clone,
+            PartialEq,
+            Eq,
+            Debug,
+            CanonicalSerialize,
+            CanonicalDeserialize,
+            Serialize,
+            Deserialize,
+        )]
+        pub struct $request<E: Pairing> {
+            /// Commitment to the hidden messages
+            #[serde_as(as = "ArkObjectBytes")]
+            pub commitment: E::G1Affine,
+            /// Indices of the hidden messages
+            pub committed_indices: BTreeSet<usize>,
+            #[serde_as(as = "ArkObjectBytes")]
+            pub t: E::G1Affine,
+            pub response: SchnorrResponse<E::G1Affine>,
+        }
+
+        impl<E: Pairing> $protocol<E> {
+            /// Commit to the hidden messages and execute the commit-to-randomness step of the Schnorr protocol.
+            fn new<'a, R: RngCore>(
+                rng: &mut R,
+                blinding: Option<E::ScalarField>,
+                committed_messages: BTreeMap<usize, MessageOrBlinding<'a, E::ScalarField>>,
+                params: &$params<E>,
+            ) -> Result<Self, BBSPlusError> {
+                if committed_messages.is_empty() {
+                    return Err(BBSPlusError::NoMessageToSign);
+                }
+                if committed_messages.len() > params.supported_message_count() {
+                    return Err(BBSPlusError::MessageCountIncompatibleWithSigParams(
+                        committed_messages.len(),
+                        params.supported_message_count(),
+                    ));
+                }
+                let committed_indices = committed_messages.keys().copied().collect();
+                let bases = params.bases_for(&committed_indices)?;
+
+                let mut wits = Vec::with_capacity(bases.len());
+                let mut randomness = Vec::with_capacity(bases.len());
+                if let Some(blinding) = blinding {
+                    wits.push(blinding);
+                    randomness.push(rand(rng));
+                }
+                for (i, m) in committed_messages {
+                    let (message, r) = match m {
+                        MessageOrBlinding::BlindMessageRandomly(message) => (message, rand(rng)),
+                        MessageOrBlinding::BlindMessageWithConcreteBlinding {
+                            message,
+                            blinding,
+                        } => (message, blinding),
+                        MessageOrBlinding::RevealMessage(_) => {
+                            return Err(BBSPlusError::CannotCommitToRevealedMessage(i))
+                        }
+                    };
+                    wits.push(*message);
+                    randomness.push(r);
+                }
+                let commitment = E::G1::msm_unchecked(&bases, &wits).into_affine();
+                let sc_comm = SchnorrCommitment::new(&bases, randomness);
+                Ok(Self {
+                    commitment,
+                    committed_indices,
+                    sc_comm,
+                    sc_wits: wits,
+                })
+            }
+
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                params: &$params<E>,
+                writer: W,
+            ) -> Result<(), BBSPlusError> {
+                compute_challenge_contribution(
+                    &params.bases_for(&self.committed_indices)?,
+                    &self.commitment,
+                    &self.sc_comm.t,
+                    writer,
+                )
+            }
+
+            /// Generate the request using the given challenge. The challenge must include the contribution of this
+            /// protocol and a nonce from the signer.
+            pub fn gen_request(self, challenge: &E::ScalarField) -> Result<$request<E>, BBSPlusError> {
+                let response = self.sc_comm.response(&self.sc_wits, challenge)?;
+                Ok($request {
+                    commitment: self.commitment,
+                    committed_indices: self.committed_indices.clone(),
+                    t: self.sc_comm.t,
+                    response,
+                })
+            }
+
+            /// Generate the request where the challenge is created by hashing the contribution of this protocol and
+            /// the signer's nonce.
+            pub fn gen_request_with_nonce<D: Digest>(
+                self,
+                nonce: &[u8],
+                params: &$params<E>,
+            ) -> Result<$request<E>, BBSPlusError> {
+                let mut challenge_bytes = Vec::new();
+                self.challenge_contribution(params, &mut challenge_bytes)?;
+                challenge_bytes.extend_from_slice(nonce);
+                let challenge = compute_random_oracle_challenge::<E::ScalarField, D>(&challenge_bytes);
+                self.gen_request(&challenge)
+            }
+        }
+
+        impl<E: Pairing> $request<E> {
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                params: &$params<E>,
+                writer: W,
+            ) -> Result<(), BBSPlusError> {
+                compute_challenge_contribution(
+                    &params.bases_for(&self.committed_indices)?,
+                    &self.commitment,
+                    &self.t,
+                    writer,
+                )
+            }
+
+            /// Verify the proof of knowledge of the opening of the commitment. Called by the signer before signing.
+            pub fn verify(
+                &self,
+                challenge: &E::ScalarField,
+                params: &$params<E>,
+            ) -> Result<(), BBSPlusError> {
+                if self.committed_indices.is_empty() {
+                    return Err(BBSPlusError::NoMessageToSign);
+                }
+                let bases = params.bases_for(&self.committed_indices)?;
+                self.response
+                    .is_valid(&bases, &self.commitment, &self.t, challenge)?;
+                Ok(())
+            }
+
+            /// Verify the request where the challenge was created with `gen_request_with_nonce`.
+            pub fn verify_with_nonce<D: Digest>(
+                &self,
+                nonce: &[u8],
+                params: &$params<E>,
+            ) -> Result<(), BBSPlusError> {
+                let mut challenge_bytes = Vec::new();
+                self.challenge_contribution(params, &mut challenge_bytes)?;
+                challenge_bytes.extend_from_slice(nonce);
+                let challenge = compute_random_oracle_challenge::<E::ScalarField, D>(&challenge_bytes);
+                self.verify(&challenge, params)
+            }
+
+            /// Get the response from the Schnorr protocol for the committed message at index `msg_idx`. Used to
+            /// prove equality of the committed message with messages in other proofs.
+            pub fn get_resp_for_message(
+                &self,
+                msg_idx: usize,
+                params: &$params<E>,
+            ) -> Result<&E::ScalarField, BBSPlusError> {
+                let pos = self
+                    .committed_indices
+                    .iter()
+                    .position(|i| *i == msg_idx)
+                    .ok_or(BBSPlusError::InvalidMsgIdxForResponse(msg_idx))?;
+                let offset = params.blinding_base().map_or(0, |_| 1);
+                Ok(self.response.get_response(pos + offset)?)
+            }
+
+            /// Checks that the messages known to the signer don't overlap with the committed ones and together
+            /// they are all the messages supported by the params.
+            fn check_uncommitted_messages(
+                &self,
+                uncommitted_messages: &BTreeMap<usize, &E::ScalarField>,
+                params: &$params<E>,
+            ) -> Result<(), BBSPlusError> {
+                if let Some(i) = uncommitted_messages
+                    .keys()
+                    .find(|i| self.committed_indices.contains(i))
+                {
+                    return Err(BBSPlusError::MessageIndexAlreadyCommitted(*i));
+                }
+                let count = uncommitted_messages.len() + self.committed_indices.len();
+                if count != params.supported_message_count() {
+                    return Err(BBSPlusError::MessageCountIncompatibleWithSigParams(
+                        count,
+                        params.supported_message_count(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_blind_signature_request!(
+    BlindSignatureRequestProtocol,
+    BlindSignatureRequest,
+    SignatureParamsG1,
+    "BBS+"
+);
+impl_blind_signature_request!(
+    BlindSignature23RequestProtocol,
+    BlindSignature23Request,
+    SignatureParams23G1,
+    "BBS"
+);
+
+impl<E: Pairing> BlindSignatureRequestProtocol<E> {
+    /// Initiate the protocol by committing to the given messages as `h_0 * blinding + \sum_{i}(h_i*m_i)`. The
+    /// requester must keep `blinding` to unblind the signature.
+    pub fn init<'a, R: RngCore>(
+        rng: &mut R,
+        blinding: E::ScalarField,
+        committed_messages: BTreeMap<usize, MessageOrBlinding<'a, E::ScalarField>>,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<Self, BBSPlusError> {
+        Self::new(rng, Some(blinding), committed_messages, params)
+    }
+}
+
+impl<E: Pairing> BlindSignature23RequestProtocol<E> {
+    /// Initiate the protocol by committing to the given messages as `\sum_{i}(h_i*m_i)`.
+    pub fn init<'a, R: RngCore>(
+        rng: &mut R,
+        committed_messages: BTreeMap<usize, MessageOrBlinding<'a, E::ScalarField>>,
+        params: &SignatureParams23G1<E>,
+    ) -> Result<Self, BBSPlusError> {
+        Self::new(rng, None, committed_messages, params)
+    }
+}
+
+impl<E: Pairing> BlindSignatureRequest<E> {
+    /// Create a blind signature over the committed messages and `uncommitted_messages`. Assumes that the request
+    /// has been verified.
+    pub fn sign<R: RngCore>(
+        &self,
+        rng: &mut R,
+        uncommitted_messages: BTreeMap<usize, &E::ScalarField>,
+        sk: &SecretKey<E::ScalarField>,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<SignatureG1<E>, BBSPlusError> {
+        self.check_uncommitted_messages(&uncommitted_messages, params)?;
+        SignatureG1::new_with_committed_messages(
+            rng,
+            &self.commitment,
+            uncommitted_messages,
+            sk,
+            params,
+        )
+    }
+
+    /// Unblind the signature using the blinding from the commitment and verify it over all `messages`.
+    pub fn unblind(
+        &self,
+        blinded_sig: SignatureG1<E>,
+        blinding: &E::ScalarField,
+        messages: &[E::ScalarField],
+        pk: &PublicKeyG2<E>,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<SignatureG1<E>, BBSPlusError> {
+        let sig = blinded_sig.unblind(blinding);
+        sig.verify(messages, pk.clone(), params.clone())?;
+        Ok(sig)
+    }
+}
+
+impl<E: Pairing> BlindSignature23Request<E> {
+    /// Create a blind signature over the committed messages and `uncommitted_messages`. Assumes that the request
+    /// has been verified.
+    pub fn sign<R: RngCore>(
+        &self,
+        rng: &mut R,
+        uncommitted_messages: BTreeMap<usize, &E::ScalarField>,
+        sk: &SecretKey<E::ScalarField>,
+        params: &SignatureParams23G1<E>,
+    ) -> Result<Signature23G1<E>, BBSPlusError> {
+        self.check_uncommitted_messages(&uncommitted_messages, params)?;
+        Signature23G1::new_with_committed_messages(
+            rng,
+            &self.commitment,
+            uncommitted_messages,
+            sk,
+            params,
+        )
+    }
+
+    /// BBS signatures don't need unblinding so this only verifies the signature over all `messages`.
+    pub fn unblind(
+        &self,
+        blinded_sig: Signature23G1<E>,
+        messages: &[E::ScalarField],
+        pk: &PublicKeyG2<E>,
+        params: &SignatureParams23G1<E>,
+    ) -> Result<Signature23G1<E>, BBSPlusError> {
+        blinded_sig.verify(messages, pk.clone(), params.clone())?;
+        Ok(blinded_sig)
+    }
+}
+
+fn compute_challenge_contribution<G: AffineRepr, W: Write>(
+    bases: &[G],
+    commitment: &G,
+    t: &G,
+    mut writer: W,
+) -> Result<(), BBSPlusError> {
+    for b in bases {
+        b.serialize_compressed(&mut writer)?;
+    }
+    commitment.serialize_compressed(&mut writer)?;
+    t.serialize_compressed(&mut writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        setup::{KeypairG2, PreparedSignatureParamsG1},
+        test_serialization,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+
+    #[test]
+    fn blind_bbs_plus_signature() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 8;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+        let keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let nonce = b"signer's nonce";
+
+        let committed_indices = [0, 3, 5];
+        let blinding = Fr::rand(&mut rng);
+        let shared_blinding = Fr::rand(&mut rng);
+        let committed_messages = committed_indices
+            .iter()
+            .map(|i| {
+                let m = if *i == 3 {
+                    MessageOrBlinding::blind_message_with(&messages[*i], shared_blinding)
+                } else {
+                    MessageOrBlinding::BlindMessageRandomly(&messages[*i])
+                };
+                (*i, m)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let protocol =
+            BlindSignatureRequestProtocol::init(&mut rng, blinding, committed_messages, &params)
+                .unwrap();
+        assert_eq!(
+            protocol.commitment,
+            params
+                .commit_to_messages(
+                    committed_indices.iter().map(|i| (*i, &messages[*i])),
+                    &blinding
+                )
+                .unwrap()
+        );
+        let request = protocol
+            .gen_request_with_nonce::<Blake2b512>(nonce, &params)
+            .unwrap();
+        test_serialization!(BlindSignatureRequest<Bls12_381>, request);
+
+        request
+            .verify_with_nonce::<Blake2b512>(nonce, &params)
+            .unwrap();
+        // Request is bound to the nonce
+        assert!(request
+            .verify_with_nonce::<Blake2b512>(b"another nonce", &params)
+            .is_err());
+        let mut tampered = request.clone();
+        tampered.committed_indices = [0, 3, 6].into_iter().collect();
+        assert!(tampered
+            .verify_with_nonce::<Blake2b512>(nonce, &params)
+            .is_err());
+
+        // Response for message committed with a known blinding can be linked to other proofs
+        let mut challenge_bytes = vec![];
+        request
+            .challenge_contribution(&params, &mut challenge_bytes)
+            .unwrap();
+        challenge_bytes.extend_from_slice(nonce);
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&challenge_bytes);
+        assert_eq!(
+            *request.get_resp_for_message(3, &params).unwrap(),
+            shared_blinding + messages[3] * challenge
+        );
+        assert!(request.get_resp_for_message(1, &params).is_err());
+
+        let mut uncommitted_messages = (0..message_count as usize)
+            .filter(|i| !committed_indices.contains(i))
+            .map(|i| (i, &messages[i]))
+            .collect::<BTreeMap<_, _>>();
+        let blinded_sig = request
+            .sign(
+                &mut rng,
+                uncommitted_messages.clone(),
+                &keypair.secret_key,
+                &params,
+            )
+            .unwrap();
+        // Blinded signature isn't valid
+        assert!(blinded_sig
+            .verify(
+                &messages,
+                keypair.public_key.clone(),
+                PreparedSignatureParamsG1::from(params.clone())
+            )
+            .is_err());
+        let sig = request
+            .unblind(
+                blinded_sig,
+                &blinding,
+                &messages,
+                &keypair.public_key,
+                &params,
+            )
+            .unwrap();
+        sig.verify(&messages, keypair.public_key.clone(), params.clone())
+            .unwrap();
+
+        // Signer can't sign over the committed messages
+        uncommitted_messages.insert(3, &messages[3]);
+        assert!(matches!(
+            request.sign(&mut rng, uncommitted_messages, &keypair.secret_key, &params),
+            Err(BBSPlusError::MessageIndexAlreadyCommitted(3))
+        ));
+
+        // Revealed messages can't be committed
+        assert!(matches!(
+            BlindSignatureRequestProtocol::init(
+                &mut rng,
+                blinding,
+                [(1, MessageOrBlinding::RevealMessage(&messages[1]))]
+                    .into_iter()
+                    .collect(),
+                &params
+            ),
+            Err(BBSPlusError::CannotCommitToRevealedMessage(1))
+        ));
+    }
+
+    #[test]
+    fn blind_bbs_signature() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 8;
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let params = SignatureParams23G1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+        let keypair =
+            KeypairG2::<Bls12_381>::generate_using_rng_and_bbs23_params(&mut rng, &params);
+        let nonce = b"signer's nonce";
+
+        let committed_indices = [1, 2, 7];
+        let committed_messages = committed_indices
+            .iter()
+            .map(|i| (*i, MessageOrBlinding::BlindMessageRandomly(&messages[*i])))
+            .collect::<BTreeMap<_, _>>();
+        let protocol =
+            BlindSignature23RequestProtocol::init(&mut rng, committed_messages, &params).unwrap();
+        let request = protocol
+            .gen_request_with_nonce::<Blake2b512>(nonce, &params)
+            .unwrap();
+        test_serialization!(BlindSignature23Request<Bls12_381>, request);
+
+        request
+            .verify_with_nonce::<Blake2b512>(nonce, &params)
+            .unwrap();
+        assert!(request
+            .verify_with_nonce::<Blake2b512>(b"another nonce", &params)
+            .is_err());
+
+        let uncommitted_messages = (0..message_count as usize)
+            .filter(|i| !committed_indices.contains(i))
+            .map(|i| (i, &messages[i]))
+            .collect::<BTreeMap<_, _>>();
+        let blinded_sig = request
+            .sign(&mut rng, uncommitted_messages, &keypair.secret_key, &params)
+            .unwrap();
+        let sig = request
+            .unblind(blinded_sig, &messages, &keypair.public_key, &params)
+            .unwrap();
+        sig.verify(&messages, keypair.public_key.clone(), params.clone())
+            .unwrap();
+
+        // Missing messages are rejected
+        let fewer_messages = (0..message_count as usize)
+            .filter(|i| !committed_indices.contains(i) && *i != 6)
+            .map(|i| (i, &messages[i]))
+            .collect::<BTreeMap<_, _>>();
+        assert!(matches!(
+            request.sign(&mut rng, fewer_messages, &keypair.secret_key, &params),
+            Err(BBSPlusError::MessageCountIncompatibleWithSigParams(7, 8))
+        ));
+    }
+}
@@ -0,0 +1,1043 @@
+//! Limited-use tokens with double-spending detection, in the style of compact e-cash from the paper
+//! [Compact E-Cash](https://eprint.iacr.org/2005/060)
+//!
+//! A token is a BBS or BBS+ signature over the holder's identity secret `id`, serial secret `k`, tag secret `t` and
+//! any other messages, at indices [`IDENTITY_IDX`], [`SERIAL_SECRET_IDX`] and [`TAG_SECRET_IDX`] respectively. The
+//! secrets are hidden from the signer by requesting a blind signature, see [`TokenSecrets::committed_messages`].
+//! A token can be shown `limit` times in each epoch, like a day, by using a different counter `j` in `[0, limit)`
+//! in each show. With `x = epoch * 2^32 + j`, each show reveals
+//! - the serial number `S = g * 1/(k + x + 1)`, which is the Dodis-Yampolskiy PRF of `x` under the key `k`,
+//! - the double-spending tag `T = u * id + g * R/(t + x + 1)` where `R` is the tag challenge chosen by the verifier
+//!   and must be unique for each show, e.g. by hashing the verifier's id and a nonce.
+//!
+//! along with a proof of knowledge of the signature that proves that `S` and `T` were created from the hidden
+//! signed secrets and that `j` is less than `limit`. As `S` depends only on `k` and `x`, showing a token more than
+//! `limit` times in an epoch results in a repeated serial number. `x` doesn't depend on `limit` since the verifier
+//! chooses it and it isn't signed, so shows in different epochs never share `x` whatever the limits. The tags of 2 shows with the same serial number
+//! reveal the identity public key `u * id` of the holder, see [`ShowValues::extract_identity`].
+//!
+//! To prove the inverse relation of the tag without revealing `g * 1/(t + x + 1)`, the prover commits to
+//! `a = 1/(t + x + 1)` as `C_a = g * a + h * rho` and proves `C_a * (t + x + 1) = g + h * delta` where
+//! `delta = rho * (t + x + 1)`. The counter is committed as `C_j = g * j + h * r_j` and an OR proof shows that `C_j`
+//! commits to one of `0, 1, ..., limit - 1`, so the proof size is linear in `limit`.
+
+use crate::{
+    error::BBSPlusError,
+    proof::{MessageOrBlinding, PoKOfSignatureG1Proof, PoKOfSignatureG1Protocol},
+    proof_23_cdl::{PoKOfSignature23G1Proof, PoKOfSignature23G1Protocol},
+    setup::{PublicKeyG2, SignatureParams23G1, SignatureParamsG1},
+    signature::SignatureG1,
+    signature_23::Signature23G1,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    rand::RngCore,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use digest::Digest;
+use dock_crypto_utils::{affine_group_element_from_byte_slices, misc::rand, serde_utils::*};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Index of the identity secret in the signed messages
+pub const IDENTITY_IDX: usize = 0;
+/// Index of the serial secret in the signed messages
+pub const SERIAL_SECRET_IDX: usize = 1;
+/// Index of the tag secret in the signed messages
+pub const TAG_SECRET_IDX: usize = 2;
+
+// Positions of the witnesses in the Schnorr responses
+const ID: usize = 0;
+const K: usize = 1;
+const T: usize = 2;
+const J: usize = 3;
+const R_J: usize = 4;
+const A: usize = 5;
+const RHO: usize = 6;
+const DELTA: usize = 7;
+const WITNESS_COUNT: usize = 8;
+
+/// Public parameters used to create the serial numbers, tags and commitments
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct TokenParams<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub g: E::G1Affine,
+    /// Base of the identity public key
+    #[serde_as(as = "ArkObjectBytes")]
+    pub u: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub h: E::G1Affine,
+}
+
+/// Secrets of the token holder which are signed as the messages at [`IDENTITY_IDX`], [`SERIAL_SECRET_IDX`] and
+/// [`TAG_SECRET_IDX`]
+#[serde_as]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Zeroize,
+    ZeroizeOnDrop,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct TokenSecrets<F: PrimeField> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub identity: F,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub serial_secret: F,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub tag_secret: F,
+}
+
+/// Public inputs of a show which the verifier must know
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ShowContext<F: PrimeField> {
+    pub epoch: u64,
+    /// Number of shows allowed per epoch
+    pub limit: u32,
+    /// Chosen by the verifier and must be unique for each show
+    #[serde_as(as = "ArkObjectBytes")]
+    pub tag_challenge: F,
+}
+
+/// Values revealed by the holder in a show
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ShowValues<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub serial: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub tag: E::G1Affine,
+    /// Commitment to the counter
+    #[serde_as(as = "ArkObjectBytes")]
+    pub counter_commitment: E::G1Affine,
+    /// Commitment to the inverse used in the tag
+    #[serde_as(as = "ArkObjectBytes")]
+    pub inverse_commitment: E::G1Affine,
+}
+
+/// Protocol to prove that the show values were created correctly. Used along with a proof of knowledge of the
+/// signature.
+#[serde_as]
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Zeroize,
+    ZeroizeOnDrop,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ShowProtocol<E: Pairing> {
+    #[zeroize(skip)]
+    pub values: ShowValues<E>,
+    /// Commitments to randomness of the Schnorr protocol for each relation
+    #[zeroize(skip)]
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub t: Vec<E::G1Affine>,
+    /// Commitments of the OR proof for the counter, 1 for each possible value
+    #[zeroize(skip)]
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub range_t: Vec<E::G1Affine>,
+    counter: u32,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    witnesses: Vec<E::ScalarField>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    blindings: Vec<E::ScalarField>,
+    /// Simulated challenges of the OR proof. The one at the index of the counter is unused.
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    range_challenges: Vec<E::ScalarField>,
+    /// Simulated responses of the OR proof except at the index of the counter which has the blinding.
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    range_responses: Vec<E::ScalarField>,
+}
+
+/// Proof that the show values were created correctly
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ShowProof<E: Pairing> {
+    pub values: ShowValues<E>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub t: Vec<E::G1Affine>,
+    /// Responses for `id, k, t, j, r_j, a, rho, -delta` in that order
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub responses: Vec<E::ScalarField>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub range_challenges: Vec<E::ScalarField>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub range_responses: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> TokenParams<E> {
+    /// Generate params by hashing a known string
+    pub fn new<D: Digest>(label: &[u8]) -> Self {
+        Self {
+            g: affine_group_element_from_byte_slices!(label, b" : g"),
+            u: affine_group_element_from_byte_slices!(label, b" : u"),
+            h: affine_group_element_from_byte_slices!(label, b" : h"),
+        }
+    }
+
+    /// Public key of the identity secret which is revealed on double-spending
+    pub fn identity_public_key(&self, identity: &E::ScalarField) -> E::G1Affine {
+        self.u.mul_bigint(identity.into_bigint()).into_affine()
+    }
+}
+
+impl<F: PrimeField> TokenSecrets<F> {
+    /// Create random serial and tag secrets for the given identity secret
+    pub fn new<R: RngCore>(rng: &mut R, identity: F) -> Self {
+        Self {
+            identity,
+            serial_secret: F::rand(rng),
+            tag_secret: F::rand(rng),
+        }
+    }
+
+    /// The secrets as messages to be committed in a request for a blind signature
+    pub fn committed_messages(&self) -> BTreeMap<usize, MessageOrBlinding<'_, F>> {
+        BTreeMap::from([
+            (
+                IDENTITY_IDX,
+                MessageOrBlinding::BlindMessageRandomly(&self.identity),
+            ),
+            (
+                SERIAL_SECRET_IDX,
+                MessageOrBlinding::BlindMessageRandomly(&self.serial_secret),
+            ),
+            (
+                TAG_SECRET_IDX,
+                MessageOrBlinding::BlindMessageRandomly(&self.tag_secret),
+            ),
+        ])
+    }
+}
+
+impl<F: PrimeField> ShowContext<F> {
+    pub fn new(epoch: u64, limit: u32, tag_challenge: F) -> Self {
+        Self {
+            epoch,
+            limit,
+            tag_challenge,
+        }
+    }
+
+    /// `1 + epoch * 2^32` so that the PRF input plus 1 is `j + offset`. As the counter `j` is a `u32`, the PRF
+    /// inputs of different epochs are distinct.
+    fn offset(&self) -> F {
+        F::one() + F::from(self.epoch) * F::from(1u64 << 32)
+    }
+
+    fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), BBSPlusError> {
+        self.epoch.serialize_compressed(&mut writer)?;
+        self.limit.serialize_compressed(&mut writer)?;
+        self.tag_challenge.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl<E: Pairing> ShowValues<E> {
+    /// Given the values and tag challenges of 2 shows with the same serial number, return the identity public key
+    /// of the holder.
+    pub fn extract_identity(
+        &self,
+        tag_challenge: &E::ScalarField,
+        other: &Self,
+        other_tag_challenge: &E::ScalarField,
+    ) -> Result<E::G1Affine, BBSPlusError> {
+        if self.serial != other.serial {
+            return Err(BBSPlusError::SerialNumbersDiffer);
+        }
+        // T_1 = u * id + g * R_1 * a and T_2 = u * id + g * R_2 * a so
+        // u * id = (T_1 * R_2 - T_2 * R_1) / (R_2 - R_1)
+        let denom = (*other_tag_challenge - tag_challenge)
+            .inverse()
+            .ok_or(BBSPlusError::SameTagChallengeInShows)?;
+        Ok(E::G1::msm_unchecked(
+            &[self.tag, other.tag],
+            &[*other_tag_challenge * denom, -(*tag_challenge * denom)],
+        )
+        .into_affine())
+    }
+
+    /// For each relation, its bases, positions of its witnesses and the instance
+    #[allow(clippy::type_complexity)]
+    fn relations(
+        &self,
+        token_params: &TokenParams<E>,
+        context: &ShowContext<E::ScalarField>,
+    ) -> Vec<(Vec<E::G1Affine>, Vec<usize>, E::G1Affine)> {
+        let offset = context.offset();
+        let g = token_params.g.into_group();
+        let mut instances = E::G1::normalize_batch(&[
+            g - self.serial * offset,
+            g - self.inverse_commitment * offset,
+            g * context.tag_challenge,
+        ]);
+        let g_r = instances.pop().unwrap();
+        let y_inverse = instances.pop().unwrap();
+        let y_serial = instances.pop().unwrap();
+        vec![
+            // S * k + S * j = g - S * offset
+            (vec![self.serial, self.serial], vec![K, J], y_serial),
+            // C_j = g * j + h * r_j
+            (
+                vec![token_params.g, token_params.h],
+                vec![J, R_J],
+                self.counter_commitment,
+            ),
+            // C_a = g * a + h * rho
+            (
+                vec![token_params.g, token_params.h],
+                vec![A, RHO],
+                self.inverse_commitment,
+            ),
+            // C_a * t + C_a * j + h * -delta = g - C_a * offset
+            (
+                vec![
+                    self.inverse_commitment,
+                    self.inverse_commitment,
+                    token_params.h,
+                ],
+                vec![T, J, DELTA],
+                y_inverse,
+            ),
+            // T = u * id + g * R * a
+            (vec![token_params.u, g_r], vec![ID, A], self.tag),
+        ]
+    }
+
+    /// `C_j - g * i` for each possible counter `i`, each of which is `h * r_j` for the actual counter
+    fn range_instances(&self, token_params: &TokenParams<E>, limit: u32) -> Vec<E::G1Affine> {
+        let c_j = self.counter_commitment.into_group();
+        let instances = (0..limit)
+            .map(|i| c_j - token_params.g * E::ScalarField::from(i))
+            .collect::<Vec<_>>();
+        E::G1::normalize_batch(&instances)
+    }
+
+    fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), BBSPlusError> {
+        self.serial.serialize_compressed(&mut writer)?;
+        self.tag.serialize_compressed(&mut writer)?;
+        self.counter_commitment.serialize_compressed(&mut writer)?;
+        self.inverse_commitment.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl<E: Pairing> ShowProtocol<E> {
+    /// Create the show values for `counter` and commit to the randomness. `secrets_blindings` are the blindings of
+    /// `id`, `k` and `t` which must also be used in the proof of knowledge of the signature.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        secrets: &TokenSecrets<E::ScalarField>,
+        secrets_blindings: [E::ScalarField; 3],
+        counter: u32,
+        token_params: &TokenParams<E>,
+        context: &ShowContext<E::ScalarField>,
+    ) -> Result<Self, BBSPlusError> {
+        if counter >= context.limit {
+            return Err(BBSPlusError::TokenCounterOutOfRange(counter, context.limit));
+        }
+        let j = E::ScalarField::from(counter);
+        let offset = context.offset();
+        let k_inv = (secrets.serial_secret + j + offset)
+            .inverse()
+            .ok_or(BBSPlusError::CannotInvert0)?;
+        let t_plus = secrets.tag_secret + j + offset;
+        let a = t_plus.inverse().ok_or(BBSPlusError::CannotInvert0)?;
+        let r_j = E::ScalarField::rand(rng);
+        let rho = E::ScalarField::rand(rng);
+
+        let g = token_params.g.into_group();
+        let mut values = E::G1::normalize_batch(&[
+            g * k_inv,
+            token_params.u * secrets.identity + g * (context.tag_challenge * a),
+            g * j + token_params.h * r_j,
+            g * a + token_params.h * rho,
+        ]);
+        let values = ShowValues {
+            inverse_commitment: values.pop().unwrap(),
+            counter_commitment: values.pop().unwrap(),
+            tag: values.pop().unwrap(),
+            serial: values.pop().unwrap(),
+        };
+
+        let mut witnesses = vec![E::ScalarField::zero(); WITNESS_COUNT];
+        witnesses[ID] = secrets.identity;
+        witnesses[K] = secrets.serial_secret;
+        witnesses[T] = secrets.tag_secret;
+        witnesses[J] = j;
+        witnesses[R_J] = r_j;
+        witnesses[A] = a;
+        witnesses[RHO] = rho;
+        witnesses[DELTA] = -(rho * t_plus);
+        let mut blindings = (0..WITNESS_COUNT)
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        blindings[ID] = secrets_blindings[0];
+        blindings[K] = secrets_blindings[1];
+        blindings[T] = secrets_blindings[2];
+
+        let t = values
+            .relations(token_params, context)
+            .into_iter()
+            .map(|(bases, pos, _)| {
+                let b = pos.iter().map(|p| blindings[*p]).collect::<Vec<_>>();
+                E::G1::msm_unchecked(&bases, &b)
+            })
+            .collect::<Vec<_>>();
+
+        // OR proof where all but the actual counter's branch is simulated
+        let mut range_challenges = Vec::with_capacity(context.limit as usize);
+        let mut range_responses = Vec::with_capacity(context.limit as usize);
+        let range_t = values
+            .range_instances(token_params, context.limit)
+            .into_iter()
+            .enumerate()
+            .map(|(i, y)| {
+                let z = E::ScalarField::rand(rng);
+                range_responses.push(z);
+                if i == counter as usize {
+                    range_challenges.push(E::ScalarField::zero());
+                    token_params.h * z
+                } else {
+                    let c = E::ScalarField::rand(rng);
+                    range_challenges.push(c);
+                    token_params.h * z - y * c
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            values,
+            t: E::G1::normalize_batch(&t),
+            range_t: E::G1::normalize_batch(&range_t),
+            counter,
+            witnesses,
+            blindings,
+            range_challenges,
+            range_responses,
+        })
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        context: &ShowContext<E::ScalarField>,
+        writer: W,
+    ) -> Result<(), BBSPlusError> {
+        compute_challenge_contribution(&self.values, &self.t, &self.range_t, context, writer)
+    }
+
+    pub fn gen_proof(self, challenge: &E::ScalarField) -> ShowProof<E> {
+        let responses = self
+            .blindings
+            .iter()
+            .zip(self.witnesses.iter())
+            .map(|(b, w)| *b + *w * challenge)
+            .collect();
+        let counter = self.counter as usize;
+        let mut range_challenges = self.range_challenges.clone();
+        let mut range_responses = self.range_responses.clone();
+        range_challenges[counter] = *challenge
+            - range_challenges
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != counter)
+                .map(|(_, c)| *c)
+                .sum::<E::ScalarField>();
+        range_responses[counter] += range_challenges[counter] * self.witnesses[R_J];
+        ShowProof {
+            values: self.values.clone(),
+            t: self.t.clone(),
+            responses,
+            range_challenges,
+            range_responses,
+        }
+    }
+}
+
+impl<E: Pairing> ShowProof<E> {
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        token_params: &TokenParams<E>,
+        context: &ShowContext<E::ScalarField>,
+        writer: W,
+    ) -> Result<(), BBSPlusError> {
+        compute_challenge_contribution(
+            &self.values,
+            &self.t,
+            &self.range_t(token_params, context.limit)?,
+            context,
+            writer,
+        )
+    }
+
+    /// Verify the proof. The responses for `id`, `k` and `t` must be checked for equality with the ones in the proof of
+    /// knowledge of the signature.
+    pub fn verify(
+        &self,
+        challenge: &E::ScalarField,
+        token_params: &TokenParams<E>,
+        context: &ShowContext<E::ScalarField>,
+    ) -> Result<(), BBSPlusError> {
+        if self.responses.len() != WITNESS_COUNT || self.t.len() != 5 {
+            return Err(BBSPlusError::InvalidProof);
+        }
+        if self.values.serial.is_zero() || self.values.tag.is_zero() {
+            return Err(BBSPlusError::InvalidProof);
+        }
+        for ((bases, pos, y), t) in self
+            .values
+            .relations(token_params, context)
+            .into_iter()
+            .zip(self.t.iter())
+        {
+            let mut scalars = pos.iter().map(|p| self.responses[*p]).collect::<Vec<_>>();
+            let mut bases = bases;
+            bases.push(y);
+            scalars.push(-*challenge);
+            if E::G1::msm_unchecked(&bases, &scalars).into_affine() != *t {
+                return Err(BBSPlusError::InvalidProof);
+            }
+        }
+        // The OR proof's commitments are recomputed so its challenge is checked here
+        if self.range_challenges.iter().sum::<E::ScalarField>() != *challenge {
+            return Err(BBSPlusError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Response for the secret at index `idx` of the signed messages
+    pub fn get_resp_for_secret(&self, idx: usize) -> Result<&E::ScalarField, BBSPlusError> {
+        match idx {
+            IDENTITY_IDX => Ok(&self.responses[ID]),
+            SERIAL_SECRET_IDX => Ok(&self.responses[K]),
+            TAG_SECRET_IDX => Ok(&self.responses[T]),
+            _ => Err(BBSPlusError::InvalidMsgIdxForResponse(idx)),
+        }
+    }
+
+    /// Commitments of the OR proof computed from its challenges and responses
+    fn range_t(
+        &self,
+        token_params: &TokenParams<E>,
+        limit: u32,
+    ) -> Result<Vec<E::G1Affine>, BBSPlusError> {
+        if self.range_challenges.len() != limit as usize
+            || self.range_responses.len() != limit as usize
+        {
+            return Err(BBSPlusError::InvalidProof);
+        }
+        let range_t = self
+            .values
+            .range_instances(token_params, limit)
+            .into_iter()
+            .zip(self.range_challenges.iter())
+            .zip(self.range_responses.iter())
+            .map(|((y, c), z)| E::G1::msm_unchecked(&[token_params.h, y], &[*z, -*c]))
+            .collect::<Vec<_>>();
+        Ok(E::G1::normalize_batch(&range_t))
+    }
+}
+
+fn compute_challenge_contribution<E: Pairing, W: Write>(
+    values: &ShowValues<E>,
+    t: &[E::G1Affine],
+    range_t: &[E::G1Affine],
+    context: &ShowContext<E::ScalarField>,
+    mut writer: W,
+) -> Result<(), BBSPlusError> {
+    context.challenge_contribution(&mut writer)?;
+    values.challenge_contribution(&mut writer)?;
+    for p in t.iter().chain(range_t.iter()) {
+        p.serialize_compressed(&mut writer)?;
+    }
+    Ok(())
+}
+
+macro_rules! impl_token_show {
+    ( $protocol:ident, $proof:ident, $sig:ident, $params:ident, $pok_protocol:ident, $pok_proof:ident, $scheme:literal ) => {
+        #[doc = concat!("Protocol to show a token which is a ", $scheme, " signature")]
+        #[derive(
+            Clone,
+            PartialEq,
+            Eq,
+            Debug,
+            Zeroize,
+            ZeroizeOnDrop,
+            CanonicalSerialize,
+            CanonicalDeserialize,
+            Serialize,
+            Deserialize,
+        )]
+        #[serde(bound = "")]
+        pub struct $protocol<E: Pairing> {
+            pub sig_protocol: $pok_protocol<E>,
+            pub show_protocol: ShowProtocol<E>,
+        }
+
+        #[doc = concat!("Proof of a show of a token which is a ", $scheme, " signature")]
+        #[derive(
+            Clone,
+            PartialEq,
+            Eq,
+            Debug,
+            CanonicalSerialize,
+            CanonicalDeserialize,
+            Serialize,
+            Deserialize,
+        )]
+        #[serde(bound = "")]
+        pub struct $proof<E: Pairing> {
+            pub sig_proof: $pok_proof<E>,
+            pub show_proof: ShowProof<E>,
+        }
+
+        impl<E: Pairing> $protocol<E> {
+            /// Initiate the protocol to show the token `signature` using the given `counter`. Like
+            /// the proof of knowledge of signature, each message can be revealed or blinded but the secrets at
+            /// [`IDENTITY_IDX`], [`SERIAL_SECRET_IDX`] and [`TAG_SECRET_IDX`] must not be revealed.
+            pub fn init<'a, MBI, R: RngCore>(
+                rng: &mut R,
+                signature: &$sig<E>,
+                params: &$params<E>,
+                messages_and_blindings: MBI,
+                token_params: &TokenParams<E>,
+                counter: u32,
+                context: &ShowContext<E::ScalarField>,
+            ) -> Result<Self, BBSPlusError>
+            where
+                MBI: IntoIterator<Item = MessageOrBlinding<'a, E::ScalarField>>,
+            {
+                let mut secrets = [None; 3];
+                let mut blindings = [E::ScalarField::zero(); 3];
+                let messages_and_blindings = messages_and_blindings
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, m)| {
+                        if i > TAG_SECRET_IDX {
+                            return Ok(m);
+                        }
+                        let (message, blinding) = match m {
+                            MessageOrBlinding::BlindMessageRandomly(message) => {
+                                (message, rand(rng))
+                            }
+                            MessageOrBlinding::BlindMessageWithConcreteBlinding {
+                                message,
+                                blinding,
+                            } => (message, blinding),
+                            MessageOrBlinding::RevealMessage(_) => {
+                                return Err(BBSPlusError::CannotRevealTokenSecret(i))
+                            }
+                        };
+                        secrets[i] = Some(*message);
+                        blindings[i] = blinding;
+                        Ok(MessageOrBlinding::blind_message_with(message, blinding))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let secrets = match secrets {
+                    [Some(identity), Some(serial_secret), Some(tag_secret)] => TokenSecrets {
+                        identity,
+                        serial_secret,
+                        tag_secret,
+                    },
+                    _ => {
+                        return Err(BBSPlusError::MessageCountIncompatibleWithSigParams(
+                            messages_and_blindings.len(),
+                            TAG_SECRET_IDX + 1,
+                        ))
+                    }
+                };
+                let sig_protocol =
+                    $pok_protocol::init(rng, signature, params, messages_and_blindings)?;
+                let show_protocol =
+                    ShowProtocol::init(rng, &secrets, blindings, counter, token_params, context)?;
+                Ok(Self {
+                    sig_protocol,
+                    show_protocol,
+                })
+            }
+
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                revealed_msgs: &BTreeMap<usize, E::ScalarField>,
+                params: &$params<E>,
+                context: &ShowContext<E::ScalarField>,
+                mut writer: W,
+            ) -> Result<(), BBSPlusError> {
+                self.sig_protocol
+                    .challenge_contribution(revealed_msgs, params, &mut writer)?;
+                self.show_protocol.challenge_contribution(context, writer)
+            }
+
+            pub fn gen_proof(self, challenge: &E::ScalarField) -> Result<$proof<E>, BBSPlusError> {
+                let show_proof = self.show_protocol.clone().gen_proof(challenge);
+                let sig_proof = self.sig_protocol.clone().gen_proof(challenge)?;
+                Ok($proof {
+                    sig_proof,
+                    show_proof,
+                })
+            }
+        }
+
+        impl<E: Pairing> $proof<E> {
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                revealed_msgs: &BTreeMap<usize, E::ScalarField>,
+                params: &$params<E>,
+                token_params: &TokenParams<E>,
+                context: &ShowContext<E::ScalarField>,
+                mut writer: W,
+            ) -> Result<(), BBSPlusError> {
+                self.sig_proof
+                    .challenge_contribution(revealed_msgs, params, &mut writer)?;
+                self.show_proof
+                    .challenge_contribution(token_params, context, writer)
+            }
+
+            /// Verify the proof of knowledge of the signature and the show values. The verifier must also check
+            /// that it hasn't seen the serial number before, else use [`ShowValues::extract_identity`].
+            pub fn verify(
+                &self,
+                revealed_msgs: &BTreeMap<usize, E::ScalarField>,
+                challenge: &E::ScalarField,
+                pk: &PublicKeyG2<E>,
+                params: &$params<E>,
+                token_params: &TokenParams<E>,
+                context: &ShowContext<E::ScalarField>,
+            ) -> Result<(), BBSPlusError> {
+                let revealed_msg_ids = revealed_msgs.keys().copied().collect::<BTreeSet<_>>();
+                for idx in [IDENTITY_IDX, SERIAL_SECRET_IDX, TAG_SECRET_IDX] {
+                    if self
+                        .sig_proof
+                        .get_resp_for_message(idx, &revealed_msg_ids)?
+                        != self.show_proof.get_resp_for_secret(idx)?
+                    {
+                        return Err(BBSPlusError::InvalidProof);
+                    }
+                }
+                self.show_proof.verify(challenge, token_params, context)?;
+                self.sig_proof
+                    .verify(revealed_msgs, challenge, pk.clone(), params.clone())
+            }
+
+            /// Values revealed in this show
+            pub fn values(&self) -> &ShowValues<E> {
+                &self.show_proof.values
+            }
+        }
+    };
+}
+
+impl_token_show!(
+    TokenShowProtocol,
+    TokenShowProof,
+    SignatureG1,
+    SignatureParamsG1,
+    PoKOfSignatureG1Protocol,
+    PoKOfSignatureG1Proof,
+    "BBS+"
+);
+impl_token_show!(
+    Token23ShowProtocol,
+    Token23ShowProof,
+    Signature23G1,
+    SignatureParams23G1,
+    PoKOfSignature23G1Protocol,
+    PoKOfSignature23G1Proof,
+    "BBS"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blind_signature::{BlindSignature23RequestProtocol, BlindSignatureRequestProtocol},
+        setup::KeypairG2,
+        test_serialization,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+    use schnorr_pok::compute_random_oracle_challenge;
+
+    type G1 = <Bls12_381 as Pairing>::G1Affine;
+
+    macro_rules! check_shows {
+        ($rng:ident, $protocol:ident, $proof:ident, $sig:ident, $params:ident, $keypair:ident, $secrets:ident, $messages:ident) => {{
+            let token_params = TokenParams::<Bls12_381>::new::<Blake2b512>(b"test");
+            let limit = 3;
+            // Extra message at index 3 is revealed
+            let revealed_msgs = BTreeMap::from([(3, $messages[3])]);
+            let show = |rng: &mut StdRng, counter: u32, context: &ShowContext<Fr>| {
+                let mbi = $messages.iter().enumerate().map(|(i, m)| {
+                    if revealed_msgs.contains_key(&i) {
+                        MessageOrBlinding::RevealMessage(m)
+                    } else {
+                        MessageOrBlinding::BlindMessageRandomly(m)
+                    }
+                });
+                let protocol =
+                    $protocol::init(rng, &$sig, &$params, mbi, &token_params, counter, context)?;
+                let mut bytes = vec![];
+                protocol
+                    .challenge_contribution(&revealed_msgs, &$params, context, &mut bytes)
+                    .unwrap();
+                let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&bytes);
+                protocol.gen_proof(&challenge)
+            };
+            let verify = |proof: &$proof<Bls12_381>, context: &ShowContext<Fr>| {
+                let mut bytes = vec![];
+                proof.challenge_contribution(
+                    &revealed_msgs,
+                    &$params,
+                    &token_params,
+                    context,
+                    &mut bytes,
+                )?;
+                let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&bytes);
+                proof.verify(
+                    &revealed_msgs,
+                    &challenge,
+                    &$keypair.public_key,
+                    &$params,
+                    &token_params,
+                    context,
+                )
+            };
+
+            // All shows in an epoch have different serial numbers
+            let mut serials = BTreeSet::new();
+            let mut proofs = vec![];
+            for counter in 0..limit {
+                let context = ShowContext::new(1, limit, Fr::rand(&mut $rng));
+                let proof = show(&mut $rng, counter, &context).unwrap();
+                verify(&proof, &context).unwrap();
+                assert!(serials.insert(proof.values().serial.to_string()));
+                proofs.push((proof, context));
+            }
+            let proof_0 = proofs[0].0.clone();
+            test_serialization!($proof<Bls12_381>, proof_0);
+
+            // Counter can't be more than the limit
+            let context = ShowContext::new(1, limit, Fr::rand(&mut $rng));
+            assert!(matches!(
+                show(&mut $rng, limit, &context),
+                Err(BBSPlusError::TokenCounterOutOfRange(3, 3))
+            ));
+
+            // Same counter in another epoch gives a new serial number
+            let context = ShowContext::new(2, limit, Fr::rand(&mut $rng));
+            let proof = show(&mut $rng, 0, &context).unwrap();
+            verify(&proof, &context).unwrap();
+            assert!(!serials.contains(&proof.values().serial.to_string()));
+
+            // The serial number doesn't depend on the limit so contexts with different limits don't give the same
+            // serial number, which would reveal the identity of an honest holder
+            let context_a = ShowContext::new(2, 5, Fr::rand(&mut $rng));
+            let context_b = ShowContext::new(1, 10, Fr::rand(&mut $rng));
+            let proof_a = show(&mut $rng, 0, &context_a).unwrap();
+            let proof_b = show(&mut $rng, 0, &context_b).unwrap();
+            verify(&proof_a, &context_a).unwrap();
+            verify(&proof_b, &context_b).unwrap();
+            assert_ne!(proof_a.values().serial, proof_b.values().serial);
+            let proof_c = show(&mut $rng, 5, &context_b).unwrap();
+            assert_ne!(proof_a.values().serial, proof_c.values().serial);
+            // Same epoch and counter give the same serial number whatever the limit
+            assert_eq!(proofs[0].0.values().serial, proof_b.values().serial);
+
+            // Proof fails with a different context
+            let (proof_1, context_1) = &proofs[1];
+            let mut wrong_context = context_1.clone();
+            wrong_context.epoch = 2;
+            assert!(verify(proof_1, &wrong_context).is_err());
+            let mut wrong_context = context_1.clone();
+            wrong_context.limit = 2;
+            assert!(verify(proof_1, &wrong_context).is_err());
+            let mut wrong_context = context_1.clone();
+            wrong_context.tag_challenge = Fr::rand(&mut $rng);
+            assert!(verify(proof_1, &wrong_context).is_err());
+
+            // Tampered show values are rejected
+            let mut wrong_proof = proof_1.clone();
+            wrong_proof.show_proof.values.tag = G1::rand(&mut $rng);
+            assert!(verify(&wrong_proof, context_1).is_err());
+            let mut wrong_proof = proof_1.clone();
+            wrong_proof.show_proof.values.serial = G1::rand(&mut $rng);
+            assert!(verify(&wrong_proof, context_1).is_err());
+
+            // Double-spending reveals the identity
+            let context_2 = ShowContext::new(1, limit, Fr::rand(&mut $rng));
+            let proof_2 = show(&mut $rng, 1, &context_2).unwrap();
+            verify(&proof_2, &context_2).unwrap();
+            assert_eq!(proof_1.values().serial, proof_2.values().serial);
+            assert_eq!(
+                proof_1
+                    .values()
+                    .extract_identity(
+                        &context_1.tag_challenge,
+                        proof_2.values(),
+                        &context_2.tag_challenge
+                    )
+                    .unwrap(),
+                token_params.identity_public_key(&$secrets.identity)
+            );
+            assert!(matches!(
+                proof_1.values().extract_identity(
+                    &context_1.tag_challenge,
+                    proofs[0].0.values(),
+                    &proofs[0].1.tag_challenge
+                ),
+                Err(BBSPlusError::SerialNumbersDiffer)
+            ));
+
+            // Secrets can't be revealed
+            let mbi = $messages
+                .iter()
+                .map(MessageOrBlinding::RevealMessage)
+                .collect::<Vec<_>>();
+            assert!(matches!(
+                $protocol::init(
+                    &mut $rng,
+                    &$sig,
+                    &$params,
+                    mbi,
+                    &token_params,
+                    0,
+                    &context_2
+                ),
+                Err(BBSPlusError::CannotRevealTokenSecret(0))
+            ));
+        }};
+    }
+
+    #[test]
+    fn bbs_plus_tokens() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, 5);
+        let keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let identity = Fr::rand(&mut rng);
+        let secrets = TokenSecrets::new(&mut rng, identity);
+        let mut messages = vec![secrets.identity, secrets.serial_secret, secrets.tag_secret];
+        messages.push(Fr::rand(&mut rng));
+        messages.push(Fr::rand(&mut rng));
+
+        // Issuance with the secrets hidden from the signer
+        let blinding = Fr::rand(&mut rng);
+        let request = BlindSignatureRequestProtocol::init(
+            &mut rng,
+            blinding,
+            secrets.committed_messages(),
+            &params,
+        )
+        .unwrap()
+        .gen_request_with_nonce::<Blake2b512>(b"nonce", &params)
+        .unwrap();
+        request
+            .verify_with_nonce::<Blake2b512>(b"nonce", &params)
+            .unwrap();
+        let blinded_sig = request
+            .sign(
+                &mut rng,
+                BTreeMap::from([(3, &messages[3]), (4, &messages[4])]),
+                &keypair.secret_key,
+                &params,
+            )
+            .unwrap();
+        let sig = request
+            .unblind(
+                blinded_sig,
+                &blinding,
+                &messages,
+                &keypair.public_key,
+                &params,
+            )
+            .unwrap();
+
+        check_shows!(
+            rng,
+            TokenShowProtocol,
+            TokenShowProof,
+            sig,
+            params,
+            keypair,
+            secrets,
+            messages
+        );
+    }
+
+    #[test]
+    fn bbs_tokens() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = SignatureParams23G1::<Bls12_381>::generate_using_rng(&mut rng, 5);
+        let keypair =
+            KeypairG2::<Bls12_381>::generate_using_rng_and_bbs23_params(&mut rng, &params);
+        let identity = Fr::rand(&mut rng);
+        let secrets = TokenSecrets::new(&mut rng, identity);
+        let mut messages = vec![secrets.identity, secrets.serial_secret, secrets.tag_secret];
+        messages.push(Fr::rand(&mut rng));
+        messages.push(Fr::rand(&mut rng));
+
+        let request =
+            BlindSignature23RequestProtocol::init(&mut rng, secrets.committed_messages(), &params)
+                .unwrap()
+                .gen_request_with_nonce::<Blake2b512>(b"nonce", &params)
+                .unwrap();
+        request
+            .verify_with_nonce::<Blake2b512>(b"nonce", &params)
+            .unwrap();
+        let blinded_sig = request
+            .sign(
+                &mut rng,
+                BTreeMap::from([(3, &messages[3]), (4, &messages[4])]),
+                &keypair.secret_key,
+                &params,
+            )
+            .unwrap();
+        let sig = request
+            .unblind(blinded_sig, &messages, &keypair.public_key, &params)
+            .unwrap();
+
+        check_shows!(
+            rng,
+            Token23ShowProtocol,
+            Token23ShowProof,
+            sig,
+            params,
+            keypair,
+            secrets,
+            messages
+        );
+    }
+}
@@ -1,13 +1,14 @@
 // TODO: At some point this should be replaced with crates anyhow and thiserror but thiserror is no_std compatible at the moment.
 
 use ark_serialize::SerializationError;
-use ark_std::fmt::Debug;
+use ark_std::{fmt::Debug, vec::Vec};
 use dock_crypto_utils::{
     serde_utils::ArkSerializationError,
     try_iter::{IndexIsOutOfBounds, InvalidPair},
 };
 use oblivious_transfer_protocols::{error::OTError, ParticipantId};
 use schnorr_pok::error::SchnorrError;
+use secret_sharing_and_dkg::error::SSError;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -18,6 +19,8 @@ pub enum BBSPlusError {
     /// Signature's `A` is 0
     ZeroSignature,
     InvalidSignature,
+    /// Indices of the signatures that failed verification in a batch
+    InvalidSignatures(Vec<usize>),
     /// Pairing check failed during verification of proof of knowledge of signature
     PairingCheckFailed,
     /// 1st schnorr proof failed during verification of proof of knowledge of signature
@@ -57,6 +60,41 @@ pub enum BBSPlusError {
     AlreadyHaveChallengesFrom(ParticipantId),
     SenderEitherNotReadyForResponseOrAlreadySentIt(ParticipantId),
     ReceiverEitherNotReadyForHashedKeysOrAlreadyVerifiedIt(ParticipantId),
+    /// Key material has fewer bytes than required
+    KeyMaterialTooShort(usize),
+    KeyInfoTooLong(usize),
+    InvalidSecretKey,
+    InvalidPublicKey,
+    /// Expected and found length of the encoded object
+    IncorrectOctetsLength(usize, usize),
+    InvalidProof,
+    SSError(SSError),
+    /// Receiver of the message and the participant that got it
+    MessageNotForParticipant(ParticipantId, ParticipantId),
+    /// Message belongs to a round that the session has already finished
+    UnexpectedMessageFrom(ParticipantId),
+    SessionNotFinished,
+    /// Base OT with this participant has not finished
+    IncompleteBaseOTWith(ParticipantId),
+    /// Multiplication protocol with this participant has not finished
+    IncompleteMultiplicationWith(ParticipantId),
+    /// This participant is present in the output of one phase but not of the other
+    ParticipantSetMismatch(ParticipantId),
+    DuplicateSignatureShareFrom(ParticipantId),
+    ZeroSignatureShareFrom(ParticipantId),
+    MissingPublicKeyShareFor(ParticipantId),
+    /// Number of signature shares and the threshold
+    InsufficientSignatureShares(usize, usize),
+    /// Message to be hidden from the signer was marked as revealed
+    CannotCommitToRevealedMessage(usize),
+    /// Signer was given a message that the requester committed to
+    MessageIndexAlreadyCommitted(usize),
+    /// Counter used in a token show and the limit of shows
+    TokenCounterOutOfRange(u32, u32),
+    /// Secret of the token at this index was marked as revealed
+    CannotRevealTokenSecret(usize),
+    SerialNumbersDiffer,
+    SameTagChallengeInShows,
 }
 
 impl From<SchnorrError> for BBSPlusError {
@@ -88,3 +126,9 @@ impl From<OTError> for BBSPlusError {
         Self::OTError(e)
     }
 }
+
+impl From<SSError> for BBSPlusError {
+    fn from(e: SSError) -> Self {
+        Self::SSError(e)
+    }
+}
@@ -0,0 +1,111 @@
+//! Ciphersuites of the IETF draft and the hashing primitives they use, i.e. `expand_message`, `hash_to_scalar`
+//! and hashing to G1 as per [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html)
+
+use ark_bls12_381::{Fr, G1Affine};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalDeserialize;
+use ark_std::vec::Vec;
+use dock_crypto_utils::hash_to_curve::{hash_to_curve, ExpandMessage, ExpandMsgXmd, ExpandMsgXof};
+use sha2::Sha256;
+use sha3::Shake256;
+
+/// Length of the output of `expand_message` used for hashing to a scalar
+pub const EXPAND_LEN: usize = 48;
+
+/// Length of a serialized scalar
+pub const OCTET_SCALAR_LENGTH: usize = 32;
+
+/// Length of a serialized G1 element
+pub const OCTET_POINT_LENGTH: usize = 48;
+
+/// Length of a serialized G2 element
+pub const OCTET_PUBLIC_KEY_LENGTH: usize = 96;
+
+/// A ciphersuite of the IETF BBS draft. The ciphersuite determines the hash function used in `expand_message`
+/// and the fixed base point `P1`.
+pub trait Ciphersuite {
+    /// The unique identifier of the ciphersuite.
+    const CIPHERSUITE_ID: &'static [u8];
+
+    /// Compressed encoding of the fixed G1 point `P1` which is part of each signature's `B`
+    const P1: [u8; OCTET_POINT_LENGTH];
+
+    /// Variant of `expand_message` of RFC 9380 used by this ciphersuite
+    type Expander: ExpandMessage;
+
+    /// `expand_message` of RFC 9380 with the hash function of this ciphersuite
+    fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        Self::Expander::expand_message(msg, dst, len_in_bytes)
+    }
+
+    /// Identifier of the interface where messages are mapped to scalars by hashing and generators are created by
+    /// hashing to G1. All domain separation tags of the draft are prefixed with this.
+    fn api_id() -> Vec<u8> {
+        [Self::CIPHERSUITE_ID, b"H2G_HM2S_"].concat()
+    }
+
+    fn p1() -> G1Affine {
+        G1Affine::deserialize_compressed(&Self::P1[..]).unwrap()
+    }
+
+    /// Hash to a scalar by interpreting `EXPAND_LEN` bytes of `expand_message` as a big-endian integer and reducing it
+    /// modulo the group order
+    fn hash_to_scalar(msg: &[u8], dst: &[u8]) -> Fr {
+        Fr::from_be_bytes_mod_order(&Self::expand_message(msg, dst, EXPAND_LEN))
+    }
+
+    /// `hash_to_curve` for G1 of RFC 9380 using the simplified SWU map
+    fn hash_to_curve_g1(msg: &[u8], dst: &[u8]) -> G1Affine {
+        hash_to_curve::<G1Affine, Self::Expander>(msg, dst)
+    }
+}
+
+/// Ciphersuite `BLS12-381-SHA-256` which uses `expand_message_xmd` with SHA-256
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bls12381Sha256;
+
+/// Ciphersuite `BLS12-381-SHAKE-256` which uses `expand_message_xof` with SHAKE-256
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bls12381Shake256;
+
+impl Ciphersuite for Bls12381Sha256 {
+    const CIPHERSUITE_ID: &'static [u8] = b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+    const P1: [u8; OCTET_POINT_LENGTH] = [
+        0xa8, 0xce, 0x25, 0x61, 0x02, 0x84, 0x08, 0x21, 0xa3, 0xe9, 0x4e, 0xa9, 0x02, 0x5e, 0x46,
+        0x62, 0xb2, 0x05, 0x76, 0x2f, 0x97, 0x76, 0xb3, 0xa7, 0x66, 0xc8, 0x72, 0xb9, 0x48, 0xf1,
+        0xfd, 0x22, 0x5e, 0x7c, 0x59, 0x69, 0x85, 0x88, 0xe7, 0x0d, 0x11, 0x40, 0x6d, 0x16, 0x1b,
+        0x4e, 0x28, 0xc9,
+    ];
+
+    type Expander = ExpandMsgXmd<Sha256>;
+}
+
+impl Ciphersuite for Bls12381Shake256 {
+    const CIPHERSUITE_ID: &'static [u8] = b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_";
+    const P1: [u8; OCTET_POINT_LENGTH] = [
+        0x89, 0x29, 0xdf, 0xbc, 0x7e, 0x66, 0x42, 0xc4, 0xed, 0x9c, 0xba, 0x08, 0x56, 0xe4, 0x93,
+        0xf8, 0xb9, 0xd7, 0xd5, 0xfc, 0xb0, 0xc3, 0x1e, 0xf8, 0xfd, 0xcd, 0x34, 0xd5, 0x06, 0x48,
+        0xa5, 0x6c, 0x79, 0x5e, 0x10, 0x6e, 0x9e, 0xad, 0xa6, 0xe0, 0xbd, 0xa3, 0x86, 0xb4, 0x14,
+        0x15, 0x07, 0x55,
+    ];
+
+    type Expander = ExpandMsgXof<Shake256>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_serialize::CanonicalSerialize;
+
+    #[test]
+    fn hash_to_curve_vectors() {
+        // Test vector from RFC 9380 appendix J.9.1
+        let p = Bls12381Sha256::hash_to_curve_g1(
+            b"",
+            b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        );
+        let mut bytes = vec![];
+        p.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(hex::encode(bytes), "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a108ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265");
+    }
+}
@@ -0,0 +1,25 @@
+//! BBS signatures as per the IETF draft [The BBS Signature Scheme](https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-signatures/)
+//! with the ciphersuites `BLS12-381-SHA-256` and `BLS12-381-SHAKE-256`.
+//!
+//! Unlike [`signature_23`], messages are octet-strings which are hashed to scalars, the generators are created by
+//! hashing to G1 as per [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html) and keys, signatures and proofs
+//! have the octet encodings defined in the draft so that they interoperate with other implementations of the draft.
+//! Signatures are [`Signature23G1`] over `Bls12_381` and can also be verified with [`Signature23G1::verify`] using
+//! the params from [`signature::signature_params`] and the `domain` as the first message.
+//!
+//! Per-verifier pseudonyms using the ciphersuites of the draft BBS per Verifier Linkability and [`proof_23`] are in
+//! [`pseudonym`].
+//!
+//! [`proof_23`]: crate::proof_23
+//! [`signature_23`]: crate::signature_23
+//! [`Signature23G1`]: crate::signature_23::Signature23G1
+//! [`Signature23G1::verify`]: crate::signature_23::Signature23G1::verify
+
+pub mod ciphersuite;
+pub mod proof;
+pub mod pseudonym;
+pub mod signature;
+
+pub use ciphersuite::{Bls12381Sha256, Bls12381Shake256, Ciphersuite};
+pub use proof::{proof_gen, proof_verify, Proof};
+pub use signature::{keygen, sign, sk_to_pk, verify};
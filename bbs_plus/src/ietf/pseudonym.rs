@@ -0,0 +1,656 @@
+//! Per-verifier pseudonyms in the style of the IETF draft [BBS per Verifier Linkability](https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-per-verifier-linkability/).
+//!
+//! The prover chooses a secret scalar `prover_nym` and gets it signed blindly as the second last message of the
+//! signature by sending a commitment `H_{L+1} * prover_nym + H_{L+2} * secret_prover_blind`, with a proof of
+//! knowledge of the committed values, to the signer. `secret_prover_blind` is a random scalar which hides
+//! `prover_nym` from the signer and is signed as the last message. When presenting to a verifier, the prover outputs
+//! the pseudonym `hash_to_curve_g1(verifier_id) * prover_nym` along with a proof of knowledge of the signature from
+//! [`proof_23`] which also proves that the pseudonym was created using the signed `prover_nym`. Thus presentations
+//! to the same verifier are linkable but presentations to different verifiers are not.
+//!
+//! The generators, hashing and `domain` are those of the draft's ciphersuites with the api id
+//! `ciphersuite_id || "H2G_HM2S_PSEUDONYM_"` but the blind issuance and the proof are not the draft's, so
+//! commitments, signatures and proofs from this module don't interoperate with other implementations of the draft
+//! or with [`signature`] and [`proof`].
+//!
+//! [`proof_23`]: crate::proof_23
+//! [`signature`]: crate::ietf::signature
+//! [`proof`]: crate::ietf::proof
+
+use crate::{
+    error::BBSPlusError,
+    ietf::{
+        ciphersuite::{Ciphersuite, OCTET_POINT_LENGTH, OCTET_SCALAR_LENGTH},
+        signature::{
+            calculate_domain, check_indices, hash_to_scalar_dst, messages_to_scalars,
+            octets_to_point, octets_to_scalar, point_to_octets, scalar_to_octets, signature_params,
+        },
+    },
+    proof::MessageOrBlinding,
+    proof_23::{PoKOfSignature23G1Proof, PoKOfSignature23G1Protocol},
+    setup::{PublicKeyG2, SecretKey},
+    signature_23::Signature23G1,
+};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData,
+    rand::RngCore,
+    vec::Vec,
+    UniformRand,
+};
+
+/// Length of an encoded commitment to `prover_nym` with its proof of knowledge
+pub const OCTET_NYM_COMMITMENT_LENGTH: usize = OCTET_POINT_LENGTH + 3 * OCTET_SCALAR_LENGTH;
+
+/// The ciphersuite `C` with the api id of the pseudonym interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PseudonymCiphersuite<C: Ciphersuite>(PhantomData<C>);
+
+impl<C: Ciphersuite> Ciphersuite for PseudonymCiphersuite<C> {
+    const CIPHERSUITE_ID: &'static [u8] = C::CIPHERSUITE_ID;
+    const P1: [u8; OCTET_POINT_LENGTH] = C::P1;
+
+    type Expander = C::Expander;
+
+    fn api_id() -> Vec<u8> {
+        [Self::CIPHERSUITE_ID, b"H2G_HM2S_PSEUDONYM_"].concat()
+    }
+}
+
+/// Commitment to `prover_nym` and `secret_prover_blind` and the proof of knowledge of the committed values
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct NymCommitment {
+    pub commitment: G1Affine,
+    pub s_nym: Fr,
+    pub s_blind: Fr,
+    pub challenge: Fr,
+}
+
+/// Proof of knowledge of a signature over the messages, `prover_nym` and `secret_prover_blind` which also proves
+/// that the pseudonym is created from the signed `prover_nym`. `T` is the commitment of the Schnorr protocol for the
+/// pseudonym.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PseudonymProof {
+    pub proof: PoKOfSignature23G1Proof<Bls12_381>,
+    pub T: G1Affine,
+    pub challenge: Fr,
+}
+
+/// Commit to `prover_nym` to be signed along with `message_count` messages known to the signer. Returns the
+/// commitment `H_{L+1} * prover_nym + H_{L+2} * secret_prover_blind` and `secret_prover_blind` which the prover
+/// must keep to verify the signature and create proofs.
+pub fn commit<R: RngCore, C: Ciphersuite>(
+    rng: &mut R,
+    prover_nym: &Fr,
+    message_count: usize,
+) -> Result<(NymCommitment, Fr), BBSPlusError> {
+    let (h_nym, h_blind) = nym_generators::<C>(message_count);
+    let secret_prover_blind = Fr::rand(rng);
+    let commitment =
+        G1Projective::msm_unchecked(&[h_nym, h_blind], &[*prover_nym, secret_prover_blind])
+            .into_affine();
+    let nym_tilde = Fr::rand(rng);
+    let blind_tilde = Fr::rand(rng);
+    let t = G1Projective::msm_unchecked(&[h_nym, h_blind], &[nym_tilde, blind_tilde]).into_affine();
+    let challenge = commitment_challenge::<C>(&commitment, &t, &h_nym, &h_blind)?;
+    Ok((
+        NymCommitment {
+            commitment,
+            s_nym: nym_tilde + *prover_nym * challenge,
+            s_blind: blind_tilde + secret_prover_blind * challenge,
+            challenge,
+        },
+        secret_prover_blind,
+    ))
+}
+
+/// Verify the proof of knowledge of the values committed in `commitment`
+pub fn verify_commitment<C: Ciphersuite>(
+    commitment: &NymCommitment,
+    message_count: usize,
+) -> Result<(), BBSPlusError> {
+    let (h_nym, h_blind) = nym_generators::<C>(message_count);
+    // T = H_{L+1} * s_nym + H_{L+2} * s_blind - commitment * challenge
+    let t = G1Projective::msm_unchecked(
+        &[h_nym, h_blind, commitment.commitment],
+        &[commitment.s_nym, commitment.s_blind, -commitment.challenge],
+    )
+    .into_affine();
+    if commitment_challenge::<C>(&commitment.commitment, &t, &h_nym, &h_blind)?
+        != commitment.challenge
+    {
+        return Err(BBSPlusError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// Called by the signer to sign the octet-string messages, an optional header and the `prover_nym` and
+/// `secret_prover_blind` committed in `commitment`. Verifies the proof of knowledge of the committed values before
+/// signing.
+pub fn blind_sign<C: Ciphersuite, M: AsRef<[u8]>>(
+    sk: &SecretKey<Fr>,
+    pk: &PublicKeyG2<Bls12_381>,
+    commitment: &NymCommitment,
+    header: &[u8],
+    messages: &[M],
+) -> Result<Signature23G1<Bls12_381>, BBSPlusError> {
+    verify_commitment::<C>(commitment, messages.len())?;
+    let params = signature_params::<PseudonymCiphersuite<C>>(messages.len() + 2);
+    let domain = calculate_domain::<PseudonymCiphersuite<C>>(pk, &params.h, header)?;
+    let mut msgs = Vec::with_capacity(messages.len() + 1);
+    msgs.push(domain);
+    msgs.append(&mut messages_to_scalars::<PseudonymCiphersuite<C>, _>(
+        messages,
+    ));
+
+    // e = hash_to_scalar(serialize((SK, msg_1, ..., msg_L, commitment, domain)), signature_dst)
+    let mut e_input = scalar_to_octets(&sk.0);
+    for m in &msgs[1..] {
+        e_input.append(&mut scalar_to_octets(m));
+    }
+    e_input.append(&mut point_to_octets(&commitment.commitment)?);
+    e_input.append(&mut scalar_to_octets(&domain));
+    let e = PseudonymCiphersuite::<C>::hash_to_scalar(
+        &e_input,
+        &hash_to_scalar_dst::<PseudonymCiphersuite<C>>(),
+    );
+
+    // B = P1 + Q_1 * domain + \sum_i H_i * msg_i + commitment
+    let b = params.b(msgs.iter().enumerate())? + commitment.commitment;
+    let e_plus_x_inv = (e + sk.0).inverse().ok_or(BBSPlusError::CannotInvert0)?;
+    Ok(Signature23G1 {
+        A: (b * e_plus_x_inv).into_affine(),
+        e,
+    })
+}
+
+/// Called by the prover to verify the signature created with [`blind_sign`] over the messages, `prover_nym` and
+/// `secret_prover_blind`
+pub fn verify<C: Ciphersuite, M: AsRef<[u8]>>(
+    pk: &PublicKeyG2<Bls12_381>,
+    signature: &Signature23G1<Bls12_381>,
+    header: &[u8],
+    messages: &[M],
+    prover_nym: &Fr,
+    secret_prover_blind: &Fr,
+) -> Result<(), BBSPlusError> {
+    let params = signature_params::<PseudonymCiphersuite<C>>(messages.len() + 2);
+    let domain = calculate_domain::<PseudonymCiphersuite<C>>(pk, &params.h, header)?;
+    let mut msgs = Vec::with_capacity(messages.len() + 3);
+    msgs.push(domain);
+    msgs.append(&mut messages_to_scalars::<PseudonymCiphersuite<C>, _>(
+        messages,
+    ));
+    msgs.push(*prover_nym);
+    msgs.push(*secret_prover_blind);
+    signature.verify(&msgs, pk.clone(), params)
+}
+
+/// Create the pseudonym of `prover_nym` for the verifier with id `verifier_id`
+pub fn pseudonym<C: Ciphersuite>(verifier_id: &[u8], prover_nym: &Fr) -> G1Affine {
+    verifier_point::<C>(verifier_id)
+        .mul_bigint(prover_nym.into_bigint())
+        .into_affine()
+}
+
+/// Create a proof of knowledge of `signature` using [`PoKOfSignature23G1Protocol`] where the messages at
+/// `disclosed_indices` (0-based) are disclosed and also prove that the returned pseudonym for `verifier_id` is
+/// created from the signed `prover_nym`. `prover_nym` and `secret_prover_blind` are never disclosed.
+#[allow(clippy::too_many_arguments)]
+pub fn proof_gen<R: RngCore, C: Ciphersuite, M: AsRef<[u8]>>(
+    rng: &mut R,
+    pk: &PublicKeyG2<Bls12_381>,
+    signature: &Signature23G1<Bls12_381>,
+    header: &[u8],
+    ph: &[u8],
+    messages: &[M],
+    prover_nym: &Fr,
+    secret_prover_blind: &Fr,
+    verifier_id: &[u8],
+    disclosed_indices: &BTreeSet<usize>,
+) -> Result<(PseudonymProof, G1Affine), BBSPlusError> {
+    check_indices(disclosed_indices.iter(), messages.len())?;
+    let params = signature_params::<PseudonymCiphersuite<C>>(messages.len() + 2);
+    let domain = calculate_domain::<PseudonymCiphersuite<C>>(pk, &params.h, header)?;
+    let msgs = messages_to_scalars::<PseudonymCiphersuite<C>, _>(messages);
+
+    // The protocol proves knowledge of `r` and of each undisclosed message multiplied by `r` where `r` randomizes
+    // the signature. The pseudonym is proven to be created from `prover_nym` with the relation
+    // `pseudonym * r - OP * (prover_nym * r) = 0` so the blindings of `r` and `prover_nym * r` are chosen here.
+    let r = Fr::rand(rng);
+    let r_tilde = Fr::rand(rng);
+    let nym_tilde = Fr::rand(rng);
+    let protocol = PoKOfSignature23G1Protocol::init(
+        rng,
+        Some(r),
+        Some(r_tilde),
+        signature,
+        &params,
+        [MessageOrBlinding::RevealMessage(&domain)]
+            .into_iter()
+            .chain(msgs.iter().enumerate().map(|(i, m)| {
+                if disclosed_indices.contains(&i) {
+                    MessageOrBlinding::RevealMessage(m)
+                } else {
+                    MessageOrBlinding::BlindMessageRandomly(m)
+                }
+            }))
+            .chain([
+                MessageOrBlinding::blind_message_with(prover_nym, nym_tilde),
+                MessageOrBlinding::BlindMessageRandomly(secret_prover_blind),
+            ]),
+    )?;
+
+    let op = verifier_point::<C>(verifier_id);
+    let pseudonym = op.mul_bigint(prover_nym.into_bigint()).into_affine();
+    // T = pseudonym * r_tilde - OP * nym_tilde
+    let T = G1Projective::msm_unchecked(&[pseudonym, op], &[r_tilde, -nym_tilde]).into_affine();
+
+    let revealed_msgs = revealed_messages(domain, disclosed_indices.iter().map(|i| (*i, msgs[*i])));
+    let mut bytes = Vec::new();
+    protocol.challenge_contribution(&revealed_msgs, &params, &mut bytes)?;
+    let challenge = proof_challenge::<C>(bytes, &pseudonym, &op, &T, ph)?;
+    let proof = protocol.gen_proof(&challenge)?;
+    Ok((
+        PseudonymProof {
+            proof,
+            T,
+            challenge,
+        },
+        pseudonym,
+    ))
+}
+
+/// Verify a proof and pseudonym created with [`proof_gen`]. The total number of messages known to the signer is the
+/// number of disclosed and undisclosed messages, excluding `prover_nym` and `secret_prover_blind`.
+pub fn proof_verify<C: Ciphersuite, M: AsRef<[u8]>>(
+    pk: &PublicKeyG2<Bls12_381>,
+    proof: &PseudonymProof,
+    pseudonym: &G1Affine,
+    header: &[u8],
+    ph: &[u8],
+    verifier_id: &[u8],
+    disclosed_messages: &BTreeMap<usize, M>,
+) -> Result<(), BBSPlusError> {
+    if pseudonym.is_zero() {
+        return Err(BBSPlusError::InvalidProof);
+    }
+    // The responses are for `r`, `-e`, the undisclosed messages, `prover_nym` and `secret_prover_blind`
+    let undisclosed_count = proof
+        .proof
+        .sc_resp
+        .len()
+        .checked_sub(4)
+        .ok_or(BBSPlusError::InvalidProof)?;
+    let message_count = disclosed_messages.len() + undisclosed_count;
+    check_indices(disclosed_messages.keys(), message_count)?;
+    let params = signature_params::<PseudonymCiphersuite<C>>(message_count + 2);
+    let domain = calculate_domain::<PseudonymCiphersuite<C>>(pk, &params.h, header)?;
+    let (disclosed_indices, disclosed_msgs): (Vec<_>, Vec<_>) = disclosed_messages
+        .iter()
+        .map(|(i, m)| (*i, m.as_ref()))
+        .unzip();
+    let disclosed_msgs = messages_to_scalars::<PseudonymCiphersuite<C>, _>(&disclosed_msgs);
+    let revealed_msgs =
+        revealed_messages(domain, disclosed_indices.into_iter().zip(disclosed_msgs));
+
+    let op = verifier_point::<C>(verifier_id);
+    let mut bytes = Vec::new();
+    proof
+        .proof
+        .challenge_contribution(&revealed_msgs, &params, &mut bytes)?;
+    if proof_challenge::<C>(bytes, pseudonym, &op, &proof.T, ph)? != proof.challenge {
+        return Err(BBSPlusError::InvalidProof);
+    }
+
+    // pseudonym * r_hat - OP * nym_hat == T where `r_hat` and `nym_hat` are the responses for `r` and
+    // `prover_nym * r`
+    let r_hat = proof.proof.get_resp_for_known_messages_commitment();
+    let nym_hat = proof
+        .proof
+        .get_resp_for_message(message_count + 1, &revealed_msgs.keys().copied().collect())?;
+    if G1Projective::msm_unchecked(&[*pseudonym, op], &[*r_hat, -*nym_hat]).into_affine() != proof.T
+    {
+        return Err(BBSPlusError::InvalidProof);
+    }
+    proof
+        .proof
+        .verify(&revealed_msgs, &proof.challenge, pk.clone(), params)
+}
+
+impl NymCommitment {
+    /// Encode as `commitment || s_nym || s_blind || challenge`
+    pub fn to_octets(&self) -> Result<Vec<u8>, BBSPlusError> {
+        let mut bytes = point_to_octets(&self.commitment)?;
+        bytes.append(&mut scalar_to_octets(&self.s_nym));
+        bytes.append(&mut scalar_to_octets(&self.s_blind));
+        bytes.append(&mut scalar_to_octets(&self.challenge));
+        Ok(bytes)
+    }
+
+    /// Decode a commitment encoded with [`Self::to_octets`]. Rejects the identity as the commitment
+    pub fn from_octets(bytes: &[u8]) -> Result<Self, BBSPlusError> {
+        if bytes.len() != OCTET_NYM_COMMITMENT_LENGTH {
+            return Err(BBSPlusError::IncorrectOctetsLength(
+                OCTET_NYM_COMMITMENT_LENGTH,
+                bytes.len(),
+            ));
+        }
+        let (point, scalars) = bytes.split_at(OCTET_POINT_LENGTH);
+        let commitment = octets_to_point(point)?;
+        if commitment.is_zero() {
+            return Err(BBSPlusError::InvalidProof);
+        }
+        let scalars = scalars
+            .chunks(OCTET_SCALAR_LENGTH)
+            .map(octets_to_scalar)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            commitment,
+            s_nym: scalars[0],
+            s_blind: scalars[1],
+            challenge: scalars[2],
+        })
+    }
+}
+
+/// Generators of `prover_nym` and `secret_prover_blind`, i.e. the last 2 messages, when the signer knows
+/// `message_count` messages
+fn nym_generators<C: Ciphersuite>(message_count: usize) -> (G1Affine, G1Affine) {
+    let h = signature_params::<PseudonymCiphersuite<C>>(message_count + 2).h;
+    (h[message_count + 1], h[message_count + 2])
+}
+
+/// Hash the verifier id to the point `OP` whose multiple by `prover_nym` is the pseudonym
+fn verifier_point<C: Ciphersuite>(verifier_id: &[u8]) -> G1Affine {
+    let dst = [
+        PseudonymCiphersuite::<C>::api_id().as_slice(),
+        b"PSEUDONYM_H2C_",
+    ]
+    .concat();
+    C::hash_to_curve_g1(verifier_id, &dst)
+}
+
+/// The messages revealed in the proof of knowledge of the signature, i.e. `domain` and the disclosed messages whose
+/// indices are shifted by 1 as `domain` is the first signed message
+fn revealed_messages(
+    domain: Fr,
+    disclosed: impl Iterator<Item = (usize, Fr)>,
+) -> BTreeMap<usize, Fr> {
+    [(0, domain)]
+        .into_iter()
+        .chain(disclosed.map(|(i, m)| (i + 1, m)))
+        .collect()
+}
+
+/// Hash the challenge contribution of the proof of knowledge of the signature, the pseudonym, `OP`, `T` and the
+/// presentation header to the challenge
+fn proof_challenge<C: Ciphersuite>(
+    mut bytes: Vec<u8>,
+    pseudonym: &G1Affine,
+    op: &G1Affine,
+    t: &G1Affine,
+    ph: &[u8],
+) -> Result<Fr, BBSPlusError> {
+    for p in [pseudonym, op, t] {
+        bytes.append(&mut point_to_octets(p)?);
+    }
+    bytes.extend_from_slice(&(ph.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(ph);
+    Ok(C::hash_to_scalar(
+        &bytes,
+        &hash_to_scalar_dst::<PseudonymCiphersuite<C>>(),
+    ))
+}
+
+/// Hash `(commitment, H_{L+1}, H_{L+2}, T)` to the challenge of the proof of knowledge of `prover_nym` and
+/// `secret_prover_blind`
+fn commitment_challenge<C: Ciphersuite>(
+    commitment: &G1Affine,
+    t: &G1Affine,
+    h_nym: &G1Affine,
+    h_blind: &G1Affine,
+) -> Result<Fr, BBSPlusError> {
+    let mut c_octs = point_to_octets(commitment)?;
+    c_octs.append(&mut point_to_octets(h_nym)?);
+    c_octs.append(&mut point_to_octets(h_blind)?);
+    c_octs.append(&mut point_to_octets(t)?);
+    let dst = [
+        PseudonymCiphersuite::<C>::api_id().as_slice(),
+        b"COMMIT_H2S_",
+    ]
+    .concat();
+    Ok(C::hash_to_scalar(&c_octs, &dst))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ietf::{
+        ciphersuite::{Bls12381Sha256, Bls12381Shake256},
+        signature::{keygen, sk_to_pk},
+    };
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    fn check_pseudonyms<C: Ciphersuite>() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let sk = keygen::<C>(&[1; 32], b"", None).unwrap();
+        let pk = sk_to_pk(&sk);
+        let header = b"header";
+        let ph = b"presentation header";
+        let messages = (0..5)
+            .map(|i| format!("msg-{}", i).into_bytes())
+            .collect::<Vec<_>>();
+
+        // Issuance
+        let prover_nym = Fr::rand(&mut rng);
+        let (commitment, blind) = commit::<_, C>(&mut rng, &prover_nym, messages.len()).unwrap();
+        let bytes = commitment.to_octets().unwrap();
+        assert_eq!(bytes.len(), OCTET_NYM_COMMITMENT_LENGTH);
+        assert_eq!(NymCommitment::from_octets(&bytes).unwrap(), commitment);
+
+        // Commitments to the same `prover_nym` differ as they are blinded
+        let (other_commitment, other_blind) =
+            commit::<_, C>(&mut rng, &prover_nym, messages.len()).unwrap();
+        assert_ne!(other_commitment.commitment, commitment.commitment);
+        assert_ne!(other_blind, blind);
+        verify_commitment::<C>(&other_commitment, messages.len()).unwrap();
+
+        // Commitment for a different number of messages or with an invalid proof is rejected
+        assert!(blind_sign::<C, _>(&sk, &pk, &commitment, header, &messages[1..]).is_err());
+        let mut wrong = commitment.clone();
+        wrong.s_nym += Fr::from(1u64);
+        assert!(matches!(
+            blind_sign::<C, _>(&sk, &pk, &wrong, header, &messages),
+            Err(BBSPlusError::InvalidProof)
+        ));
+
+        let sig = blind_sign::<C, _>(&sk, &pk, &commitment, header, &messages).unwrap();
+        verify::<C, _>(&pk, &sig, header, &messages, &prover_nym, &blind).unwrap();
+        assert!(verify::<C, _>(&pk, &sig, header, &messages, &Fr::rand(&mut rng), &blind).is_err());
+        assert!(verify::<C, _>(&pk, &sig, header, &messages, &prover_nym, &other_blind).is_err());
+        assert!(verify::<C, _>(&pk, &sig, b"", &messages, &prover_nym, &blind).is_err());
+
+        // Presentation
+        let verifier_1 = b"verifier-1";
+        let verifier_2 = b"verifier-2";
+        for disclosed in [
+            BTreeSet::new(),
+            BTreeSet::from([2]),
+            (0..messages.len()).collect(),
+        ] {
+            let disclosed_messages = disclosed
+                .iter()
+                .map(|i| (*i, messages[*i].as_slice()))
+                .collect::<BTreeMap<_, _>>();
+
+            let (proof_1, nym_1) = proof_gen::<_, C, _>(
+                &mut rng,
+                &pk,
+                &sig,
+                header,
+                ph,
+                &messages,
+                &prover_nym,
+                &blind,
+                verifier_1,
+                &disclosed,
+            )
+            .unwrap();
+            assert_eq!(nym_1, pseudonym::<C>(verifier_1, &prover_nym));
+            proof_verify::<C, _>(
+                &pk,
+                &proof_1,
+                &nym_1,
+                header,
+                ph,
+                verifier_1,
+                &disclosed_messages,
+            )
+            .unwrap();
+
+            // Same pseudonym for the same verifier, different for another
+            let (proof_2, nym_2) = proof_gen::<_, C, _>(
+                &mut rng,
+                &pk,
+                &sig,
+                header,
+                ph,
+                &messages,
+                &prover_nym,
+                &blind,
+                verifier_1,
+                &disclosed,
+            )
+            .unwrap();
+            assert_eq!(nym_1, nym_2);
+            assert_ne!(proof_1, proof_2);
+            let (proof_3, nym_3) = proof_gen::<_, C, _>(
+                &mut rng,
+                &pk,
+                &sig,
+                header,
+                ph,
+                &messages,
+                &prover_nym,
+                &blind,
+                verifier_2,
+                &disclosed,
+            )
+            .unwrap();
+            assert_ne!(nym_1, nym_3);
+            proof_verify::<C, _>(
+                &pk,
+                &proof_3,
+                &nym_3,
+                header,
+                ph,
+                verifier_2,
+                &disclosed_messages,
+            )
+            .unwrap();
+
+            // Proof can't be used with a different verifier or pseudonym
+            assert!(proof_verify::<C, _>(
+                &pk,
+                &proof_1,
+                &nym_1,
+                header,
+                ph,
+                verifier_2,
+                &disclosed_messages
+            )
+            .is_err());
+            assert!(proof_verify::<C, _>(
+                &pk,
+                &proof_1,
+                &nym_3,
+                header,
+                ph,
+                verifier_1,
+                &disclosed_messages
+            )
+            .is_err());
+            assert!(proof_verify::<C, _>(
+                &pk,
+                &proof_1,
+                &nym_1,
+                header,
+                b"",
+                verifier_1,
+                &disclosed_messages
+            )
+            .is_err());
+            // Proof can't be used with a disclosed message changed
+            if let Some(i) = disclosed.iter().next() {
+                let mut wrong_messages = disclosed_messages.clone();
+                wrong_messages.insert(*i, b"wrong");
+                assert!(proof_verify::<C, _>(
+                    &pk,
+                    &proof_1,
+                    &nym_1,
+                    header,
+                    ph,
+                    verifier_1,
+                    &wrong_messages
+                )
+                .is_err());
+            }
+        }
+
+        // A different `prover_nym` can't be used with the signature
+        let other_nym = Fr::rand(&mut rng);
+        let (proof, nym) = proof_gen::<_, C, _>(
+            &mut rng,
+            &pk,
+            &sig,
+            header,
+            ph,
+            &messages,
+            &other_nym,
+            &blind,
+            verifier_1,
+            &BTreeSet::new(),
+        )
+        .unwrap();
+        assert!(proof_verify::<C, &[u8]>(
+            &pk,
+            &proof,
+            &nym,
+            header,
+            ph,
+            verifier_1,
+            &BTreeMap::new()
+        )
+        .is_err());
+
+        // `prover_nym` can't be disclosed
+        assert!(proof_gen::<_, C, _>(
+            &mut rng,
+            &pk,
+            &sig,
+            header,
+            ph,
+            &messages,
+            &prover_nym,
+            &blind,
+            verifier_1,
+            &BTreeSet::from([messages.len()]),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn pseudonyms_sha256() {
+        check_pseudonyms::<Bls12381Sha256>();
+    }
+
+    #[test]
+    fn pseudonyms_shake256() {
+        check_pseudonyms::<Bls12381Shake256>();
+    }
+}
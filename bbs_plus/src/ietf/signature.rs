@@ -0,0 +1,421 @@
+//! Key generation, signing and verification as per the IETF draft along with the octet encodings of keys and
+//! signatures. The signature is a BBS signature [`Signature23G1`] where the first message is the `domain` (which
+//! binds the public key, generators and header) and the remaining messages are the hashed octet-string messages.
+
+use crate::{
+    error::BBSPlusError,
+    ietf::ciphersuite::{
+        Ciphersuite, EXPAND_LEN, OCTET_POINT_LENGTH, OCTET_PUBLIC_KEY_LENGTH, OCTET_SCALAR_LENGTH,
+    },
+    setup::{PublicKeyG2, SecretKey, SignatureParams23G1},
+    signature_23::Signature23G1,
+};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::try_iter::IndexIsOutOfBounds;
+
+/// Secret key length must be at least this many bytes
+pub const MIN_KEY_MATERIAL_LENGTH: usize = 32;
+
+/// Length of an encoded signature
+pub const OCTET_SIGNATURE_LENGTH: usize = OCTET_POINT_LENGTH + OCTET_SCALAR_LENGTH;
+
+/// Derive a secret key from `key_material` of at least [`MIN_KEY_MATERIAL_LENGTH`] bytes and an optional `key_info`.
+/// If `key_dst` is not given, the ciphersuite's default is used.
+pub fn keygen<C: Ciphersuite>(
+    key_material: &[u8],
+    key_info: &[u8],
+    key_dst: Option<&[u8]>,
+) -> Result<SecretKey<Fr>, BBSPlusError> {
+    if key_material.len() < MIN_KEY_MATERIAL_LENGTH {
+        return Err(BBSPlusError::KeyMaterialTooShort(key_material.len()));
+    }
+    let key_info_len =
+        u16::try_from(key_info.len()).map_err(|_| BBSPlusError::KeyInfoTooLong(key_info.len()))?;
+    let default_dst;
+    let key_dst = match key_dst {
+        Some(d) => d,
+        None => {
+            default_dst = [C::api_id().as_slice(), b"KEYGEN_DST_"].concat();
+            &default_dst
+        }
+    };
+    let derive_input = [key_material, &key_info_len.to_be_bytes(), key_info].concat();
+    let sk = C::hash_to_scalar(&derive_input, key_dst);
+    if sk.is_zero() {
+        return Err(BBSPlusError::InvalidSecretKey);
+    }
+    Ok(SecretKey(sk))
+}
+
+/// Public key corresponding to the secret key. This is the secret key multiplied with the base point of G2
+pub fn sk_to_pk(sk: &SecretKey<Fr>) -> PublicKeyG2<Bls12_381> {
+    PublicKeyG2(
+        G2Affine::generator()
+            .mul_bigint(sk.0.into_bigint())
+            .into_affine(),
+    )
+}
+
+/// Create `count` generators by hashing to G1. `generator_seed` defaults to the one used for message generators.
+pub fn create_generators<C: Ciphersuite>(
+    count: usize,
+    generator_seed: Option<&[u8]>,
+) -> Vec<G1Affine> {
+    let api_id = C::api_id();
+    let seed_dst = [api_id.as_slice(), b"SIG_GENERATOR_SEED_"].concat();
+    let generator_dst = [api_id.as_slice(), b"SIG_GENERATOR_DST_"].concat();
+    let default_seed;
+    let generator_seed = match generator_seed {
+        Some(s) => s,
+        None => {
+            default_seed = [api_id.as_slice(), b"MESSAGE_GENERATOR_SEED"].concat();
+            &default_seed
+        }
+    };
+    let mut v = C::expand_message(generator_seed, &seed_dst, EXPAND_LEN);
+    (1..=count as u64)
+        .map(|i| {
+            v = C::expand_message(
+                &[v.as_slice(), &i.to_be_bytes()].concat(),
+                &seed_dst,
+                EXPAND_LEN,
+            );
+            C::hash_to_curve_g1(&v, &generator_dst)
+        })
+        .collect()
+}
+
+/// Map each octet-string message to a scalar by hashing it
+pub fn messages_to_scalars<C: Ciphersuite, M: AsRef<[u8]>>(messages: &[M]) -> Vec<Fr> {
+    let dst = [C::api_id().as_slice(), b"MAP_MSG_TO_SCALAR_AS_HASH_"].concat();
+    messages
+        .iter()
+        .map(|m| C::hash_to_scalar(m.as_ref(), &dst))
+        .collect()
+}
+
+/// Signature params for signing `message_count` messages. `g1` is `P1`, `h` is `(Q_1, H_1, ..., H_L)` and `g2` is
+/// the base point of G2. The signed messages are `(domain, msg_1, ..., msg_L)`
+pub fn signature_params<C: Ciphersuite>(message_count: usize) -> SignatureParams23G1<Bls12_381> {
+    SignatureParams23G1 {
+        g1: C::p1(),
+        g2: G2Affine::generator(),
+        h: create_generators::<C>(message_count + 1, None),
+    }
+}
+
+/// Sign the octet-string messages and an optional header
+pub fn sign<C: Ciphersuite, M: AsRef<[u8]>>(
+    sk: &SecretKey<Fr>,
+    pk: &PublicKeyG2<Bls12_381>,
+    header: &[u8],
+    messages: &[M],
+) -> Result<Signature23G1<Bls12_381>, BBSPlusError> {
+    let params = signature_params::<C>(messages.len());
+    let msgs = messages_to_scalars::<C, _>(messages);
+    let domain = calculate_domain::<C>(pk, &params.h, header)?;
+    core_sign::<C>(sk, &params, domain, &msgs)
+}
+
+/// Verify a signature created with [`sign`]
+pub fn verify<C: Ciphersuite, M: AsRef<[u8]>>(
+    pk: &PublicKeyG2<Bls12_381>,
+    signature: &Signature23G1<Bls12_381>,
+    header: &[u8],
+    messages: &[M],
+) -> Result<(), BBSPlusError> {
+    let params = signature_params::<C>(messages.len());
+    let domain = calculate_domain::<C>(pk, &params.h, header)?;
+    let mut msgs = Vec::with_capacity(messages.len() + 1);
+    msgs.push(domain);
+    msgs.append(&mut messages_to_scalars::<C, _>(messages));
+    signature.verify(&msgs, pk.clone(), params)
+}
+
+pub(crate) fn core_sign<C: Ciphersuite>(
+    sk: &SecretKey<Fr>,
+    params: &SignatureParams23G1<Bls12_381>,
+    domain: Fr,
+    messages: &[Fr],
+) -> Result<Signature23G1<Bls12_381>, BBSPlusError> {
+    // e = hash_to_scalar(serialize((SK, msg_1, ..., msg_L, domain)), signature_dst)
+    let mut e_input = Vec::with_capacity((messages.len() + 2) * OCTET_SCALAR_LENGTH);
+    e_input.append(&mut scalar_to_octets(&sk.0));
+    for m in messages {
+        e_input.append(&mut scalar_to_octets(m));
+    }
+    e_input.append(&mut scalar_to_octets(&domain));
+    let e = C::hash_to_scalar(&e_input, &hash_to_scalar_dst::<C>());
+
+    let mut msgs = Vec::with_capacity(messages.len() + 1);
+    msgs.push(domain);
+    msgs.extend_from_slice(messages);
+    let b = params.b(msgs.iter().enumerate())?;
+    let e_plus_x_inv = (e + sk.0).inverse().ok_or(BBSPlusError::CannotInvert0)?;
+    Ok(Signature23G1 {
+        A: b.mul_bigint(e_plus_x_inv.into_bigint()).into_affine(),
+        e,
+    })
+}
+
+/// Hash the public key, the generators `(Q_1, H_1, ..., H_L)` and the header to the scalar `domain`
+pub(crate) fn calculate_domain<C: Ciphersuite>(
+    pk: &PublicKeyG2<Bls12_381>,
+    generators: &[G1Affine],
+    header: &[u8],
+) -> Result<Fr, BBSPlusError> {
+    let mut dom_input = pk_to_octets(pk)?;
+    dom_input.extend_from_slice(&(generators.len() as u64 - 1).to_be_bytes());
+    for g in generators {
+        dom_input.append(&mut point_to_octets(g)?);
+    }
+    dom_input.append(&mut C::api_id());
+    dom_input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+    dom_input.extend_from_slice(header);
+    Ok(C::hash_to_scalar(&dom_input, &hash_to_scalar_dst::<C>()))
+}
+
+pub(crate) fn hash_to_scalar_dst<C: Ciphersuite>() -> Vec<u8> {
+    [C::api_id().as_slice(), b"H2S_"].concat()
+}
+
+/// Encode the signature as `A || e`
+pub fn signature_to_octets(signature: &Signature23G1<Bls12_381>) -> Result<Vec<u8>, BBSPlusError> {
+    let mut bytes = point_to_octets(&signature.A)?;
+    bytes.append(&mut scalar_to_octets(&signature.e));
+    Ok(bytes)
+}
+
+/// Decode a signature encoded with [`signature_to_octets`]. Rejects signatures where `A` is the identity or `e` is 0
+pub fn octets_to_signature(bytes: &[u8]) -> Result<Signature23G1<Bls12_381>, BBSPlusError> {
+    if bytes.len() != OCTET_SIGNATURE_LENGTH {
+        return Err(BBSPlusError::IncorrectOctetsLength(
+            OCTET_SIGNATURE_LENGTH,
+            bytes.len(),
+        ));
+    }
+    let A = octets_to_point(&bytes[..OCTET_POINT_LENGTH])?;
+    if A.is_zero() {
+        return Err(BBSPlusError::ZeroSignature);
+    }
+    let e = octets_to_scalar(&bytes[OCTET_POINT_LENGTH..])?;
+    if e.is_zero() {
+        return Err(BBSPlusError::InvalidSignature);
+    }
+    Ok(Signature23G1 { A, e })
+}
+
+/// Encode the secret key as a 32-byte big-endian integer
+pub fn sk_to_octets(sk: &SecretKey<Fr>) -> Vec<u8> {
+    scalar_to_octets(&sk.0)
+}
+
+/// Decode a secret key encoded with [`sk_to_octets`]
+pub fn octets_to_sk(bytes: &[u8]) -> Result<SecretKey<Fr>, BBSPlusError> {
+    let sk = octets_to_scalar(bytes)?;
+    if sk.is_zero() {
+        return Err(BBSPlusError::InvalidSecretKey);
+    }
+    Ok(SecretKey(sk))
+}
+
+/// Encode the public key as a compressed G2 point
+pub fn pk_to_octets(pk: &PublicKeyG2<Bls12_381>) -> Result<Vec<u8>, BBSPlusError> {
+    let mut bytes = Vec::with_capacity(OCTET_PUBLIC_KEY_LENGTH);
+    pk.0.serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decode a public key encoded with [`pk_to_octets`]. Rejects points not in the subgroup and the identity
+pub fn octets_to_pk(bytes: &[u8]) -> Result<PublicKeyG2<Bls12_381>, BBSPlusError> {
+    if bytes.len() != OCTET_PUBLIC_KEY_LENGTH {
+        return Err(BBSPlusError::IncorrectOctetsLength(
+            OCTET_PUBLIC_KEY_LENGTH,
+            bytes.len(),
+        ));
+    }
+    let pk = G2Affine::deserialize_compressed(bytes)?;
+    if pk.is_zero() {
+        return Err(BBSPlusError::InvalidPublicKey);
+    }
+    Ok(PublicKeyG2(pk))
+}
+
+pub(crate) fn scalar_to_octets(s: &Fr) -> Vec<u8> {
+    s.into_bigint().to_bytes_be()
+}
+
+/// Decode a 32-byte big-endian integer. Fails if its not less than the group order
+pub(crate) fn octets_to_scalar(bytes: &[u8]) -> Result<Fr, BBSPlusError> {
+    if bytes.len() != OCTET_SCALAR_LENGTH {
+        return Err(BBSPlusError::IncorrectOctetsLength(
+            OCTET_SCALAR_LENGTH,
+            bytes.len(),
+        ));
+    }
+    let mut le = bytes.to_vec();
+    le.reverse();
+    Ok(Fr::deserialize_compressed(le.as_slice())?)
+}
+
+pub(crate) fn point_to_octets(p: &G1Affine) -> Result<Vec<u8>, BBSPlusError> {
+    let mut bytes = Vec::with_capacity(OCTET_POINT_LENGTH);
+    p.serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+pub(crate) fn octets_to_point(bytes: &[u8]) -> Result<G1Affine, BBSPlusError> {
+    Ok(G1Affine::deserialize_compressed(bytes)?)
+}
+
+/// Check that each index is less than `length`
+pub(crate) fn check_indices<'a>(
+    indices: impl Iterator<Item = &'a usize>,
+    length: usize,
+) -> Result<(), BBSPlusError> {
+    for &index in indices {
+        if index >= length {
+            return Err(IndexIsOutOfBounds { index, length }.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ietf::ciphersuite::{Bls12381Sha256, Bls12381Shake256};
+
+    // Values from the test fixtures of the draft
+    const KEY_MATERIAL: &str = "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+    const KEY_INFO: &str = "746869732d49532d736f6d652d6b65792d6d657461646174612d746f2d62652d757365642d696e2d746573742d6b65792d67656e";
+    const HEADER: &str = "11223344556677889900aabbccddeeff";
+    const MSG_1: &str = "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02";
+
+    fn check_keypair<C: Ciphersuite>(sk: &str, pk: &str) {
+        let key_material = hex::decode(KEY_MATERIAL).unwrap();
+        let key_info = hex::decode(KEY_INFO).unwrap();
+        let secret_key = keygen::<C>(&key_material, &key_info, None).unwrap();
+        assert_eq!(hex::encode(sk_to_octets(&secret_key)), sk);
+        let public_key = sk_to_pk(&secret_key);
+        assert_eq!(hex::encode(pk_to_octets(&public_key).unwrap()), pk);
+
+        assert_eq!(octets_to_sk(&hex::decode(sk).unwrap()).unwrap(), secret_key);
+        assert_eq!(octets_to_pk(&hex::decode(pk).unwrap()).unwrap(), public_key);
+        assert!(matches!(
+            keygen::<C>(&key_material[..31], &key_info, None),
+            Err(BBSPlusError::KeyMaterialTooShort(31))
+        ));
+    }
+
+    fn check_generators<C: Ciphersuite>(expected: &[&str]) {
+        // P1 is the generator created from a different seed
+        let seed = [C::api_id().as_slice(), b"BP_MESSAGE_GENERATOR_SEED"].concat();
+        assert_eq!(create_generators::<C>(1, Some(&seed)), vec![C::p1()]);
+
+        let generators = create_generators::<C>(expected.len(), None);
+        for (g, e) in generators.iter().zip(expected) {
+            assert_eq!(hex::encode(point_to_octets(g).unwrap()), *e);
+        }
+    }
+
+    fn check_sign_verify<C: Ciphersuite>() {
+        let key_material = hex::decode(KEY_MATERIAL).unwrap();
+        let sk = keygen::<C>(&key_material, b"", None).unwrap();
+        let pk = sk_to_pk(&sk);
+        let header = hex::decode(HEADER).unwrap();
+        let messages = vec![
+            b"msg-1".to_vec(),
+            b"msg-2".to_vec(),
+            vec![],
+            b"msg-4".to_vec(),
+        ];
+
+        let sig = sign::<C, _>(&sk, &pk, &header, &messages).unwrap();
+        verify::<C, _>(&pk, &sig, &header, &messages).unwrap();
+        // No messages
+        let sig_1 = sign::<C, Vec<u8>>(&sk, &pk, &header, &[]).unwrap();
+        verify::<C, Vec<u8>>(&pk, &sig_1, &header, &[]).unwrap();
+
+        assert!(verify::<C, _>(&pk, &sig, b"", &messages).is_err());
+        assert!(verify::<C, _>(&pk, &sig, &header, &messages[1..]).is_err());
+        let mut wrong_messages = messages.clone();
+        wrong_messages.swap(0, 1);
+        assert!(verify::<C, _>(&pk, &sig, &header, &wrong_messages).is_err());
+        let other_pk = sk_to_pk(&keygen::<C>(&key_material, b"other", None).unwrap());
+        assert!(verify::<C, _>(&other_pk, &sig, &header, &messages).is_err());
+
+        let bytes = signature_to_octets(&sig).unwrap();
+        assert_eq!(bytes.len(), OCTET_SIGNATURE_LENGTH);
+        assert_eq!(octets_to_signature(&bytes).unwrap(), sig);
+        assert!(octets_to_signature(&bytes[1..]).is_err());
+        let mut bytes_with_zero_e = bytes.clone();
+        bytes_with_zero_e[OCTET_POINT_LENGTH..].fill(0);
+        assert!(octets_to_signature(&bytes_with_zero_e).is_err());
+        // e not less than the group order
+        let mut bytes_with_big_e = bytes.clone();
+        bytes_with_big_e[OCTET_POINT_LENGTH..].fill(0xff);
+        assert!(octets_to_signature(&bytes_with_big_e).is_err());
+    }
+
+    #[test]
+    fn sha256_fixtures() {
+        check_keypair::<Bls12381Sha256>(
+            "60e55110f76883a13d030b2f6bd11883422d5abde717569fc0731f51237169fc",
+            "a820f230f6ae38503b86c70dc50b61c58a77e45c39ab25c0652bbaa8fa136f2851bd4781c9dcde39fc9d1d52c9e60268061e7d7632171d91aa8d460acee0e96f1e7c4cfb12d3ff9ab5d5dc91c277db75c845d649ef3c4f63aebc364cd55ded0c",
+        );
+        check_generators::<Bls12381Sha256>(&[
+            "a9ec65b70a7fbe40c874c9eb041c2cb0a7af36ccec1bea48fa2ba4c2eb67ef7f9ecb17ed27d38d27cdeddff44c8137be",
+            "98cd5313283aaf5db1b3ba8611fe6070d19e605de4078c38df36019fbaad0bd28dd090fd24ed27f7f4d22d5ff5dea7d4",
+        ]);
+
+        let msg = hex::decode(MSG_1).unwrap();
+        assert_eq!(
+            hex::encode(scalar_to_octets(
+                &messages_to_scalars::<Bls12381Sha256, _>(&[&msg])[0]
+            )),
+            "1cb5bb86114b34dc438a911617655a1db595abafac92f47c5001799cf624b430"
+        );
+
+        // Signature over a single message
+        let sk = octets_to_sk(
+            &hex::decode("60e55110f76883a13d030b2f6bd11883422d5abde717569fc0731f51237169fc")
+                .unwrap(),
+        )
+        .unwrap();
+        let pk = sk_to_pk(&sk);
+        let header = hex::decode(HEADER).unwrap();
+        let sig = sign::<Bls12381Sha256, _>(&sk, &pk, &header, &[&msg]).unwrap();
+        let expected = "84773160b824e194073a57493dac1a20b667af70cd2352d8af241c77658da5253aa8458317cca0eae615690d55b1f27164657dcafee1d5c1973947aa70e2cfbb4c892340be5969920d0916067b4565a0";
+        assert_eq!(hex::encode(signature_to_octets(&sig).unwrap()), expected);
+        let sig = octets_to_signature(&hex::decode(expected).unwrap()).unwrap();
+        verify::<Bls12381Sha256, _>(&pk, &sig, &header, &[&msg]).unwrap();
+
+        // Can also be verified as a BBS signature over the domain and the hashed message
+        let params = signature_params::<Bls12381Sha256>(1);
+        let domain = calculate_domain::<Bls12381Sha256>(&pk, &params.h, &header).unwrap();
+        let mut msgs = vec![domain];
+        msgs.append(&mut messages_to_scalars::<Bls12381Sha256, _>(&[&msg]));
+        sig.verify(&msgs, pk, params).unwrap();
+
+        // TODO: Check the multi-message signature of the draft's SHA-256 fixtures
+        check_sign_verify::<Bls12381Sha256>();
+    }
+
+    #[test]
+    fn shake256_fixtures() {
+        check_keypair::<Bls12381Shake256>(
+            "2eee0f60a8a3a8bec0ee942bfd46cbdae9a0738ee68f5a64e7238311cf09a079",
+            "92d37d1d6cd38fea3a873953333eab23a4c0377e3e049974eb62bd45949cdeb18fb0490edcd4429adff56e65cbce42cf188b31bddbd619e419b99c2c41b38179eb001963bc3decaae0d9f702c7a8c004f207f46c734a5eae2e8e82833f3e7ea5",
+        );
+        // TODO: Check the generators, the single and multi-message signatures of the draft's SHAKE-256
+        // fixtures (`fixtures/bls12-381-shake-256/` in the draft's repository). Only the keypair and `P1`
+        // are checked against the draft for now.
+        check_generators::<Bls12381Shake256>(&[]);
+        check_sign_verify::<Bls12381Shake256>();
+    }
+}
@@ -41,6 +41,9 @@
 //! 5. BBS proof of knowledge of signature module - [`proof_23`]
 //! 6. BBS proof of knowledge of signature module, alternate implementation - [`proof_23_alternate`]
 //! 7. Threshold BBS and BBS+ signatures - [`threshold`]
+//! 8. BBS signatures and proofs as per the IETF draft with ciphersuites `BLS12-381-SHA-256` and `BLS12-381-SHAKE-256` - [`ietf`], requires the `ietf` feature
+//! 9. Requesting blind BBS and BBS+ signatures - [`blind_signature`]
+//! 10. Limited-use tokens with double-spending detection - [`ecash`]
 //!
 //! The implementation tries to use the same variable names as the paper and thus violate Rust's naming conventions at places.
 //!
@@ -52,8 +55,15 @@
 //! [`proof_23`]: crate::proof_23
 //! [`proof_23_alternate`]: crate::proof_23_cdl
 //! [`threshold`]: crate::threshold
+//! [`ietf`]: crate::ietf
+//! [`blind_signature`]: crate::blind_signature
+//! [`ecash`]: crate::ecash
 
+pub mod blind_signature;
+pub mod ecash;
 pub mod error;
+#[cfg(feature = "ietf")]
+pub mod ietf;
 pub mod proof;
 pub mod proof_23;
 pub mod proof_23_cdl;
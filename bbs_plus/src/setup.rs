@@ -61,13 +61,12 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use core::iter::once;
 use dock_crypto_utils::{
-    affine_group_element_from_byte_slices,
     aliases::*,
     concat_slices,
-    hashing_utils::projective_group_elem_from_try_and_incr,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
     iter::*,
     join,
-    misc::{n_projective_group_elements, seq_pairs_satisfy},
+    misc::{n_projective_group_elements_with_hasher, seq_pairs_satisfy},
     serde_utils::*,
     try_iter::CheckLeft,
 };
@@ -250,16 +249,25 @@ macro_rules! impl_sig_params {
             /// attack but since all this is public knowledge, it is fine.
             /// This is useful if people need to be convinced that the discrete log of group elements wrt each other is not known.
             pub fn new<D: Digest>(label: &[u8], message_count: u32) -> Self {
+                Self::new_with_hasher::<TryAndIncrement<D>>(label, message_count)
+            }
+
+            /// Same as `Self::new` but group elements are derived using the given hasher, like
+            /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+            pub fn new_with_hasher<H>(label: &[u8], message_count: u32) -> Self
+            where
+                H: GroupElemHasher<E::$group_affine> + GroupElemHasher<E::$other_group_affine>,
+            {
                 assert_ne!(message_count, 0);
 
                 let ((h, [g1, h_0]), g2) = join!(
                     {
-                        let g1 = projective_group_elem_from_try_and_incr::<E::$group_affine, D>(
+                        let g1 = <H as GroupElemHasher<E::$group_affine>>::hash_to_projective(
                             &concat_slices!(label, b" : g1"),
                         );
                         let h_bytes = concat_slices!(label, b" : h_");
                         // h_i for i in 0 to message_count
-                        let h = n_projective_group_elements::<E::$group_affine, D>(
+                        let h = n_projective_group_elements_with_hasher::<E::$group_affine, H>(
                             0..message_count + 1,
                             &h_bytes,
                         );
@@ -274,7 +282,9 @@ macro_rules! impl_sig_params {
                             <[_; 2]>::try_from(normalized_g1_and_h).unwrap(),
                         )
                     },
-                    affine_group_element_from_byte_slices!(label, b" : g2")
+                    <H as GroupElemHasher<E::$other_group_affine>>::hash_to_affine(
+                        &concat_slices!(label, b" : g2")
+                    )
                 );
 
                 Self { g1, g2, h_0, h }
@@ -517,13 +527,22 @@ impl<E: Pairing> SignatureParams23G1<E> {
     /// attack but since all this is public knowledge, it is fine.
     /// This is useful if people need to be convinced that the discrete log of group elements wrt each other is not known.
     pub fn new<D: Digest>(label: &[u8], message_count: u32) -> Self {
+        Self::new_with_hasher::<TryAndIncrement<D>>(label, message_count)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H>(label: &[u8], message_count: u32) -> Self
+    where
+        H: GroupElemHasher<E::G1Affine> + GroupElemHasher<E::G2Affine>,
+    {
         assert_ne!(message_count, 0);
         // Group element by hashing `label`||`g1`, `label`||`g2` and `label`||`h_i` for i in 1 to message_count.
         let (g1, g2, h) = join!(
-            affine_group_element_from_byte_slices!(label, b" : g1"),
-            affine_group_element_from_byte_slices!(label, b" : g2"),
+            <H as GroupElemHasher<E::G1Affine>>::hash_to_affine(&concat_slices!(label, b" : g1")),
+            <H as GroupElemHasher<E::G2Affine>>::hash_to_affine(&concat_slices!(label, b" : g2")),
             {
-                let h: Vec<_> = n_projective_group_elements::<E::G1Affine, D>(
+                let h: Vec<_> = n_projective_group_elements_with_hasher::<E::G1Affine, H>(
                     1..message_count + 1,
                     &concat_slices!(label, b" : h_"),
                 )
@@ -728,6 +747,32 @@ mod tests {
         test_params!(SignatureParams23G1, message_count);
     }
 
+    #[test]
+    fn params_with_hash_to_curve() {
+        use dock_crypto_utils::hashing_utils::HashToCurve;
+        use sha2::Sha256;
+
+        macro_rules! check {
+            ($params:ident) => {
+                let label = "test".as_bytes();
+                let params = $params::<Bls12_381>::new_with_hasher::<HashToCurve<Sha256>>(label, 5);
+                assert!(params.is_valid());
+                assert_eq!(
+                    params,
+                    $params::<Bls12_381>::new_with_hasher::<HashToCurve<Sha256>>(label, 5)
+                );
+                assert_ne!(params, $params::<Bls12_381>::new::<Sha256>(label, 5));
+                assert_eq!(
+                    $params::<Bls12_381>::new::<Blake2b512>(label, 5),
+                    $params::<Bls12_381>::new_with_hasher::<TryAndIncrement<Blake2b512>>(label, 5)
+                );
+            };
+        }
+        check!(SignatureParamsG1);
+        check!(SignatureParamsG2);
+        check!(SignatureParams23G1);
+    }
+
     #[test]
     fn proof_of_knowledge_of_public_key() {
         macro_rules! check {
@@ -83,7 +83,10 @@
 //! ```
 
 use crate::error::BBSPlusError;
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup, Group,
+};
 use ark_ff::{fields::Field, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
@@ -97,7 +100,7 @@ use crate::{
         SignatureParamsG1, SignatureParamsG2,
     },
 };
-use dock_crypto_utils::serde_utils::*;
+use dock_crypto_utils::{randomized_pairing_check::RandomizedPairingChecker, serde_utils::*};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -297,6 +300,50 @@ impl<E: Pairing> SignatureG1<E> {
     }
 }
 
+impl<E: Pairing> SignatureG1<E> {
+    /// Same as `Self::verify` but adds the pairing check to the given `RandomizedPairingChecker` so
+    /// that it can be done together with other pairing checks. The caller must call `pairing_checker.verify()`
+    pub fn verify_with_randomized_pairing_checker(
+        &self,
+        messages: &[E::ScalarField],
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), BBSPlusError> {
+        let params = params.into();
+        let b = self.pre_verify(messages, &params)?;
+        let Aeb = self.A.mul(self.e) - b;
+        pairing_checker.add_multiple_sources_and_target(
+            &[self.A, Aeb.into_affine()],
+            [pk.into().0, params.g2],
+            &PairingOutput::zero(),
+        );
+        Ok(())
+    }
+
+    /// Verify several signatures, each with its own messages, public key and params, with a single
+    /// `RandomizedPairingChecker`. If the batch does not verify, it is bisected to find the invalid
+    /// signatures and their indices in `batch` are returned in `BBSPlusError::InvalidSignatures`.
+    /// Assumes that the public keys and parameters have been validated already.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        batch: &[SignatureG1BatchItem<E>],
+    ) -> Result<(), BBSPlusError> {
+        let checks = batch
+            .iter()
+            .map(|(sig, messages, pk, params)| {
+                let b = sig.pre_verify(messages, params).ok()?;
+                let Aeb = sig.A.mul(sig.e) - b;
+                Some((
+                    [sig.A, Aeb.into_affine()],
+                    [pk.0.clone(), params.g2.clone()],
+                ))
+            })
+            .collect();
+        verify_pairing_checks_in_batch::<E, R>(rng, checks)
+    }
+}
+
 impl<E: Pairing> SignatureG2<E> {
     /// Verify the validity of the signature. Assumes that the public key and parameters
     /// have been validated already.
@@ -324,6 +371,71 @@ impl<E: Pairing> SignatureG2<E> {
     }
 }
 
+/// A signature to verify in a batch along with its messages, public key and params
+pub type SignatureG1BatchItem<'a, E> = (
+    &'a SignatureG1<E>,
+    &'a [<E as Pairing>::ScalarField],
+    &'a PreparedPublicKeyG2<E>,
+    &'a PreparedSignatureParamsG1<E>,
+);
+
+/// Pairing check `e(a_1, b_1) * e(a_2, b_2) == 1` for a signature in a batch
+pub(crate) type PairingCheck<E> = (
+    [<E as Pairing>::G1Affine; 2],
+    [<E as Pairing>::G2Prepared; 2],
+);
+
+/// `None` if the signature already failed the checks done before the pairing check.
+pub(crate) type PairingCheckInBatch<E> = Option<PairingCheck<E>>;
+
+/// Verify the pairing checks of a batch of signatures and return the indices of the failing ones in
+/// `BBSPlusError::InvalidSignatures`
+pub(crate) fn verify_pairing_checks_in_batch<E: Pairing, R: RngCore>(
+    rng: &mut R,
+    checks: Vec<PairingCheckInBatch<E>>,
+) -> Result<(), BBSPlusError> {
+    let mut invalid = Vec::new();
+    let mut candidates = Vec::with_capacity(checks.len());
+    for (i, check) in checks.into_iter().enumerate() {
+        match check {
+            Some(c) => candidates.push((i, c)),
+            None => invalid.push(i),
+        }
+    }
+    find_failing_pairing_checks::<E, R>(rng, &candidates, &mut invalid);
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        invalid.sort_unstable();
+        Err(BBSPlusError::InvalidSignatures(invalid))
+    }
+}
+
+/// Check all given pairing checks together and if that fails, bisect to find the failing ones
+fn find_failing_pairing_checks<E: Pairing, R: RngCore>(
+    rng: &mut R,
+    checks: &[(usize, PairingCheck<E>)],
+    failing: &mut Vec<usize>,
+) {
+    if checks.is_empty() {
+        return;
+    }
+    let mut pairing_checker = RandomizedPairingChecker::<E>::new_using_rng(rng, true);
+    for (_, (a, b)) in checks {
+        pairing_checker.add_multiple_sources_and_target(a, b.clone(), &PairingOutput::zero());
+    }
+    if pairing_checker.verify() {
+        return;
+    }
+    if checks.len() == 1 {
+        failing.push(checks[0].0);
+        return;
+    }
+    let (left, right) = checks.split_at(checks.len() / 2);
+    find_failing_pairing_checks::<E, R>(rng, left, failing);
+    find_failing_pairing_checks::<E, R>(rng, right, failing);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,4 +596,100 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn batch_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_counts = [1, 5, 10];
+
+        // Signatures under different params and keys, 2 signatures per params and key
+        let mut params = vec![];
+        let mut pks = vec![];
+        let mut sigs = vec![];
+        let mut messages = vec![];
+        for message_count in message_counts {
+            let p = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+            let keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &p);
+            for _ in 0..2 {
+                let msgs = (0..message_count)
+                    .map(|_| Fr::rand(&mut rng))
+                    .collect::<Vec<_>>();
+                sigs.push(
+                    SignatureG1::<Bls12_381>::new(&mut rng, &msgs, &keypair.secret_key, &p)
+                        .unwrap(),
+                );
+                messages.push(msgs);
+            }
+            params.push(PreparedSignatureParamsG1::from(p));
+            pks.push(PreparedPublicKeyG2::from(keypair.public_key.clone()));
+        }
+
+        let batch = |sigs: &[SignatureG1<Bls12_381>], messages: &[Vec<Fr>]| {
+            SignatureG1::verify_batch(
+                &mut StdRng::seed_from_u64(1u64),
+                &sigs
+                    .iter()
+                    .zip(messages.iter())
+                    .enumerate()
+                    .map(|(i, (s, m))| (s, m.as_slice(), &pks[i / 2], &params[i / 2]))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let start = Instant::now();
+        batch(&sigs, &messages).unwrap();
+        println!(
+            "Time to verify {} signatures in a batch is {:?}",
+            sigs.len(),
+            start.elapsed()
+        );
+
+        let start = Instant::now();
+        for (i, (s, m)) in sigs.iter().zip(messages.iter()).enumerate() {
+            s.verify(m, pks[i / 2].clone(), params[i / 2].clone())
+                .unwrap();
+        }
+        println!(
+            "Time to verify {} signatures one by one is {:?}",
+            sigs.len(),
+            start.elapsed()
+        );
+
+        // Corrupt some signatures, change a message of another and give wrong number of messages to one
+        let mut invalid_sigs = sigs.clone();
+        let mut invalid_messages = messages.clone();
+        invalid_sigs[1].e = Fr::rand(&mut rng);
+        invalid_sigs[4].A = G1Affine::rand(&mut rng);
+        invalid_messages[2][0] = Fr::rand(&mut rng);
+        invalid_messages[5].pop();
+        match batch(&invalid_sigs, &invalid_messages) {
+            Err(BBSPlusError::InvalidSignatures(indices)) => assert_eq!(indices, vec![1, 2, 4, 5]),
+            _ => panic!("batch should have failed"),
+        }
+
+        // Using the randomized pairing checker directly
+        let mut checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+        for (i, (s, m)) in sigs.iter().zip(messages.iter()).enumerate() {
+            s.verify_with_randomized_pairing_checker(
+                m,
+                pks[i / 2].clone(),
+                params[i / 2].clone(),
+                &mut checker,
+            )
+            .unwrap();
+        }
+        assert!(checker.verify());
+        invalid_sigs[1]
+            .verify_with_randomized_pairing_checker(
+                &messages[1],
+                pks[0].clone(),
+                params[0].clone(),
+                &mut checker,
+            )
+            .unwrap();
+        assert!(!checker.verify());
+
+        // Empty batch is trivially valid
+        SignatureG1::<Bls12_381>::verify_batch(&mut rng, &[]).unwrap();
+    }
 }
@@ -6,14 +6,18 @@ use crate::{
         MultiMessageSignatureParams, PreparedPublicKeyG2, PreparedSignatureParams23G1, SecretKey,
         SignatureParams23G1,
     },
+    signature::{verify_pairing_checks_in_batch, PairingCheckInBatch},
+};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup, Group,
 };
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ff::{fields::Field, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::BTreeMap, fmt::Debug, ops::Mul, rand::RngCore, vec::Vec, UniformRand, Zero,
 };
-use dock_crypto_utils::serde_utils::*;
+use dock_crypto_utils::{randomized_pairing_check::RandomizedPairingChecker, serde_utils::*};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -135,6 +139,14 @@ impl<E: Pairing> Signature23G1<E> {
     }
 }
 
+/// A signature to verify in a batch along with its messages, public key and params
+pub type Signature23G1BatchItem<'a, E> = (
+    &'a Signature23G1<E>,
+    &'a [<E as Pairing>::ScalarField],
+    &'a PreparedPublicKeyG2<E>,
+    &'a PreparedSignatureParams23G1<E>,
+);
+
 impl<E: Pairing> Signature23G1<E> {
     /// Verify the validity of the signature. Assumes that the public key and parameters
     /// have been validated already.
@@ -163,6 +175,48 @@ impl<E: Pairing> Signature23G1<E> {
         }
         Ok(())
     }
+
+    /// Same as `Self::verify` but adds the pairing check to the given `RandomizedPairingChecker` so
+    /// that it can be done together with other pairing checks. The caller must call `pairing_checker.verify()`
+    pub fn verify_with_randomized_pairing_checker(
+        &self,
+        messages: &[E::ScalarField],
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParams23G1<E>>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), BBSPlusError> {
+        let params = params.into();
+        let b = self.pre_verify(messages, &params)?;
+        let Aeb = self.A.mul(self.e) - b;
+        pairing_checker.add_multiple_sources_and_target(
+            &[self.A, Aeb.into_affine()],
+            [pk.into().0, params.g2],
+            &PairingOutput::zero(),
+        );
+        Ok(())
+    }
+
+    /// Verify several signatures, each with its own messages, public key and params, with a single
+    /// `RandomizedPairingChecker`. If the batch does not verify, it is bisected to find the invalid
+    /// signatures and their indices in `batch` are returned in `BBSPlusError::InvalidSignatures`.
+    /// Assumes that the public keys and parameters have been validated already.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        batch: &[Signature23G1BatchItem<E>],
+    ) -> Result<(), BBSPlusError> {
+        let checks = batch
+            .iter()
+            .map(|(sig, messages, pk, params)| -> PairingCheckInBatch<E> {
+                let b = sig.pre_verify(messages, params).ok()?;
+                let Aeb = sig.A.mul(sig.e) - b;
+                Some((
+                    [sig.A, Aeb.into_affine()],
+                    [pk.0.clone(), params.g2.clone()],
+                ))
+            })
+            .collect();
+        verify_pairing_checks_in_batch::<E, R>(rng, checks)
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +277,51 @@ mod tests {
 
         drop(sig);
     }
+
+    #[test]
+    fn batch_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let mut params = vec![];
+        let mut pks = vec![];
+        let mut sigs = vec![];
+        let mut messages = vec![];
+        for message_count in [2, 7, 7, 3] {
+            let p = SignatureParams23G1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+            let keypair = KeypairG2::<Bls12_381>::generate_using_rng_and_bbs23_params(&mut rng, &p);
+            let msgs = (0..message_count)
+                .map(|_| Fr::rand(&mut rng))
+                .collect::<Vec<_>>();
+            sigs.push(
+                Signature23G1::<Bls12_381>::new(&mut rng, &msgs, &keypair.secret_key, &p).unwrap(),
+            );
+            messages.push(msgs);
+            params.push(PreparedSignatureParams23G1::from(p));
+            pks.push(PreparedPublicKeyG2::from(keypair.public_key.clone()));
+        }
+
+        let batch = |sigs: &[Signature23G1<Bls12_381>], pks: &[PreparedPublicKeyG2<Bls12_381>]| {
+            Signature23G1::verify_batch(
+                &mut StdRng::seed_from_u64(1u64),
+                &(0..sigs.len())
+                    .map(|i| (&sigs[i], messages[i].as_slice(), &pks[i], &params[i]))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        batch(&sigs, &pks).unwrap();
+
+        // Swap the public keys of 2 signatures with the same number of messages
+        let mut wrong_pks = pks.clone();
+        wrong_pks.swap(1, 2);
+        match batch(&sigs, &wrong_pks) {
+            Err(BBSPlusError::InvalidSignatures(indices)) => assert_eq!(indices, vec![1, 2]),
+            _ => panic!("batch should have failed"),
+        }
+
+        let mut invalid_sigs = sigs.clone();
+        invalid_sigs[3].A = G1Affine::zero();
+        match batch(&invalid_sigs, &pks) {
+            Err(BBSPlusError::InvalidSignatures(indices)) => assert_eq!(indices, vec![3]),
+            _ => panic!("batch should have failed"),
+        }
+    }
 }
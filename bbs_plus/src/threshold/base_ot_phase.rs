@@ -222,18 +222,30 @@ impl<G: AffineRepr> BaseOTPhase<G> {
         }
     }
 
-    pub fn finish(mut self) -> BaseOTPhaseOutput {
-        // TODO: Ensure keys from everyone
+    /// Returns an error naming the participant with whom the base OT hasn't finished, i.e. either the keys are missing or
+    /// their verification hasn't been done.
+    pub fn finish(mut self) -> Result<BaseOTPhaseOutput, BBSPlusError> {
+        for id in self.sender_setup.keys() {
+            if !self.sender_keys.contains_key(id) || self.sender_challenger.contains_key(id) {
+                return Err(BBSPlusError::IncompleteBaseOTWith(*id));
+            }
+        }
         let mut base_ot_receiver = BTreeMap::new();
         for (id, choice) in self.receiver_choices {
-            let keys = self.receiver_keys.remove(&id).unwrap();
+            if self.receiver_responder.contains_key(&id) {
+                return Err(BBSPlusError::IncompleteBaseOTWith(id));
+            }
+            let keys = self
+                .receiver_keys
+                .remove(&id)
+                .ok_or(BBSPlusError::IncompleteBaseOTWith(id))?;
             base_ot_receiver.insert(id, (choice, keys));
         }
-        BaseOTPhaseOutput {
+        Ok(BaseOTPhaseOutput {
             id: self.id,
             sender_keys: self.sender_keys,
             receiver: base_ot_receiver,
-        }
+        })
     }
 }
 
@@ -324,7 +336,7 @@ pub mod tests {
 
         let mut base_ot_outputs = vec![];
         for b in base_ots {
-            base_ot_outputs.push(b.finish());
+            base_ot_outputs.push(b.finish().unwrap());
         }
 
         for base_ot in &base_ot_outputs {
@@ -348,6 +360,22 @@ pub mod tests {
         let num_parties = 5;
         let all_party_set = (1..=num_parties).into_iter().collect::<BTreeSet<_>>();
 
-        do_base_ot_for_threshold_sig::<16>(&mut rng, num_base_ot, num_parties, all_party_set);
+        do_base_ot_for_threshold_sig::<16>(
+            &mut rng,
+            num_base_ot,
+            num_parties,
+            all_party_set.clone(),
+        );
+
+        // Can't finish before base OT with everyone is done
+        let B = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let mut others = all_party_set;
+        others.remove(&3);
+        let (base_ot, _) =
+            BaseOTPhase::init::<_, Blake2b512>(&mut rng, 3, others, num_base_ot, &B).unwrap();
+        assert!(matches!(
+            base_ot.finish(),
+            Err(BBSPlusError::IncompleteBaseOTWith(4))
+        ));
     }
 }
@@ -0,0 +1,436 @@
+//! Distributed key generation for threshold BBS and BBS+ so that no single party ever knows the signing key.
+//! Uses the DKG from the FROST paper from `secret_sharing_and_dkg::frost_dkg`, i.e. Feldman VSS where each party also
+//! proves knowledge of its secret, with `g2` of the signature params as the public key base.
+//!
+//! The DKG messages are processed using the methods of `frost_dkg::Round1State` and `frost_dkg::Round2State` and once
+//! a signer has received the shares of all other signers, it calls `ThresholdKey::finish_dkg` to get its share of the
+//! signing key, the threshold public key and the public key shares of all signers. The share of the signing key is then
+//! used as the `signing_key` in `Phase1::finish_for_bbs_plus` or `Phase1::finish_for_bbs`.
+//!
+//! The shares of the signing key can be refreshed periodically without changing the public key using
+//! `ThresholdKey::start_refresh` and `ThresholdKey::finish_refresh`. Each signer deals a sharing of 0 to all signers,
+//! and the commitments to the dealt polynomials are published so that verifiers can refresh the public key shares
+//! using `refresh_public_key_shares`.
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::RngCore,
+    vec,
+    vec::Vec,
+};
+use digest::Digest;
+use oblivious_transfer_protocols::ParticipantId;
+use secret_sharing_and_dkg::{
+    common::{CommitmentToCoefficients, Share, Shares},
+    error::SSError,
+    feldman_dvss_dkg::reconstruct_threshold_public_key,
+    frost_dkg, share_refresh,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::BBSPlusError, setup::PublicKeyG2};
+
+pub type Round1State<E> = frost_dkg::Round1State<<E as Pairing>::G2Affine>;
+pub type Round1Msg<E> = frost_dkg::Round1Msg<<E as Pairing>::G2Affine>;
+pub type Round2State<E> = frost_dkg::Round2State<<E as Pairing>::G2Affine>;
+pub type RefreshAccumulator<E> = share_refresh::RefreshAccumulator<<E as Pairing>::G2Affine>;
+
+/// Output of the DKG for a signer
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct ThresholdKey<E: Pairing> {
+    /// Shamir share of the signing key
+    pub share: Share<E::ScalarField>,
+    /// Public key corresponding to the signing key
+    pub public_key: PublicKeyG2<E>,
+    /// Public key share `g2 * share` of each signer, including this one
+    pub public_key_shares: BTreeMap<ParticipantId, PublicKeyG2<E>>,
+}
+
+impl<E: Pairing> ThresholdKey<E> {
+    /// Start the DKG with a random secret. `protocol_id` is used as the context of the proof of knowledge
+    /// of the secret so should be unique for each DKG run. `g2` is from the signature params
+    pub fn start_dkg<R: RngCore, D: Digest>(
+        rng: &mut R,
+        participant_id: ParticipantId,
+        threshold: ParticipantId,
+        total: ParticipantId,
+        protocol_id: &[u8],
+        g2: &E::G2Affine,
+    ) -> Result<(Round1State<E>, Round1Msg<E>), BBSPlusError> {
+        Ok(Round1State::<E>::start_with_random_secret::<R, D>(
+            rng,
+            participant_id,
+            threshold,
+            total,
+            protocol_id,
+            g2,
+        )?)
+    }
+
+    /// Finish the DKG after receiving shares from all other signers in round 2
+    pub fn finish_dkg(round2: Round2State<E>, g2: &E::G2Affine) -> Result<Self, BBSPlusError> {
+        let public_key_shares = Self::compute_public_key_shares(&round2)?;
+        let (share, own_pk, pk) = round2.finish(g2)?;
+        debug_assert_eq!(public_key_shares.get(&share.id).unwrap().0, own_pk);
+        Ok(Self {
+            share,
+            public_key: PublicKeyG2(pk),
+            public_key_shares,
+        })
+    }
+
+    pub fn id(&self) -> ParticipantId {
+        self.share.id
+    }
+
+    pub fn threshold(&self) -> ParticipantId {
+        self.share.threshold
+    }
+
+    /// Share of the signing key to be used in phase 1 of threshold signing
+    pub fn signing_key_share(&self) -> &E::ScalarField {
+        &self.share.share
+    }
+
+    /// Start refreshing the share of the signing key by dealing shares of 0 to all signers. The share for each of the
+    /// other signers, i.e. the one with the signer's id, is sent to it along with the commitments which are also published
+    /// for verifiers. The received shares are added to the returned accumulator using `RefreshAccumulator::add_received_share`.
+    /// The signer ids need not be contiguous, e.g. after a signer is removed.
+    #[allow(clippy::type_complexity)]
+    pub fn start_refresh<R: RngCore>(
+        &self,
+        rng: &mut R,
+        g2: &E::G2Affine,
+    ) -> Result<
+        (
+            RefreshAccumulator<E>,
+            Shares<E::ScalarField>,
+            CommitmentToCoefficients<E::G2Affine>,
+        ),
+        BBSPlusError,
+    > {
+        // Deal shares for ids up to the largest one and keep the shares of the signers
+        let max_id = self
+            .public_key_shares
+            .keys()
+            .last()
+            .copied()
+            .unwrap_or_default()
+            .max(self.id());
+        let (shares, comms) = share_refresh::deal_zero_sharing(rng, self.threshold(), max_id, g2)?;
+        let shares = Shares(
+            shares
+                .0
+                .into_iter()
+                .filter(|s| self.public_key_shares.contains_key(&s.id))
+                .collect(),
+        );
+        let own_share = shares
+            .0
+            .iter()
+            .find(|s| s.id == self.id())
+            .ok_or(SSError::InvalidParticipantId(self.id()))?
+            .clone();
+        let mut acc = RefreshAccumulator::<E>::new(self.id(), self.threshold());
+        acc.add_self_share(own_share, comms.clone());
+        Ok((acc, shares, comms))
+    }
+
+    /// Finish refreshing after receiving shares of 0 from all signers. Returns the key with the new share of the signing
+    /// key and public key shares, the public key remains unchanged.
+    pub fn finish_refresh(
+        &self,
+        acc: RefreshAccumulator<E>,
+        g2: &E::G2Affine,
+    ) -> Result<Self, BBSPlusError> {
+        let dealers = self
+            .public_key_shares
+            .keys()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let coeff_comms = acc.coeff_comms.clone();
+        let share = acc.finalize(&self.share, &dealers)?;
+        let public_key_shares =
+            refresh_public_key_shares(&self.public_key_shares, &coeff_comms, self.threshold())?;
+        if public_key_shares.get(&share.id).unwrap().0 != (*g2 * share.share).into_affine() {
+            return Err(SSError::InvalidShare.into());
+        }
+        Ok(Self {
+            share,
+            public_key: self.public_key.clone(),
+            public_key_shares,
+        })
+    }
+
+    /// Public key share of signer `id` is the evaluation of the committed polynomial at `id`, i.e.
+    /// `\sum_{k} C_k * id^k` where `C_k` is the sum of commitments to the `k`-th coefficients by all signers
+    fn compute_public_key_shares(
+        round2: &Round2State<E>,
+    ) -> Result<BTreeMap<ParticipantId, PublicKeyG2<E>>, BBSPlusError> {
+        let threshold = round2.threshold as usize;
+        let mut comm_coeffs = vec![E::G2::zero(); threshold];
+        for comm in round2.coeff_comms.values() {
+            if !comm.supports_threshold(round2.threshold) {
+                return Err(SSError::DoesNotSupportThreshold(round2.threshold).into());
+            }
+            for (c, comm_c) in comm_coeffs.iter_mut().zip(comm.0.iter()) {
+                *c += comm_c;
+            }
+        }
+        let ids = round2.coeff_comms.keys().copied().collect::<Vec<_>>();
+        let pk_shares = ids
+            .iter()
+            .map(|id| {
+                let x = E::ScalarField::from(*id);
+                // Horner's method
+                comm_coeffs
+                    .iter()
+                    .rev()
+                    .fold(E::G2::zero(), |acc, c| acc * x + c)
+            })
+            .collect::<Vec<_>>();
+        Ok(ids
+            .into_iter()
+            .zip(E::G2::normalize_batch(&pk_shares))
+            .map(|(id, pk)| (id, PublicKeyG2(pk)))
+            .collect())
+    }
+}
+
+/// Verify that the public key shares of a threshold number of signers combine to the threshold public key
+pub fn verify_public_key_shares<E: Pairing>(
+    public_key: &PublicKeyG2<E>,
+    public_key_shares: &BTreeMap<ParticipantId, PublicKeyG2<E>>,
+    threshold: ParticipantId,
+) -> Result<(), BBSPlusError> {
+    let pks = public_key_shares
+        .iter()
+        .map(|(id, pk)| (*id, pk.0))
+        .collect::<Vec<_>>();
+    let pk = reconstruct_threshold_public_key(pks, threshold)?;
+    if pk != public_key.0 || pk.is_zero() {
+        return Err(BBSPlusError::InvalidPublicKey);
+    }
+    Ok(())
+}
+
+/// Refresh the public key shares of the signers using the commitments published by the signers while refreshing their shares
+pub fn refresh_public_key_shares<E: Pairing>(
+    public_key_shares: &BTreeMap<ParticipantId, PublicKeyG2<E>>,
+    coeff_comms: &BTreeMap<ParticipantId, CommitmentToCoefficients<E::G2Affine>>,
+    threshold: ParticipantId,
+) -> Result<BTreeMap<ParticipantId, PublicKeyG2<E>>, BBSPlusError> {
+    let pk_shares = public_key_shares
+        .iter()
+        .map(|(id, pk)| (*id, pk.0))
+        .collect();
+    Ok(
+        share_refresh::refresh_public_key_shares(&pk_shares, coeff_comms, threshold)?
+            .into_iter()
+            .map(|(id, pk)| (id, PublicKeyG2(pk)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+    use ark_ff::Zero;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+    use secret_sharing_and_dkg::common::lagrange_basis_at_0;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type G2 = <Bls12_381 as Pairing>::G2Affine;
+
+    /// Run the DKG among all `total` signers. Also returns the signing key which would be unknown
+    /// in practice, only for testing
+    pub fn dkg_keygen<R: RngCore>(
+        rng: &mut R,
+        threshold: ParticipantId,
+        total: ParticipantId,
+        g2: &G2,
+    ) -> (Fr, Vec<ThresholdKey<Bls12_381>>) {
+        let protocol_id = b"test-dkg";
+        let mut round1_states = vec![];
+        let mut round1_msgs = vec![];
+        for i in 1..=total {
+            let (state, msg) = ThresholdKey::<Bls12_381>::start_dkg::<_, Blake2b512>(
+                rng,
+                i,
+                threshold,
+                total,
+                protocol_id,
+                g2,
+            )
+            .unwrap();
+            round1_states.push(state);
+            round1_msgs.push(msg);
+        }
+        let sk = round1_states.iter().map(|s| s.secret).sum::<Fr>();
+
+        for (i, state) in round1_states.iter_mut().enumerate() {
+            for (j, msg) in round1_msgs.iter().enumerate() {
+                if i != j {
+                    state
+                        .add_received_message::<Blake2b512>(msg.clone(), protocol_id, g2)
+                        .unwrap();
+                }
+            }
+        }
+
+        let mut round2_states = vec![];
+        let mut all_shares = vec![];
+        for state in round1_states {
+            let (round2, shares) = state.finish().unwrap();
+            round2_states.push(round2);
+            all_shares.push(shares);
+        }
+        for (i, state) in round2_states.iter_mut().enumerate() {
+            for (j, shares) in all_shares.iter().enumerate() {
+                if i != j {
+                    state
+                        .add_received_share(j as ParticipantId + 1, shares.0[i].clone(), g2)
+                        .unwrap();
+                }
+            }
+        }
+
+        let keys = round2_states
+            .into_iter()
+            .map(|s| ThresholdKey::finish_dkg(s, g2).unwrap())
+            .collect();
+        (sk, keys)
+    }
+
+    #[test]
+    fn distributed_key_generation() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let g2 = G2::rand(&mut rng);
+
+        for (threshold, total) in [(2, 3), (3, 5), (5, 8)] {
+            let (sk, keys) = dkg_keygen(&mut rng, threshold, total, &g2);
+            assert_eq!(keys.len(), total as usize);
+
+            let public_key = keys[0].public_key.clone();
+            assert_eq!(public_key.0, g2 * sk);
+            for (i, key) in keys.iter().enumerate() {
+                assert_eq!(key.id(), i as ParticipantId + 1);
+                assert_eq!(key.threshold(), threshold);
+                assert_eq!(key.public_key, public_key);
+                assert_eq!(key.public_key_shares, keys[0].public_key_shares);
+                assert_eq!(
+                    key.public_key_shares.get(&key.id()).unwrap().0,
+                    g2 * key.signing_key_share()
+                );
+            }
+            verify_public_key_shares(&public_key, &keys[0].public_key_shares, threshold).unwrap();
+
+            let mut wrong_pk_shares = keys[0].public_key_shares.clone();
+            wrong_pk_shares.insert(1, PublicKeyG2(G2::rand(&mut rng)));
+            assert!(verify_public_key_shares(&public_key, &wrong_pk_shares, threshold).is_err());
+
+            // Any threshold number of signers can reconstruct the signing key
+            for signers in [
+                (1..=threshold).collect::<Vec<_>>(),
+                (total - threshold + 1..=total).collect::<Vec<_>>(),
+            ] {
+                let mut reconstructed = Fr::zero();
+                for id in &signers {
+                    reconstructed += lagrange_basis_at_0::<Fr>(&signers, *id)
+                        * keys[*id as usize - 1].signing_key_share();
+                }
+                assert_eq!(reconstructed, sk);
+            }
+        }
+    }
+
+    #[test]
+    fn refresh_key_shares() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let g2 = G2::rand(&mut rng);
+
+        for (threshold, total, removed) in [(2, 3, None), (3, 5, None), (3, 5, Some(2))] {
+            let (sk, mut keys) = dkg_keygen(&mut rng, threshold, total, &g2);
+            // A removed signer leaves a gap in the ids of the remaining signers
+            if let Some(removed) = removed {
+                keys.remove(removed as usize - 1);
+                for key in keys.iter_mut() {
+                    key.public_key_shares.remove(&removed);
+                }
+            }
+
+            let mut accs = vec![];
+            let mut all_shares = vec![];
+            let mut all_comms = BTreeMap::new();
+            for key in &keys {
+                let (acc, shares, comms) = key.start_refresh(&mut rng, &g2).unwrap();
+                accs.push(acc);
+                all_shares.push(shares);
+                all_comms.insert(key.id(), comms);
+            }
+
+            // Can't finish before receiving shares from everyone
+            assert!(keys[0].finish_refresh(accs[0].clone(), &g2).is_err());
+
+            for (acc, key) in accs.iter_mut().zip(keys.iter()) {
+                for (dealer, shares) in keys.iter().zip(all_shares.iter()) {
+                    assert_eq!(shares.0.len(), keys.len());
+                    if dealer.id() != key.id() {
+                        let share = shares.0.iter().find(|s| s.id == key.id()).unwrap();
+                        acc.add_received_share(
+                            dealer.id(),
+                            share.clone(),
+                            all_comms[&dealer.id()].clone(),
+                            &g2,
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+
+            let new_keys = keys
+                .iter()
+                .zip(accs)
+                .map(|(key, acc)| key.finish_refresh(acc, &g2).unwrap())
+                .collect::<Vec<_>>();
+
+            // Verifiers refresh the public key shares from the published commitments
+            let new_pk_shares =
+                refresh_public_key_shares(&keys[0].public_key_shares, &all_comms, threshold)
+                    .unwrap();
+            for (key, new_key) in keys.iter().zip(new_keys.iter()) {
+                assert_eq!(new_key.public_key, key.public_key);
+                assert_eq!(new_key.public_key_shares, new_pk_shares);
+                assert_ne!(new_key.signing_key_share(), key.signing_key_share());
+                assert_ne!(
+                    new_key.public_key_shares[&key.id()],
+                    key.public_key_shares[&key.id()]
+                );
+            }
+            verify_public_key_shares(&new_keys[0].public_key, &new_pk_shares, threshold).unwrap();
+
+            let signers = new_keys
+                .iter()
+                .take(threshold as usize)
+                .map(|k| k.id())
+                .collect::<Vec<_>>();
+            let mut reconstructed = Fr::zero();
+            for key in new_keys.iter().take(threshold as usize) {
+                reconstructed +=
+                    lagrange_basis_at_0::<Fr>(&signers, key.id()) * key.signing_key_share();
+            }
+            assert_eq!(reconstructed, sk);
+        }
+    }
+}
@@ -9,13 +9,20 @@
 //! in threshold signing, phase 1 and 2 support batching meaning that to generate `n` signatures only a single execution
 //! of phase 1 and 2 needs to done, although with larger inputs. Then `n` executions of phase 3 are done to generate
 //! the signature.
-//! Also its assumed that parties have done the DKG as well as the base OT and stored their results.
+//! Also its assumed that parties have done the DKG as well as the base OT and stored their results. The DKG can be done
+//! using the `keygen` module.
+//! The `session` module has a state machine per signer which drives all phases of threshold BBS+ signing.
 //! Both BBS and BBS+ implementations share the same multiplication phase and the base OT phase but their phase 1 is slightly different.
+//!
+//! A signer sending an incorrect `R` or `u` in its signature share is detected only when the aggregated signature fails
+//! to verify and can't be identified since the shares are not accompanied by proofs of correctness.
 
 pub mod base_ot_phase;
 pub mod cointoss;
+pub mod keygen;
 pub mod multiplication_phase;
 pub mod randomness_generation_phase;
+pub mod session;
 pub mod threshold_bbs;
 pub mod threshold_bbs_plus;
 pub mod utils;
@@ -199,10 +199,19 @@ impl<F: PrimeField, const KAPPA: u16, const STATISTICAL_SECURITY_PARAMETER: u16>
         Ok(())
     }
 
-    pub fn finish(self) -> Phase2Output<F> {
-        Phase2Output {
+    /// Returns an error naming the participant with whom the multiplication hasn't finished
+    pub fn finish(self) -> Result<Phase2Output<F>, BBSPlusError> {
+        if let Some(id) = self
+            .multiplication_party1
+            .keys()
+            .chain(self.multiplication_party2.keys())
+            .next()
+        {
+            return Err(BBSPlusError::IncompleteMultiplicationWith(*id));
+        }
+        Ok(Phase2Output {
             z_A: self.z_A,
             z_B: self.z_B,
-        }
+        })
     }
 }
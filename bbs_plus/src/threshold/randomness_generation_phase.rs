@@ -72,6 +72,21 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         self,
         signing_key: &F,
     ) -> Result<(Vec<ParticipantId>, Vec<F>, Vec<F>, Vec<F>), BBSPlusError> {
+        // Every participant should have taken part in both the joint randomness and the zero sharing protocols
+        for id in self.zero_sharing_protocol.cointoss_protocols.keys() {
+            if !self.commitment_protocol.has_shares_from(id) {
+                return Err(BBSPlusError::MissingSharesFromParticipant(*id));
+            }
+        }
+        for id in self.commitment_protocol.other_commitments.keys() {
+            if !self
+                .zero_sharing_protocol
+                .cointoss_protocols
+                .contains_key(id)
+            {
+                return Err(BBSPlusError::UnexpectedParticipant(*id));
+            }
+        }
         let others = self
             .commitment_protocol
             .other_shares
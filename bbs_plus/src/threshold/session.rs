@@ -0,0 +1,868 @@
+//! A per-signer state machine that drives all phases of threshold BBS+ signing, i.e. the base OT (optional as it
+//! needs to be done only once), both rounds of phase 1 and both rounds of phase 2 so that the caller only needs to
+//! move `Envelope`s between signers over its own transport.
+//!
+//! A signer creates its session with `ThresholdBBSPlusSession::start_with_base_ot` or, if it has the output of an
+//! earlier base OT with the other signers, `ThresholdBBSPlusSession::start`. Both return the messages to be sent to the
+//! other signers. Each received message is passed to `ThresholdBBSPlusSession::process` which returns the messages to
+//! be sent in response. The session moves to the next round on its own once it has received the expected message from
+//! every other signer in the current round. Messages that belong to a later round than the session's current one
+//! (possible when the transport reorders them) are buffered and processed once the session reaches that round.
+//! Once the session is in `SessionRound::Finished`, `ThresholdBBSPlusSession::signature_share` creates the signature
+//! shares for the batch.
+//!
+//! The session and the messages implement `CanonicalSerialize` and `CanonicalDeserialize` so the session can be
+//! persisted between rounds.
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::{collections::BTreeSet, rand::RngCore, vec, vec::Vec};
+use digest::{Digest, DynDigest};
+use oblivious_transfer_protocols::{
+    base_ot::simplest_ot::{Challenges, HashedKey, ReceiverPubKeys, Responses},
+    ot_based_multiplication::{
+        dkls18_mul_2p::MultiplicationOTEParams, dkls19_batch_mul_2p::GadgetVector,
+    },
+    ParticipantId,
+};
+
+use crate::{
+    error::BBSPlusError,
+    setup::SignatureParamsG1,
+    threshold::{
+        base_ot_phase::{BaseOTPhase, BaseOTPhaseOutput, SenderPubKeyAndProof},
+        cointoss::Commitments,
+        multiplication_phase::{Message1, Message2, Phase2, Phase2Output},
+        randomness_generation_phase::Phase1,
+        threshold_bbs_plus::{BBSPlusSignatureShare, Phase1Output},
+    },
+};
+
+/// Round of the signing session. The rounds are run in the order of declaration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SessionRound {
+    /// Base OT with each of the other signers
+    BaseOT,
+    /// Phase 1, exchanging commitments for the joint randomness and the zero sharing
+    Phase1Commitments,
+    /// Phase 1, exchanging the committed shares
+    Phase1Shares,
+    /// Phase 2, the OT based multiplication
+    Phase2,
+    /// Signature shares can be created
+    Finished,
+}
+
+/// Messages exchanged by the signers during a session
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message<E: Pairing, const SALT_SIZE: usize> {
+    /// Sent by the base OT sender, i.e. the signer with the smaller id
+    BaseOTSenderPubKey(SenderPubKeyAndProof<E::G1Affine>),
+    /// Sent by the base OT receiver in response to `Message::BaseOTSenderPubKey`
+    BaseOTReceiverPubKey(ReceiverPubKeys<E::G1Affine>),
+    /// Sent by the base OT sender in response to `Message::BaseOTReceiverPubKey`
+    BaseOTChallenges(Challenges),
+    /// Sent by the base OT receiver in response to `Message::BaseOTChallenges`
+    BaseOTResponses(Responses),
+    /// Sent by the base OT sender in response to `Message::BaseOTResponses`
+    BaseOTHashedKeys(Vec<(HashedKey, HashedKey)>),
+    /// Commitments for the joint randomness and the zero sharing with the receiver
+    Phase1Commitments {
+        comm: Commitments,
+        comm_zero_share: Commitments,
+    },
+    /// Shares committed in `Message::Phase1Commitments`
+    Phase1Shares {
+        shares: Vec<(E::ScalarField, [u8; SALT_SIZE])>,
+        zero_shares: Vec<(E::ScalarField, [u8; SALT_SIZE])>,
+    },
+    /// Sent by Party2 of the multiplication protocol, i.e. the signer with the smaller id
+    Phase2Message1(Message1<E::ScalarField>),
+    /// Sent by Party1 of the multiplication protocol in response to `Message::Phase2Message1`
+    Phase2Message2(Message2<E::ScalarField>),
+}
+
+/// A message along with its sender and receiver
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Envelope<E: Pairing, const SALT_SIZE: usize> {
+    pub sender: ParticipantId,
+    pub receiver: ParticipantId,
+    pub message: Message<E, SALT_SIZE>,
+}
+
+/// Parameters of the session which must be same for all signers
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SessionConfig<E: Pairing, const KAPPA: u16, const STATISTICAL_SECURITY_PARAMETER: u16> {
+    /// Unique for each session
+    pub protocol_id: Vec<u8>,
+    /// Number of signatures being generated in the session
+    pub batch_size: u32,
+    /// Group generator used in the base OT
+    pub base_ot_g: E::G1Affine,
+    pub ote_params: MultiplicationOTEParams<KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+    pub gadget_vector: GadgetVector<E::ScalarField, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+}
+
+/// Signing session of a single signer. `BASE_OT_KEY_SIZE` and `SALT_SIZE` are the key size of the base OT and the
+/// salt size of phase 1 respectively.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ThresholdBBSPlusSession<
+    E: Pairing,
+    const BASE_OT_KEY_SIZE: u16,
+    const SALT_SIZE: usize,
+    const KAPPA: u16,
+    const STATISTICAL_SECURITY_PARAMETER: u16,
+> {
+    pub id: ParticipantId,
+    /// Other signers in this session
+    pub others: BTreeSet<ParticipantId>,
+    pub config: SessionConfig<E, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+    /// Share of the signing key
+    pub signing_key: E::ScalarField,
+    pub round: SessionRound,
+    /// Signers from which the expected messages of the current round have been received
+    pub done_with: BTreeSet<ParticipantId>,
+    /// Received messages of a future round
+    pub pending: Vec<Envelope<E, SALT_SIZE>>,
+    pub base_ot: Option<BaseOTPhase<E::G1Affine>>,
+    pub base_ot_output: Option<BaseOTPhaseOutput>,
+    pub phase1: Option<Phase1<E::ScalarField, SALT_SIZE>>,
+    pub phase1_output: Option<Phase1Output<E::ScalarField>>,
+    pub phase2: Option<Phase2<E::ScalarField, KAPPA, STATISTICAL_SECURITY_PARAMETER>>,
+    pub phase2_output: Option<Phase2Output<E::ScalarField>>,
+}
+
+impl<E: Pairing, const SALT_SIZE: usize> Message<E, SALT_SIZE> {
+    /// The round in which this message is processed by its receiver
+    pub fn round(&self) -> SessionRound {
+        match self {
+            Self::BaseOTSenderPubKey(_)
+            | Self::BaseOTReceiverPubKey(_)
+            | Self::BaseOTChallenges(_)
+            | Self::BaseOTResponses(_)
+            | Self::BaseOTHashedKeys(_) => SessionRound::BaseOT,
+            Self::Phase1Commitments { .. } => SessionRound::Phase1Commitments,
+            Self::Phase1Shares { .. } => SessionRound::Phase1Shares,
+            Self::Phase2Message1(_) | Self::Phase2Message2(_) => SessionRound::Phase2,
+        }
+    }
+}
+
+impl<
+        E: Pairing,
+        const BASE_OT_KEY_SIZE: u16,
+        const SALT_SIZE: usize,
+        const KAPPA: u16,
+        const STATISTICAL_SECURITY_PARAMETER: u16,
+    >
+    ThresholdBBSPlusSession<E, BASE_OT_KEY_SIZE, SALT_SIZE, KAPPA, STATISTICAL_SECURITY_PARAMETER>
+{
+    /// Start a session which begins with the base OT with the other signers
+    pub fn start_with_base_ot<R: RngCore, D: Digest>(
+        rng: &mut R,
+        id: ParticipantId,
+        others: BTreeSet<ParticipantId>,
+        signing_key: E::ScalarField,
+        config: SessionConfig<E, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+    ) -> Result<(Self, Vec<Envelope<E, SALT_SIZE>>), BBSPlusError> {
+        if others.contains(&id) {
+            return Err(BBSPlusError::ParticipantCannotBePresentInOthers(id));
+        }
+        let (base_ot, sender_pks) = BaseOTPhase::init::<R, D>(
+            rng,
+            id,
+            others.clone(),
+            config.ote_params.num_base_ot(),
+            &config.base_ot_g,
+        )?;
+        let out = sender_pks
+            .into_iter()
+            .map(|(receiver, pk)| Envelope {
+                sender: id,
+                receiver,
+                message: Message::BaseOTSenderPubKey(pk),
+            })
+            .collect();
+        let session = Self {
+            id,
+            others,
+            config,
+            signing_key,
+            round: SessionRound::BaseOT,
+            done_with: BTreeSet::new(),
+            pending: Vec::new(),
+            base_ot: Some(base_ot),
+            base_ot_output: None,
+            phase1: None,
+            phase1_output: None,
+            phase2: None,
+            phase2_output: None,
+        };
+        Ok((session, out))
+    }
+
+    /// Start a session using the output of an earlier base OT with the other signers
+    pub fn start<R: RngCore>(
+        rng: &mut R,
+        id: ParticipantId,
+        others: BTreeSet<ParticipantId>,
+        signing_key: E::ScalarField,
+        config: SessionConfig<E, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+        base_ot_output: BaseOTPhaseOutput,
+    ) -> Result<(Self, Vec<Envelope<E, SALT_SIZE>>), BBSPlusError> {
+        if others.contains(&id) {
+            return Err(BBSPlusError::ParticipantCannotBePresentInOthers(id));
+        }
+        let mut session = Self {
+            id,
+            others,
+            config,
+            signing_key,
+            round: SessionRound::Phase1Commitments,
+            done_with: BTreeSet::new(),
+            pending: Vec::new(),
+            base_ot: None,
+            base_ot_output: Some(base_ot_output),
+            phase1: None,
+            phase1_output: None,
+            phase2: None,
+            phase2_output: None,
+        };
+        let mut out = vec![];
+        session.start_phase1(rng, &mut out)?;
+        Ok((session, out))
+    }
+
+    /// Process a message received from another signer and return the messages to be sent in response
+    pub fn process<R: RngCore, D: Digest + Default + DynDigest + Clone>(
+        &mut self,
+        rng: &mut R,
+        envelope: Envelope<E, SALT_SIZE>,
+    ) -> Result<Vec<Envelope<E, SALT_SIZE>>, BBSPlusError> {
+        if envelope.receiver != self.id {
+            return Err(BBSPlusError::MessageNotForParticipant(
+                envelope.receiver,
+                self.id,
+            ));
+        }
+        if !self.others.contains(&envelope.sender) {
+            return Err(BBSPlusError::UnexpectedParticipant(envelope.sender));
+        }
+        let mut out = vec![];
+        self.handle::<R, D>(rng, envelope, &mut out)?;
+        // Buffered messages might have become processable after a round change
+        while let Some(i) = self
+            .pending
+            .iter()
+            .position(|e| e.message.round() == self.round)
+        {
+            let envelope = self.pending.remove(i);
+            self.handle::<R, D>(rng, envelope, &mut out)?;
+        }
+        Ok(out)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.round == SessionRound::Finished
+    }
+
+    /// Output of the base OT which can be used to start future sessions with the same signers
+    pub fn base_ot_output(&self) -> Option<&BaseOTPhaseOutput> {
+        self.base_ot_output.as_ref()
+    }
+
+    /// Create the share of the signature at index `sig_index_in_batch` of the batch
+    pub fn signature_share(
+        &self,
+        messages: &[E::ScalarField],
+        sig_index_in_batch: usize,
+        sig_params: &SignatureParamsG1<E>,
+    ) -> Result<BBSPlusSignatureShare<E>, BBSPlusError> {
+        match (&self.phase1_output, &self.phase2_output) {
+            (Some(phase1), Some(phase2)) => {
+                BBSPlusSignatureShare::new(messages, sig_index_in_batch, phase1, phase2, sig_params)
+            }
+            _ => Err(BBSPlusError::SessionNotFinished),
+        }
+    }
+
+    fn handle<R: RngCore, D: Digest + Default + DynDigest + Clone>(
+        &mut self,
+        rng: &mut R,
+        envelope: Envelope<E, SALT_SIZE>,
+        out: &mut Vec<Envelope<E, SALT_SIZE>>,
+    ) -> Result<(), BBSPlusError> {
+        let msg_round = envelope.message.round();
+        if msg_round > self.round {
+            self.pending.push(envelope);
+            return Ok(());
+        }
+        let sender = envelope.sender;
+        if msg_round < self.round {
+            return Err(BBSPlusError::UnexpectedMessageFrom(sender));
+        }
+        let id = self.id;
+        let reply = |message| Envelope {
+            sender: id,
+            receiver: sender,
+            message,
+        };
+        match envelope.message {
+            Message::BaseOTSenderPubKey(pk) => {
+                let pk = self
+                    .base_ot
+                    .as_mut()
+                    .unwrap()
+                    .receive_sender_pubkey::<R, D, BASE_OT_KEY_SIZE>(
+                        rng,
+                        sender,
+                        pk,
+                        &self.config.base_ot_g,
+                    )?;
+                out.push(reply(Message::BaseOTReceiverPubKey(pk)));
+            }
+            Message::BaseOTReceiverPubKey(pk) => {
+                let challenges = self
+                    .base_ot
+                    .as_mut()
+                    .unwrap()
+                    .receive_receiver_pubkey::<BASE_OT_KEY_SIZE>(sender, pk)?;
+                out.push(reply(Message::BaseOTChallenges(challenges)));
+            }
+            Message::BaseOTChallenges(challenges) => {
+                let responses = self
+                    .base_ot
+                    .as_mut()
+                    .unwrap()
+                    .receive_challenges(sender, challenges)?;
+                out.push(reply(Message::BaseOTResponses(responses)));
+            }
+            Message::BaseOTResponses(responses) => {
+                let hashed_keys = self
+                    .base_ot
+                    .as_mut()
+                    .unwrap()
+                    .receive_responses(sender, responses)?;
+                out.push(reply(Message::BaseOTHashedKeys(hashed_keys)));
+                self.done_with.insert(sender);
+            }
+            Message::BaseOTHashedKeys(hashed_keys) => {
+                self.base_ot
+                    .as_mut()
+                    .unwrap()
+                    .receive_hashed_keys(sender, hashed_keys)?;
+                self.done_with.insert(sender);
+            }
+            Message::Phase1Commitments {
+                comm,
+                comm_zero_share,
+            } => {
+                self.phase1
+                    .as_mut()
+                    .unwrap()
+                    .receive_commitment(sender, comm, comm_zero_share)?;
+                self.done_with.insert(sender);
+            }
+            Message::Phase1Shares {
+                shares,
+                zero_shares,
+            } => {
+                self.phase1
+                    .as_mut()
+                    .unwrap()
+                    .receive_shares(sender, shares, zero_shares)?;
+                self.done_with.insert(sender);
+            }
+            Message::Phase2Message1(msg) => {
+                let msg = self.phase2.as_mut().unwrap().receive_message1::<D>(
+                    sender,
+                    msg,
+                    &self.config.gadget_vector,
+                )?;
+                out.push(reply(Message::Phase2Message2(msg)));
+                self.done_with.insert(sender);
+            }
+            Message::Phase2Message2(msg) => {
+                self.phase2.as_mut().unwrap().receive_message2::<D>(
+                    sender,
+                    msg,
+                    &self.config.gadget_vector,
+                )?;
+                self.done_with.insert(sender);
+            }
+        }
+        if self.done_with == self.others {
+            self.next_round::<R, D>(rng, out)?;
+        }
+        Ok(())
+    }
+
+    /// Finish the current round and start the next one
+    fn next_round<R: RngCore, D: Default + DynDigest + Clone>(
+        &mut self,
+        rng: &mut R,
+        out: &mut Vec<Envelope<E, SALT_SIZE>>,
+    ) -> Result<(), BBSPlusError> {
+        self.done_with.clear();
+        match self.round {
+            SessionRound::BaseOT => {
+                self.base_ot_output = Some(self.base_ot.take().unwrap().finish()?);
+                self.start_phase1(rng, out)?;
+            }
+            SessionRound::Phase1Commitments => {
+                let phase1 = self.phase1.as_ref().unwrap();
+                let shares = phase1.get_comm_shares_and_salts();
+                for other in &self.others {
+                    out.push(Envelope {
+                        sender: self.id,
+                        receiver: *other,
+                        message: Message::Phase1Shares {
+                            shares: shares.clone(),
+                            zero_shares: phase1
+                                .get_comm_shares_and_salts_for_zero_sharing_protocol_with_other(
+                                    other,
+                                ),
+                        },
+                    });
+                }
+                self.round = SessionRound::Phase1Shares;
+            }
+            SessionRound::Phase1Shares => {
+                let phase1_output = self
+                    .phase1
+                    .take()
+                    .unwrap()
+                    .finish_for_bbs_plus::<D>(&self.signing_key)?;
+                let (phase2, msg1s) = Phase2::init(
+                    rng,
+                    self.id,
+                    phase1_output.masked_signing_key_shares.clone(),
+                    phase1_output.masked_rs.clone(),
+                    self.base_ot_output.clone().unwrap(),
+                    self.others.clone(),
+                    self.config.ote_params,
+                    &self.config.gadget_vector,
+                )?;
+                out.extend(msg1s.into_iter().map(|(receiver, msg)| Envelope {
+                    sender: self.id,
+                    receiver,
+                    message: Message::Phase2Message1(msg),
+                }));
+                self.phase1_output = Some(phase1_output);
+                self.phase2 = Some(phase2);
+                self.round = SessionRound::Phase2;
+            }
+            SessionRound::Phase2 => {
+                self.phase2_output = Some(self.phase2.take().unwrap().finish()?);
+                self.round = SessionRound::Finished;
+            }
+            SessionRound::Finished => (),
+        }
+        Ok(())
+    }
+
+    fn start_phase1<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        out: &mut Vec<Envelope<E, SALT_SIZE>>,
+    ) -> Result<(), BBSPlusError> {
+        let (phase1, comm, mut comm_zero_shares) = Phase1::init_for_bbs_plus(
+            rng,
+            self.config.batch_size,
+            self.id,
+            self.others.clone(),
+            self.config.protocol_id.clone(),
+        )?;
+        for other in &self.others {
+            out.push(Envelope {
+                sender: self.id,
+                receiver: *other,
+                message: Message::Phase1Commitments {
+                    comm: comm.clone(),
+                    comm_zero_share: comm_zero_shares.remove(other).unwrap(),
+                },
+            });
+        }
+        self.phase1 = Some(phase1);
+        self.round = SessionRound::Phase1Commitments;
+        Ok(())
+    }
+}
+
+mod serialization {
+    use super::*;
+
+    impl Valid for SessionRound {
+        fn check(&self) -> Result<(), SerializationError> {
+            Ok(())
+        }
+    }
+
+    impl CanonicalSerialize for SessionRound {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            (*self as u8).serialize_with_mode(writer, compress)
+        }
+
+        fn serialized_size(&self, compress: Compress) -> usize {
+            0u8.serialized_size(compress)
+        }
+    }
+
+    impl CanonicalDeserialize for SessionRound {
+        fn deserialize_with_mode<R: Read>(
+            reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            let t = u8::deserialize_with_mode(reader, compress, validate)?;
+            match t {
+                0u8 => Ok(Self::BaseOT),
+                1u8 => Ok(Self::Phase1Commitments),
+                2u8 => Ok(Self::Phase1Shares),
+                3u8 => Ok(Self::Phase2),
+                4u8 => Ok(Self::Finished),
+                _ => Err(SerializationError::InvalidData),
+            }
+        }
+    }
+
+    impl<E: Pairing, const SALT_SIZE: usize> Valid for Message<E, SALT_SIZE> {
+        fn check(&self) -> Result<(), SerializationError> {
+            match self {
+                Self::BaseOTSenderPubKey(m) => m.check(),
+                Self::BaseOTReceiverPubKey(m) => m.check(),
+                Self::BaseOTChallenges(m) => m.check(),
+                Self::BaseOTResponses(m) => m.check(),
+                Self::BaseOTHashedKeys(m) => m.check(),
+                Self::Phase1Commitments {
+                    comm,
+                    comm_zero_share,
+                } => {
+                    comm.check()?;
+                    comm_zero_share.check()
+                }
+                Self::Phase1Shares {
+                    shares,
+                    zero_shares,
+                } => {
+                    shares.check()?;
+                    zero_shares.check()
+                }
+                Self::Phase2Message1(m) => m.check(),
+                Self::Phase2Message2(m) => m.check(),
+            }
+        }
+    }
+
+    impl<E: Pairing, const SALT_SIZE: usize> CanonicalSerialize for Message<E, SALT_SIZE> {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            mut writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            match self {
+                Self::BaseOTSenderPubKey(m) => {
+                    0u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::BaseOTReceiverPubKey(m) => {
+                    1u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::BaseOTChallenges(m) => {
+                    2u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::BaseOTResponses(m) => {
+                    3u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::BaseOTHashedKeys(m) => {
+                    4u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::Phase1Commitments {
+                    comm,
+                    comm_zero_share,
+                } => {
+                    5u8.serialize_with_mode(&mut writer, compress)?;
+                    comm.serialize_with_mode(&mut writer, compress)?;
+                    comm_zero_share.serialize_with_mode(&mut writer, compress)
+                }
+                Self::Phase1Shares {
+                    shares,
+                    zero_shares,
+                } => {
+                    6u8.serialize_with_mode(&mut writer, compress)?;
+                    shares.serialize_with_mode(&mut writer, compress)?;
+                    zero_shares.serialize_with_mode(&mut writer, compress)
+                }
+                Self::Phase2Message1(m) => {
+                    7u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+                Self::Phase2Message2(m) => {
+                    8u8.serialize_with_mode(&mut writer, compress)?;
+                    m.serialize_with_mode(&mut writer, compress)
+                }
+            }
+        }
+
+        fn serialized_size(&self, compress: Compress) -> usize {
+            0u8.serialized_size(compress)
+                + match self {
+                    Self::BaseOTSenderPubKey(m) => m.serialized_size(compress),
+                    Self::BaseOTReceiverPubKey(m) => m.serialized_size(compress),
+                    Self::BaseOTChallenges(m) => m.serialized_size(compress),
+                    Self::BaseOTResponses(m) => m.serialized_size(compress),
+                    Self::BaseOTHashedKeys(m) => m.serialized_size(compress),
+                    Self::Phase1Commitments {
+                        comm,
+                        comm_zero_share,
+                    } => comm.serialized_size(compress) + comm_zero_share.serialized_size(compress),
+                    Self::Phase1Shares {
+                        shares,
+                        zero_shares,
+                    } => shares.serialized_size(compress) + zero_shares.serialized_size(compress),
+                    Self::Phase2Message1(m) => m.serialized_size(compress),
+                    Self::Phase2Message2(m) => m.serialized_size(compress),
+                }
+        }
+    }
+
+    impl<E: Pairing, const SALT_SIZE: usize> CanonicalDeserialize for Message<E, SALT_SIZE> {
+        fn deserialize_with_mode<R: Read>(
+            mut reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            let t = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+            match t {
+                0u8 => Ok(Self::BaseOTSenderPubKey(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                1u8 => Ok(Self::BaseOTReceiverPubKey(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                2u8 => Ok(Self::BaseOTChallenges(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                3u8 => Ok(Self::BaseOTResponses(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                4u8 => Ok(Self::BaseOTHashedKeys(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                5u8 => Ok(Self::Phase1Commitments {
+                    comm: CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?,
+                    comm_zero_share: CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?,
+                }),
+                6u8 => Ok(Self::Phase1Shares {
+                    shares: CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?,
+                    zero_shares: CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?,
+                }),
+                7u8 => Ok(Self::Phase2Message1(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                8u8 => Ok(Self::Phase2Message2(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                _ => Err(SerializationError::InvalidData),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold::keygen::tests::dkg_keygen;
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{
+        collections::{BTreeMap, VecDeque},
+        rand::{rngs::StdRng, Rng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type G1 = <Bls12_381 as Pairing>::G1Affine;
+
+    const BASE_OT_KEY_SIZE: u16 = 128;
+    const SALT_SIZE: usize = 256;
+    const KAPPA: u16 = 256;
+    const STATISTICAL_SECURITY_PARAMETER: u16 = 80;
+
+    type Session = ThresholdBBSPlusSession<
+        Bls12_381,
+        BASE_OT_KEY_SIZE,
+        SALT_SIZE,
+        KAPPA,
+        STATISTICAL_SECURITY_PARAMETER,
+    >;
+    type Env = Envelope<Bls12_381, SALT_SIZE>;
+
+    fn to_bytes<T: CanonicalSerialize>(t: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        t.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Simulated network which delivers the messages in random order. The sessions are persisted
+    /// as bytes and loaded before processing each message.
+    fn run_network(
+        rng: &mut StdRng,
+        sessions: &mut BTreeMap<ParticipantId, Vec<u8>>,
+        initial_msgs: Vec<Env>,
+    ) {
+        let mut network = initial_msgs.iter().map(to_bytes).collect::<VecDeque<_>>();
+        while !network.is_empty() {
+            let i = rng.gen_range(0..network.len());
+            let env = Env::deserialize_compressed(network.remove(i).unwrap().as_slice()).unwrap();
+            let bytes = sessions.get(&env.receiver).unwrap();
+            let mut session = Session::deserialize_compressed(bytes.as_slice()).unwrap();
+            for m in session.process::<_, Blake2b512>(rng, env).unwrap() {
+                network.push_back(to_bytes(&m));
+            }
+            sessions.insert(session.id, to_bytes(&session));
+        }
+    }
+
+    #[test]
+    fn signing_session_over_simulated_network() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let ote_params = MultiplicationOTEParams::<KAPPA, STATISTICAL_SECURITY_PARAMETER> {};
+        let gadget_vector = GadgetVector::<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>::new::<
+            Blake2b512,
+        >(ote_params, b"test-gadget-vector");
+
+        let message_count = 3;
+        let batch_size = 2;
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+        let (_, keys) = dkg_keygen(&mut rng, 3, 5, &params.g2);
+        let public_key = keys[0].public_key.clone();
+        let signers = [1, 3, 4].into_iter().collect::<BTreeSet<ParticipantId>>();
+        let base_ot_g = G1::rand(&mut rng);
+
+        let config = |protocol_id: &[u8]| SessionConfig::<
+            Bls12_381,
+            KAPPA,
+            STATISTICAL_SECURITY_PARAMETER,
+        > {
+            protocol_id: protocol_id.to_vec(),
+            batch_size,
+            base_ot_g,
+            ote_params,
+            gadget_vector: gadget_vector.clone(),
+        };
+
+        let check_signatures = |rng: &mut StdRng, sessions: &BTreeMap<ParticipantId, Vec<u8>>| {
+            let sessions = sessions
+                .values()
+                .map(|b| Session::deserialize_compressed(b.as_slice()).unwrap())
+                .collect::<Vec<_>>();
+            for k in 0..batch_size as usize {
+                let messages = (0..message_count)
+                    .map(|_| Fr::rand(rng))
+                    .collect::<Vec<_>>();
+                let shares = sessions
+                    .iter()
+                    .map(|s| {
+                        assert!(s.is_finished());
+                        s.signature_share(&messages, k, &params).unwrap()
+                    })
+                    .collect::<Vec<_>>();
+                let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+                sig.verify(&messages, public_key.clone(), params.clone())
+                    .unwrap();
+            }
+        };
+
+        // First session does the base OT as well
+        let mut sessions = BTreeMap::new();
+        let mut msgs = vec![];
+        for id in &signers {
+            let mut others = signers.clone();
+            others.remove(id);
+            let (session, out) = Session::start_with_base_ot::<_, Blake2b512>(
+                &mut rng,
+                *id,
+                others,
+                *keys[*id as usize - 1].signing_key_share(),
+                config(b"session-1"),
+            )
+            .unwrap();
+            assert_eq!(session.round, SessionRound::BaseOT);
+            assert!(session
+                .signature_share(&[Fr::rand(&mut rng); 3], 0, &params)
+                .is_err());
+            sessions.insert(*id, to_bytes(&session));
+            msgs.extend(out);
+        }
+
+        // Messages not meant for the session are rejected
+        let mut session = Session::deserialize_compressed(sessions[&3].as_slice()).unwrap();
+        let mut wrong = msgs.iter().find(|m| m.receiver == 4).unwrap().clone();
+        assert!(session
+            .process::<_, Blake2b512>(&mut rng, wrong.clone())
+            .is_err());
+        wrong.receiver = 3;
+        wrong.sender = 2;
+        assert!(session.process::<_, Blake2b512>(&mut rng, wrong).is_err());
+
+        run_network(&mut rng, &mut sessions, msgs);
+        check_signatures(&mut rng, &sessions);
+
+        // Second session reuses the output of base OT from the first one
+        let base_ot_outputs = sessions
+            .iter()
+            .map(|(id, b)| {
+                let session = Session::deserialize_compressed(b.as_slice()).unwrap();
+                (*id, session.base_ot_output().unwrap().clone())
+            })
+            .collect::<BTreeMap<_, _>>();
+        let mut sessions = BTreeMap::new();
+        let mut msgs = vec![];
+        for id in &signers {
+            let mut others = signers.clone();
+            others.remove(id);
+            let (session, out) = Session::start(
+                &mut rng,
+                *id,
+                others,
+                *keys[*id as usize - 1].signing_key_share(),
+                config(b"session-2"),
+                base_ot_outputs[id].clone(),
+            )
+            .unwrap();
+            assert_eq!(session.round, SessionRound::Phase1Commitments);
+            sessions.insert(*id, to_bytes(&session));
+            msgs.extend(out);
+        }
+        run_network(&mut rng, &mut sessions, msgs);
+        check_signatures(&mut rng, &sessions);
+    }
+}
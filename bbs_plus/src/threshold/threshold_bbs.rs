@@ -1,8 +1,15 @@
-use ark_ec::{AffineRepr, CurveGroup};
-
-use super::{cointoss::Commitments, multiplication_phase::Phase2Output, utils::compute_R_and_u};
+use ark_ec::AffineRepr;
+
+use super::{
+    cointoss::Commitments,
+    multiplication_phase::Phase2Output,
+    utils::{
+        check_participants_across_phases, check_signers_of_shares, compute_A_from_shares,
+        compute_R_and_u,
+    },
+};
 use ark_ec::pairing::Pairing;
-use ark_ff::{Field, PrimeField, Zero};
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
@@ -13,7 +20,10 @@ use digest::DynDigest;
 
 use crate::{
     error::BBSPlusError,
-    setup::{MultiMessageSignatureParams, SignatureParams23G1},
+    setup::{
+        MultiMessageSignatureParams, PreparedPublicKeyG2, PreparedSignatureParams23G1, PublicKeyG2,
+        SignatureParams23G1,
+    },
     signature_23::Signature23G1,
     threshold::randomness_generation_phase::Phase1,
 };
@@ -80,7 +90,6 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         self,
         signing_key: &F,
     ) -> Result<Phase1Output<F>, BBSPlusError> {
-        // TODO: Ensure every one has participated in both protocols
         let id = self.id;
         let batch_size = self.batch_size;
         let r = self.r.clone();
@@ -140,6 +149,7 @@ impl<E: Pairing> BBSSignatureShare<E> {
         phase2: &Phase2Output<E::ScalarField>,
         sig_params: &SignatureParams23G1<E>,
     ) -> Result<Self, BBSPlusError> {
+        check_participants_across_phases(&phase1.others, phase2)?;
         let b = sig_params.b(uncommitted_messages)?;
         let commitment_plus_b = b + commitment;
         let (R, u) = compute_R_and_u(
@@ -159,12 +169,13 @@ impl<E: Pairing> BBSSignatureShare<E> {
         })
     }
 
+    /// Aggregate the shares into a signature. Errors name the signer whose share is inconsistent with others. Note that
+    /// the `R` and `u` of a share can't be verified individually as `u` contains the signer's outputs of the multiplication
+    /// with each of the other signers and `R` isn't bound to anything the others know. So an incorrect `R` or `u` is only
+    /// detected by the verification of the aggregated signature and can't be attributed to a signer.
     pub fn aggregate(sig_shares: Vec<Self>) -> Result<Signature23G1<E>, BBSPlusError> {
-        // TODO: Ensure correct threshold. Share should contain threshold and share id
-        let mut sum_R = E::G1::zero();
-        let mut sum_u = E::ScalarField::zero();
         let mut expected_e = E::ScalarField::zero();
-        for (i, share) in sig_shares.into_iter().enumerate() {
+        for (i, share) in sig_shares.iter().enumerate() {
             if i == 0 {
                 expected_e = share.e;
             } else {
@@ -172,14 +183,30 @@ impl<E: Pairing> BBSSignatureShare<E> {
                     return Err(BBSPlusError::IncorrectEByParticipant(share.id));
                 }
             }
-            sum_u += share.u;
-            sum_R += share.R;
         }
-        let A = sum_R * sum_u.inverse().unwrap();
-        Ok(Signature23G1 {
-            A: A.into_affine(),
-            e: expected_e,
-        })
+        let A = compute_A_from_shares(sig_shares.iter().map(|s| (s.id, &s.R, &s.u)))?;
+        Ok(Signature23G1 { A, e: expected_e })
+    }
+
+    /// Same as `Self::aggregate` but also checks that the shares are from at least `threshold` signers, each of them
+    /// has a public key share, and verifies the aggregated signature. A failed verification doesn't identify the signer
+    /// of an incorrect share.
+    pub fn aggregate_and_verify(
+        sig_shares: Vec<Self>,
+        messages: &[E::ScalarField],
+        public_key_shares: &BTreeMap<ParticipantId, PublicKeyG2<E>>,
+        threshold: ParticipantId,
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParams23G1<E>>,
+    ) -> Result<Signature23G1<E>, BBSPlusError> {
+        check_signers_of_shares(
+            sig_shares.iter().map(|s| s.id),
+            public_key_shares,
+            threshold,
+        )?;
+        let sig = Self::aggregate(sig_shares)?;
+        sig.verify(messages, pk, params)?;
+        Ok(sig)
     }
 }
 
@@ -191,12 +218,9 @@ pub mod tests {
     use ark_ff::Zero;
     use std::time::{Duration, Instant};
 
-    use crate::{
-        setup::{PublicKeyG2, SecretKey},
-        threshold::{
-            base_ot_phase::tests::do_base_ot_for_threshold_sig, multiplication_phase::Phase2,
-            threshold_bbs_plus::tests::trusted_party_keygen,
-        },
+    use crate::threshold::{
+        base_ot_phase::tests::do_base_ot_for_threshold_sig, keygen::tests::dkg_keygen,
+        multiplication_phase::Phase2,
     };
     use ark_std::{
         rand::{rngs::StdRng, SeedableRng},
@@ -227,8 +251,12 @@ pub mod tests {
         let total_signers = 8;
         let all_party_set = (1..=total_signers).into_iter().collect::<BTreeSet<_>>();
         let threshold_party_set = (1..=threshold_signers).into_iter().collect::<BTreeSet<_>>();
-        let (sk, sk_shares) =
-            trusted_party_keygen::<_, Fr>(&mut rng, threshold_signers, total_signers);
+        let message_count = 5;
+        let params = SignatureParams23G1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+
+        // Signers run the DKG to get their shares of the signing key and the public key
+        let (sk, keys) = dkg_keygen(&mut rng, threshold_signers, total_signers, &params.g2);
+        let public_key = keys[0].public_key.clone();
 
         let base_ot_outputs = do_base_ot_for_threshold_sig::<BASE_OT_KEY_SIZE>(
             &mut rng,
@@ -237,11 +265,6 @@ pub mod tests {
             all_party_set.clone(),
         );
 
-        let message_count = 5;
-        let params = SignatureParams23G1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
-        let public_key =
-            PublicKeyG2::generate_using_secret_key_and_bbs23_params(&SecretKey(sk), &params);
-
         println!(
             "For a batch size of {} BBS signatures and {} signers",
             sig_batch_size, threshold_signers
@@ -286,6 +309,13 @@ pub mod tests {
             }
         }
 
+        // Phase 1 can't finish without shares from everyone
+        let incomplete_round1 = round1s[0].clone();
+        assert!(matches!(
+            incomplete_round1.finish_for_bbs::<Blake2b512>(keys[0].signing_key_share()),
+            Err(BBSPlusError::MissingSharesFromParticipant(2))
+        ));
+
         for i in 1..=threshold_signers {
             for j in 1..=threshold_signers {
                 if i != j {
@@ -301,7 +331,9 @@ pub mod tests {
 
         let mut expected_sk = Fr::zero();
         for (i, round1) in round1s.into_iter().enumerate() {
-            let out = round1.finish_for_bbs::<Blake2b512>(&sk_shares[i]).unwrap();
+            let out = round1
+                .finish_for_bbs::<Blake2b512>(keys[i].signing_key_share())
+                .unwrap();
             expected_sk += out.masked_signing_key_shares.iter().sum::<Fr>();
             round1outs.push(out);
         }
@@ -344,13 +376,22 @@ pub mod tests {
             }
         }
 
+        // Phase 2 can't finish without multiplication with everyone
+        assert!(matches!(
+            round2s[0].clone().finish(),
+            Err(BBSPlusError::IncompleteMultiplicationWith(2))
+        ));
+
         for (sender_id, receiver_id, m2) in all_msg_2s {
             round2s[receiver_id as usize - 1]
                 .receive_message2::<Blake2b512>(sender_id, m2, &gadget_vector)
                 .unwrap();
         }
 
-        let round2_outputs = round2s.into_iter().map(|p| p.finish()).collect::<Vec<_>>();
+        let round2_outputs = round2s
+            .into_iter()
+            .map(|p| p.finish().unwrap())
+            .collect::<Vec<_>>();
         println!("Phase 2 took {:?}", start.elapsed());
 
         for i in 1..=threshold_signers {
@@ -395,10 +436,70 @@ pub mod tests {
             sig_shares_time += start.elapsed();
 
             let start = Instant::now();
-            let sig = BBSSignatureShare::aggregate(shares).unwrap();
+            let sig = BBSSignatureShare::aggregate(shares.clone()).unwrap();
             sig_aggr_time += start.elapsed();
             sig.verify(&messages, public_key.clone(), params.clone())
                 .unwrap();
+
+            let public_key_shares = &keys[0].public_key_shares;
+            let aggregate_and_verify = |shares| {
+                BBSSignatureShare::aggregate_and_verify(
+                    shares,
+                    &messages,
+                    public_key_shares,
+                    threshold_signers,
+                    public_key.clone(),
+                    params.clone(),
+                )
+            };
+            assert_eq!(aggregate_and_verify(shares.clone()).unwrap(), sig);
+
+            // Offending signers are identified
+            let mut bad_shares = shares.clone();
+            bad_shares[2].e = Fr::rand(&mut rng);
+            assert!(matches!(
+                aggregate_and_verify(bad_shares),
+                Err(BBSPlusError::IncorrectEByParticipant(3))
+            ));
+
+            let mut bad_shares = shares.clone();
+            bad_shares.push(shares[1].clone());
+            assert!(matches!(
+                aggregate_and_verify(bad_shares),
+                Err(BBSPlusError::DuplicateSignatureShareFrom(2))
+            ));
+
+            let mut bad_shares = shares.clone();
+            bad_shares[3].id = total_signers + 1;
+            assert!(matches!(
+                aggregate_and_verify(bad_shares),
+                Err(BBSPlusError::MissingPublicKeyShareFor(id)) if id == total_signers + 1
+            ));
+
+            let mut bad_shares = shares.clone();
+            bad_shares[1].R = <Bls12_381 as Pairing>::G1Affine::zero();
+            assert!(matches!(
+                aggregate_and_verify(bad_shares),
+                Err(BBSPlusError::ZeroSignatureShareFrom(2))
+            ));
+
+            assert!(matches!(
+                aggregate_and_verify(shares[1..].to_vec()),
+                Err(BBSPlusError::InsufficientSignatureShares(4, 5))
+            ));
+
+            let mut bad_shares = shares.clone();
+            bad_shares[0].u = Fr::rand(&mut rng);
+            assert!(matches!(
+                aggregate_and_verify(bad_shares),
+                Err(BBSPlusError::InvalidSignature)
+            ));
+
+            // Outputs of phase 1 and 2 must have the same participants
+            assert!(matches!(
+                BBSSignatureShare::new(&messages, k, &round1outs[0], &round2_outputs[1], &params),
+                Err(BBSPlusError::ParticipantSetMismatch(2))
+            ));
         }
 
         println!("Generating signature shares took {:?}", sig_shares_time);
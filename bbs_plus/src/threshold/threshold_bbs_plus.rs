@@ -1,6 +1,6 @@
 use crate::threshold::cointoss::Commitments;
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_ff::{Field, PrimeField, Zero};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{PrimeField, Zero};
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
@@ -13,12 +13,21 @@ use oblivious_transfer_protocols::ParticipantId;
 
 use crate::{
     error::BBSPlusError,
-    setup::{MultiMessageSignatureParams, SignatureParamsG1},
+    setup::{
+        MultiMessageSignatureParams, PreparedPublicKeyG2, PreparedSignatureParamsG1, PublicKeyG2,
+        SignatureParamsG1,
+    },
     signature::SignatureG1,
     threshold::randomness_generation_phase::Phase1,
 };
 
-use super::{multiplication_phase::Phase2Output, utils::compute_R_and_u};
+use super::{
+    multiplication_phase::Phase2Output,
+    utils::{
+        check_participants_across_phases, check_signers_of_shares, compute_A_from_shares,
+        compute_R_and_u,
+    },
+};
 
 /// The length of vectors `r`, `e`, `s`, `masked_signing_key_shares`, `masked_rs` should
 /// be `batch_size` each item of the vector corresponds to 1 signature
@@ -83,7 +92,6 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         self,
         signing_key: &F,
     ) -> Result<Phase1Output<F>, BBSPlusError> {
-        // TODO: Ensure every one has participated in both protocols
         let id = self.id;
         let batch_size = self.batch_size;
         let r = self.r.clone();
@@ -145,6 +153,7 @@ impl<E: Pairing> BBSPlusSignatureShare<E> {
         phase2: &Phase2Output<E::ScalarField>,
         sig_params: &SignatureParamsG1<E>,
     ) -> Result<Self, BBSPlusError> {
+        check_participants_across_phases(&phase1.others, phase2)?;
         let b = sig_params.b(uncommitted_messages, &phase1.s[sig_index_in_batch])?;
         let commitment_plus_b = b + commitment;
         let (R, u) = compute_R_and_u(
@@ -165,13 +174,14 @@ impl<E: Pairing> BBSPlusSignatureShare<E> {
         })
     }
 
+    /// Aggregate the shares into a signature. Errors name the signer whose share is inconsistent with others. Note that
+    /// the `R` and `u` of a share can't be verified individually as `u` contains the signer's outputs of the multiplication
+    /// with each of the other signers and `R` isn't bound to anything the others know. So an incorrect `R` or `u` is only
+    /// detected by the verification of the aggregated signature and can't be attributed to a signer.
     pub fn aggregate(sig_shares: Vec<Self>) -> Result<SignatureG1<E>, BBSPlusError> {
-        // TODO: Ensure correct threshold. Share should contain threshold and share id
-        let mut sum_R = E::G1::zero();
-        let mut sum_u = E::ScalarField::zero();
         let mut expected_e = E::ScalarField::zero();
         let mut expected_s = E::ScalarField::zero();
-        for (i, share) in sig_shares.into_iter().enumerate() {
+        for (i, share) in sig_shares.iter().enumerate() {
             if i == 0 {
                 expected_e = share.e;
                 expected_s = share.s;
@@ -183,16 +193,35 @@ impl<E: Pairing> BBSPlusSignatureShare<E> {
                     return Err(BBSPlusError::IncorrectSByParticipant(share.id));
                 }
             }
-            sum_u += share.u;
-            sum_R += share.R;
         }
-        let A = sum_R * sum_u.inverse().unwrap();
+        let A = compute_A_from_shares(sig_shares.iter().map(|s| (s.id, &s.R, &s.u)))?;
         Ok(SignatureG1 {
-            A: A.into_affine(),
+            A,
             e: expected_e,
             s: expected_s,
         })
     }
+
+    /// Same as `Self::aggregate` but also checks that the shares are from at least `threshold` signers, each of them
+    /// has a public key share, and verifies the aggregated signature. A failed verification doesn't identify the signer
+    /// of an incorrect share.
+    pub fn aggregate_and_verify(
+        sig_shares: Vec<Self>,
+        messages: &[E::ScalarField],
+        public_key_shares: &BTreeMap<ParticipantId, PublicKeyG2<E>>,
+        threshold: ParticipantId,
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+    ) -> Result<SignatureG1<E>, BBSPlusError> {
+        check_signers_of_shares(
+            sig_shares.iter().map(|s| s.id),
+            public_key_shares,
+            threshold,
+        )?;
+        let sig = Self::aggregate(sig_shares)?;
+        sig.verify(messages, pk, params)?;
+        Ok(sig)
+    }
 }
 
 #[cfg(test)]
@@ -414,7 +443,7 @@ pub mod tests {
                 .map(|p| {
                     let start = Instant::now();
                     let i = p.id;
-                    let o = p.finish();
+                    let o = p.finish().unwrap();
                     phase2_times.insert(i, *phase2_times.get(&i).unwrap() + start.elapsed());
                     o
                 })
@@ -1,7 +1,14 @@
-use crate::threshold::multiplication_phase::Phase2Output;
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
-use ark_std::{cfg_into_iter, ops::Mul, vec::Vec};
+use crate::{
+    error::BBSPlusError, setup::PublicKeyG2, threshold::multiplication_phase::Phase2Output,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_std::{
+    cfg_into_iter,
+    collections::{BTreeMap, BTreeSet},
+    ops::Mul,
+    vec::Vec,
+};
 use itertools::Itertools;
 use oblivious_transfer_protocols::ParticipantId;
 
@@ -54,3 +61,74 @@ pub fn compute_R_and_u<G: AffineRepr>(
     }
     (R, u)
 }
+
+/// Check that phase 2 was run with the same participants as phase 1
+pub fn check_participants_across_phases<F: PrimeField>(
+    phase1_others: &[ParticipantId],
+    phase2: &Phase2Output<F>,
+) -> Result<(), BBSPlusError> {
+    let phase2_others = phase2
+        .z_A
+        .keys()
+        .chain(phase2.z_B.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+    for id in phase1_others {
+        if !phase2_others.contains(id) {
+            return Err(BBSPlusError::ParticipantSetMismatch(*id));
+        }
+    }
+    for id in phase2_others {
+        if !phase1_others.contains(&id) {
+            return Err(BBSPlusError::ParticipantSetMismatch(id));
+        }
+    }
+    Ok(())
+}
+
+/// Compute `A` of the signature as `(\sum_i R_i) / (\sum_i u_i)` from the `(id, R_i, u_i)` of signature shares. Fails
+/// if a signer has sent more than 1 share or a share with `R_i` or `u_i` as 0.
+pub fn compute_A_from_shares<'a, G: AffineRepr>(
+    shares: impl Iterator<Item = (ParticipantId, &'a G, &'a G::ScalarField)>,
+) -> Result<G, BBSPlusError> {
+    let mut ids = BTreeSet::new();
+    let mut sum_R = G::Group::zero();
+    let mut sum_u = G::ScalarField::zero();
+    for (id, R, u) in shares {
+        if !ids.insert(id) {
+            return Err(BBSPlusError::DuplicateSignatureShareFrom(id));
+        }
+        if R.is_zero() || u.is_zero() {
+            return Err(BBSPlusError::ZeroSignatureShareFrom(id));
+        }
+        sum_R += R;
+        sum_u += u;
+    }
+    let sum_u_inv = sum_u.inverse().ok_or(BBSPlusError::CannotInvert0)?;
+    Ok((sum_R * sum_u_inv).into_affine())
+}
+
+/// Check that the signature shares are from at least `threshold` signers and each of them has a public key share.
+/// Whether the public key shares correspond to the public key can be checked once with `keygen::verify_public_key_shares`
+pub fn check_signers_of_shares<E: Pairing>(
+    ids: impl Iterator<Item = ParticipantId>,
+    public_key_shares: &BTreeMap<ParticipantId, PublicKeyG2<E>>,
+    threshold: ParticipantId,
+) -> Result<(), BBSPlusError> {
+    let mut signers = BTreeSet::new();
+    for id in ids {
+        if !signers.insert(id) {
+            return Err(BBSPlusError::DuplicateSignatureShareFrom(id));
+        }
+        if !public_key_shares.contains_key(&id) {
+            return Err(BBSPlusError::MissingPublicKeyShareFor(id));
+        }
+    }
+    if signers.len() < threshold as usize {
+        return Err(BBSPlusError::InsufficientSignatureShares(
+            signers.len(),
+            threshold as usize,
+        ));
+    }
+    Ok(())
+}
@@ -2,7 +2,10 @@ use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{cfg_into_iter, rand::RngCore, vec::Vec, UniformRand};
 use digest::Digest;
-use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
+use dock_crypto_utils::{
+    concat_slices,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
+};
 
 use crate::util::base_bits;
 #[cfg(feature = "parallel")]
@@ -17,24 +20,22 @@ pub struct SetupParams<Gr: AffineRepr> {
 
 impl<Gr: AffineRepr> SetupParams<Gr> {
     pub fn new<D: Digest>(label: &[u8], g_count: u32, h_count: u32) -> Self {
-        let g = affine_group_elem_from_try_and_incr::<Gr, D>(&concat_slices![label, b" : G"]);
+        Self::new_with_hasher::<TryAndIncrement<D>>(label, g_count, h_count)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H: GroupElemHasher<Gr>>(
+        label: &[u8],
+        g_count: u32,
+        h_count: u32,
+    ) -> Self {
+        let g = H::hash_to_affine(&concat_slices![label, b" : G"]);
         let g_vec = cfg_into_iter!((0..g_count))
-            .map(|i| {
-                affine_group_elem_from_try_and_incr::<Gr, D>(&concat_slices![
-                    label,
-                    b" : g_",
-                    i.to_le_bytes()
-                ])
-            })
+            .map(|i| H::hash_to_affine(&concat_slices![label, b" : g_", i.to_le_bytes()]))
             .collect::<Vec<Gr>>();
         let h_vec = cfg_into_iter!((0..h_count))
-            .map(|i| {
-                affine_group_elem_from_try_and_incr::<Gr, D>(&concat_slices![
-                    label,
-                    b" : h_",
-                    i.to_le_bytes()
-                ])
-            })
+            .map(|i| H::hash_to_affine(&concat_slices![label, b" : h_", i.to_le_bytes()]))
             .collect::<Vec<Gr>>();
         Self {
             G: g,
@@ -1,6 +1,7 @@
 //! Secret sharing and distributed key generation.
 
 pub mod common;
+pub mod refresh;
 pub mod shamir_ss;
 
 use common::*;
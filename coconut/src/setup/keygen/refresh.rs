@@ -0,0 +1,351 @@
+//! Proactive refresh of the secret keys of the authorities without changing the threshold secret key.
+//!
+//! Each authority deals a sharing of 0 for `x` and each of `y` and sends every authority its share. The commitments
+//! to the coefficients of the dealt polynomials are public and let any verifier refresh the public keys of the
+//! authorities. The threshold public key doesn't change, so the aggregated signatures remain verifiable with it.
+//! All authorities must use the dealings of the same set of dealers.
+
+use alloc::vec::Vec;
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::Zero;
+use ark_serialize::*;
+use ark_std::{collections::BTreeMap, rand::RngCore};
+use secret_sharing_and_dkg::{
+    common::{CommitmentToCoefficients, ParticipantId, Share},
+    error::SSError,
+    feldman_vss, share_refresh,
+};
+
+use super::*;
+use crate::{
+    helpers::points,
+    setup::{PublicKey, SecretKey, SignatureParams},
+};
+
+/// Commitments to the coefficients of the polynomials sharing 0 dealt by an authority.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RefreshCommitments<E: Pairing> {
+    /// Commitments for `x` with `g_tilde` as the base.
+    pub x_tilde: CommitmentToCoefficients<E::G2Affine>,
+    /// Commitments for each of `y` with `g` as the base.
+    pub y: Vec<CommitmentToCoefficients<E::G1Affine>>,
+    /// Commitments for each of `y` with `g_tilde` as the base.
+    pub y_tilde: Vec<CommitmentToCoefficients<E::G2Affine>>,
+}
+
+impl<E: Pairing> RefreshCommitments<E> {
+    /// Checks that all commitments are of polynomials sharing 0 and supporting the threshold, and that commitments
+    /// for `y` in both groups are to the same coefficients.
+    pub fn verify(
+        &self,
+        dealer_id: ParticipantId,
+        Threshold(threshold, _): Threshold,
+        SignatureParams { g, g_tilde, .. }: &SignatureParams<E>,
+    ) -> Result<(), SSError> {
+        if self.y.len() != self.y_tilde.len() {
+            return Err(SSError::InvalidZeroSharingFrom(dealer_id));
+        }
+        share_refresh::verify_zero_sharing_commitments(dealer_id, &self.x_tilde, threshold)?;
+        let minus_g = -g.into_group();
+        for (y, y_tilde) in self.y.iter().zip(self.y_tilde.iter()) {
+            share_refresh::verify_zero_sharing_commitments(dealer_id, y, threshold)?;
+            share_refresh::verify_zero_sharing_commitments(dealer_id, y_tilde, threshold)?;
+            if y.0.len() != y_tilde.0.len() {
+                return Err(SSError::InvalidZeroSharingFrom(dealer_id));
+            }
+            for (c, c_tilde) in y.0.iter().zip(y_tilde.0.iter()) {
+                if !E::multi_pairing([c.into_group(), minus_g], [*g_tilde, *c_tilde]).is_zero() {
+                    return Err(SSError::InvalidZeroSharingFrom(dealer_id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Called by an authority to deal shares of 0 supporting `message_count` messages for all authorities. Returns the
+/// shares for all authorities as secret keys to be added to their current ones and commitments to be published.
+#[allow(clippy::type_complexity)]
+pub fn deal_refresh<R: RngCore, E: Pairing>(
+    rng: &mut R,
+    message_count: u32,
+    Threshold(threshold, total): Threshold,
+    SignatureParams { g, g_tilde, .. }: &SignatureParams<E>,
+) -> Result<(Vec<SecretKey<E::ScalarField>>, RefreshCommitments<E>), SSError> {
+    let (x_shares, x_tilde) =
+        share_refresh::deal_zero_sharing::<_, E::G2Affine>(rng, threshold, total, g_tilde)?;
+
+    let mut y_shares = Vec::with_capacity(message_count as usize);
+    let mut y = Vec::with_capacity(message_count as usize);
+    let mut y_tilde = Vec::with_capacity(message_count as usize);
+    for _ in 0..message_count {
+        let (shares, comms, poly) = feldman_vss::deal_secret::<_, E::G2Affine>(
+            rng,
+            E::ScalarField::zero(),
+            threshold,
+            total,
+            g_tilde,
+        )?;
+        y.push(points(g, &poly.coeffs).into());
+        y_tilde.push(comms);
+        y_shares.push(shares);
+    }
+
+    let secrets = (0..total as usize)
+        .map(|i| SecretKey {
+            x: x_shares.0[i].share,
+            y: y_shares.iter().map(|shares| shares.0[i].share).collect(),
+        })
+        .collect();
+
+    Ok((
+        secrets,
+        RefreshCommitments {
+            x_tilde,
+            y,
+            y_tilde,
+        },
+    ))
+}
+
+/// Called by the authority with id `participant_id` once it has received the shares of 0 and commitments from all
+/// dealers, including itself. Verifies the received shares and returns the new secret key.
+pub fn refresh_secret_key<E: Pairing>(
+    sk: &SecretKey<E::ScalarField>,
+    participant_id: ParticipantId,
+    threshold: Threshold,
+    received: &BTreeMap<ParticipantId, (SecretKey<E::ScalarField>, RefreshCommitments<E>)>,
+    params: &SignatureParams<E>,
+) -> Result<SecretKey<E::ScalarField>, SSError> {
+    if !received.contains_key(&participant_id) {
+        return Err(SSError::MissingSomeParticipants(participant_id));
+    }
+    let Threshold(t, _) = threshold;
+    let mut new_sk = sk.clone();
+    for (dealer_id, (delta, comms)) in received {
+        comms.verify(*dealer_id, threshold, params)?;
+        if delta.y.len() != sk.y.len() || comms.y_tilde.len() != sk.y.len() {
+            return Err(SSError::InvalidZeroSharingFrom(*dealer_id));
+        }
+        let verify = |share: &E::ScalarField, comms: &CommitmentToCoefficients<E::G2Affine>| {
+            Share {
+                id: participant_id,
+                threshold: t,
+                share: *share,
+            }
+            .verify(comms, &params.g_tilde)
+        };
+        verify(&delta.x, &comms.x_tilde)?;
+        for (y, y_comms) in delta.y.iter().zip(comms.y_tilde.iter()) {
+            verify(y, y_comms)?;
+        }
+
+        new_sk.x += delta.x;
+        for (y, delta_y) in new_sk.y.iter_mut().zip(delta.y.iter()) {
+            *y += delta_y;
+        }
+    }
+    Ok(new_sk)
+}
+
+/// Refresh the public key of the authority with id `participant_id` using the commitments published by all dealers.
+pub fn refresh_public_key<E: Pairing>(
+    pk: &PublicKey<E>,
+    participant_id: ParticipantId,
+    Threshold(threshold, _): Threshold,
+    commitments: &BTreeMap<ParticipantId, RefreshCommitments<E>>,
+) -> Result<PublicKey<E>, SSError> {
+    fn refresh<G: AffineRepr>(
+        id: ParticipantId,
+        pk_share: G,
+        threshold: u16,
+        comms: BTreeMap<ParticipantId, CommitmentToCoefficients<G>>,
+    ) -> Result<G, SSError> {
+        let pk_shares = BTreeMap::from([(id, pk_share)]);
+        share_refresh::refresh_public_key_shares(&pk_shares, &comms, threshold)
+            .map(|mut new| new.remove(&id).unwrap())
+    }
+
+    for (dealer_id, comms) in commitments {
+        if comms.y.len() != pk.beta.len() || comms.y_tilde.len() != pk.beta_tilde.len() {
+            return Err(SSError::InvalidZeroSharingFrom(*dealer_id));
+        }
+    }
+    let alpha_tilde = refresh(
+        participant_id,
+        pk.alpha_tilde,
+        threshold,
+        commitments
+            .iter()
+            .map(|(id, c)| (*id, c.x_tilde.clone()))
+            .collect(),
+    )?;
+    let beta = pk
+        .beta
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            refresh(
+                participant_id,
+                *b,
+                threshold,
+                commitments
+                    .iter()
+                    .map(|(id, c)| (*id, c.y[i].clone()))
+                    .collect(),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let beta_tilde = pk
+        .beta_tilde
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            refresh(
+                participant_id,
+                *b,
+                threshold,
+                commitments
+                    .iter()
+                    .map(|(id, c)| (*id, c.y_tilde[i].clone()))
+                    .collect(),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PublicKey {
+        alpha_tilde,
+        beta,
+        beta_tilde,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    use ark_bls12_381::Bls12_381;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+    use secret_sharing_and_dkg::common::Shares;
+
+    use crate::{
+        setup::{keygen::shamir_ss::deal, test_setup},
+        AggregatedSignature, BlindSignature, CommitmentOrMessage,
+    };
+
+    #[test]
+    fn refresh_keys() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 3;
+        let (_, _, params, msgs) = test_setup::<Bls12_381, Blake2b512, _>(&mut rng, message_count);
+        let h = params.h[0];
+        let threshold = Threshold::new(3, 5).unwrap();
+        let Threshold(t, total) = threshold;
+
+        let (threshold_sk, sks) = deal(&mut rng, message_count as u32, threshold).unwrap();
+        let vk = PublicKey::new(&threshold_sk, &params);
+        let pks = sks
+            .iter()
+            .map(|sk| PublicKey::new(sk, &params))
+            .collect::<Vec<_>>();
+
+        let mut received = (0..total).map(|_| BTreeMap::new()).collect::<Vec<_>>();
+        let mut all_comms = BTreeMap::new();
+        for dealer_id in 1..=total {
+            let (deltas, comms) =
+                deal_refresh(&mut rng, message_count as u32, threshold, &params).unwrap();
+            comms.verify(dealer_id, threshold, &params).unwrap();
+            for (r, delta) in received.iter_mut().zip(deltas) {
+                r.insert(dealer_id, (delta, comms.clone()));
+            }
+            all_comms.insert(dealer_id, comms);
+        }
+
+        // A tampered share is rejected
+        let mut tampered = received[0].clone();
+        tampered.get_mut(&2).unwrap().0.y[1] += ark_bls12_381::Fr::from(1u64);
+        assert!(refresh_secret_key(&sks[0], 1, threshold, &tampered, &params).is_err());
+
+        // Commitments to a non-zero secret are rejected
+        let mut tampered = all_comms.clone();
+        tampered.get_mut(&3).unwrap().y[0].0[0] = params.g;
+        assert!(matches!(
+            tampered[&3].verify(3, threshold, &params),
+            Err(SSError::InvalidZeroSharingFrom(3))
+        ));
+
+        let new_sks = sks
+            .iter()
+            .zip(received.iter())
+            .enumerate()
+            .map(|(i, (sk, r))| {
+                refresh_secret_key(sk, i as u16 + 1, threshold, r, &params).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // The threshold secret key is unchanged
+        for j in 0..=message_count {
+            let shares = Shares(
+                new_sks
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .take(t as usize)
+                    .map(|(i, sk)| Share {
+                        id: i as u16 + 1,
+                        threshold: t,
+                        share: if j == 0 { sk.x } else { sk.y[j - 1] },
+                    })
+                    .collect(),
+            );
+            let expected = if j == 0 {
+                threshold_sk.x
+            } else {
+                threshold_sk.y[j - 1]
+            };
+            assert_eq!(shares.reconstruct_secret().unwrap(), expected);
+        }
+
+        // Verifiers can refresh the public keys of the authorities
+        for (i, (pk, sk)) in pks.iter().zip(new_sks.iter()).enumerate() {
+            let new_pk = refresh_public_key(pk, i as u16 + 1, threshold, &all_comms).unwrap();
+            assert_ne!(&new_pk, pk);
+            assert_eq!(new_pk, PublicKey::new(sk, &params));
+        }
+
+        let sign = |sk: &SecretKey<_>| {
+            BlindSignature::<Bls12_381>::new(
+                msgs.iter()
+                    .copied()
+                    .map(CommitmentOrMessage::RevealedMessage),
+                sk,
+                &h,
+            )
+            .unwrap()
+            .unblind(core::iter::empty(), &PublicKey::new(sk, &params))
+            .unwrap()
+        };
+        let old_sigs = sks.iter().map(sign).collect::<Vec<_>>();
+        let new_sigs = new_sks.iter().map(sign).collect::<Vec<_>>();
+
+        // Signatures with the refreshed keys are verifiable with the same threshold public key
+        let aggregated = AggregatedSignature::new(
+            new_sigs
+                .iter()
+                .enumerate()
+                .skip(2)
+                .map(|(i, s)| (i as u16 + 1, s)),
+            &h,
+        )
+        .unwrap();
+        aggregated.verify(&msgs, &vk, &params).unwrap();
+
+        // Signatures with old and refreshed keys can't be combined
+        let mixed = [(1, &old_sigs[0]), (2, &new_sigs[1]), (3, &new_sigs[2])];
+        let aggregated = AggregatedSignature::new(mixed, &h).unwrap();
+        aggregated.verify(&msgs, &vk, &params).unwrap_err();
+    }
+}
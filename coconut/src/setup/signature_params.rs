@@ -8,7 +8,9 @@ use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::*;
 use serde_with::serde_as;
 use utils::{
-    affine_group_element_from_byte_slices, concat_slices, misc::n_affine_group_elements,
+    concat_slices,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
+    misc::n_affine_group_elements_with_hasher,
     serde_utils::ArkObjectBytes,
 };
 
@@ -35,11 +37,26 @@ pub struct SignatureParams<E: Pairing> {
 impl<E: Pairing> SignatureParams<E> {
     /// Generates `g`, `g_tilde` and `h`. These params are shared between signer and all users.
     pub fn new<D: digest::Digest>(label: &[u8], message_count: u32) -> Self {
+        Self::new_with_hasher::<TryAndIncrement<D>>(label, message_count)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H>(label: &[u8], message_count: u32) -> Self
+    where
+        H: GroupElemHasher<E::G1Affine> + GroupElemHasher<E::G2Affine>,
+    {
         let (g, g_tilde, h) = join!(
-            affine_group_element_from_byte_slices!(label, b" : g"),
-            affine_group_element_from_byte_slices!(label, b" : g_tilde"),
-            n_affine_group_elements::<_, D>(0..message_count, &concat_slices!(label, b" : h_"))
-                .collect()
+            <H as GroupElemHasher<E::G1Affine>>::hash_to_affine(&concat_slices!(label, b" : g")),
+            <H as GroupElemHasher<E::G2Affine>>::hash_to_affine(&concat_slices!(
+                label,
+                b" : g_tilde"
+            )),
+            n_affine_group_elements_with_hasher::<_, H>(
+                0..message_count,
+                &concat_slices!(label, b" : h_")
+            )
+            .collect()
         );
 
         Self { g, g_tilde, h }
@@ -16,6 +16,7 @@ use super::{
     error::AggregationError,
     key::{VKey, WKey},
 };
+use crate::ptau::PowersOfTau;
 
 /// Maximum size of the generic SRS constructed from Filecoin and Zcash power of
 /// taus.
@@ -268,6 +269,38 @@ impl<E: Pairing> GenericSRS<E> {
         (pk, vk)
     }
 
+    /// Create the SRS from the outputs of 2 distinct powers of tau ceremonies, like the ones imported from .ptau
+    /// files. The powers of `tau` of the first ceremony are used as powers of `alpha` and of the second as powers
+    /// of `beta`. The size of the SRS is limited by the number of powers in G2 of either ceremony. The powers
+    /// should have been checked using `PowersOfTau::verify` if they come from an untrusted source.
+    pub fn from_powers_of_tau(
+        first: &PowersOfTau<E>,
+        second: &PowersOfTau<E>,
+    ) -> Result<Self, AggregationError> {
+        let size = ark_std::cmp::min(first.tau_g2.len(), second.tau_g2.len());
+        if size < 2 || first.tau_g1.len() < size || second.tau_g1.len() < size {
+            return Err(AggregationError::InvalidSRS(
+                "Insufficient powers of tau".to_string(),
+            ));
+        }
+        if first.tau_g1[0] != second.tau_g1[0] || first.tau_g2[0] != second.tau_g2[0] {
+            return Err(AggregationError::InvalidSRS(
+                "Powers of tau use different generators".to_string(),
+            ));
+        }
+        if first.tau_g1[1] == second.tau_g1[1] {
+            return Err(AggregationError::InvalidSRS(
+                "Powers of tau should be from distinct ceremonies".to_string(),
+            ));
+        }
+        Ok(Self {
+            g_alpha_powers: first.tau_g1[..size].to_vec(),
+            h_alpha_powers: first.tau_g2[..size].to_vec(),
+            g_beta_powers: second.tau_g1[..size].to_vec(),
+            h_beta_powers: second.tau_g2[..size].to_vec(),
+        })
+    }
+
     pub fn write<W: Write>(&self, mut writer: W) -> Result<(), AggregationError> {
         (self.g_alpha_powers.len() as u32).serialize_compressed(&mut writer)?;
         write_vec(
@@ -1,6 +1,9 @@
 use crate::{
     aggregation::{groth16, legogroth16, srs, srs::PreparedProverSRS},
-    create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+    create_random_proof, generate_parameters_using_powers_of_tau, generate_random_parameters,
+    prepare_verifying_key,
+    ptau::{setup_fake_powers_of_tau, PowersOfTau},
+    verify_proof,
 };
 use ark_bls12_381::{Bls12_381, Fr};
 use ark_ff::{Field, One};
@@ -556,3 +559,63 @@ fn groth16_aggregation_multiply() {
         start.elapsed()
     );
 }
+
+#[test]
+fn legogroth16_aggregation_with_srs_from_powers_of_tau() {
+    let num_constraints = 100;
+    let nproofs = 8;
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    // 1 ceremony for the circuit and 2 for SnarkPack's SRS
+    let powers_1 = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 7);
+    let powers_2 = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 4);
+    let powers_3 = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 5);
+
+    let mut bytes = vec![];
+    powers_2.write(&mut bytes).unwrap();
+    let powers_2 = PowersOfTau::<Bls12_381>::new(std::io::Cursor::new(bytes)).unwrap();
+
+    let params = {
+        let c = Benchmark::<Fr>::new(num_constraints);
+        generate_parameters_using_powers_of_tau(c, &powers_1, 10, &mut rng).unwrap()
+    };
+    let pvk = prepare_verifying_key(&params.vk);
+
+    assert!(srs::GenericSRS::from_powers_of_tau(&powers_2, &powers_2).is_err());
+    let srs = srs::GenericSRS::from_powers_of_tau(&powers_2, &powers_3).unwrap();
+    // Limited by the smaller ceremony
+    assert_eq!(srs.g_alpha_powers.len(), 16);
+    let (prover_srs, ver_srs) = srs.specialize(nproofs);
+
+    let proofs = (0..nproofs)
+        .map(|_| {
+            let c = Benchmark::new(num_constraints);
+            let v = Fr::rand(&mut rng);
+            create_random_proof(c, v, &params, &mut rng).expect("proof creation failed")
+        })
+        .collect::<Vec<_>>();
+    let inputs: Vec<_> = [Fr::one(); 2].to_vec();
+    let all_inputs = (0..nproofs).map(|_| inputs.clone()).collect::<Vec<_>>();
+    for proof in &proofs {
+        verify_proof(&pvk, proof, &inputs).unwrap();
+    }
+
+    let mut prover_transcript = new_merlin_transcript(b"test aggregation");
+    prover_transcript.append(b"public-inputs", &all_inputs);
+    let aggregate_proof =
+        legogroth16::aggregate_proofs(prover_srs, &mut prover_transcript, &proofs)
+            .expect("error in aggregation");
+
+    let mut ver_transcript = new_merlin_transcript(b"test aggregation");
+    ver_transcript.append(b"public-inputs", &all_inputs);
+    legogroth16::verify_aggregate_proof(
+        &ver_srs,
+        &pvk,
+        &all_inputs,
+        &aggregate_proof,
+        &mut rng,
+        &mut ver_transcript,
+        None,
+    )
+    .expect("error in verification");
+}
@@ -27,4 +27,15 @@ pub enum CircomError {
     /// Contains the error message when instantiating WASM module
     WasmInstantiationError(String),
     R1CSFileParsing(String),
+    /// Contains the error message when opening sym file
+    UnableToOpenSymFile(String),
+    SymFileParsing(String),
+    /// No signal with this name exists in the symbol table
+    SignalNotFound(String),
+    /// The signal with this name was removed by the compiler's optimizations and has no wire
+    SignalEliminated(String),
+    /// The signal with this name is not an output or a public input
+    SignalNotPublic(String),
+    /// The signal with this name is not a private input
+    SignalNotPrivate(String),
 }
@@ -4,6 +4,8 @@ pub mod r1cs;
 /// Parser for .r1cs file generated by Circom compiler.
 #[cfg(feature = "std")]
 pub mod r1cs_reader;
+/// Parser for .sym file generated by Circom compiler. Maps signal names to wires of the circuit.
+pub mod sym;
 #[cfg(test)]
 mod tests;
 pub mod wasm;
@@ -21,4 +23,5 @@ pub const BLS12_381_ORDER: &str =
 pub use circuit::CircomCircuit;
 pub use error::CircomError;
 pub use r1cs::R1CS;
+pub use sym::SymbolTable;
 pub use witness::WitnessCalculator;
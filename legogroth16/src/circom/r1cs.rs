@@ -44,7 +44,12 @@ impl Default for Curve {
 }
 
 /// Result of the parsed R1CS file.
-#[derive(Clone, Debug, Default, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// The serialization is versioned since `num_private_inputs` was added later. The first byte is the curve
+/// tag, with the high bit set when `num_private_inputs` follows it. Bytes without that bit were serialized
+/// before the field existed and are deserialized with `num_private_inputs` set to `num_private`, i.e. all
+/// private wires are treated as inputs as was done then.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct R1CS<E: Pairing> {
     pub curve: Curve,
     /// Total number of public values in the circuit. Includes public inputs and outputs and the always
@@ -56,6 +61,9 @@ pub struct R1CS<E: Pairing> {
     pub constraints: Vec<Constraint<E>>,
     /// The indices of the vector specify the wire index and the value specifies the label index
     pub wire_to_label_mapping: Vec<usize>,
+    /// Number of private inputs in the circuit. These are the first private wires and precede the intermediate
+    /// wires.
+    pub num_private_inputs: u32,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
@@ -96,6 +104,7 @@ impl<E: Pairing> From<R1CSFile<E>> for R1CS<E> {
             num_public: num_inputs,
             constraints: file.constraints,
             wire_to_label_mapping: file.wire_mapping.iter().map(|e| *e as usize).collect(),
+            num_private_inputs: file.header.n_prv_in,
         }
     }
 }
@@ -113,6 +122,91 @@ mod serialization {
     use super::*;
     use ark_serialize::{Compress, SerializationError, Valid, Validate};
 
+    /// Set in the curve tag of a serialized `R1CS` when `num_private_inputs` is present
+    const WITH_NUM_PRIVATE_INPUTS: u8 = 0x80;
+
+    impl Curve {
+        fn tag(&self) -> u8 {
+            match self {
+                Self::Bn128 => 0,
+                Self::Bls12_381 => 1,
+            }
+        }
+
+        fn from_tag(tag: u8) -> Result<Self, SerializationError> {
+            match tag {
+                0u8 => Ok(Curve::Bn128),
+                1u8 => Ok(Curve::Bls12_381),
+                _ => Err(SerializationError::InvalidData),
+            }
+        }
+    }
+
+    impl<E: Pairing> Valid for R1CS<E> {
+        fn check(&self) -> Result<(), SerializationError> {
+            self.constraints.check()
+        }
+    }
+
+    impl<E: Pairing> CanonicalSerialize for R1CS<E> {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            mut writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            (self.curve.tag() | WITH_NUM_PRIVATE_INPUTS)
+                .serialize_with_mode(&mut writer, compress)?;
+            self.num_private_inputs
+                .serialize_with_mode(&mut writer, compress)?;
+            self.num_public.serialize_with_mode(&mut writer, compress)?;
+            self.num_private
+                .serialize_with_mode(&mut writer, compress)?;
+            self.constraints
+                .serialize_with_mode(&mut writer, compress)?;
+            self.wire_to_label_mapping
+                .serialize_with_mode(&mut writer, compress)
+        }
+
+        fn serialized_size(&self, compress: Compress) -> usize {
+            0u8.serialized_size(compress)
+                + self.num_private_inputs.serialized_size(compress)
+                + self.num_public.serialized_size(compress)
+                + self.num_private.serialized_size(compress)
+                + self.constraints.serialized_size(compress)
+                + self.wire_to_label_mapping.serialized_size(compress)
+        }
+    }
+
+    impl<E: Pairing> CanonicalDeserialize for R1CS<E> {
+        fn deserialize_with_mode<R: Read>(
+            mut reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            let tag = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+            let curve = Curve::from_tag(tag & !WITH_NUM_PRIVATE_INPUTS)?;
+            let num_private_inputs = if tag & WITH_NUM_PRIVATE_INPUTS != 0 {
+                Some(u32::deserialize_with_mode(&mut reader, compress, validate)?)
+            } else {
+                None
+            };
+            let num_public = u32::deserialize_with_mode(&mut reader, compress, validate)?;
+            let num_private = u32::deserialize_with_mode(&mut reader, compress, validate)?;
+            let constraints =
+                Vec::<Constraint<E>>::deserialize_with_mode(&mut reader, compress, validate)?;
+            let wire_to_label_mapping =
+                Vec::<usize>::deserialize_with_mode(&mut reader, compress, validate)?;
+            Ok(Self {
+                curve,
+                num_public,
+                num_private,
+                constraints,
+                wire_to_label_mapping,
+                num_private_inputs: num_private_inputs.unwrap_or(num_private),
+            })
+        }
+    }
+
     impl Valid for Curve {
         fn check(&self) -> Result<(), SerializationError> {
             Ok(())
@@ -164,12 +258,7 @@ mod serialization {
             compress: Compress,
             validate: Validate,
         ) -> Result<Self, SerializationError> {
-            // let t: u8 = CanonicalDeserialize::deserialize(&mut reader)?;
-            match u8::deserialize_with_mode(&mut reader, compress, validate)? {
-                0u8 => Ok(Curve::Bn128),
-                1u8 => Ok(Curve::Bls12_381),
-                _ => Err(SerializationError::InvalidData),
-            }
+            Self::from_tag(u8::deserialize_with_mode(&mut reader, compress, validate)?)
         }
     }
 }
@@ -727,4 +727,45 @@ mod tests {
             Curve::Bls12_381,
         );
     }
+
+    #[test]
+    fn r1cs_serialization_with_and_without_num_private_inputs() {
+        use crate::circom::r1cs::R1CS;
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let r1cs: R1CS<Bls12_381> = R1CSFile::<Bls12_381>::new_from_file(abs_path(
+            "test-vectors/bls12-381/less_than_public_64.r1cs",
+        ))
+        .unwrap()
+        .into();
+        assert!(r1cs.num_private_inputs < r1cs.num_private);
+
+        let mut bytes = vec![];
+        r1cs.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), r1cs.compressed_size());
+        assert_eq!(
+            R1CS::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap(),
+            r1cs
+        );
+
+        // Serialized as before `num_private_inputs` was added
+        let mut old_bytes = vec![];
+        r1cs.curve.serialize_compressed(&mut old_bytes).unwrap();
+        r1cs.num_public
+            .serialize_compressed(&mut old_bytes)
+            .unwrap();
+        r1cs.num_private
+            .serialize_compressed(&mut old_bytes)
+            .unwrap();
+        r1cs.constraints
+            .serialize_compressed(&mut old_bytes)
+            .unwrap();
+        r1cs.wire_to_label_mapping
+            .serialize_compressed(&mut old_bytes)
+            .unwrap();
+        let old = R1CS::<Bls12_381>::deserialize_compressed(&old_bytes[..]).unwrap();
+        assert_eq!(old.num_private_inputs, r1cs.num_private);
+        assert_eq!(old.constraints, r1cs.constraints);
+        assert_eq!(old.wire_to_label_mapping, r1cs.wire_to_label_mapping);
+    }
 }
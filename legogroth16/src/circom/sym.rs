@@ -0,0 +1,385 @@
+//! Parser for .sym file generated by Circom compiler when invoked with flag `--sym`.
+//!
+//! Each line of the file describes a signal of the circuit as `<label index>,<wire index>,<component index>,<signal name>`
+//! where the wire index is -1 when the signal was removed by the compiler's optimizations. The signal name is fully
+//! qualified like `main.age` or `main.n2b.out[3]`.
+//!
+//! The wires of a circuit compiled by Circom are ordered as: the constant 1, public outputs, public inputs, private inputs
+//! and then the intermediate signals. This allows finding the position of a signal among the public inputs given to the
+//! verifier or among the private inputs that are committed to by the LegoGroth16 proof.
+
+use crate::circom::{error::CircomError, r1cs::R1CS};
+use ark_ec::pairing::Pairing;
+use ark_std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+/// A signal of the circuit as described in a single line of the .sym file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signal {
+    pub label_index: u64,
+    /// Index of the wire corresponding to this signal. `None` if the signal was eliminated during compilation.
+    pub wire_index: Option<usize>,
+    pub component_index: u64,
+    /// Fully qualified name of the signal like `main.age`
+    pub name: String,
+}
+
+/// Result of the parsed .sym file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    /// Signals in the order they appear in the .sym file
+    pub signals: Vec<Signal>,
+    /// Signal name -> index in `Self::signals`
+    pub name_to_signal: BTreeMap<String, usize>,
+}
+
+impl SymbolTable {
+    #[cfg(feature = "std")]
+    pub fn new_from_file(path: impl AsRef<std::path::Path>) -> Result<Self, CircomError> {
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            log::error!("Encountered error while opening sym file: {:?}", err);
+            CircomError::UnableToOpenSymFile(format!(
+                "Encountered error while opening sym file: {:?}",
+                err
+            ))
+        })?;
+        Self::new(&contents)
+    }
+
+    /// Parse the contents of the .sym file
+    pub fn new(contents: &str) -> Result<Self, CircomError> {
+        let mut signals = Vec::new();
+        let mut name_to_signal = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Signal name is the last column and does not contain a comma
+            let mut parts = line.splitn(4, ',');
+            let label_index = parse_column::<u64>(parts.next(), i)?;
+            let wire_index = parse_column::<i64>(parts.next(), i)?;
+            let component_index = parse_column::<u64>(parts.next(), i)?;
+            let name = parts
+                .next()
+                .map(|n| n.trim())
+                .filter(|n| !n.is_empty())
+                .ok_or_else(|| {
+                    CircomError::SymFileParsing(format!("Missing signal name on line {}", i + 1))
+                })?;
+            let wire_index = match wire_index {
+                -1 => None,
+                w if w >= 0 => Some(w as usize),
+                w => {
+                    return Err(CircomError::SymFileParsing(format!(
+                        "Invalid wire index {} on line {}",
+                        w,
+                        i + 1
+                    )))
+                }
+            };
+            if name_to_signal
+                .insert(String::from(name), signals.len())
+                .is_some()
+            {
+                return Err(CircomError::SymFileParsing(format!(
+                    "Duplicate signal {} on line {}",
+                    name,
+                    i + 1
+                )));
+            }
+            signals.push(Signal {
+                label_index,
+                wire_index,
+                component_index,
+                name: String::from(name),
+            });
+        }
+        Ok(Self {
+            signals,
+            name_to_signal,
+        })
+    }
+
+    pub fn get_signal(&self, name: &str) -> Option<&Signal> {
+        self.name_to_signal.get(name).map(|i| &self.signals[*i])
+    }
+
+    /// Get the wire index of the signal with the given fully qualified name like `main.age`.
+    pub fn wire_index(&self, name: &str) -> Result<usize, CircomError> {
+        let signal = self
+            .get_signal(name)
+            .ok_or_else(|| CircomError::SignalNotFound(String::from(name)))?;
+        signal
+            .wire_index
+            .ok_or_else(|| CircomError::SignalEliminated(String::from(name)))
+    }
+
+    /// Get the wire indices of the signal with the given fully qualified name. If the signal is an
+    /// array like `main.in` in `signal input in[3]`, the wire indices of all its elements are returned
+    /// in the order `main.in[0]`, `main.in[1]`, `main.in[2]`. For a multidimensional array, the elements
+    /// are returned in the order Circom flattens them. For a non-array signal, a single wire index is returned.
+    pub fn wire_indices(&self, name: &str) -> Result<Vec<usize>, CircomError> {
+        if self.name_to_signal.contains_key(name) {
+            return Ok(ark_std::vec![self.wire_index(name)?]);
+        }
+        let mut elements = self
+            .signals
+            .iter()
+            .filter(|s| is_array_element(name, &s.name))
+            .collect::<Vec<_>>();
+        if elements.is_empty() {
+            return Err(CircomError::SignalNotFound(String::from(name)));
+        }
+        elements.sort_by_key(|s| s.label_index);
+        elements
+            .into_iter()
+            .map(|s| {
+                s.wire_index
+                    .ok_or_else(|| CircomError::SignalEliminated(s.name.clone()))
+            })
+            .collect()
+    }
+
+    /// Get the position of the given signal among the public values (outputs and public inputs) of the
+    /// circuit, i.e. the index in the public inputs given to the verifier. The constant 1 is not
+    /// counted as the verifier does not provide it.
+    pub fn public_input_index<E: Pairing>(
+        &self,
+        r1cs: &R1CS<E>,
+        name: &str,
+    ) -> Result<usize, CircomError> {
+        let wire = self.wire_index(name)?;
+        Self::wire_to_public_input_index(r1cs, name, wire)
+    }
+
+    /// Same as `Self::public_input_index` but for an array signal
+    pub fn public_input_indices<E: Pairing>(
+        &self,
+        r1cs: &R1CS<E>,
+        name: &str,
+    ) -> Result<Vec<usize>, CircomError> {
+        self.wire_indices(name)?
+            .into_iter()
+            .map(|w| Self::wire_to_public_input_index(r1cs, name, w))
+            .collect()
+    }
+
+    /// Get the position of the given private input signal among the private wires of the circuit. The private
+    /// inputs precede the intermediate signals so when the LegoGroth16 proof commits to the first `n` private
+    /// wires, this is the index of the signal among the committed witnesses. Returns an error if the signal is
+    /// public or an intermediate signal.
+    pub fn private_input_index<E: Pairing>(
+        &self,
+        r1cs: &R1CS<E>,
+        name: &str,
+    ) -> Result<usize, CircomError> {
+        let wire = self.wire_index(name)?;
+        Self::wire_to_private_input_index(r1cs, name, wire)
+    }
+
+    /// Same as `Self::private_input_index` but for an array signal
+    pub fn private_input_indices<E: Pairing>(
+        &self,
+        r1cs: &R1CS<E>,
+        name: &str,
+    ) -> Result<Vec<usize>, CircomError> {
+        self.wire_indices(name)?
+            .into_iter()
+            .map(|w| Self::wire_to_private_input_index(r1cs, name, w))
+            .collect()
+    }
+
+    fn wire_to_public_input_index<E: Pairing>(
+        r1cs: &R1CS<E>,
+        name: &str,
+        wire: usize,
+    ) -> Result<usize, CircomError> {
+        // Wire 0 is the constant 1
+        if wire == 0 || wire >= r1cs.num_public as usize {
+            return Err(CircomError::SignalNotPublic(String::from(name)));
+        }
+        Ok(wire - 1)
+    }
+
+    fn wire_to_private_input_index<E: Pairing>(
+        r1cs: &R1CS<E>,
+        name: &str,
+        wire: usize,
+    ) -> Result<usize, CircomError> {
+        if wire < r1cs.num_public as usize
+            || wire >= (r1cs.num_public + r1cs.num_private_inputs) as usize
+        {
+            return Err(CircomError::SignalNotPrivate(String::from(name)));
+        }
+        Ok(wire - r1cs.num_public as usize)
+    }
+}
+
+/// Returns true if `signal` is an element of the array signal `name`, i.e. `name` followed by one or more
+/// indices like `main.in[0]` or `main.in[1][2]` but not a signal of a component in an array like `main.in[0].out`
+fn is_array_element(name: &str, signal: &str) -> bool {
+    let mut rest = match signal.strip_prefix(name) {
+        Some(r) if !r.is_empty() => r,
+        _ => return false,
+    };
+    while !rest.is_empty() {
+        let index = match rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            Some((index, r)) => {
+                rest = r;
+                index
+            }
+            None => return false,
+        };
+        if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+    }
+    true
+}
+
+fn parse_column<T: core::str::FromStr>(col: Option<&str>, line: usize) -> Result<T, CircomError> {
+    col.and_then(|c| c.trim().parse::<T>().ok()).ok_or_else(|| {
+        CircomError::SymFileParsing(format!("Invalid or missing column on line {}", line + 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circom::{
+        circuit::{tests::set_circuit_wires, CircomCircuit},
+        tests::abs_path,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_std::{
+        rand::{prelude::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn parse() {
+        let symbols = SymbolTable::new(
+            "1,1,0,main.out\n2,2,0,main.in[0]\n3,3,0,main.in[1]\n4,-1,1,main.c.x\n5,4,2,main.in2[0]\n6,5,3,main.m[0][1]\n7,6,4,main.m[0].x\n\n",
+        )
+        .unwrap();
+        assert_eq!(symbols.signals.len(), 7);
+        assert_eq!(symbols.wire_index("main.out").unwrap(), 1);
+        assert_eq!(symbols.wire_indices("main.out").unwrap(), vec![1]);
+        assert_eq!(symbols.wire_indices("main.in").unwrap(), vec![2, 3]);
+        // Neither signals with the same prefix nor signals of components in an array are array elements
+        assert_eq!(symbols.wire_indices("main.in2").unwrap(), vec![4]);
+        assert_eq!(symbols.wire_indices("main.m").unwrap(), vec![5]);
+        assert_eq!(
+            symbols.wire_indices("main.i"),
+            Err(CircomError::SignalNotFound("main.i".to_string()))
+        );
+        assert_eq!(symbols.get_signal("main.c.x").unwrap().component_index, 1);
+        assert_eq!(
+            symbols.wire_index("main.c.x"),
+            Err(CircomError::SignalEliminated("main.c.x".to_string()))
+        );
+        assert_eq!(
+            symbols.wire_index("main.in"),
+            Err(CircomError::SignalNotFound("main.in".to_string()))
+        );
+        assert_eq!(
+            symbols.wire_indices("main.x"),
+            Err(CircomError::SignalNotFound("main.x".to_string()))
+        );
+
+        assert!(SymbolTable::new("1,1,0").is_err());
+        assert!(SymbolTable::new("1,a,0,main.a").is_err());
+        assert!(SymbolTable::new("1,-2,0,main.a").is_err());
+        assert!(SymbolTable::new("1,1,0,main.a\n2,2,0,main.a").is_err());
+    }
+
+    #[test]
+    fn signal_positions() {
+        fn check<E: Pairing>(r1cs_file_path: &str, wasm_file_path: &str, sym_file_path: &str) {
+            let mut circuit = CircomCircuit::<E>::from_r1cs_file(abs_path(r1cs_file_path)).unwrap();
+            let symbols = SymbolTable::new_from_file(abs_path(sym_file_path)).unwrap();
+
+            let mut rng = StdRng::seed_from_u64(0u64);
+            let (a, b) = {
+                let a = u64::rand(&mut rng);
+                let b = u64::rand(&mut rng);
+                if a < b {
+                    (E::ScalarField::from(a), E::ScalarField::from(b))
+                } else {
+                    (E::ScalarField::from(b), E::ScalarField::from(a))
+                }
+            };
+            let mut inputs = HashMap::new();
+            inputs.insert("a".to_string(), vec![a]);
+            inputs.insert("b".to_string(), vec![b]);
+            set_circuit_wires(&mut circuit, &abs_path(wasm_file_path), inputs);
+            let wires = circuit.wires.clone().unwrap();
+            let public = circuit.get_public_inputs().unwrap();
+
+            // The symbol table agrees with the wire to label mapping in the .r1cs file
+            for signal in &symbols.signals {
+                if let Some(w) = signal.wire_index {
+                    assert_eq!(
+                        circuit.r1cs.wire_to_label_mapping[w],
+                        signal.label_index as usize
+                    );
+                }
+            }
+
+            // `b` is the public input and `a` is the private input
+            assert_eq!(wires[symbols.wire_index("main.a").unwrap()], a);
+            assert_eq!(wires[symbols.wire_index("main.b").unwrap()], b);
+            assert_eq!(
+                public[symbols.public_input_index(&circuit.r1cs, "main.b").unwrap()],
+                b
+            );
+            assert_eq!(
+                public[symbols
+                    .public_input_index(&circuit.r1cs, "main.out")
+                    .unwrap()],
+                E::ScalarField::from(1u64)
+            );
+            assert_eq!(
+                symbols
+                    .private_input_index(&circuit.r1cs, "main.a")
+                    .unwrap(),
+                0
+            );
+            assert_eq!(
+                symbols.private_input_index(&circuit.r1cs, "main.b"),
+                Err(CircomError::SignalNotPrivate("main.b".to_string()))
+            );
+            assert_eq!(
+                symbols.public_input_index(&circuit.r1cs, "main.a"),
+                Err(CircomError::SignalNotPublic("main.a".to_string()))
+            );
+            // Intermediate signals are not private inputs
+            assert_eq!(
+                symbols.private_input_index(&circuit.r1cs, "main.n2b.out[0]"),
+                Err(CircomError::SignalNotPrivate("main.n2b.out[0]".to_string()))
+            );
+            assert_eq!(
+                symbols
+                    .private_input_indices(&circuit.r1cs, "main.n2b.out")
+                    .unwrap_err(),
+                CircomError::SignalEliminated("main.n2b.out[63]".to_string())
+            );
+        }
+
+        // The .sym files weren't generated by the compiler but written to match the wire to label mapping of
+        // the .r1cs files. They should be replaced with the output of
+        // `circom less_than_public_64.circom --r1cs --wasm --sym -p <bn128|bls12381>`.
+        check::<Bn254>(
+            "test-vectors/bn128/less_than_public_64.r1cs",
+            "test-vectors/bn128/less_than_public_64.wasm",
+            "test-vectors/bn128/less_than_public_64.sym",
+        );
+        check::<Bls12_381>(
+            "test-vectors/bls12-381/less_than_public_64.r1cs",
+            "test-vectors/bls12-381/less_than_public_64.wasm",
+            "test-vectors/bls12-381/less_than_public_64.sym",
+        );
+    }
+}
@@ -1,38 +1,38 @@
-use crate::{Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
-use ark_crypto_primitives::snark::constraints::{CircuitSpecificSetupSNARKGadget, SNARKGadget};
-use ark_crypto_primitives::snark::{BooleanInputVar, SNARK};
-use ark_ec::{AffineRepr, PairingEngine};
-use ark_r1cs_std::groups::CurveVar;
-use ark_r1cs_std::{
-    alloc::{AllocVar, AllocationMode},
-    bits::boolean::Boolean,
-    bits::uint8::UInt8,
-    eq::EqGadget,
-    pairing::PairingVar,
-    ToBitsGadget, ToBytesGadget,
-};
+//! Constraints for the LegoGroth16 verifier so that LegoGroth16 proofs can be verified inside another circuit, i.e.
+//! recursive proof composition. The circuit verifying the proof is over the base field of the curve used to create
+//! the proof so a cycle or a 2-chain of curves is needed, like BLS12-377 and BW6-761 where the scalar field of BW6-761
+//! is the base field of BLS12-377.
+//!
+//! Apart from verifying the proof, the circuit can also check that the commitment to the witnesses in the proof (`proof.d`)
+//! is to the given witnesses, the same as [`verify_witness_commitment`] does outside the circuit.
+//!
+//! [`verify_witness_commitment`]: crate::verify_witness_commitment
+
+use crate::{PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
 use ark_relations::r1cs::{Namespace, SynthesisError};
 use ark_std::{borrow::Borrow, marker::PhantomData, vec::Vec};
 
-/// The proof variable for the Groth16 construction
+/// The proof variable for the LegoGroth16 construction
 #[derive(Derivative)]
 #[derivative(Clone(bound = "P::G1Var: Clone, P::G2Var: Clone"))]
-pub struct ProofVar<E: PairingEngine, P: PairingVar<E>> {
+pub struct ProofVar<E: Pairing, P: PairingVar<E, E::BaseField>> {
     /// The `A` element in `G1`.
     pub a: P::G1Var,
     /// The `B` element in `G2`.
     pub b: P::G2Var,
     /// The `C` element in `G1`.
     pub c: P::G1Var,
+    /// The `D` element in `G1`. Commits to a subset of private inputs of the circuit
+    pub d: P::G1Var,
 }
 
-/// A variable representing the Groth16 verifying key in the constraint system.
+/// A variable representing the LegoGroth16 verifying key in the constraint system.
 #[derive(Derivative)]
-#[derivative(
-    Clone(bound = "P::G1Var: Clone, P::GTVar: Clone, P::G1PreparedVar: Clone, \
-    P::G2PreparedVar: Clone, ")
-)]
-pub struct VerifyingKeyVar<E: PairingEngine, P: PairingVar<E>> {
+#[derivative(Clone(bound = "P::G1Var: Clone, P::G2Var: Clone"))]
+pub struct VerifyingKeyVar<E: Pairing, P: PairingVar<E, E::BaseField>> {
     #[doc(hidden)]
     pub alpha_g1: P::G1Var,
     #[doc(hidden)]
@@ -43,34 +43,18 @@ pub struct VerifyingKeyVar<E: PairingEngine, P: PairingVar<E>> {
     pub delta_g2: P::G2Var,
     #[doc(hidden)]
     pub gamma_abc_g1: Vec<P::G1Var>,
+    #[doc(hidden)]
+    pub eta_gamma_inv_g1: P::G1Var,
+    /// Number of witnesses committed in `proof.d`. This is not a variable in the constraint system.
+    pub commit_witness_count: u32,
 }
 
-impl<E: PairingEngine, P: PairingVar<E>> VerifyingKeyVar<E, P> {
-    /// Prepare `self` for use in proof verification.
-    pub fn prepare(&self) -> Result<PreparedVerifyingKeyVar<E, P>, SynthesisError> {
-        let alpha_g1_pc = P::prepare_g1(&self.alpha_g1)?;
-        let beta_g2_pc = P::prepare_g2(&self.beta_g2)?;
-
-        let alpha_g1_beta_g2 = P::pairing(alpha_g1_pc, beta_g2_pc)?;
-        let gamma_g2_neg_pc = P::prepare_g2(&self.gamma_g2.negate()?)?;
-        let delta_g2_neg_pc = P::prepare_g2(&self.delta_g2.negate()?)?;
-
-        Ok(PreparedVerifyingKeyVar {
-            alpha_g1_beta_g2,
-            gamma_g2_neg_pc,
-            delta_g2_neg_pc,
-            gamma_abc_g1: self.gamma_abc_g1.clone(),
-        })
-    }
-}
-
-/// Preprocessed verification key parameters variable for the Groth16 construction
+/// Preprocessed verification key parameters variable for the LegoGroth16 construction
 #[derive(Derivative)]
-#[derivative(
-    Clone(bound = "P::G1Var: Clone, P::GTVar: Clone, P::G1PreparedVar: Clone, \
-    P::G2PreparedVar: Clone, ")
-)]
-pub struct PreparedVerifyingKeyVar<E: PairingEngine, P: PairingVar<E>> {
+#[derivative(Clone(
+    bound = "P::G1Var: Clone, P::GTVar: Clone, P::G1PreparedVar: Clone, P::G2PreparedVar: Clone"
+))]
+pub struct PreparedVerifyingKeyVar<E: Pairing, P: PairingVar<E, E::BaseField>> {
     #[doc(hidden)]
     pub alpha_g1_beta_g2: P::GTVar,
     #[doc(hidden)]
@@ -79,73 +63,157 @@ pub struct PreparedVerifyingKeyVar<E: PairingEngine, P: PairingVar<E>> {
     pub delta_g2_neg_pc: P::G2PreparedVar,
     #[doc(hidden)]
     pub gamma_abc_g1: Vec<P::G1Var>,
+    #[doc(hidden)]
+    pub eta_gamma_inv_g1: P::G1Var,
+    /// Number of witnesses committed in `proof.d`. This is not a variable in the constraint system.
+    pub commit_witness_count: u32,
+}
+
+/// Elements of the scalar field of the pairing, like the public inputs of the proof or the committed witnesses,
+/// in the constraint system over the base field. Each element is represented by its little-endian bits. When
+/// allocated, each element is allocated as a single element of the base field so the scalar field must be
+/// smaller than the base field, which is the case for pairing friendly curves used for recursion.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct ScalarsVar<E: Pairing> {
+    pub val: Vec<Vec<Boolean<E::BaseField>>>,
+    _pairing: PhantomData<E>,
+}
+
+impl<E: Pairing> From<Vec<Vec<Boolean<E::BaseField>>>> for ScalarsVar<E> {
+    fn from(val: Vec<Vec<Boolean<E::BaseField>>>) -> Self {
+        Self {
+            val,
+            _pairing: PhantomData,
+        }
+    }
 }
 
-/// Constraints for the verifier of the SNARK of [[Groth16]](https://eprint.iacr.org/2016/260.pdf).
-pub struct Groth16VerifierGadget<E, P>
+impl<E: Pairing> ScalarsVar<E> {
+    pub fn len(&self) -> usize {
+        self.val.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.val.is_empty()
+    }
+}
+
+/// Constraints for the verifier of LegoGroth16 proofs.
+pub struct LegoGroth16VerifierGadget<E, P>
 where
-    E: PairingEngine,
-    P: PairingVar<E>,
+    E: Pairing,
+    P: PairingVar<E, E::BaseField>,
 {
     _pairing_engine: PhantomData<E>,
     _pairing_gadget: PhantomData<P>,
 }
 
-impl<E: PairingEngine, P: PairingVar<E, E::Fq>> SNARKGadget<E::ScalarField, E::Fq, Groth16<E>>
-    for Groth16VerifierGadget<E, P>
-{
-    type ProcessedVerifyingKeyVar = PreparedVerifyingKeyVar<E, P>;
-    type VerifyingKeyVar = VerifyingKeyVar<E, P>;
-    type InputVar = BooleanInputVar<E::ScalarField, E::Fq>;
-    type ProofVar = ProofVar<E, P>;
+impl<E: Pairing, P: PairingVar<E, E::BaseField>> VerifyingKeyVar<E, P> {
+    /// Prepare `self` for use in proof verification.
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(self)))]
+    pub fn prepare(&self) -> Result<PreparedVerifyingKeyVar<E, P>, SynthesisError> {
+        let alpha_g1_pc = P::prepare_g1(&self.alpha_g1)?;
+        let beta_g2_pc = P::prepare_g2(&self.beta_g2)?;
 
-    type VerifierSize = usize;
+        let alpha_g1_beta_g2 = P::pairing(alpha_g1_pc, beta_g2_pc)?;
+        let gamma_g2_neg_pc = P::prepare_g2(&self.gamma_g2.negate()?)?;
+        let delta_g2_neg_pc = P::prepare_g2(&self.delta_g2.negate()?)?;
 
-    fn verifier_size(
-        circuit_vk: &<Groth16<E> as SNARK<E::ScalarField>>::VerifyingKey,
-    ) -> Self::VerifierSize {
-        circuit_vk.gamma_abc_g1.len()
+        Ok(PreparedVerifyingKeyVar {
+            alpha_g1_beta_g2,
+            gamma_g2_neg_pc,
+            delta_g2_neg_pc,
+            gamma_abc_g1: self.gamma_abc_g1.clone(),
+            eta_gamma_inv_g1: self.eta_gamma_inv_g1.clone(),
+            commit_witness_count: self.commit_witness_count,
+        })
     }
 
-    /// Allocates `N::Proof` in `cs` without performing
-    /// subgroup checks.
-    #[tracing::instrument(target = "r1cs", skip(cs, f))]
-    fn new_proof_unchecked<T: Borrow<Proof<E>>>(
-        cs: impl Into<Namespace<E::Fq>>,
+    /// Check that the commitment in the proof (`proof.d`) commits to the given witnesses with randomness `v`.
+    /// Returns a boolean which is true iff the commitment is correct.
+    pub fn verify_witness_commitment(
+        &self,
+        proof: &ProofVar<E, P>,
+        public_inputs_count: usize,
+        witnesses_expected_in_commitment: &ScalarsVar<E>,
+        v: &[Boolean<E::BaseField>],
+    ) -> Result<Boolean<E::BaseField>, SynthesisError> {
+        verify_witness_commitment::<E, P>(
+            &self.gamma_abc_g1,
+            &self.eta_gamma_inv_g1,
+            proof,
+            public_inputs_count,
+            witnesses_expected_in_commitment,
+            v,
+        )
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, E::BaseField>> PreparedVerifyingKeyVar<E, P> {
+    /// Check that the commitment in the proof (`proof.d`) commits to the given witnesses with randomness `v`.
+    /// Returns a boolean which is true iff the commitment is correct.
+    pub fn verify_witness_commitment(
+        &self,
+        proof: &ProofVar<E, P>,
+        public_inputs_count: usize,
+        witnesses_expected_in_commitment: &ScalarsVar<E>,
+        v: &[Boolean<E::BaseField>],
+    ) -> Result<Boolean<E::BaseField>, SynthesisError> {
+        verify_witness_commitment::<E, P>(
+            &self.gamma_abc_g1,
+            &self.eta_gamma_inv_g1,
+            proof,
+            public_inputs_count,
+            witnesses_expected_in_commitment,
+            v,
+        )
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, E::BaseField>> LegoGroth16VerifierGadget<E, P> {
+    /// Allocates the proof in `cs` without performing subgroup checks.
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
+    pub fn new_proof_unchecked<T: Borrow<Proof<E>>>(
+        cs: impl Into<Namespace<E::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
-    ) -> Result<Self::ProofVar, SynthesisError> {
+    ) -> Result<ProofVar<E, P>, SynthesisError> {
         let ns = cs.into();
         let cs = ns.cs();
         f().and_then(|proof| {
             let proof = proof.borrow();
-            let a = CurveVar::new_variable_omit_prime_order_check(
+            let a = P::G1Var::new_variable_omit_prime_order_check(
                 ark_relations::ns!(cs, "Proof.a"),
                 || Ok(proof.a.into_group()),
                 mode,
             )?;
-            let b = CurveVar::new_variable_omit_prime_order_check(
+            let b = P::G2Var::new_variable_omit_prime_order_check(
                 ark_relations::ns!(cs, "Proof.b"),
                 || Ok(proof.b.into_group()),
                 mode,
             )?;
-            let c = CurveVar::new_variable_omit_prime_order_check(
+            let c = P::G1Var::new_variable_omit_prime_order_check(
                 ark_relations::ns!(cs, "Proof.c"),
                 || Ok(proof.c.into_group()),
                 mode,
             )?;
-            Ok(ProofVar { a, b, c })
+            let d = P::G1Var::new_variable_omit_prime_order_check(
+                ark_relations::ns!(cs, "Proof.d"),
+                || Ok(proof.d.into_group()),
+                mode,
+            )?;
+            Ok(ProofVar { a, b, c, d })
         })
     }
 
-    /// Allocates `N::Proof` in `cs` without performing
-    /// subgroup checks.
-    #[tracing::instrument(target = "r1cs", skip(cs, f))]
-    fn new_verification_key_unchecked<T: Borrow<VerifyingKey<E>>>(
-        cs: impl Into<Namespace<E::Fq>>,
+    /// Allocates the verification key in `cs` without performing subgroup checks.
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
+    pub fn new_verification_key_unchecked<T: Borrow<VerifyingKey<E>>>(
+        cs: impl Into<Namespace<E::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
-    ) -> Result<Self::VerifyingKeyVar, SynthesisError> {
+    ) -> Result<VerifyingKeyVar<E, P>, SynthesisError> {
         let ns = cs.into();
         let cs = ns.cs();
         f().and_then(|vk| {
@@ -181,6 +249,11 @@ impl<E: PairingEngine, P: PairingVar<E, E::Fq>> SNARKGadget<E::ScalarField, E::F
                     )
                 })
                 .collect::<Result<Vec<_>, _>>()?;
+            let eta_gamma_inv_g1 = P::G1Var::new_variable_omit_prime_order_check(
+                ark_relations::ns!(cs, "eta_gamma_inv_g1"),
+                || Ok(vk.eta_gamma_inv_g1.into_group()),
+                mode,
+            )?;
 
             Ok(VerifyingKeyVar {
                 alpha_g1,
@@ -188,49 +261,51 @@ impl<E: PairingEngine, P: PairingVar<E, E::Fq>> SNARKGadget<E::ScalarField, E::F
                 gamma_g2,
                 delta_g2,
                 gamma_abc_g1,
+                eta_gamma_inv_g1,
+                commit_witness_count: vk.commit_witness_count,
             })
         })
     }
 
-    #[tracing::instrument(target = "r1cs", skip(circuit_pvk, x, proof))]
-    fn verify_with_processed_vk(
-        circuit_pvk: &Self::ProcessedVerifyingKeyVar,
-        x: &Self::InputVar,
-        proof: &Self::ProofVar,
-    ) -> Result<Boolean<E::Fq>, SynthesisError> {
-        let circuit_pvk = circuit_pvk.clone();
-
-        let g_ic = {
-            let mut g_ic: P::G1Var = circuit_pvk.gamma_abc_g1[0].clone();
-            let mut input_len = 1;
-            let mut public_inputs = x.clone().into_iter();
+    /// Verify the proof with the prepared verifying key. Returns a boolean which is true iff the proof is valid.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(target = "r1cs", skip(circuit_pvk, public_inputs, proof))
+    )]
+    pub fn verify_with_processed_vk(
+        circuit_pvk: &PreparedVerifyingKeyVar<E, P>,
+        public_inputs: &ScalarsVar<E>,
+        proof: &ProofVar<E, P>,
+    ) -> Result<Boolean<E::BaseField>, SynthesisError> {
+        if (public_inputs.len() + 1) > circuit_pvk.gamma_abc_g1.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+
+        let d = {
+            let mut d: P::G1Var = circuit_pvk.gamma_abc_g1[0].clone();
             for (input, b) in public_inputs
-                .by_ref()
+                .val
+                .iter()
                 .zip(circuit_pvk.gamma_abc_g1.iter().skip(1))
             {
-                let encoded_input_i: P::G1Var = b.scalar_mul_le(input.to_bits_le()?.iter())?;
-                g_ic += encoded_input_i;
-                input_len += 1;
+                d += b.scalar_mul_le(input.iter())?;
             }
-            // Check that the input and the query in the verification are of the
-            // same length.
-            assert!(input_len == circuit_pvk.gamma_abc_g1.len() && public_inputs.next().is_none());
-            g_ic
+            d += &proof.d;
+            d
         };
 
         let test_exp = {
             let proof_a_prep = P::prepare_g1(&proof.a)?;
             let proof_b_prep = P::prepare_g2(&proof.b)?;
             let proof_c_prep = P::prepare_g1(&proof.c)?;
-
-            let g_ic_prep = P::prepare_g1(&g_ic)?;
+            let d_prep = P::prepare_g1(&d)?;
 
             P::miller_loop(
-                &[proof_a_prep, g_ic_prep, proof_c_prep],
+                &[proof_a_prep, proof_c_prep, d_prep],
                 &[
                     proof_b_prep,
-                    circuit_pvk.gamma_g2_neg_pc.clone(),
                     circuit_pvk.delta_g2_neg_pc.clone(),
+                    circuit_pvk.gamma_g2_neg_pc.clone(),
                 ],
             )?
         };
@@ -239,32 +314,52 @@ impl<E: PairingEngine, P: PairingVar<E, E::Fq>> SNARKGadget<E::ScalarField, E::F
         test.is_eq(&circuit_pvk.alpha_g1_beta_g2)
     }
 
-    #[tracing::instrument(target = "r1cs", skip(circuit_vk, x, proof))]
-    fn verify(
-        circuit_vk: &Self::VerifyingKeyVar,
-        x: &Self::InputVar,
-        proof: &Self::ProofVar,
-    ) -> Result<Boolean<E::Fq>, SynthesisError> {
+    /// Verify the proof with the verifying key. Returns a boolean which is true iff the proof is valid.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(target = "r1cs", skip(circuit_vk, public_inputs, proof))
+    )]
+    pub fn verify(
+        circuit_vk: &VerifyingKeyVar<E, P>,
+        public_inputs: &ScalarsVar<E>,
+        proof: &ProofVar<E, P>,
+    ) -> Result<Boolean<E::BaseField>, SynthesisError> {
         let pvk = circuit_vk.prepare()?;
-        Self::verify_with_processed_vk(&pvk, x, proof)
+        Self::verify_with_processed_vk(&pvk, public_inputs, proof)
     }
 }
 
-impl<E, P> CircuitSpecificSetupSNARKGadget<E::ScalarField, E::Fq, Groth16<E>> for Groth16VerifierGadget<E, P>
-where
-    E: PairingEngine,
-    P: PairingVar<E, E::Fq>,
-{
+/// `proof.d = sum_i(witness_i * gamma_abc_g1[1 + public_inputs_count + i]) + v * eta_gamma_inv_g1`
+fn verify_witness_commitment<E: Pairing, P: PairingVar<E, E::BaseField>>(
+    gamma_abc_g1: &[P::G1Var],
+    eta_gamma_inv_g1: &P::G1Var,
+    proof: &ProofVar<E, P>,
+    public_inputs_count: usize,
+    witnesses_expected_in_commitment: &ScalarsVar<E>,
+    v: &[Boolean<E::BaseField>],
+) -> Result<Boolean<E::BaseField>, SynthesisError> {
+    if (public_inputs_count + witnesses_expected_in_commitment.len() + 1) > gamma_abc_g1.len() {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+    let mut d = eta_gamma_inv_g1.scalar_mul_le(v.iter())?;
+    for (w, b) in witnesses_expected_in_commitment
+        .val
+        .iter()
+        .zip(gamma_abc_g1.iter().skip(1 + public_inputs_count))
+    {
+        d += b.scalar_mul_le(w.iter())?;
+    }
+    proof.d.is_eq(&d)
 }
 
-impl<E, P> AllocVar<PreparedVerifyingKey<E>, E::Fq> for PreparedVerifyingKeyVar<E, P>
+impl<E, P> AllocVar<PreparedVerifyingKey<E>, E::BaseField> for PreparedVerifyingKeyVar<E, P>
 where
-    E: PairingEngine,
-    P: PairingVar<E>,
+    E: Pairing,
+    P: PairingVar<E, E::BaseField>,
 {
-    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
     fn new_variable<T: Borrow<PreparedVerifyingKey<E>>>(
-        cs: impl Into<Namespace<E::Fq>>,
+        cs: impl Into<Namespace<E::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -275,7 +370,7 @@ where
             let pvk = pvk.borrow();
             let alpha_g1_beta_g2 = P::GTVar::new_variable(
                 ark_relations::ns!(cs, "alpha_g1_beta_g2"),
-                || Ok(pvk.alpha_g1_beta_g2.clone()),
+                || Ok(pvk.alpha_g1_beta_g2.0),
                 mode,
             )?;
 
@@ -297,24 +392,32 @@ where
                 mode,
             )?;
 
+            let eta_gamma_inv_g1 = P::G1Var::new_variable(
+                ark_relations::ns!(cs, "eta_gamma_inv_g1"),
+                || Ok(pvk.vk.eta_gamma_inv_g1),
+                mode,
+            )?;
+
             Ok(Self {
                 alpha_g1_beta_g2,
                 gamma_g2_neg_pc,
                 delta_g2_neg_pc,
                 gamma_abc_g1,
+                eta_gamma_inv_g1,
+                commit_witness_count: pvk.vk.commit_witness_count,
             })
         })
     }
 }
 
-impl<E, P> AllocVar<VerifyingKey<E>, E::Fq> for VerifyingKeyVar<E, P>
+impl<E, P> AllocVar<VerifyingKey<E>, E::BaseField> for VerifyingKeyVar<E, P>
 where
-    E: PairingEngine,
-    P: PairingVar<E>,
+    E: Pairing,
+    P: PairingVar<E, E::BaseField>,
 {
-    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
     fn new_variable<T: Borrow<VerifyingKey<E>>>(
-        cs: impl Into<Namespace<E::Fq>>,
+        cs: impl Into<Namespace<E::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -328,6 +431,8 @@ where
                 gamma_g2,
                 delta_g2,
                 gamma_abc_g1,
+                eta_gamma_inv_g1,
+                commit_witness_count,
             } = vk.borrow().clone();
             let alpha_g1 =
                 P::G1Var::new_variable(ark_relations::ns!(cs, "alpha_g1"), || Ok(alpha_g1), mode)?;
@@ -338,26 +443,37 @@ where
             let delta_g2 =
                 P::G2Var::new_variable(ark_relations::ns!(cs, "delta_g2"), || Ok(delta_g2), mode)?;
 
-            let gamma_abc_g1 = Vec::new_variable(cs.clone(), || Ok(gamma_abc_g1), mode)?;
+            let gamma_abc_g1 = Vec::new_variable(
+                ark_relations::ns!(cs, "gamma_abc_g1"),
+                || Ok(gamma_abc_g1),
+                mode,
+            )?;
+            let eta_gamma_inv_g1 = P::G1Var::new_variable(
+                ark_relations::ns!(cs, "eta_gamma_inv_g1"),
+                || Ok(eta_gamma_inv_g1),
+                mode,
+            )?;
             Ok(Self {
                 alpha_g1,
                 beta_g2,
                 gamma_g2,
                 delta_g2,
                 gamma_abc_g1,
+                eta_gamma_inv_g1,
+                commit_witness_count,
             })
         })
     }
 }
 
-impl<E, P> AllocVar<Proof<E>, E::Fq> for ProofVar<E, P>
+impl<E, P> AllocVar<Proof<E>, E::BaseField> for ProofVar<E, P>
 where
-    E: PairingEngine,
-    P: PairingVar<E>,
+    E: Pairing,
+    P: PairingVar<E, E::BaseField>,
 {
-    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
     fn new_variable<T: Borrow<Proof<E>>>(
-        cs: impl Into<Namespace<E::Fq>>,
+        cs: impl Into<Namespace<E::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -365,23 +481,67 @@ where
         let cs = ns.cs();
 
         f().and_then(|proof| {
-            let Proof { a, b, c } = proof.borrow().clone();
+            let Proof { a, b, c, d } = proof.borrow().clone();
             let a = P::G1Var::new_variable(ark_relations::ns!(cs, "a"), || Ok(a), mode)?;
             let b = P::G2Var::new_variable(ark_relations::ns!(cs, "b"), || Ok(b), mode)?;
             let c = P::G1Var::new_variable(ark_relations::ns!(cs, "c"), || Ok(c), mode)?;
-            Ok(Self { a, b, c })
+            let d = P::G1Var::new_variable(ark_relations::ns!(cs, "d"), || Ok(d), mode)?;
+            Ok(Self { a, b, c, d })
         })
     }
 }
 
-impl<E, P> ToBytesGadget<E::Fq> for VerifyingKeyVar<E, P>
+impl<E: Pairing> AllocVar<[E::ScalarField], E::BaseField> for ScalarsVar<E> {
+    /// Allocates each scalar as a single element of the base field and enforces that it is less than the modulus
+    /// of the scalar field so that each scalar has a single representation.
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(cs, f)))]
+    fn new_variable<T: Borrow<[E::ScalarField]>>(
+        cs: impl Into<Namespace<E::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        if E::ScalarField::MODULUS_BIT_SIZE >= E::BaseField::MODULUS_BIT_SIZE {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        f().and_then(|scalars| {
+            let val = scalars
+                .borrow()
+                .iter()
+                .map(|s| {
+                    let s = E::BaseField::from_le_bytes_mod_order(&s.into_bigint().to_bytes_le());
+                    let s = FpVar::new_variable(ark_relations::ns!(cs, "scalar"), || Ok(s), mode)?;
+                    scalar_bits_le::<E>(&s)
+                })
+                .collect::<Result<Vec<_>, SynthesisError>>()?;
+            Ok(Self::from(val))
+        })
+    }
+}
+
+/// Little-endian bits of a scalar allocated as an element of the base field. Enforces that the scalar is less
+/// than the modulus of the scalar field as otherwise `s` and `s + r` would both be accepted as the same scalar.
+fn scalar_bits_le<E: Pairing>(
+    s: &FpVar<E::BaseField>,
+) -> Result<Vec<Boolean<E::BaseField>>, SynthesisError> {
+    let mut r_minus_one = E::ScalarField::MODULUS;
+    r_minus_one.sub_with_borrow(&1u64.into());
+    let mut bits = s.to_bits_le()?;
+    Boolean::enforce_smaller_or_equal_than_le(&bits, r_minus_one)?;
+    bits.truncate(E::ScalarField::MODULUS_BIT_SIZE as usize);
+    Ok(bits)
+}
+
+impl<E, P> ToBytesGadget<E::BaseField> for VerifyingKeyVar<E, P>
 where
-    E: PairingEngine,
-    P: PairingVar<E>,
+    E: Pairing,
+    P: PairingVar<E, E::BaseField>,
 {
     #[inline]
-    #[tracing::instrument(target = "r1cs", skip(self))]
-    fn to_bytes(&self) -> Result<Vec<UInt8<E::Fq>>, SynthesisError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(target = "r1cs", skip(self)))]
+    fn to_bytes(&self) -> Result<Vec<UInt8<E::BaseField>>, SynthesisError> {
         let mut bytes = Vec::new();
         bytes.extend_from_slice(&self.alpha_g1.to_bytes()?);
         bytes.extend_from_slice(&self.beta_g2.to_bytes()?);
@@ -390,146 +550,267 @@ where
         for g in &self.gamma_abc_g1 {
             bytes.extend_from_slice(&g.to_bytes()?);
         }
+        bytes.extend_from_slice(&self.eta_gamma_inv_g1.to_bytes()?);
         Ok(bytes)
     }
 }
 
 #[cfg(test)]
-mod test {
-    use crate::{constraints::Groth16VerifierGadget, Groth16};
-    use ark_crypto_primitives::snark::constraints::SNARKGadget;
-    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
-    use ark_ec::pairing::Pairing;
-    use ark_ff::{Field, UniformRand};
-    use ark_mnt4_298::{
-        constraints::PairingVar as MNT4PairingVar, Fr as MNT4Fr, MNT4_298 as MNT4PairingEngine,
+mod tests {
+    use super::*;
+    use crate::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key,
+        tests::MySillyCircuit, verify_proof, verify_witness_commitment,
     };
-    use ark_mnt6_298::Fr as MNT6Fr;
-    use ark_r1cs_std::bits::boolean::Boolean;
-    use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget};
+    use ark_bls12_377::{constraints::PairingVar as Bls12_377PairingVar, Bls12_377, Fq, Fr};
+    use ark_cp6_782::CP6_782;
     use ark_relations::{
-        lc, ns,
-        r1cs::{ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError},
+        ns,
+        r1cs::{ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef},
+    };
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
     };
-    use ark_std::ops::MulAssign;
-    use ark_std::rand::{rngs::StdRng, SeedableRng};
-
-    #[derive(Copy, Clone)]
-    struct Circuit<F: Field> {
-        a: Option<F>,
-        b: Option<F>,
-        num_constraints: usize,
-        num_variables: usize,
-    }
-
-    impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for Circuit<ConstraintF> {
-        fn generate_constraints(
-            self,
-            cs: ConstraintSystemRef<ConstraintF>,
-        ) -> Result<(), SynthesisError> {
-            let a = cs.new_witness_variable(|| self.a.ok_or(SynthesisError::AssignmentMissing))?;
-            let b = cs.new_witness_variable(|| self.b.ok_or(SynthesisError::AssignmentMissing))?;
-            let c = cs.new_input_variable(|| {
-                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
-                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
-
-                a.mul_assign(&b);
-                Ok(a)
-            })?;
 
-            for _ in 0..(self.num_variables - 3) {
-                cs.new_witness_variable(|| self.a.ok_or(SynthesisError::AssignmentMissing))?;
-            }
+    type Gadget = LegoGroth16VerifierGadget<Bls12_377, Bls12_377PairingVar>;
+    type ProofV = ProofVar<Bls12_377, Bls12_377PairingVar>;
+    type VkV = VerifyingKeyVar<Bls12_377, Bls12_377PairingVar>;
+    type PvkV = PreparedVerifyingKeyVar<Bls12_377, Bls12_377PairingVar>;
 
-            for _ in 0..self.num_constraints {
-                cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + c)
-                    .unwrap();
-            }
-            Ok(())
-        }
+    fn scalar_bits(cs: &ConstraintSystemRef<Fq>, s: &Fr) -> Vec<Boolean<Fq>> {
+        ScalarsVar::<Bls12_377>::new_witness(ns!(cs, "scalar"), || Ok(vec![*s]))
+            .unwrap()
+            .val
+            .remove(0)
     }
 
-    type TestSNARK = Groth16<MNT4PairingEngine>;
-    type TestSNARKGadget = Groth16VerifierGadget<MNT4PairingEngine, MNT4PairingVar>;
-
     #[test]
-    fn groth16_snark_test() {
+    fn verify_proof_in_circuit() {
         let mut rng = StdRng::seed_from_u64(0u64);
-        let a = MNT4Fr::rand(&mut rng);
-        let b = MNT4Fr::rand(&mut rng);
-        let mut c = a;
-        c.mul_assign(&b);
-
-        let circ = Circuit {
-            a: Some(a.clone()),
-            b: Some(b.clone()),
-            num_constraints: 100,
-            num_variables: 25,
-        };
+        let commit_witness_count = 1;
+        let params = generate_random_parameters::<Bls12_377, _, _>(
+            MySillyCircuit { a: None, b: None },
+            commit_witness_count,
+            &mut rng,
+        )
+        .unwrap();
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let a = Fr::rand(&mut rng);
+        let b = Fr::rand(&mut rng);
+        let c = a * b;
+        let v = Fr::rand(&mut rng);
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            v,
+            &params,
+            &mut rng,
+        )
+        .unwrap();
+        verify_proof(&pvk, &proof, &[c]).unwrap();
+        verify_witness_commitment(&params.vk, &proof, 1, &[a], &v).unwrap();
 
-        let (pk, vk) = TestSNARK::setup(circ, &mut rng).unwrap();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let inputs = ScalarsVar::<Bls12_377>::new_input(ns!(cs, "inputs"), || Ok(vec![c])).unwrap();
+        let proof_var = ProofV::new_witness(ns!(cs, "proof"), || Ok(proof.clone())).unwrap();
+        let vk_var = VkV::new_constant(ns!(cs, "vk"), params.vk.clone()).unwrap();
+        assert_eq!(vk_var.commit_witness_count, commit_witness_count);
 
-        let proof = TestSNARK::prove(&pk, circ.clone(), &mut rng).unwrap();
+        Gadget::verify(&vk_var, &inputs, &proof_var)
+            .unwrap()
+            .enforce_equal(&Boolean::TRUE)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // Committed witness and randomness
+        let a_var = ScalarsVar::<Bls12_377>::from(vec![scalar_bits(&cs, &a)]);
+        let v_var = scalar_bits(&cs, &v);
+        vk_var
+            .verify_witness_commitment(&proof_var, 1, &a_var, &v_var)
+            .unwrap()
+            .enforce_equal(&Boolean::TRUE)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
 
-        assert!(
-            TestSNARK::verify(&vk, &vec![c], &proof).unwrap(),
-            "The native verification check fails."
-        );
+        // Wrong witness or randomness
+        let b_var = ScalarsVar::<Bls12_377>::from(vec![scalar_bits(&cs, &b)]);
+        assert!(!vk_var
+            .verify_witness_commitment(&proof_var, 1, &b_var, &v_var)
+            .unwrap()
+            .value()
+            .unwrap());
+        let a_bits = scalar_bits(&cs, &a);
+        assert!(!vk_var
+            .verify_witness_commitment(&proof_var, 1, &a_var, &a_bits)
+            .unwrap()
+            .value()
+            .unwrap());
 
-        let cs_sys = ConstraintSystem::<MNT6Fr>::new();
-        let cs = ConstraintSystemRef::new(cs_sys);
+        // Wrong public input
+        let wrong_inputs =
+            ScalarsVar::<Bls12_377>::new_input(ns!(cs, "inputs"), || Ok(vec![a])).unwrap();
+        assert!(!Gadget::verify(&vk_var, &wrong_inputs, &proof_var)
+            .unwrap()
+            .value()
+            .unwrap());
+
+        // Too many public inputs
+        let too_many =
+            ScalarsVar::<Bls12_377>::new_input(ns!(cs, "inputs"), || Ok(vec![c, a, b])).unwrap();
+        assert!(Gadget::verify(&vk_var, &too_many, &proof_var).is_err());
+        assert!(cs.is_satisfied().unwrap());
+    }
 
-        let input_gadget = <TestSNARKGadget as SNARKGadget<
-            <MNT4PairingEngine as PairingEngine>::Fr,
-            <MNT4PairingEngine as PairingEngine>::Fq,
-            TestSNARK,
-        >>::InputVar::new_input(ns!(cs, "new_input"), || Ok(vec![c]))
-        .unwrap();
-        let proof_gadget = <TestSNARKGadget as SNARKGadget<
-            <MNT4PairingEngine as PairingEngine>::Fr,
-            <MNT4PairingEngine as PairingEngine>::Fq,
-            TestSNARK,
-        >>::ProofVar::new_witness(ns!(cs, "alloc_proof"), || Ok(proof))
-        .unwrap();
-        let vk_gadget = <TestSNARKGadget as SNARKGadget<
-            <MNT4PairingEngine as PairingEngine>::Fr,
-            <MNT4PairingEngine as PairingEngine>::Fq,
-            TestSNARK,
-        >>::VerifyingKeyVar::new_constant(ns!(cs, "alloc_vk"), vk.clone())
+    #[test]
+    fn verify_proof_in_circuit_with_prepared_vk() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = generate_random_parameters::<Bls12_377, _, _>(
+            MySillyCircuit { a: None, b: None },
+            2,
+            &mut rng,
+        )
         .unwrap();
-        <TestSNARKGadget as SNARKGadget<
-            <MNT4PairingEngine as PairingEngine>::Fr,
-            <MNT4PairingEngine as PairingEngine>::Fq,
-            TestSNARK,
-        >>::verify(&vk_gadget, &input_gadget, &proof_gadget)
-        .unwrap()
-        .enforce_equal(&Boolean::constant(true))
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let a = Fr::rand(&mut rng);
+        let b = Fr::rand(&mut rng);
+        let c = a * b;
+        let v = Fr::rand(&mut rng);
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            v,
+            &params,
+            &mut rng,
+        )
         .unwrap();
 
-        assert!(
-            cs.is_satisfied().unwrap(),
-            "Constraints not satisfied: {}",
-            cs.which_is_unsatisfied().unwrap().unwrap_or_default()
-        );
-
-        let pvk = TestSNARK::process_vk(&vk).unwrap();
-        let pvk_gadget = <TestSNARKGadget as SNARKGadget<
-            <MNT4PairingEngine as PairingEngine>::Fr,
-            <MNT4PairingEngine as PairingEngine>::Fq,
-            TestSNARK,
-        >>::ProcessedVerifyingKeyVar::new_constant(
-            ns!(cs, "alloc_pvk"), pvk.clone()
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let inputs = ScalarsVar::<Bls12_377>::new_input(ns!(cs, "inputs"), || Ok(vec![c])).unwrap();
+        let proof_var =
+            Gadget::new_proof_unchecked(ns!(cs, "proof"), || Ok(proof), AllocationMode::Witness)
+                .unwrap();
+        let pvk_var = PvkV::new_constant(ns!(cs, "pvk"), pvk).unwrap();
+        Gadget::verify_with_processed_vk(&pvk_var, &inputs, &proof_var)
+            .unwrap()
+            .enforce_equal(&Boolean::TRUE)
+            .unwrap();
+
+        // Both committed witnesses
+        let witnesses =
+            ScalarsVar::<Bls12_377>::new_witness(ns!(cs, "witnesses"), || Ok(vec![a, b])).unwrap();
+        let v_var = scalar_bits(&cs, &v);
+        pvk_var
+            .verify_witness_commitment(&proof_var, 1, &witnesses, &v_var)
+            .unwrap()
+            .enforce_equal(&Boolean::TRUE)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // Verifying key as witness
+        let vk_var = Gadget::new_verification_key_unchecked(
+            ns!(cs, "vk"),
+            || Ok(params.vk.clone()),
+            AllocationMode::Witness,
         )
         .unwrap();
-        TestSNARKGadget::verify_with_processed_vk(&pvk_gadget, &input_gadget, &proof_gadget)
+        assert_eq!(vk_var.to_bytes().unwrap().len(), {
+            let vk_const = VkV::new_constant(ns!(cs, "vk"), params.vk.clone()).unwrap();
+            vk_const.to_bytes().unwrap().len()
+        });
+        Gadget::verify(&vk_var, &inputs, &proof_var)
             .unwrap()
-            .enforce_equal(&Boolean::constant(true))
+            .enforce_equal(&Boolean::TRUE)
             .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
 
-        assert!(
-            cs.is_satisfied().unwrap(),
-            "Constraints not satisfied: {}",
-            cs.which_is_unsatisfied().unwrap().unwrap_or_default()
-        );
+    #[test]
+    fn scalars_must_be_canonical() {
+        // The scalar is allocated as an element of the base field which is larger than the scalar field so a
+        // prover could assign `s + r` instead of `s`
+        let r = Fq::from_le_bytes_mod_order(&Fr::MODULUS.to_bytes_le());
+        for (value, valid) in [
+            (Fq::from(5u64), true),
+            (r - Fq::from(1u64), true),
+            (r, false),
+            (r + Fq::from(5u64), false),
+        ] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let s = FpVar::new_witness(ns!(cs, "scalar"), || Ok(value)).unwrap();
+            scalar_bits_le::<Bls12_377>(&s).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), valid);
+        }
+    }
+
+    /// Circuit over the base field of BLS12-377 that verifies a LegoGroth16 proof created over BLS12-377
+    struct VerifierCircuit {
+        vk: VerifyingKey<Bls12_377>,
+        proof: Proof<Bls12_377>,
+        public_input: Fr,
+    }
+
+    impl ConstraintSynthesizer<Fq> for VerifierCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fq>) -> Result<(), SynthesisError> {
+            let inputs = ScalarsVar::<Bls12_377>::new_input(ns!(cs, "inputs"), || {
+                Ok(vec![self.public_input])
+            })?;
+            let proof = ProofV::new_witness(ns!(cs, "proof"), || Ok(self.proof))?;
+            let vk = VkV::new_constant(ns!(cs, "vk"), self.vk)?;
+            Gadget::verify(&vk, &inputs, &proof)?.enforce_equal(&Boolean::TRUE)
+        }
+    }
+
+    #[test]
+    fn recursive_proof() {
+        // Prove over BLS12-377 and then prove the verification of that proof over CP6-782 whose scalar field is
+        // the base field of BLS12-377
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let inner_params = generate_random_parameters::<Bls12_377, _, _>(
+            MySillyCircuit { a: None, b: None },
+            1,
+            &mut rng,
+        )
+        .unwrap();
+        let a = Fr::rand(&mut rng);
+        let b = Fr::rand(&mut rng);
+        let c = a * b;
+        let inner_proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            Fr::rand(&mut rng),
+            &inner_params,
+            &mut rng,
+        )
+        .unwrap();
+
+        let circuit = || VerifierCircuit {
+            vk: inner_params.vk.clone(),
+            proof: inner_proof.clone(),
+            public_input: c,
+        };
+        let outer_params =
+            generate_random_parameters::<CP6_782, _, _>(circuit(), 0, &mut rng).unwrap();
+        let outer_proof =
+            create_random_proof(circuit(), Fq::rand(&mut rng), &outer_params, &mut rng).unwrap();
+        let outer_pvk = prepare_verifying_key::<CP6_782>(&outer_params.vk);
+
+        // The public input of the outer proof is the public input of the inner proof as an element of the base field
+        let c_in_base_field = Fq::from_le_bytes_mod_order(&c.into_bigint().to_bytes_le());
+        verify_proof(&outer_pvk, &outer_proof, &[c_in_base_field]).unwrap();
+        assert!(verify_proof(
+            &outer_pvk,
+            &outer_proof,
+            &[c_in_base_field + Fq::from(1u64)]
+        )
+        .is_err());
     }
 }
@@ -1,7 +1,15 @@
-use crate::link::{EK, PP, VK};
-use ark_ec::pairing::{Pairing, PairingOutput};
+use crate::{
+    error::Error,
+    link::{EK, PP, VK},
+};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup, VariableBaseMSM,
+};
 use ark_serialize::*;
-use ark_std::vec::Vec;
+use ark_std::{format, rand::RngCore, string::ToString, vec::Vec, UniformRand, Zero};
+use blake2::Blake2b512;
+use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
 
 /// A proof in the Groth16 SNARK
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
@@ -211,4 +219,125 @@ impl<E: Pairing> VerifyingKey<E> {
         key.push(self.eta_gamma_inv_g1);
         key
     }
+
+    /// Check that the verifying key is well-formed. This should be called on a key received from an
+    /// untrusted party before using it. Checks that all elements are valid and in the correct subgroup,
+    /// none of them is the identity, `gamma` and `delta` are different and that the key has bases for
+    /// the committed witnesses.
+    pub fn validate(&self) -> crate::Result<()> {
+        self.check()
+            .map_err(|e| Error::InvalidVerifyingKey(format!("{:?}", e)))?;
+        if self.alpha_g1.is_zero()
+            || self.beta_g2.is_zero()
+            || self.gamma_g2.is_zero()
+            || self.delta_g2.is_zero()
+            || self.eta_gamma_inv_g1.is_zero()
+        {
+            return Err(Error::InvalidVerifyingKey(
+                "Identity element in verifying key".to_string(),
+            ));
+        }
+        if self.gamma_g2 == self.delta_g2 {
+            return Err(Error::InvalidVerifyingKey(
+                "gamma and delta are equal".to_string(),
+            ));
+        }
+        if self.gamma_abc_g1.len() < 1 + self.commit_witness_count as usize {
+            return Err(Error::InvalidVerifyingKey(format!(
+                "Expected at least {} elements in gamma_abc_g1 but found {}",
+                1 + self.commit_witness_count,
+                self.gamma_abc_g1.len()
+            )));
+        }
+        if let Some(i) = self.gamma_abc_g1.iter().position(|g| g.is_zero()) {
+            return Err(Error::InvalidVerifyingKey(format!(
+                "Identity element at index {} in gamma_abc_g1",
+                i
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<E: Pairing> ProvingKey<E> {
+    /// Check that the proving key is well-formed. This is a cheap check that validates the verifying key
+    /// and checks that `beta`, `delta` and `eta` are consistent between the proving and verifying key.
+    /// It does not check the queries, use [`Self::validate_queries`] for that.
+    pub fn validate(&self) -> crate::Result<()> {
+        self.vk
+            .validate()
+            .map_err(|e| Error::InvalidProvingKey(format!("{:?}", e)))?;
+        let common = &self.common;
+        for (name, g) in [
+            ("beta_g1", &common.beta_g1),
+            ("delta_g1", &common.delta_g1),
+            ("eta_delta_inv_g1", &common.eta_delta_inv_g1),
+        ] {
+            g.check()
+                .map_err(|e| Error::InvalidProvingKey(format!("{}: {:?}", name, e)))?;
+            if g.is_zero() {
+                return Err(Error::InvalidProvingKey(format!("{} is identity", name)));
+            }
+        }
+        if common.b_g1_query.len() != common.b_g2_query.len() {
+            return Err(Error::InvalidProvingKey(format!(
+                "b_g1_query has {} elements but b_g2_query has {}",
+                common.b_g1_query.len(),
+                common.b_g2_query.len()
+            )));
+        }
+        // Checks e(beta_g1, delta_g2) == e(delta_g1, beta_g2) and
+        // e(eta/delta * G, delta * H) == e(eta/gamma * G, gamma * H) in a single multi-pairing by scaling the
+        // 2nd equation with a challenge `c` created by hashing the elements, i.e.
+        // e(beta_g1 + c * eta/delta * G, delta * H) * e(-delta_g1, beta * H) * e(-c * eta/gamma * G, gamma * H) == 1
+        let mut bytes = Vec::new();
+        for g in [
+            &common.beta_g1,
+            &common.delta_g1,
+            &common.eta_delta_inv_g1,
+            &self.vk.eta_gamma_inv_g1,
+        ] {
+            g.serialize_compressed(&mut bytes).unwrap();
+        }
+        for g in [&self.vk.beta_g2, &self.vk.gamma_g2, &self.vk.delta_g2] {
+            g.serialize_compressed(&mut bytes).unwrap();
+        }
+        let c = field_elem_from_try_and_incr::<E::ScalarField, Blake2b512>(&bytes);
+        let g1 = E::G1::normalize_batch(&[
+            common.beta_g1 + common.eta_delta_inv_g1 * c,
+            -common.delta_g1.into_group(),
+            self.vk.eta_gamma_inv_g1 * -c,
+        ]);
+        if !E::multi_pairing(g1, [self.vk.delta_g2, self.vk.beta_g2, self.vk.gamma_g2]).is_zero() {
+            return Err(Error::InvalidProvingKey(
+                "beta, delta or eta inconsistent with verifying key".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Does the checks of [`Self::validate`] and additionally checks that all elements of the queries are
+    /// valid and that the queries `b_g1_query` and `b_g2_query` have the same discrete logs. Much more
+    /// expensive than [`Self::validate`] as it is linear in the size of the circuit.
+    pub fn validate_queries<R: RngCore>(&self, rng: &mut R) -> crate::Result<()> {
+        self.validate()?;
+        self.common
+            .check()
+            .map_err(|e| Error::InvalidProvingKey(format!("{:?}", e)))?;
+        let common = &self.common;
+        // e(\sum_i r_i * b_g1_query_i, delta * H) == e(delta * G, \sum_i r_i * b_g2_query_i)
+        let r = (0..common.b_g1_query.len())
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let b_g1 = E::G1::msm_unchecked(&common.b_g1_query, &r);
+        let b_g2 = E::G2::msm_unchecked(&common.b_g2_query, &r);
+        if E::pairing(b_g1.into_affine(), self.vk.delta_g2)
+            != E::pairing(common.delta_g1, b_g2.into_affine())
+        {
+            return Err(Error::InvalidProvingKey(
+                "b_g1_query and b_g2_query are inconsistent".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
@@ -5,6 +5,7 @@ use crate::circom::error::CircomError;
 
 use crate::link::error::LinkError;
 use ark_relations::r1cs::SynthesisError;
+use ark_std::string::String;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Error {
@@ -15,6 +16,16 @@ pub enum Error {
     InvalidLinkCommitment,
     InvalidWitnessCommitment,
     InsufficientWitnessesForCommitment(usize, usize),
+    PowersOfTauParsing(String),
+    InvalidPowersOfTau(String),
+    /// The QAP evaluation domain of the circuit is larger than supported by the powers of tau. Contains
+    /// the required domain size and the maximum supported domain size
+    InsufficientPowersOfTau(usize, usize),
+    InvalidMPCParameters(String),
+    InvalidVerifyingKey(String),
+    InvalidProvingKey(String),
+    /// Contribution at the given index in the phase-2 ceremony is invalid
+    InvalidMPCContribution(usize),
     #[cfg(feature = "circom")]
     CircomError(CircomError),
     #[cfg(feature = "aggregation")]
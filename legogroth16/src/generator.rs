@@ -1,11 +1,15 @@
 use crate::{
     link::{PESubspaceSnark, SparseMatrix, SubspaceSnark, PP},
+    ptau::PowersOfTau,
     r1cs_to_qap::LibsnarkReduction,
     LinkPublicGenerators, ProvingKey, ProvingKeyCommon, ProvingKeyWithLink, Vec, VerifyingKey,
     VerifyingKeyWithLink,
 };
-use ark_ec::{pairing::Pairing, scalar_mul::fixed_base::FixedBase, CurveGroup, Group};
-use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ark_ec::{
+    pairing::Pairing, scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup, Group,
+    VariableBaseMSM,
+};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_relations::r1cs::{
     ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisError, SynthesisMode,
@@ -441,6 +445,202 @@ where
     Ok((ProvingKey { vk, common }, num_instance_variables))
 }
 
+/// Create parameters for a circuit using the output of a phase-1 trusted setup ceremony (powers of tau) such
+/// that `tau`, `alpha` and `beta` are not known to the caller. Only `gamma`, `delta` and `eta` are generated
+/// using the given `rng` and thus known to the caller. The powers should have been checked using
+/// `PowersOfTau::verify` if they come from an untrusted source.
+/// `commit_witness_count` is the number of witnesses committed in proof
+pub fn generate_parameters_using_powers_of_tau<E, C, R>(
+    circuit: C,
+    powers: &PowersOfTau<E>,
+    commit_witness_count: u32,
+    rng: &mut R,
+) -> crate::Result<ProvingKey<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField>,
+    R: Rng,
+{
+    let gamma = E::ScalarField::rand(rng);
+    let delta = E::ScalarField::rand(rng);
+    let eta = E::ScalarField::rand(rng);
+    generate_parameters_using_powers_of_tau_and_toxic_waste(
+        circuit,
+        powers,
+        gamma,
+        delta,
+        eta,
+        commit_witness_count,
+    )
+}
+
+/// Create parameters for a circuit using the output of a phase-1 trusted setup ceremony (powers of tau) and the
+/// given `gamma`, `delta` and `eta`. The generators of the groups are the ones used in the powers of tau.
+/// The QAP polynomials are evaluated at `tau` in the exponent using the Lagrange basis computed from the powers.
+pub fn generate_parameters_using_powers_of_tau_and_toxic_waste<E, C>(
+    circuit: C,
+    powers: &PowersOfTau<E>,
+    gamma: E::ScalarField,
+    delta: E::ScalarField,
+    eta: E::ScalarField,
+    commit_witness_count: u32,
+) -> crate::Result<ProvingKey<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField>,
+{
+    type D<F> = GeneralEvaluationDomain<F>;
+
+    let setup_time = start_timer!(|| "Groth16::Generator using powers of tau");
+    let cs = ConstraintSystem::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    cs.set_mode(SynthesisMode::Setup);
+
+    let synthesis_time = start_timer!(|| "Constraint synthesis");
+    circuit.generate_constraints(cs.clone())?;
+    end_timer!(synthesis_time);
+
+    let lc_time = start_timer!(|| "Inlining LCs");
+    cs.finalize();
+    end_timer!(lc_time);
+
+    let num_instance_variables = cs.num_instance_variables();
+    let num_constraints = cs.num_constraints();
+    if cs.num_witness_variables() < commit_witness_count as usize {
+        return Err(crate::error::Error::InsufficientWitnessesForCommitment(
+            cs.num_witness_variables(),
+            commit_witness_count as usize,
+        ));
+    }
+
+    let domain = D::<E::ScalarField>::new(num_constraints + num_instance_variables)
+        .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+    let domain_size = domain.size();
+    if domain_size > powers.max_domain_size() || powers.tau_g1.len() < 2 * domain_size - 1 {
+        return Err(crate::error::Error::InsufficientPowersOfTau(
+            domain_size,
+            powers.max_domain_size(),
+        ));
+    }
+
+    // Evaluations of the Lagrange polynomials at `tau` in the exponent, i.e. `L_i(tau) * G`, `L_i(tau) * H`,
+    // `alpha * L_i(tau) * G` and `beta * L_i(tau) * G`. Since `L_i(X) = 1/n * sum_j (X * w^{-i})^j`, these are
+    // the inverse FFT of the powers of `tau`
+    let lagrange_time = start_timer!(|| "Compute Lagrange basis from powers of tau");
+    let to_lagrange_g1 = |p: &[E::G1Affine]| {
+        let mut p = cfg_iter!(p[..domain_size])
+            .map(|p| p.into_group())
+            .collect::<Vec<_>>();
+        domain.ifft_in_place(&mut p);
+        E::G1::normalize_batch(&p)
+    };
+    let l_g1 = to_lagrange_g1(&powers.tau_g1);
+    let alpha_l_g1 = to_lagrange_g1(&powers.alpha_tau_g1);
+    let beta_l_g1 = to_lagrange_g1(&powers.beta_tau_g1);
+    let mut l_g2 = cfg_iter!(powers.tau_g2[..domain_size])
+        .map(|p| p.into_group())
+        .collect::<Vec<_>>();
+    domain.ifft_in_place(&mut l_g2);
+    let l_g2 = E::G2::normalize_batch(&l_g2);
+    end_timer!(lagrange_time);
+
+    // Evaluate QAP polynomials at `tau` in the exponent as done in `LibsnarkReduction::instance_map_with_evaluation`.
+    // The matrices are transposed so that the evaluation for each variable is a multi-scalar multiplication
+    // of the Lagrange basis by the variable's coefficients in the constraints.
+    let qap_time = start_timer!(|| "Evaluate QAP in the exponent");
+    let matrices = cs.to_matrices().unwrap();
+    let qap_num_variables = (num_instance_variables - 1) + cs.num_witness_variables();
+    let transpose = |m: &[Vec<(E::ScalarField, usize)>]| {
+        let mut t = vec![vec![]; qap_num_variables + 1];
+        for (i, row) in m.iter().enumerate() {
+            for &(coeff, index) in row {
+                t[index].push((coeff, i));
+            }
+        }
+        t
+    };
+    let mut a_t = transpose(&matrices.a);
+    let b_t = transpose(&matrices.b);
+    let c_t = transpose(&matrices.c);
+    for (i, a) in a_t.iter_mut().enumerate().take(num_instance_variables) {
+        a.push((E::ScalarField::one(), num_constraints + i));
+    }
+
+    /// Coefficients of a variable in the constraints with the constraint index
+    type Coeffs<F> = Vec<(F, usize)>;
+    fn eval<G: AffineRepr>(terms: &[(&[G], &Coeffs<G::ScalarField>)]) -> G::Group {
+        let (bases, scalars): (Vec<_>, Vec<_>) = terms
+            .iter()
+            .flat_map(|(bases, t)| t.iter().map(|(c, i)| (bases[*i], *c)))
+            .unzip();
+        G::Group::msm_unchecked(&bases, &scalars)
+    }
+
+    let a = cfg_iter!(a_t)
+        .map(|t| eval(&[(&l_g1, t)]))
+        .collect::<Vec<_>>();
+    let b_g1 = cfg_iter!(b_t)
+        .map(|t| eval(&[(&l_g1, t)]))
+        .collect::<Vec<_>>();
+    let b_g2 = cfg_iter!(b_t)
+        .map(|t| eval(&[(&l_g2, t)]))
+        .collect::<Vec<_>>();
+    // `(beta * a_i(tau) + alpha * b_i(tau) + c_i(tau)) * G`
+    let abc = cfg_into_iter!(0..qap_num_variables + 1)
+        .map(|i| {
+            eval(&[
+                (&beta_l_g1, &a_t[i]),
+                (&alpha_l_g1, &b_t[i]),
+                (&l_g1, &c_t[i]),
+            ])
+        })
+        .collect::<Vec<_>>();
+    end_timer!(qap_time);
+
+    let gamma_inverse = gamma.inverse().ok_or(SynthesisError::UnexpectedIdentity)?;
+    let delta_inverse = delta.inverse().ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    let n = num_instance_variables + commit_witness_count as usize;
+    let gamma_abc_g1 = cfg_iter!(abc[..n])
+        .map(|p| *p * gamma_inverse)
+        .collect::<Vec<_>>();
+    let l_query = cfg_iter!(abc[n..])
+        .map(|p| *p * delta_inverse)
+        .collect::<Vec<_>>();
+
+    // `(tau^{i+n} - tau^i) * delta^-1 * G = t(tau) * tau^i * delta^-1 * G` where `t(X) = X^n - 1` is the vanishing
+    // polynomial of the domain
+    let h_query = cfg_into_iter!(0..domain_size - 1)
+        .map(|i| (powers.tau_g1[i + domain_size].into_group() - powers.tau_g1[i]) * delta_inverse)
+        .collect::<Vec<_>>();
+
+    let g1_generator = powers.tau_g1[0].into_group();
+    let g2_generator = powers.tau_g2[0].into_group();
+
+    let vk = VerifyingKey::<E> {
+        alpha_g1: powers.alpha_tau_g1[0],
+        beta_g2: powers.beta_g2,
+        gamma_g2: (g2_generator * gamma).into_affine(),
+        delta_g2: (g2_generator * delta).into_affine(),
+        gamma_abc_g1: E::G1::normalize_batch(&gamma_abc_g1),
+        eta_gamma_inv_g1: (g1_generator * (eta * gamma_inverse)).into_affine(),
+        commit_witness_count,
+    };
+
+    let common = ProvingKeyCommon {
+        beta_g1: powers.beta_tau_g1[0],
+        delta_g1: (g1_generator * delta).into_affine(),
+        eta_delta_inv_g1: (g1_generator * (eta * delta_inverse)).into_affine(),
+        a_query: E::G1::normalize_batch(&a),
+        b_g1_query: E::G1::normalize_batch(&b_g1),
+        b_g2_query: E::G2::normalize_batch(&b_g2),
+        h_query: E::G1::normalize_batch(&h_query),
+        l_query: E::G1::normalize_batch(&l_query),
+    };
+    end_timer!(setup_time);
+    Ok(ProvingKey { vk, common })
+}
+
 #[inline]
 fn generate_randomness<E, R>(
     rng: &mut R,
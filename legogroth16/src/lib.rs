@@ -34,6 +34,12 @@ pub mod link;
 
 pub mod error;
 
+/// Import powers of tau from a phase-1 trusted setup ceremony in the .ptau format of snarkjs
+pub mod ptau;
+
+/// Multi-party phase-2 trusted setup ceremony to create proving keys
+pub mod mpc;
+
 /// Create and verify proofs for Circom programs
 #[cfg(feature = "circom")]
 pub mod circom;
@@ -42,9 +48,8 @@ pub mod circom;
 pub mod aggregation;
 
 /// Constraints for the LegoGroth16 verifier.
-// Cannot yet create a LegoGroth16 gadget (for recursive proof) so commenting it out.
-// #[cfg(feature = "r1cs")]
-// pub mod constraints;
+#[cfg(feature = "r1cs")]
+pub mod constraints;
 
 pub type Result<T> = core::result::Result<T, error::Error>;
 
@@ -0,0 +1,615 @@
+//! Multi-party computation (MPC) ceremony for the circuit specific phase (phase 2) of the trusted setup of LegoGroth16
+//! such that the proving key can be used as long as at least one participant was honest and destroyed its secrets.
+//! This follows the approach of [BGM17](https://eprint.iacr.org/2017/1050) used for Groth16 but LegoGroth16 has 3 circuit
+//! specific secrets, `gamma`, `delta` and `eta` and each participant contributes to all of them. `gamma` is needed
+//! as a secret since SAVER uses `gamma * G` and the elements `(beta * u_i(tau) + alpha * v_i(tau) + w_i(tau)) / gamma * G` as
+//! its encryption key and `eta` is the secret used in the commitment key `eta / gamma * G` and `eta / delta * G`.
+//!
+//! The ceremony works as follows:
+//! 1. The coordinator creates the initial parameters using [`MPCParameters::new`] with the output of a phase-1 ceremony
+//!    (powers of tau). This does not involve any randomness so anyone can recreate these parameters.
+//! 2. Each participant, in sequence, takes the latest parameters and calls [`MPCParameters::contribute`] which samples fresh
+//!    `gamma`, `delta` and `eta`, updates the parameters and appends a proof of the contribution. The participant publishes
+//!    the returned hash so that it can later check that its contribution was included.
+//! 3. The coordinator checks each contribution using [`MPCParameters::verify_contribution`] before passing the parameters
+//!    to the next participant.
+//! 4. Once all participants have contributed, anyone can check the whole transcript offline using [`MPCParameters::verify`]
+//!    with the circuit and the powers of tau and get the hashes of all contributions.
+//!
+//! Each contribution contains `s * x` and `r * x` for each secret `x` where `s` is a random element of group G1 and `r` is
+//! an element of group G2 derived by hashing the transcript till the previous contribution and the `s * x`. This proves
+//! knowledge of `x` and makes the contribution depend on the previous ones so contributions can't be replayed.
+//!
+//! The ceremony does not produce CP_link keys, i.e. a [`ProvingKeyWithLink`](crate::ProvingKeyWithLink). These need the
+//! keys of a subspace SNARK which have their own secrets that the ceremony does not contribute to. Use the proving key
+//! from [`MPCParameters::get_params`] and the proof's commitment to the witnesses instead.
+
+use crate::{
+    error::Error, generate_parameters_using_powers_of_tau_and_toxic_waste, ptau::PowersOfTau,
+    ProvingKey,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    cfg_iter, format,
+    rand::{Rng, RngCore},
+    string::ToString,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use blake2::{Blake2b512, Digest};
+use dock_crypto_utils::{
+    hashing_utils::affine_group_elem_from_try_and_incr,
+    randomized_pairing_check::RandomizedPairingChecker,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Hash of the initial parameters or of a contribution. Each hash depends on all previous contributions.
+pub type ContributionHash = [u8; 64];
+
+/// Parameters of the phase-2 ceremony. The proving key is in `params` and the rest is needed to verify the ceremony.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MPCParameters<E: Pairing> {
+    pub params: ProvingKey<E>,
+    /// `gamma * G`. Also needed by SAVER.
+    pub gamma_g1: E::G1Affine,
+    /// `eta * G`
+    pub eta_g1: E::G1Affine,
+    /// Hash of the initial parameters, i.e. before any contribution
+    pub cs_hash: ContributionHash,
+    pub contributions: Vec<Contribution<E>>,
+}
+
+/// Public part of a participant's contribution which proves knowledge of the participant's secrets `gamma`, `delta`
+/// and `eta` by which the previous parameters were updated.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: Pairing> {
+    /// `gamma * G` after this contribution, i.e. product of all `gamma`s till this contribution times `G`
+    pub gamma_after: E::G1Affine,
+    /// `delta * G` after this contribution
+    pub delta_after: E::G1Affine,
+    /// `eta * G` after this contribution
+    pub eta_after: E::G1Affine,
+    /// Random element `s` of G1
+    pub s: E::G1Affine,
+    pub s_gamma: E::G1Affine,
+    pub s_delta: E::G1Affine,
+    pub s_eta: E::G1Affine,
+    /// `r * x` for each secret `x` where `r` is created by hashing the previous hash and `s`
+    pub r_gamma: E::G2Affine,
+    pub r_delta: E::G2Affine,
+    pub r_eta: E::G2Affine,
+}
+
+impl<E: Pairing> MPCParameters<E> {
+    /// Create the initial parameters for the circuit using the output of a phase-1 ceremony. `gamma`, `delta`
+    /// and `eta` are 1 so these parameters must not be used to create proofs before at least one contribution.
+    /// `commit_witness_count` is the number of witnesses committed in proof
+    pub fn new<C: ConstraintSynthesizer<E::ScalarField>>(
+        circuit: C,
+        powers: &PowersOfTau<E>,
+        commit_witness_count: u32,
+    ) -> crate::Result<Self> {
+        if powers.tau_g1.is_empty()
+            || powers.tau_g2.is_empty()
+            || powers.tau_g1[0] != E::G1Affine::generator()
+            || powers.tau_g2[0] != E::G2Affine::generator()
+        {
+            return Err(Error::InvalidPowersOfTau(
+                "First powers should be the group generators".to_string(),
+            ));
+        }
+        let one = E::ScalarField::one();
+        let params = generate_parameters_using_powers_of_tau_and_toxic_waste(
+            circuit,
+            powers,
+            one,
+            one,
+            one,
+            commit_witness_count,
+        )?;
+        let cs_hash = hash_initial_params(&params);
+        Ok(Self {
+            params,
+            gamma_g1: powers.tau_g1[0],
+            eta_g1: powers.tau_g1[0],
+            cs_hash,
+            contributions: vec![],
+        })
+    }
+
+    /// Contribute randomness to the parameters. The secrets are generated using the given `rng` and are
+    /// dropped once this returns. Returns the hash of this contribution which the participant should publish
+    /// and later check that it's present in the list returned by `Self::verify`.
+    pub fn contribute<R: RngCore>(&mut self, rng: &mut R) -> ContributionHash {
+        let gamma = non_zero_field_elem::<E::ScalarField, _>(rng);
+        let delta = non_zero_field_elem::<E::ScalarField, _>(rng);
+        let eta = non_zero_field_elem::<E::ScalarField, _>(rng);
+        let gamma_inv = gamma.inverse().unwrap();
+        let delta_inv = delta.inverse().unwrap();
+
+        let previous_hash = self.last_hash();
+        let s = E::G1::rand(rng).into_affine();
+        let s_gamma = (s * gamma).into_affine();
+        let s_delta = (s * delta).into_affine();
+        let s_eta = (s * eta).into_affine();
+        let r = hash_to_g2::<E>(&previous_hash, &s, &s_gamma, &s_delta, &s_eta);
+
+        let vk = &mut self.params.vk;
+        let common = &mut self.params.common;
+        self.gamma_g1 = (self.gamma_g1 * gamma).into_affine();
+        self.eta_g1 = (self.eta_g1 * eta).into_affine();
+        vk.gamma_g2 = (vk.gamma_g2 * gamma).into_affine();
+        vk.delta_g2 = (vk.delta_g2 * delta).into_affine();
+        vk.gamma_abc_g1 = scale(&vk.gamma_abc_g1, &gamma_inv);
+        vk.eta_gamma_inv_g1 = (vk.eta_gamma_inv_g1 * (eta * gamma_inv)).into_affine();
+        common.delta_g1 = (common.delta_g1 * delta).into_affine();
+        common.eta_delta_inv_g1 = (common.eta_delta_inv_g1 * (eta * delta_inv)).into_affine();
+        common.h_query = scale(&common.h_query, &delta_inv);
+        common.l_query = scale(&common.l_query, &delta_inv);
+
+        let contribution = Contribution {
+            gamma_after: self.gamma_g1,
+            delta_after: common.delta_g1,
+            eta_after: self.eta_g1,
+            s,
+            s_gamma,
+            s_delta,
+            s_eta,
+            r_gamma: (r * gamma).into_affine(),
+            r_delta: (r * delta).into_affine(),
+            r_eta: (r * eta).into_affine(),
+        };
+        let hash = hash_contribution(&previous_hash, &contribution);
+        self.contributions.push(contribution);
+        hash
+    }
+
+    /// Verify that `after` was created by a single contribution to `self`. This is what the coordinator
+    /// of the ceremony should check after each contribution. `self` is assumed to have been verified already.
+    /// Returns the hash of the new contribution.
+    pub fn verify_contribution<R: RngCore>(
+        &self,
+        after: &Self,
+        rng: &mut R,
+    ) -> crate::Result<ContributionHash> {
+        check_unchanged(self, after)?;
+        let index = self.contributions.len();
+        if after.contributions.len() != index + 1 {
+            return Err(Error::InvalidMPCParameters(format!(
+                "Expected {} contributions but found {}",
+                index + 1,
+                after.contributions.len()
+            )));
+        }
+        if after.contributions[..index] != self.contributions[..] {
+            return Err(Error::InvalidMPCParameters(
+                "Previous contributions were modified".to_string(),
+            ));
+        }
+        let previous_hash = self.last_hash();
+        let contribution = &after.contributions[index];
+        let mut checker = RandomizedPairingChecker::<E>::new_using_rng(rng, true);
+        check_contribution(
+            &previous_hash,
+            (&self.gamma_g1, &self.params.common.delta_g1, &self.eta_g1),
+            contribution,
+            index,
+            &mut checker,
+        )?;
+        check_final_state(after, contribution)?;
+        check_updated_elements(self, after, &mut checker, rng);
+        if !checker.verify() {
+            return Err(Error::InvalidMPCContribution(index));
+        }
+        Ok(hash_contribution(&previous_hash, contribution))
+    }
+
+    /// Verify the whole transcript of the ceremony. This recreates the initial parameters from the circuit and the
+    /// powers of tau and then checks each contribution. Anyone can run this offline. Returns the hashes of all
+    /// contributions, in order.
+    pub fn verify<C: ConstraintSynthesizer<E::ScalarField>, R: RngCore>(
+        &self,
+        circuit: C,
+        powers: &PowersOfTau<E>,
+        commit_witness_count: u32,
+        rng: &mut R,
+    ) -> crate::Result<Vec<ContributionHash>> {
+        let initial = Self::new(circuit, powers, commit_witness_count)?;
+        check_unchanged(&initial, self)?;
+        if self.contributions.is_empty() {
+            return Err(Error::InvalidMPCParameters(
+                "No contributions found".to_string(),
+            ));
+        }
+
+        let mut checker = RandomizedPairingChecker::<E>::new_using_rng(rng, true);
+        let mut hashes = Vec::with_capacity(self.contributions.len());
+        let mut previous_hash = initial.cs_hash;
+        let mut before = (
+            initial.gamma_g1,
+            initial.params.common.delta_g1,
+            initial.eta_g1,
+        );
+        for (i, contribution) in self.contributions.iter().enumerate() {
+            check_contribution(
+                &previous_hash,
+                (&before.0, &before.1, &before.2),
+                contribution,
+                i,
+                &mut checker,
+            )?;
+            previous_hash = hash_contribution(&previous_hash, contribution);
+            hashes.push(previous_hash);
+            before = (
+                contribution.gamma_after,
+                contribution.delta_after,
+                contribution.eta_after,
+            );
+        }
+        check_final_state(self, self.contributions.last().unwrap())?;
+        check_updated_elements(&initial, self, &mut checker, rng);
+        if !checker.verify() {
+            return Err(Error::InvalidMPCParameters(
+                "Pairing check failed".to_string(),
+            ));
+        }
+        Ok(hashes)
+    }
+
+    /// Hashes of all contributions, in order
+    pub fn contribution_hashes(&self) -> Vec<ContributionHash> {
+        let mut hash = self.cs_hash;
+        self.contributions
+            .iter()
+            .map(|c| {
+                hash = hash_contribution(&hash, c);
+                hash
+            })
+            .collect()
+    }
+
+    /// Hash of the last contribution or of the initial parameters if there are no contributions
+    pub fn last_hash(&self) -> ContributionHash {
+        self.contribution_hashes().pop().unwrap_or(self.cs_hash)
+    }
+
+    /// Get the proving key
+    pub fn get_params(&self) -> &ProvingKey<E> {
+        &self.params
+    }
+}
+
+/// Check that the elements that don't depend on the secrets of the phase-2 ceremony are unchanged and the
+/// sizes of the ones that do are unchanged
+fn check_unchanged<E: Pairing>(
+    before: &MPCParameters<E>,
+    after: &MPCParameters<E>,
+) -> crate::Result<()> {
+    if before.cs_hash != after.cs_hash {
+        return Err(Error::InvalidMPCParameters(
+            "Hash of the initial parameters does not match".to_string(),
+        ));
+    }
+    let (b_vk, a_vk) = (&before.params.vk, &after.params.vk);
+    let (b_pk, a_pk) = (&before.params.common, &after.params.common);
+    if b_vk.alpha_g1 != a_vk.alpha_g1
+        || b_vk.beta_g2 != a_vk.beta_g2
+        || b_vk.commit_witness_count != a_vk.commit_witness_count
+        || b_pk.beta_g1 != a_pk.beta_g1
+        || b_pk.a_query != a_pk.a_query
+        || b_pk.b_g1_query != a_pk.b_g1_query
+        || b_pk.b_g2_query != a_pk.b_g2_query
+    {
+        return Err(Error::InvalidMPCParameters(
+            "Parameters not dependent on the ceremony were modified".to_string(),
+        ));
+    }
+    if b_vk.gamma_abc_g1.len() != a_vk.gamma_abc_g1.len()
+        || b_pk.h_query.len() != a_pk.h_query.len()
+        || b_pk.l_query.len() != a_pk.l_query.len()
+    {
+        return Err(Error::InvalidMPCParameters(
+            "Size of parameters was modified".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the proof of knowledge of the contributed secrets and that `gamma * G`, `delta * G` and `eta * G`
+/// were updated using them. `before` are `gamma * G`, `delta * G` and `eta * G` before this contribution.
+fn check_contribution<E: Pairing>(
+    previous_hash: &ContributionHash,
+    before: (&E::G1Affine, &E::G1Affine, &E::G1Affine),
+    contribution: &Contribution<E>,
+    index: usize,
+    checker: &mut RandomizedPairingChecker<E>,
+) -> crate::Result<()> {
+    let c = contribution;
+    if [
+        c.gamma_after,
+        c.delta_after,
+        c.eta_after,
+        c.s,
+        c.s_gamma,
+        c.s_delta,
+        c.s_eta,
+    ]
+    .iter()
+    .any(|p| p.is_zero())
+        || c.r_gamma.is_zero()
+        || c.r_delta.is_zero()
+        || c.r_eta.is_zero()
+    {
+        return Err(Error::InvalidMPCContribution(index));
+    }
+    let r = hash_to_g2::<E>(previous_hash, &c.s, &c.s_gamma, &c.s_delta, &c.s_eta);
+    // `s * x` and `r * x` use the same `x`
+    checker.add_sources(&c.s, c.r_gamma, &c.s_gamma, r);
+    checker.add_sources(&c.s, c.r_delta, &c.s_delta, r);
+    checker.add_sources(&c.s, c.r_eta, &c.s_eta, r);
+    // The elements were updated using the same `x`
+    checker.add_sources(before.0, c.r_gamma, &c.gamma_after, r);
+    checker.add_sources(before.1, c.r_delta, &c.delta_after, r);
+    checker.add_sources(before.2, c.r_eta, &c.eta_after, r);
+    Ok(())
+}
+
+/// Check that the parameters have the values given by the contribution
+fn check_final_state<E: Pairing>(
+    params: &MPCParameters<E>,
+    last: &Contribution<E>,
+) -> crate::Result<()> {
+    if params.gamma_g1 != last.gamma_after
+        || params.params.common.delta_g1 != last.delta_after
+        || params.eta_g1 != last.eta_after
+    {
+        return Err(Error::InvalidMPCParameters(
+            "Parameters do not match the last contribution".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that all elements depending on the secrets are consistent with `gamma * G`, `delta * G` and `eta * G` of
+/// `after` and that the ones with `gamma` or `delta` in the denominator were updated by the same factor as
+/// `gamma` or `delta`. Vectors are checked using random linear combinations.
+fn check_updated_elements<E: Pairing, R: RngCore>(
+    before: &MPCParameters<E>,
+    after: &MPCParameters<E>,
+    checker: &mut RandomizedPairingChecker<E>,
+    rng: &mut R,
+) {
+    let g1 = E::G1Affine::generator();
+    let g2 = E::G2Affine::generator();
+    let (b_vk, a_vk) = (&before.params.vk, &after.params.vk);
+    let (b_pk, a_pk) = (&before.params.common, &after.params.common);
+
+    checker.add_sources(&after.gamma_g1, g2, &g1, a_vk.gamma_g2);
+    checker.add_sources(&a_pk.delta_g1, g2, &g1, a_vk.delta_g2);
+    checker.add_sources(&a_vk.eta_gamma_inv_g1, a_vk.gamma_g2, &after.eta_g1, g2);
+    checker.add_sources(&a_pk.eta_delta_inv_g1, a_vk.delta_g2, &after.eta_g1, g2);
+
+    let (b, a) = random_combination::<E, _>(&b_vk.gamma_abc_g1, &a_vk.gamma_abc_g1, rng);
+    checker.add_sources(&a, a_vk.gamma_g2, &b, b_vk.gamma_g2);
+    let (b, a) = random_combination::<E, _>(&b_pk.l_query, &a_pk.l_query, rng);
+    checker.add_sources(&a, a_vk.delta_g2, &b, b_vk.delta_g2);
+    let (b, a) = random_combination::<E, _>(&b_pk.h_query, &a_pk.h_query, rng);
+    checker.add_sources(&a, a_vk.delta_g2, &b, b_vk.delta_g2);
+}
+
+/// Returns `(sum_i r_i * x_i, sum_i r_i * y_i)` for random `r_i`
+fn random_combination<E: Pairing, R: RngCore>(
+    x: &[E::G1Affine],
+    y: &[E::G1Affine],
+    rng: &mut R,
+) -> (E::G1Affine, E::G1Affine) {
+    let r = (0..x.len())
+        .map(|_| E::ScalarField::rand(rng))
+        .collect::<Vec<_>>();
+    (
+        E::G1::msm_unchecked(x, &r).into_affine(),
+        E::G1::msm_unchecked(y, &r).into_affine(),
+    )
+}
+
+fn scale<G: AffineRepr>(elems: &[G], s: &G::ScalarField) -> Vec<G> {
+    let scaled = cfg_iter!(elems).map(|e| *e * s).collect::<Vec<_>>();
+    G::Group::normalize_batch(&scaled)
+}
+
+fn non_zero_field_elem<F: Field, R: Rng>(rng: &mut R) -> F {
+    loop {
+        let f = F::rand(rng);
+        if !f.is_zero() {
+            return f;
+        }
+    }
+}
+
+fn hash_to_g2<E: Pairing>(
+    previous_hash: &ContributionHash,
+    s: &E::G1Affine,
+    s_gamma: &E::G1Affine,
+    s_delta: &E::G1Affine,
+    s_eta: &E::G1Affine,
+) -> E::G2Affine {
+    let mut bytes = previous_hash.to_vec();
+    for p in [s, s_gamma, s_delta, s_eta] {
+        p.serialize_compressed(&mut bytes).unwrap();
+    }
+    affine_group_elem_from_try_and_incr::<E::G2Affine, Blake2b512>(&bytes)
+}
+
+fn hash_initial_params<E: Pairing>(params: &ProvingKey<E>) -> ContributionHash {
+    let mut bytes = vec![];
+    params.serialize_compressed(&mut bytes).unwrap();
+    Blake2b512::digest(&bytes).into()
+}
+
+fn hash_contribution<E: Pairing>(
+    previous_hash: &ContributionHash,
+    contribution: &Contribution<E>,
+) -> ContributionHash {
+    let mut bytes = previous_hash.to_vec();
+    contribution.serialize_compressed(&mut bytes).unwrap();
+    Blake2b512::digest(&bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        create_random_proof, prepare_verifying_key, ptau::setup_fake_powers_of_tau,
+        tests::MySillyCircuit, verify_proof, verify_witness_commitment,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    fn circuit() -> MySillyCircuit<Fr> {
+        MySillyCircuit { a: None, b: None }
+    }
+
+    #[test]
+    fn ceremony() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 3);
+        let commit_witness_count = 1;
+
+        let initial = MPCParameters::new(circuit(), &powers, commit_witness_count).unwrap();
+        assert_eq!(initial.last_hash(), initial.cs_hash);
+        // Initial parameters are deterministic
+        assert_eq!(
+            initial,
+            MPCParameters::new(circuit(), &powers, commit_witness_count).unwrap()
+        );
+        // Can't verify a transcript without contributions
+        assert!(initial
+            .verify(circuit(), &powers, commit_witness_count, &mut rng)
+            .is_err());
+
+        let mut params = initial.clone();
+        let mut hashes = vec![];
+        for _ in 0..3 {
+            let before = params.clone();
+            let hash = params.contribute(&mut rng);
+            assert_eq!(before.verify_contribution(&params, &mut rng).unwrap(), hash);
+            assert_eq!(params.last_hash(), hash);
+            hashes.push(hash);
+        }
+        assert_eq!(
+            params
+                .verify(circuit(), &powers, commit_witness_count, &mut rng)
+                .unwrap(),
+            hashes
+        );
+        assert_eq!(params.contribution_hashes(), hashes);
+
+        // Transcript is for a different circuit
+        assert!(params
+            .verify(circuit(), &powers, commit_witness_count + 1, &mut rng)
+            .is_err());
+
+        // Serialization round trip
+        let mut bytes = vec![];
+        params.serialize_compressed(&mut bytes).unwrap();
+        let deserialized = MPCParameters::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(deserialized, params);
+
+        // The final parameters work for proving and verifying
+        let pk = params.get_params();
+        let pvk = prepare_verifying_key::<Bls12_381>(&pk.vk);
+        let a = Fr::rand(&mut rng);
+        let b = Fr::rand(&mut rng);
+        let v = Fr::rand(&mut rng);
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            v,
+            pk,
+            &mut rng,
+        )
+        .unwrap();
+        verify_proof(&pvk, &proof, &[a * b]).unwrap();
+        verify_witness_commitment(&pk.vk, &proof, 1, &[a], &v).unwrap();
+    }
+
+    #[test]
+    fn invalid_contributions() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 3);
+        let mut before = MPCParameters::new(circuit(), &powers, 1).unwrap();
+        before.contribute(&mut rng);
+        let mut after = before.clone();
+        after.contribute(&mut rng);
+        before.verify_contribution(&after, &mut rng).unwrap();
+
+        // No new contribution
+        assert!(before.verify_contribution(&before, &mut rng).is_err());
+
+        // Tampered queries
+        let mut p = after.clone();
+        p.params.common.l_query[0] = (p.params.common.l_query[0] * Fr::from(2u64)).into_affine();
+        assert_eq!(
+            before.verify_contribution(&p, &mut rng).unwrap_err(),
+            Error::InvalidMPCContribution(1)
+        );
+        assert!(p.verify(circuit(), &powers, 1, &mut rng).is_err());
+
+        let mut p = after.clone();
+        p.params.common.h_query[1] = p.params.common.h_query[0];
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        let mut p = after.clone();
+        p.params.vk.gamma_abc_g1[1] = (p.params.vk.gamma_abc_g1[1] * Fr::from(3u64)).into_affine();
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+        assert!(p.verify(circuit(), &powers, 1, &mut rng).is_err());
+
+        let mut p = after.clone();
+        p.params.common.eta_delta_inv_g1 = p.params.vk.eta_gamma_inv_g1;
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        let mut p = after.clone();
+        p.params.vk.delta_g2 = before.params.vk.delta_g2;
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        // Modified elements not dependent on the ceremony
+        let mut p = after.clone();
+        p.params.common.a_query[0] = p.params.common.a_query[1];
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+        assert!(p.verify(circuit(), &powers, 1, &mut rng).is_err());
+
+        // Contribution doesn't match the parameters
+        let mut p = after.clone();
+        p.contributions[1].delta_after = before.params.common.delta_g1;
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        // Proof of knowledge uses different secrets than the update
+        let mut p = after.clone();
+        p.contributions[1].r_delta = p.contributions[1].r_gamma;
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        // Replaying a previous contribution fails as `r` depends on the previous hash
+        let mut p = after.clone();
+        let mut replayed = p.contributions[0].clone();
+        replayed.gamma_after = p.contributions[1].gamma_after;
+        replayed.delta_after = p.contributions[1].delta_after;
+        replayed.eta_after = p.contributions[1].eta_after;
+        p.contributions[1] = replayed;
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+
+        // Previous contribution modified
+        let mut p = after.clone();
+        p.contributions[0] = p.contributions[1].clone();
+        assert!(before.verify_contribution(&p, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,816 @@
+//! Import of the output of a phase-1 (powers of tau) trusted setup ceremony in the .ptau format used by
+//! [snarkjs](https://github.com/iden3/snarkjs) and the [perpetual powers of tau](https://github.com/privacy-scaling-explorations/perpetualpowersoftau)
+//! ceremony. Supports BN254 (called bn128 by snarkjs) and BLS12-381.
+//!
+//! The imported powers can be used to create the generic SRS for SnarkPack aggregation using [`GenericSRS::from_powers_of_tau`]
+//! and to create LegoGroth16 proving keys using [`generate_parameters_using_powers_of_tau`] so that the parameters `tau`, `alpha`
+//! and `beta` are not known to anyone who did not corrupt every participant of the ceremony.
+//!
+//! The .ptau file has the following sections where all numbers are little-endian and field elements are in Montgomery form.
+//! A point is written uncompressed as its `x` and `y` coordinates and the point at infinity is written as all zeroes.
+//! 1. Header: size of a base field element `n8`, the base field modulus in `n8` bytes, the power and the ceremony power
+//! 2. `tau^i * G` for `i` in `0..2^{power+1}-1`
+//! 3. `tau^i * H` for `i` in `0..2^power`
+//! 4. `alpha * tau^i * G` for `i` in `0..2^power`
+//! 5. `beta * tau^i * G` for `i` in `0..2^power`
+//! 6. `beta * H`
+//! 7. Contributions (not read)
+//!
+//! [`GenericSRS::from_powers_of_tau`]: crate::aggregation::srs::GenericSRS::from_powers_of_tau
+//! [`generate_parameters_using_powers_of_tau`]: crate::generate_parameters_using_powers_of_tau
+
+use crate::error::Error;
+use ark_ec::{
+    pairing::Pairing,
+    scalar_mul::fixed_base::FixedBase,
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInteger, Field, One, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    cfg_chunks, format,
+    io::{Read, Write},
+    rand::RngCore,
+    string::ToString,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+
+#[cfg(feature = "std")]
+use std::io::{Seek, SeekFrom};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const MAGIC: [u8; 4] = *b"ptau";
+const VERSION: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const ALPHA_TAU_G1_SECTION: u32 = 4;
+const BETA_TAU_G1_SECTION: u32 = 5;
+const BETA_G2_SECTION: u32 = 6;
+const CONTRIBUTIONS_SECTION: u32 = 7;
+
+/// Largest power accepted in a .ptau file. The largest public ceremonies have power 28.
+pub const MAX_POWER: u32 = 28;
+
+/// Output of a phase-1 trusted setup ceremony. Supports circuits with QAP domain size up to `2^power`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PowersOfTau<E: Pairing> {
+    pub power: u32,
+    /// `tau^i * G` for `i` in `0..2^{power+1}-1`
+    pub tau_g1: Vec<E::G1Affine>,
+    /// `tau^i * H` for `i` in `0..2^power`
+    pub tau_g2: Vec<E::G2Affine>,
+    /// `alpha * tau^i * G` for `i` in `0..2^power`
+    pub alpha_tau_g1: Vec<E::G1Affine>,
+    /// `beta * tau^i * G` for `i` in `0..2^power`
+    pub beta_tau_g1: Vec<E::G1Affine>,
+    /// `beta * H`
+    pub beta_g2: E::G2Affine,
+}
+
+impl<E: Pairing> PowersOfTau<E> {
+    /// Maximum size of the QAP evaluation domain supported by these powers
+    pub fn max_domain_size(&self) -> usize {
+        self.tau_g2.len()
+    }
+
+    /// Check that the powers are well-formed and consistent with each other, i.e. all are powers of the
+    /// same `tau` and the `alpha` and `beta` powers are consistent with `tau`. This should be done for powers
+    /// imported from an untrusted source before using them. Pairing checks are batched using random linear
+    /// combinations so this is much cheaper than checking each power individually.
+    pub fn verify<R: RngCore>(&self, rng: &mut R) -> crate::Result<()> {
+        let n = self.tau_g2.len();
+        if n < 2 || !n.is_power_of_two() || Some(n) != 1_usize.checked_shl(self.power) {
+            return Err(Error::InvalidPowersOfTau(format!(
+                "Invalid number of powers in G2 {} for power {}",
+                n, self.power
+            )));
+        }
+        if self.tau_g1.len() != 2 * n - 1 {
+            return Err(Error::InvalidPowersOfTau(format!(
+                "Expected {} powers in G1 but found {}",
+                2 * n - 1,
+                self.tau_g1.len()
+            )));
+        }
+        if self.alpha_tau_g1.len() != n || self.beta_tau_g1.len() != n {
+            return Err(Error::InvalidPowersOfTau(
+                "Number of alpha or beta powers not equal to the number of powers in G2"
+                    .to_string(),
+            ));
+        }
+        if self.tau_g1[0] != E::G1Affine::generator() || self.tau_g2[0] != E::G2Affine::generator()
+        {
+            return Err(Error::InvalidPowersOfTau(
+                "First powers should be the group generators".to_string(),
+            ));
+        }
+        if self.tau_g1.iter().any(|p| p.is_zero())
+            || self.alpha_tau_g1.iter().any(|p| p.is_zero())
+            || self.beta_tau_g1.iter().any(|p| p.is_zero())
+            || self.tau_g2.iter().any(|p| p.is_zero())
+            || self.beta_g2.is_zero()
+        {
+            return Err(Error::InvalidPowersOfTau(
+                "Found point at infinity".to_string(),
+            ));
+        }
+        if self.tau_g1[1] == self.tau_g1[0] {
+            return Err(Error::InvalidPowersOfTau("tau is 1".to_string()));
+        }
+
+        let g1 = self.tau_g1[0];
+        let g2 = self.tau_g2[0];
+        let tau_g1 = self.tau_g1[1];
+        let tau_g2 = self.tau_g2[1];
+
+        let rho = E::ScalarField::rand(rng);
+        let mut rho_powers = Vec::with_capacity(self.tau_g1.len());
+        let mut r = E::ScalarField::one();
+        for _ in 0..self.tau_g1.len() {
+            rho_powers.push(r);
+            r *= rho;
+        }
+        let m = self.tau_g1.len() - 1;
+
+        // `sum_i rho^i * tau^i * G` and `sum_i rho^i * tau^{i+1} * G`
+        let t_g1_lo = E::G1::msm_unchecked(&self.tau_g1[..m], &rho_powers[..m]).into_affine();
+        let t_g1_hi = E::G1::msm_unchecked(&self.tau_g1[1..], &rho_powers[..m]).into_affine();
+        // `sum_i rho^i * tau^i * H` and `sum_i rho^i * tau^{i+1} * H`
+        let t_g2_lo = E::G2::msm_unchecked(&self.tau_g2[..n - 1], &rho_powers[..n - 1]);
+        let t_g2_hi = E::G2::msm_unchecked(&self.tau_g2[1..], &rho_powers[..n - 1]).into_affine();
+        // `sum_i rho^i * tau^i * H` over all powers in G2
+        let t_g2_all = (t_g2_lo + self.tau_g2[n - 1] * rho_powers[n - 1]).into_affine();
+        let t_g2_lo = t_g2_lo.into_affine();
+        let alpha_lo = E::G1::msm_unchecked(&self.alpha_tau_g1, &rho_powers[..n]).into_affine();
+        let beta_lo = E::G1::msm_unchecked(&self.beta_tau_g1, &rho_powers[..n]).into_affine();
+
+        let mut checker = RandomizedPairingChecker::<E>::new_using_rng(rng, true);
+        // Powers in G1 are successive powers of tau
+        checker.add_sources(&t_g1_hi, g2, &t_g1_lo, tau_g2);
+        // Powers in G2 are successive powers of tau
+        checker.add_sources(&g1, t_g2_hi, &tau_g1, t_g2_lo);
+        // `alpha * tau^i * G` are consistent with `alpha * G` and powers of tau
+        checker.add_sources(&alpha_lo, g2, &self.alpha_tau_g1[0], t_g2_all);
+        // `beta * tau^i * G` are consistent with `beta * G` and powers of tau
+        checker.add_sources(&beta_lo, g2, &self.beta_tau_g1[0], t_g2_all);
+        // `beta * H` is consistent with `beta * G`
+        checker.add_sources(&self.beta_tau_g1[0], g2, &g1, self.beta_g2);
+        if !checker.verify() {
+            return Err(Error::InvalidPowersOfTau(
+                "Pairing check failed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<E, P1, P2> PowersOfTau<E>
+where
+    E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    P1: SWCurveConfig,
+    P2: SWCurveConfig,
+{
+    #[cfg(feature = "std")]
+    pub fn new_from_file(path: impl AsRef<std::path::Path>) -> crate::Result<Self> {
+        Self::new(std::io::BufReader::new(open_file(path)?))
+    }
+
+    /// Same as `Self::new_from_file` but reads only the powers needed for a QAP domain of size `2^max_power`
+    #[cfg(feature = "std")]
+    pub fn new_from_file_with_max_power(
+        path: impl AsRef<std::path::Path>,
+        max_power: u32,
+    ) -> crate::Result<Self> {
+        Self::new_with_max_power(std::io::BufReader::new(open_file(path)?), max_power)
+    }
+
+    /// Read all the powers from a .ptau file. Each point is checked to be on the curve and in the correct
+    /// subgroup but the powers are not checked to be consistent with each other, use `Self::verify` for that.
+    #[cfg(feature = "std")]
+    pub fn new<R: Read + Seek>(reader: R) -> crate::Result<Self> {
+        Self::read(reader, None)
+    }
+
+    /// Same as `Self::new` but reads only the powers needed for a QAP domain of size `2^max_power`. Useful as
+    /// ceremony files are often much larger than needed by the circuit.
+    #[cfg(feature = "std")]
+    pub fn new_with_max_power<R: Read + Seek>(reader: R, max_power: u32) -> crate::Result<Self> {
+        Self::read(reader, Some(max_power))
+    }
+
+    /// Write the powers in the .ptau format with an empty contributions section
+    pub fn write<W: Write>(&self, mut writer: W) -> crate::Result<()> {
+        let n8 = field_byte_size::<P1::BaseField>();
+
+        write_bytes(&mut writer, &MAGIC)?;
+        write_bytes(&mut writer, &VERSION.to_le_bytes())?;
+        write_bytes(&mut writer, &7u32.to_le_bytes())?;
+
+        write_section_header(&mut writer, HEADER_SECTION, 4 + n8 as u64 + 4 + 4)?;
+        write_bytes(&mut writer, &(n8 as u32).to_le_bytes())?;
+        write_bytes(
+            &mut writer,
+            &<<P1::BaseField as Field>::BasePrimeField as PrimeField>::MODULUS.to_bytes_le(),
+        )?;
+        write_bytes(&mut writer, &self.power.to_le_bytes())?;
+        // Ceremony power
+        write_bytes(&mut writer, &self.power.to_le_bytes())?;
+
+        write_points_section(&mut writer, TAU_G1_SECTION, &self.tau_g1)?;
+        write_points_section(&mut writer, TAU_G2_SECTION, &self.tau_g2)?;
+        write_points_section(&mut writer, ALPHA_TAU_G1_SECTION, &self.alpha_tau_g1)?;
+        write_points_section(&mut writer, BETA_TAU_G1_SECTION, &self.beta_tau_g1)?;
+        write_points_section(&mut writer, BETA_G2_SECTION, &[self.beta_g2])?;
+
+        write_section_header(&mut writer, CONTRIBUTIONS_SECTION, 4)?;
+        write_bytes(&mut writer, &0u32.to_le_bytes())?;
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn read<R: Read + Seek>(mut reader: R, max_power: Option<u32>) -> crate::Result<Self> {
+        let mut magic = [0u8; 4];
+        read_exact(&mut reader, &mut magic)?;
+        if magic != MAGIC {
+            return Err(Error::PowersOfTauParsing(
+                "Invalid magic number".to_string(),
+            ));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(Error::PowersOfTauParsing(format!(
+                "Unsupported version {}",
+                version
+            )));
+        }
+        let num_sections = read_u32(&mut reader)?;
+
+        // Sizes in the file are untrusted so sections must lie within the file. This also bounds the memory
+        // allocated when reading a section.
+        let start = seek(&mut reader, SeekFrom::Current(0))?;
+        let file_len = seek(&mut reader, SeekFrom::End(0))?;
+        seek(&mut reader, SeekFrom::Start(start))?;
+
+        // section type -> (file offset, size)
+        let mut sections = ark_std::collections::BTreeMap::<u32, (u64, u64)>::new();
+        for _ in 0..num_sections {
+            let sec_type = read_u32(&mut reader)?;
+            let sec_size = read_u64(&mut reader)?;
+            let offset = seek(&mut reader, SeekFrom::Current(0))?;
+            let end = offset
+                .checked_add(sec_size)
+                .filter(|end| *end <= file_len)
+                .ok_or_else(|| {
+                    Error::PowersOfTauParsing(format!(
+                        "Section {} of size {} extends beyond the end of the file",
+                        sec_type, sec_size
+                    ))
+                })?;
+            sections.insert(sec_type, (offset, sec_size));
+            seek(&mut reader, SeekFrom::Start(end))?;
+        }
+        let go_to_section = |reader: &mut R, sec_type: u32| -> crate::Result<u64> {
+            let (offset, size) = sections.get(&sec_type).ok_or_else(|| {
+                Error::PowersOfTauParsing(format!("Section {} not found", sec_type))
+            })?;
+            seek(reader, SeekFrom::Start(*offset))?;
+            Ok(*size)
+        };
+
+        go_to_section(&mut reader, HEADER_SECTION)?;
+        let n8 = read_u32(&mut reader)? as usize;
+        if n8 != field_byte_size::<P1::BaseField>() {
+            return Err(Error::PowersOfTauParsing(format!(
+                "Field size {} bytes not as expected for the curve",
+                n8
+            )));
+        }
+        let mut q = vec![0u8; n8];
+        read_exact(&mut reader, &mut q)?;
+        if q != <<P1::BaseField as Field>::BasePrimeField as PrimeField>::MODULUS.to_bytes_le() {
+            return Err(Error::PowersOfTauParsing(
+                "File was created for a different curve".to_string(),
+            ));
+        }
+        let file_power = read_u32(&mut reader)?;
+        if file_power > MAX_POWER {
+            return Err(Error::PowersOfTauParsing(format!(
+                "Power {} is larger than the maximum supported {}",
+                file_power, MAX_POWER
+            )));
+        }
+        let power = match max_power {
+            Some(p) if p < file_power => p,
+            _ => file_power,
+        };
+        if power < 1 {
+            return Err(Error::PowersOfTauParsing(format!(
+                "Power should be at least 1 but was {}",
+                power
+            )));
+        }
+        // Can't overflow as `power <= file_power <= MAX_POWER`
+        let n = 1_usize << power;
+        let file_n = 1_usize << file_power;
+
+        let size = go_to_section(&mut reader, TAU_G1_SECTION)?;
+        let tau_g1 = read_points_section::<_, P1>(&mut reader, size, 2 * file_n - 1, 2 * n - 1)?;
+        let size = go_to_section(&mut reader, TAU_G2_SECTION)?;
+        let tau_g2 = read_points_section::<_, P2>(&mut reader, size, file_n, n)?;
+        let size = go_to_section(&mut reader, ALPHA_TAU_G1_SECTION)?;
+        let alpha_tau_g1 = read_points_section::<_, P1>(&mut reader, size, file_n, n)?;
+        let size = go_to_section(&mut reader, BETA_TAU_G1_SECTION)?;
+        let beta_tau_g1 = read_points_section::<_, P1>(&mut reader, size, file_n, n)?;
+        let size = go_to_section(&mut reader, BETA_G2_SECTION)?;
+        let beta_g2 = read_points_section::<_, P2>(&mut reader, size, 1, 1)?.remove(0);
+
+        Ok(Self {
+            power,
+            tau_g1,
+            tau_g2,
+            alpha_tau_g1,
+            beta_tau_g1,
+            beta_g2,
+        })
+    }
+}
+
+/// Generates powers of tau of size `2^power` with random `tau`, `alpha` and `beta`. It must NOT be used in production,
+/// only in testing, as this is insecure given we know the secret values.
+pub fn setup_fake_powers_of_tau<E: Pairing, R: RngCore>(rng: &mut R, power: u32) -> PowersOfTau<E> {
+    let tau = E::ScalarField::rand(rng);
+    let alpha = E::ScalarField::rand(rng);
+    let beta = E::ScalarField::rand(rng);
+    let n = 1_usize << power;
+
+    let mut tau_powers = Vec::with_capacity(2 * n - 1);
+    let mut t = E::ScalarField::one();
+    for _ in 0..2 * n - 1 {
+        tau_powers.push(t);
+        t *= tau;
+    }
+    let alpha_tau_powers = tau_powers[..n]
+        .iter()
+        .map(|t| alpha * t)
+        .collect::<Vec<_>>();
+    let beta_tau_powers = tau_powers[..n].iter().map(|t| beta * t).collect::<Vec<_>>();
+
+    let scalar_bits = E::ScalarField::MODULUS_BIT_SIZE as usize;
+    let g1_window = FixedBase::get_mul_window_size(4 * n);
+    let g1_table = FixedBase::get_window_table(
+        scalar_bits,
+        g1_window,
+        E::G1Affine::generator().into_group(),
+    );
+    let g2_window = FixedBase::get_mul_window_size(n + 1);
+    let g2_table = FixedBase::get_window_table(
+        scalar_bits,
+        g2_window,
+        E::G2Affine::generator().into_group(),
+    );
+
+    let tau_g1 = FixedBase::msm::<E::G1>(scalar_bits, g1_window, &g1_table, &tau_powers);
+    let alpha_tau_g1 =
+        FixedBase::msm::<E::G1>(scalar_bits, g1_window, &g1_table, &alpha_tau_powers);
+    let beta_tau_g1 = FixedBase::msm::<E::G1>(scalar_bits, g1_window, &g1_table, &beta_tau_powers);
+    let tau_g2 = FixedBase::msm::<E::G2>(scalar_bits, g2_window, &g2_table, &tau_powers[..n]);
+    PowersOfTau {
+        power,
+        tau_g1: E::G1::normalize_batch(&tau_g1),
+        tau_g2: E::G2::normalize_batch(&tau_g2),
+        alpha_tau_g1: E::G1::normalize_batch(&alpha_tau_g1),
+        beta_tau_g1: E::G1::normalize_batch(&beta_tau_g1),
+        beta_g2: (E::G2Affine::generator() * beta).into_affine(),
+    }
+}
+
+/// Number of bytes taken by an element of the base prime field
+fn field_byte_size<F: Field>() -> usize {
+    <F::BasePrimeField as PrimeField>::MODULUS.as_ref().len() * 8
+}
+
+/// Number of bytes taken by an uncompressed point whose coordinates are in field `F`
+fn point_byte_size<F: Field>() -> usize {
+    2 * F::extension_degree() as usize * field_byte_size::<F>()
+}
+
+/// Montgomery constant `R = 2^{8*n8}` as used by snarkjs
+fn montgomery_r<F: PrimeField>() -> F {
+    F::from(2u64).pow([(F::MODULUS.as_ref().len() * 64) as u64])
+}
+
+/// Decode a coordinate where each base prime field element is in Montgomery form
+fn decode_coordinate<F: Field>(bytes: &[u8], r_inv: &F::BasePrimeField) -> crate::Result<F> {
+    let n8 = field_byte_size::<F>();
+    let mut elems = Vec::with_capacity(F::extension_degree() as usize);
+    for chunk in bytes.chunks(n8) {
+        let mut repr = <F::BasePrimeField as PrimeField>::BigInt::default();
+        for (limb, limb_bytes) in repr.as_mut().iter_mut().zip(chunk.chunks(8)) {
+            let mut b = [0u8; 8];
+            b.copy_from_slice(limb_bytes);
+            *limb = u64::from_le_bytes(b);
+        }
+        let mont = F::BasePrimeField::from_bigint(repr).ok_or_else(|| {
+            Error::PowersOfTauParsing("Field element is not less than modulus".to_string())
+        })?;
+        elems.push(mont * r_inv);
+    }
+    F::from_base_prime_field_elems(&elems)
+        .ok_or_else(|| Error::PowersOfTauParsing("Invalid field element".to_string()))
+}
+
+fn encode_coordinate<F: Field, W: Write>(mut writer: W, elem: &F) -> crate::Result<()> {
+    let r = montgomery_r::<F::BasePrimeField>();
+    for e in elem.to_base_prime_field_elements() {
+        write_bytes(&mut writer, &(e * r).into_bigint().to_bytes_le())?;
+    }
+    Ok(())
+}
+
+fn decode_point<P: SWCurveConfig>(
+    bytes: &[u8],
+    r_inv: &<P::BaseField as Field>::BasePrimeField,
+) -> crate::Result<Affine<P>> {
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(Affine::<P>::zero());
+    }
+    let (x, y) = bytes.split_at(bytes.len() / 2);
+    let p = Affine::<P>::new_unchecked(decode_coordinate(x, r_inv)?, decode_coordinate(y, r_inv)?);
+    if !p.is_on_curve() {
+        return Err(Error::PowersOfTauParsing("Point not on curve".to_string()));
+    }
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::PowersOfTauParsing(
+            "Point not in correct subgroup".to_string(),
+        ));
+    }
+    Ok(p)
+}
+
+fn write_point<P: SWCurveConfig, W: Write>(mut writer: W, p: &Affine<P>) -> crate::Result<()> {
+    if p.infinity {
+        return write_bytes(writer, &vec![0u8; point_byte_size::<P::BaseField>()]);
+    }
+    encode_coordinate(&mut writer, &p.x)?;
+    encode_coordinate(&mut writer, &p.y)
+}
+
+/// Read the first `count` points of a section containing `total` points
+fn read_points_section<R: Read, P: SWCurveConfig>(
+    mut reader: R,
+    section_size: u64,
+    total: usize,
+    count: usize,
+) -> crate::Result<Vec<Affine<P>>> {
+    let size = point_byte_size::<P::BaseField>();
+    let expected_size = total
+        .checked_mul(size)
+        .ok_or_else(|| Error::PowersOfTauParsing(format!("Too many points {}", total)))?;
+    if section_size != expected_size as u64 {
+        return Err(Error::PowersOfTauParsing(format!(
+            "Section size {} not as expected {}",
+            section_size, expected_size
+        )));
+    }
+    let r_inv = montgomery_r::<<P::BaseField as Field>::BasePrimeField>()
+        .inverse()
+        .unwrap();
+    let mut buf = vec![0u8; count * size];
+    read_exact(&mut reader, &mut buf)?;
+    cfg_chunks!(buf, size)
+        .map(|b| decode_point::<P>(b, &r_inv))
+        .collect()
+}
+
+fn write_points_section<P: SWCurveConfig, W: Write>(
+    mut writer: W,
+    sec_type: u32,
+    points: &[Affine<P>],
+) -> crate::Result<()> {
+    write_section_header(
+        &mut writer,
+        sec_type,
+        (points.len() * point_byte_size::<P::BaseField>()) as u64,
+    )?;
+    for p in points {
+        write_point(&mut writer, p)?;
+    }
+    Ok(())
+}
+
+fn write_section_header<W: Write>(mut writer: W, sec_type: u32, size: u64) -> crate::Result<()> {
+    write_bytes(&mut writer, &sec_type.to_le_bytes())?;
+    write_bytes(&mut writer, &size.to_le_bytes())
+}
+
+fn write_bytes<W: Write>(mut writer: W, bytes: &[u8]) -> crate::Result<()> {
+    writer
+        .write_all(bytes)
+        .map_err(|err| Error::PowersOfTauParsing(format!("Error while writing: {:?}", err)))
+}
+
+fn read_u32<R: Read>(reader: R) -> crate::Result<u32> {
+    let mut buf = [0; 4];
+    read_exact(reader, &mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: R) -> crate::Result<u64> {
+    let mut buf = [0; 8];
+    read_exact(reader, &mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_exact<R: Read>(mut reader: R, buf: &mut [u8]) -> crate::Result<()> {
+    reader.read_exact(buf).map_err(|err| {
+        log::error!("Encountered error while parsing ptau file: {:?}", err);
+        Error::PowersOfTauParsing(format!(
+            "Encountered error while parsing ptau file: {:?}",
+            err
+        ))
+    })
+}
+
+#[cfg(feature = "std")]
+fn seek<R: Read + Seek>(mut reader: R, pos: SeekFrom) -> crate::Result<u64> {
+    reader.seek(pos).map_err(|err| {
+        log::error!("Encountered error while parsing ptau file: {:?}", err);
+        Error::PowersOfTauParsing(format!(
+            "Encountered error while parsing ptau file: {:?}",
+            err
+        ))
+    })
+}
+
+#[cfg(feature = "std")]
+fn open_file(path: impl AsRef<std::path::Path>) -> crate::Result<std::fs::File> {
+    std::fs::File::open(path).map_err(|err| {
+        log::error!("Encountered error while opening ptau file: {:?}", err);
+        Error::PowersOfTauParsing(format!(
+            "Encountered error while opening ptau file: {:?}",
+            err
+        ))
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        create_random_proof, generate_parameters_using_powers_of_tau, prepare_verifying_key,
+        tests::MySillyCircuit, verify_proof,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_std::{
+        io::Cursor,
+        rand::{rngs::StdRng, SeedableRng},
+    };
+
+    fn check_write_and_read<E, P1, P2>(power: u32)
+    where
+        E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+        P1: SWCurveConfig,
+        P2: SWCurveConfig,
+        P1::BaseField: PrimeField,
+    {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<E, _>(&mut rng, power);
+        assert_eq!(powers.max_domain_size(), 1 << power);
+        assert_eq!(powers.tau_g1.len(), (1 << (power + 1)) - 1);
+        powers.verify(&mut rng).unwrap();
+
+        let mut bytes = vec![];
+        powers.write(&mut bytes).unwrap();
+        let read = PowersOfTau::<E>::new(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(read, powers);
+
+        // Read fewer powers than present in the file
+        let truncated =
+            PowersOfTau::<E>::new_with_max_power(Cursor::new(bytes.clone()), power - 1).unwrap();
+        assert_eq!(truncated.power, power - 1);
+        assert_eq!(truncated.tau_g1, powers.tau_g1[..(1 << power) - 1].to_vec());
+        assert_eq!(truncated.tau_g2, powers.tau_g2[..1 << (power - 1)].to_vec());
+        truncated.verify(&mut rng).unwrap();
+
+        // Asking for more powers than present in the file reads all of them
+        let read =
+            PowersOfTau::<E>::new_with_max_power(Cursor::new(bytes.clone()), power + 1).unwrap();
+        assert_eq!(read, powers);
+
+        // Corrupt the magic
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'x';
+        assert!(PowersOfTau::<E>::new(Cursor::new(corrupted)).is_err());
+
+        // Truncated file
+        assert!(PowersOfTau::<E>::new(Cursor::new(bytes[..bytes.len() / 2].to_vec())).is_err());
+
+        // Offset of the power in the header section: magic, version, number of sections, section type and size
+        // and then `n8` and the modulus
+        let power_offset = 4 + 4 + 4 + 4 + 8 + 4 + field_byte_size::<P1::BaseField>();
+        assert_eq!(bytes[power_offset..power_offset + 4], power.to_le_bytes());
+        for p in [MAX_POWER + 1, 63, 64, u32::MAX] {
+            let mut corrupted = bytes.clone();
+            corrupted[power_offset..power_offset + 4].copy_from_slice(&p.to_le_bytes());
+            assert!(PowersOfTau::<E>::new(Cursor::new(corrupted)).is_err());
+        }
+
+        // Section size larger than the file. Size of the header section is right after its type.
+        let mut corrupted = bytes.clone();
+        corrupted[16..24].copy_from_slice(&(u64::MAX - 10).to_le_bytes());
+        assert!(PowersOfTau::<E>::new(Cursor::new(corrupted)).is_err());
+        let mut corrupted = bytes.clone();
+        corrupted[16..24].copy_from_slice(&(bytes.len() as u64).to_le_bytes());
+        assert!(PowersOfTau::<E>::new(Cursor::new(corrupted)).is_err());
+    }
+
+    #[test]
+    fn write_and_read() {
+        check_write_and_read::<Bn254, _, _>(4);
+        check_write_and_read::<Bls12_381, _, _>(4);
+    }
+
+    #[test]
+    fn read_file_with_known_secrets() {
+        // File written by `test-vectors/ptau/gen_bn128_known_secrets.py` which encodes the points independently of
+        // `PowersOfTau::write`. It has power 2 with tau = 5, alpha = 7 and beta = 11.
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test-vectors/ptau/bn128_known_secrets_2.ptau"
+        );
+        let powers = PowersOfTau::<Bn254>::new_from_file(path).unwrap();
+        assert_eq!(powers.power, 2);
+
+        let g1 = <Bn254 as Pairing>::G1Affine::generator();
+        let g2 = <Bn254 as Pairing>::G2Affine::generator();
+        let tau = <Bn254 as Pairing>::ScalarField::from(5u64);
+        let alpha = <Bn254 as Pairing>::ScalarField::from(7u64);
+        let beta = <Bn254 as Pairing>::ScalarField::from(11u64);
+        let tau_pow = |i: usize| tau.pow([i as u64]);
+        assert_eq!(powers.tau_g1.len(), 7);
+        for (i, p) in powers.tau_g1.iter().enumerate() {
+            assert_eq!(*p, (g1 * tau_pow(i)).into_affine());
+        }
+        assert_eq!(powers.tau_g2.len(), 4);
+        for (i, p) in powers.tau_g2.iter().enumerate() {
+            assert_eq!(*p, (g2 * tau_pow(i)).into_affine());
+        }
+        for i in 0..4 {
+            assert_eq!(
+                powers.alpha_tau_g1[i],
+                (g1 * (alpha * tau_pow(i))).into_affine()
+            );
+            assert_eq!(
+                powers.beta_tau_g1[i],
+                (g1 * (beta * tau_pow(i))).into_affine()
+            );
+        }
+        assert_eq!(powers.beta_g2, (g2 * beta).into_affine());
+        let mut rng = StdRng::seed_from_u64(0u64);
+        powers.verify(&mut rng).unwrap();
+
+        let truncated = PowersOfTau::<Bn254>::new_from_file_with_max_power(path, 1).unwrap();
+        assert_eq!(truncated.tau_g1, powers.tau_g1[..3].to_vec());
+        assert_eq!(truncated.tau_g2, powers.tau_g2[..2].to_vec());
+
+        // The file is read as written by `PowersOfTau::write` except for the ceremony power and contributions
+        let mut bytes = vec![];
+        powers.write(&mut bytes).unwrap();
+        assert_eq!(
+            PowersOfTau::<Bn254>::new(Cursor::new(bytes)).unwrap(),
+            powers
+        );
+        assert!(PowersOfTau::<Bls12_381>::new_from_file(path).is_err());
+    }
+
+    #[test]
+    fn wrong_curve() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<Bn254, _>(&mut rng, 3);
+        let mut bytes = vec![];
+        powers.write(&mut bytes).unwrap();
+        assert!(PowersOfTau::<Bls12_381>::new(Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn verify_fails_on_tampered_powers() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 3);
+        powers.verify(&mut rng).unwrap();
+
+        let mut p = powers.clone();
+        p.tau_g1[5] = (p.tau_g1[5] + p.tau_g1[0]).into_affine();
+        assert!(p.verify(&mut rng).is_err());
+
+        let mut p = powers.clone();
+        p.tau_g2[3] = (p.tau_g2[3] + p.tau_g2[0]).into_affine();
+        assert!(p.verify(&mut rng).is_err());
+
+        let mut p = powers.clone();
+        p.alpha_tau_g1[2] = (p.alpha_tau_g1[2] + p.tau_g1[0]).into_affine();
+        assert!(p.verify(&mut rng).is_err());
+
+        let mut p = powers.clone();
+        p.beta_tau_g1[1] = (p.beta_tau_g1[1] + p.tau_g1[0]).into_affine();
+        assert!(p.verify(&mut rng).is_err());
+
+        let mut p = powers.clone();
+        p.beta_g2 = (p.beta_g2 + p.tau_g2[0]).into_affine();
+        assert!(p.verify(&mut rng).is_err());
+
+        let mut p = powers.clone();
+        p.tau_g1.pop();
+        assert!(p.verify(&mut rng).is_err());
+
+        // Power too large to shift by
+        let mut p = powers.clone();
+        p.power = 100;
+        assert!(p.verify(&mut rng).is_err());
+    }
+
+    fn check_prove_and_verify<E, P1, P2>()
+    where
+        E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+        P1: SWCurveConfig,
+        P2: SWCurveConfig,
+        P1::BaseField: PrimeField,
+    {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let powers = setup_fake_powers_of_tau::<E, _>(&mut rng, 3);
+        // Go through the file format as a real ceremony output would
+        let mut bytes = vec![];
+        powers.write(&mut bytes).unwrap();
+        let powers = PowersOfTau::<E>::new(Cursor::new(bytes)).unwrap();
+
+        for commit_witness_count in [0, 1, 2] {
+            let circuit = MySillyCircuit { a: None, b: None };
+            let params = generate_parameters_using_powers_of_tau(
+                circuit,
+                &powers,
+                commit_witness_count,
+                &mut rng,
+            )
+            .unwrap();
+            assert_eq!(params.vk.alpha_g1, powers.alpha_tau_g1[0]);
+            assert_eq!(params.common.beta_g1, powers.beta_tau_g1[0]);
+            assert_eq!(params.vk.beta_g2, powers.beta_g2);
+            let pvk = prepare_verifying_key::<E>(&params.vk);
+
+            let a = E::ScalarField::rand(&mut rng);
+            let b = E::ScalarField::rand(&mut rng);
+            let c = a * b;
+            let v = E::ScalarField::rand(&mut rng);
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                v,
+                &params,
+                &mut rng,
+            )
+            .unwrap();
+            verify_proof(&pvk, &proof, &[c]).unwrap();
+            assert!(verify_proof(&pvk, &proof, &[a]).is_err());
+        }
+
+        assert_eq!(
+            generate_parameters_using_powers_of_tau(
+                MySillyCircuit::<E::ScalarField> { a: None, b: None },
+                &powers,
+                3,
+                &mut rng
+            )
+            .unwrap_err(),
+            Error::InsufficientWitnessesForCommitment(2, 3)
+        );
+
+        // Powers are too few for the circuit
+        let powers = setup_fake_powers_of_tau::<E, _>(&mut rng, 1);
+        assert_eq!(
+            generate_parameters_using_powers_of_tau(
+                MySillyCircuit::<E::ScalarField> { a: None, b: None },
+                &powers,
+                1,
+                &mut rng
+            )
+            .unwrap_err(),
+            Error::InsufficientPowersOfTau(4, 2)
+        );
+    }
+
+    #[test]
+    fn prove_and_verify_with_params_from_powers_of_tau() {
+        check_prove_and_verify::<Bn254, _, _>();
+        check_prove_and_verify::<Bls12_381, _, _>();
+    }
+}
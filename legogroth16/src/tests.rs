@@ -4,7 +4,7 @@ use crate::{
     rerandomize_proof_1, verify_proof, verify_proof_incl_cp_link, verify_witness_commitment,
     LinkPublicGenerators,
 };
-use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::Field;
 use ark_std::{
     rand::{rngs::StdRng, RngCore, SeedableRng},
@@ -21,9 +21,9 @@ use ark_relations::{
 
 /// Circuit for computation a * b
 #[derive(Clone)]
-struct MySillyCircuit<F: Field> {
-    a: Option<F>,
-    b: Option<F>,
+pub(crate) struct MySillyCircuit<F: Field> {
+    pub(crate) a: Option<F>,
+    pub(crate) b: Option<F>,
 }
 
 /// Circuit for computation a * b + c * d
@@ -564,6 +564,58 @@ where
     }
 }
 
+fn test_key_validation<E>()
+where
+    E: Pairing,
+{
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let circuit = MySillyCircuit { a: None, b: None };
+    let params = generate_random_parameters::<E, _, _>(circuit, 1, &mut rng).unwrap();
+    params.vk.validate().unwrap();
+    params.validate().unwrap();
+    params.validate_queries(&mut rng).unwrap();
+
+    // Identity element in verifying key
+    let mut vk = params.vk.clone();
+    vk.delta_g2 = E::G2Affine::zero();
+    assert!(matches!(vk.validate(), Err(Error::InvalidVerifyingKey(_))));
+
+    // Setting delta = gamma makes the commitment and proof elements interchangeable
+    let mut vk = params.vk.clone();
+    vk.delta_g2 = vk.gamma_g2;
+    assert!(matches!(vk.validate(), Err(Error::InvalidVerifyingKey(_))));
+
+    // Not enough bases for the committed witnesses
+    let mut vk = params.vk.clone();
+    vk.commit_witness_count = vk.gamma_abc_g1.len() as u32;
+    assert!(matches!(vk.validate(), Err(Error::InvalidVerifyingKey(_))));
+
+    // delta in the proving key differs from the one in the verifying key
+    let mut pk = params.clone();
+    pk.common.delta_g1 = (pk.common.delta_g1 + pk.common.beta_g1).into_affine();
+    assert!(matches!(pk.validate(), Err(Error::InvalidProvingKey(_))));
+
+    // eta in the proving key differs from the one in the verifying key
+    let mut pk = params.clone();
+    pk.common.eta_delta_inv_g1 = pk.common.delta_g1;
+    assert!(matches!(pk.validate(), Err(Error::InvalidProvingKey(_))));
+
+    // Tampered query passes the cheap check but not the full one
+    let mut pk = params.clone();
+    let i = pk
+        .common
+        .b_g1_query
+        .iter()
+        .position(|b| !b.is_zero())
+        .unwrap();
+    pk.common.b_g1_query[i] = (pk.common.b_g1_query[i] + pk.common.delta_g1).into_affine();
+    pk.validate().unwrap();
+    assert!(matches!(
+        pk.validate_queries(&mut rng),
+        Err(Error::InvalidProvingKey(_))
+    ));
+}
+
 mod bls12_377 {
     use super::*;
     use ark_bls12_377::Bls12_377;
@@ -623,6 +675,11 @@ mod bls12_381 {
     fn prove_and_verify_2() {
         test_prove_and_verify_2::<Bls12_381>(10);
     }
+
+    #[test]
+    fn key_validation() {
+        test_key_validation::<Bls12_381>();
+    }
 }
 
 mod bn254 {
@@ -643,4 +700,9 @@ mod bn254 {
     fn prove_and_verify_2() {
         test_prove_and_verify_2::<Bn254>(10);
     }
+
+    #[test]
+    fn key_validation() {
+        test_key_validation::<Bn254>();
+    }
 }
@@ -0,0 +1,159 @@
+#!/usr/bin/env python3
+"""
+Writes `bn128_known_secrets_2.ptau`, a BN254 powers of tau file in the snarkjs .ptau format with power 2 and
+the small known secrets TAU, ALPHA and BETA below. Like a file cut from a larger ceremony, the header has
+ceremony power 28 (as the `hez_final_*` files do) and the contributions section is not empty.
+
+The curve arithmetic and the encoding (little-endian Montgomery form with R = 2^256, `c0` before `c1` for
+elements of Fp2, all zeroes for the point at infinity) are implemented here independently of the Rust writer
+so that the fixture checks the reader against the format rather than against its own writer.
+
+Usage: python3 gen_bn128_known_secrets.py
+"""
+
+import os
+import struct
+
+P = 21888242871839275222246405745257275088696311157297823662689037894645226208583
+R = pow(2, 256, P)
+
+TAU = 5
+ALPHA = 7
+BETA = 11
+POWER = 2
+CEREMONY_POWER = 28
+
+
+class Fp2:
+    """Element c0 + c1*u of Fp[u]/(u^2 + 1)"""
+
+    def __init__(self, c0, c1=0):
+        self.c0 = c0 % P
+        self.c1 = c1 % P
+
+    def __add__(self, o):
+        return Fp2(self.c0 + o.c0, self.c1 + o.c1)
+
+    def __sub__(self, o):
+        return Fp2(self.c0 - o.c0, self.c1 - o.c1)
+
+    def __mul__(self, o):
+        if isinstance(o, int):
+            return Fp2(self.c0 * o, self.c1 * o)
+        return Fp2(self.c0 * o.c0 - self.c1 * o.c1, self.c0 * o.c1 + self.c1 * o.c0)
+
+    def inv(self):
+        d = pow(self.c0 * self.c0 + self.c1 * self.c1, -1, P)
+        return Fp2(self.c0 * d, -self.c1 * d)
+
+    def __eq__(self, o):
+        return self.c0 == o.c0 and self.c1 == o.c1
+
+
+class Fp(Fp2):
+    """Element of Fp, kept as an element of Fp2 with c1 = 0 to share the arithmetic"""
+
+    def __init__(self, c0):
+        super().__init__(c0, 0)
+
+
+def add(p, q):
+    """Affine addition on y^2 = x^3 + b. None is the point at infinity"""
+    if p is None:
+        return q
+    if q is None:
+        return p
+    (x1, y1), (x2, y2) = p, q
+    if x1 == x2:
+        if y1 == y2:
+            lam = x1 * x1 * 3 * (y1 * 2).inv()
+        else:
+            return None
+    else:
+        lam = (y2 - y1) * (x2 - x1).inv()
+    x3 = lam * lam - x1 - x2
+    return (x3, lam * (x1 - x3) - y1)
+
+
+def mul(p, k):
+    acc = None
+    while k:
+        if k & 1:
+            acc = add(acc, p)
+        p = add(p, p)
+        k >>= 1
+    return acc
+
+
+def on_curve(p, b):
+    x, y = p
+    return y * y == x * x * x + b
+
+
+def fp_bytes(x):
+    return (x * R % P).to_bytes(32, "little")
+
+
+def g1_bytes(p):
+    if p is None:
+        return bytes(64)
+    return fp_bytes(p[0].c0) + fp_bytes(p[1].c0)
+
+
+def g2_bytes(p):
+    if p is None:
+        return bytes(128)
+    x, y = p
+    return fp_bytes(x.c0) + fp_bytes(x.c1) + fp_bytes(y.c0) + fp_bytes(y.c1)
+
+
+def section(sec_type, data):
+    return struct.pack("<IQ", sec_type, len(data)) + data
+
+
+G1 = (Fp(1), Fp(2))
+G2 = (
+    Fp2(
+        10857046999023057135944570762232829481370756359578518086990519993285655852781,
+        11559732032986387107991004021392285783925812861821192530917403151452391805634,
+    ),
+    Fp2(
+        8495653923123431417604973247489272438418190587263600148770280649306958101930,
+        4082367875863433681332203403145435568316851327593401208105741076214120093531,
+    ),
+)
+assert on_curve(G1, Fp(3))
+assert on_curve(G2, Fp2(3) * Fp2(9, 1).inv())
+
+n = 1 << POWER
+tau_g1 = [mul(G1, pow(TAU, i)) for i in range(2 * n - 1)]
+tau_g2 = [mul(G2, pow(TAU, i)) for i in range(n)]
+alpha_tau_g1 = [mul(G1, ALPHA * pow(TAU, i)) for i in range(n)]
+beta_tau_g1 = [mul(G1, BETA * pow(TAU, i)) for i in range(n)]
+beta_g2 = mul(G2, BETA)
+
+header = struct.pack("<I", 32) + P.to_bytes(32, "little") + struct.pack("<II", POWER, CEREMONY_POWER)
+# A placeholder for a single contribution. The reader skips this section.
+contribution = (
+    g1_bytes(tau_g1[1])
+    + g2_bytes(tau_g2[1])
+    + g1_bytes(alpha_tau_g1[0])
+    + g1_bytes(beta_tau_g1[0])
+    + g2_bytes(beta_g2)
+    + bytes(64 * 4)
+)
+contributions = struct.pack("<I", 1) + contribution
+
+sections = [
+    section(1, header),
+    section(2, b"".join(g1_bytes(p) for p in tau_g1)),
+    section(3, b"".join(g2_bytes(p) for p in tau_g2)),
+    section(4, b"".join(g1_bytes(p) for p in alpha_tau_g1)),
+    section(5, b"".join(g1_bytes(p) for p in beta_tau_g1)),
+    section(6, g2_bytes(beta_g2)),
+    section(7, contributions),
+]
+
+out = os.path.join(os.path.dirname(os.path.abspath(__file__)), "bn128_known_secrets_2.ptau")
+with open(out, "wb") as f:
+    f.write(b"ptau" + struct.pack("<II", 1, len(sections)) + b"".join(sections))
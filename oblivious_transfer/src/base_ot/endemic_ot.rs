@@ -1,5 +1,6 @@
 //! OT based on the paper [Endemic Oblivious Transfer](https://eprint.iacr.org/2019/706)
-//! Allows to run single instance of 1-of-n ROT (Random OT)
+//! Allows to run single instance of 1-of-n ROT (Random OT). Both parties must use the same hasher `H` to hash
+//! to the group when using the `*_with_hasher` functions. The other functions use try-and-increment with SHA3-256.
 
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -9,7 +10,7 @@ use itertools::Itertools;
 use zeroize::Zeroize;
 
 use crate::Key;
-use dock_crypto_utils::hashing_utils::projective_group_elem_from_try_and_incr;
+use dock_crypto_utils::hashing_utils::{GroupElemHasher, TryAndIncrement};
 
 use crate::error::OTError;
 #[cfg(feature = "parallel")]
@@ -34,6 +35,16 @@ impl<G: AffineRepr> ROTReceiver<G> {
         n: u16,
         choice: u16,
         g: &G,
+    ) -> Result<(Self, Vec<G>), OTError> {
+        Self::new_with_hasher::<R, TryAndIncrement<Sha3_256>>(rng, n, choice, g)
+    }
+
+    /// Same as [`Self::new`] but hashes to the group using the given [`GroupElemHasher`]
+    pub fn new_with_hasher<R: RngCore, H: GroupElemHasher<G>>(
+        rng: &mut R,
+        n: u16,
+        choice: u16,
+        g: &G,
     ) -> Result<(Self, Vec<G>), OTError> {
         if n < 2 {
             return Err(OTError::OTShouldHaveAtLeast2Messages(n));
@@ -45,7 +56,7 @@ impl<G: AffineRepr> ROTReceiver<G> {
         let m = g.mul(t).into_affine();
         let mut r =
             G::Group::normalize_batch(&(0..n - 1).map(|_| G::Group::rand(rng)).collect::<Vec<_>>());
-        let r_i = m.into_group() - indexed_hash(choice, r.iter());
+        let r_i = m.into_group() - indexed_hash_with_hasher::<_, G, H>(choice, r.iter());
         r.insert(choice as usize, r_i.into_affine());
         Ok((Self { n, choice, t }, r))
     }
@@ -62,6 +73,16 @@ impl ROTSenderKeys {
         n: u16,
         r: Vec<G>,
         g: &G,
+    ) -> Result<(Self, Vec<G>), OTError> {
+        Self::new_with_hasher::<R, G, TryAndIncrement<Sha3_256>>(rng, n, r, g)
+    }
+
+    /// Same as [`Self::new`] but hashes to the group using the given [`GroupElemHasher`]
+    pub fn new_with_hasher<R: RngCore, G: AffineRepr, H: GroupElemHasher<G>>(
+        rng: &mut R,
+        n: u16,
+        r: Vec<G>,
+        g: &G,
     ) -> Result<(Self, Vec<G>), OTError> {
         if n < 2 {
             return Err(OTError::OTShouldHaveAtLeast2Messages(n));
@@ -73,7 +94,7 @@ impl ROTSenderKeys {
         let (m, keys) = cfg_into_iter!(0..n)
             .map(|i| {
                 let m_a = r[i as usize].into_group()
-                    + indexed_hash(
+                    + indexed_hash_with_hasher::<_, G, H>(
                         i,
                         r.iter()
                             .enumerate()
@@ -92,12 +113,24 @@ impl ROTSenderKeys {
 }
 
 pub fn indexed_hash<'a, I: Iterator<Item = &'a G>, G: AffineRepr>(index: u16, r: I) -> G::Group {
+    indexed_hash_with_hasher::<I, G, TryAndIncrement<Sha3_256>>(index, r)
+}
+
+/// Same as [`indexed_hash`] but hashes to the group using the given [`GroupElemHasher`]
+pub fn indexed_hash_with_hasher<
+    'a,
+    I: Iterator<Item = &'a G>,
+    G: AffineRepr,
+    H: GroupElemHasher<G>,
+>(
+    index: u16,
+    r: I,
+) -> G::Group {
     let mut bytes = index.to_be_bytes().to_vec();
     for r in r {
         r.serialize_compressed(&mut bytes).unwrap();
     }
-    // TODO: Replace with hash to curve
-    projective_group_elem_from_try_and_incr::<G, Sha3_256>(&bytes)
+    H::hash_to_projective(&bytes)
 }
 
 // TODO: Make it use const generic for key size and generic digest
@@ -118,6 +151,7 @@ pub mod tests {
         rand::{rngs::StdRng, SeedableRng},
         UniformRand,
     };
+    use dock_crypto_utils::hashing_utils::{HashToCurve, TryAndIncrement};
     use std::time::Instant;
 
     #[test]
@@ -125,9 +159,14 @@ pub mod tests {
         let mut rng = StdRng::seed_from_u64(0u64);
         let g = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
 
-        fn check(rng: &mut StdRng, n: u16, choice: u16, g: &<Bls12_381 as Pairing>::G1Affine) {
+        fn check<H: GroupElemHasher<<Bls12_381 as Pairing>::G1Affine>>(
+            rng: &mut StdRng,
+            n: u16,
+            choice: u16,
+            g: &<Bls12_381 as Pairing>::G1Affine,
+        ) {
             let start = Instant::now();
-            let (receiver, r) = ROTReceiver::new(rng, n, choice, g).unwrap();
+            let (receiver, r) = ROTReceiver::new_with_hasher::<_, H>(rng, n, choice, g).unwrap();
             println!(
                 "Receiver setup for 1-of-{} ROTs in {:?}",
                 n,
@@ -135,7 +174,7 @@ pub mod tests {
             );
 
             let start = Instant::now();
-            let (sender_keys, m) = ROTSenderKeys::new(rng, n, r, g).unwrap();
+            let (sender_keys, m) = ROTSenderKeys::new_with_hasher::<_, _, H>(rng, n, r, g).unwrap();
             println!(
                 "Sender creates keys for 1-of-{} ROTs in {:?}",
                 n,
@@ -153,16 +192,47 @@ pub mod tests {
             }
         }
 
-        check(&mut rng, 2, 0, &g);
-        check(&mut rng, 2, 0, &g);
-        check(&mut rng, 3, 0, &g);
-        check(&mut rng, 3, 1, &g);
-        check(&mut rng, 3, 2, &g);
-        check(&mut rng, 64, 0, &g);
-        check(&mut rng, 64, 63, &g);
-        check(&mut rng, 128, 0, &g);
-        check(&mut rng, 128, 127, &g);
-        check(&mut rng, 200, 0, &g);
-        check(&mut rng, 200, 199, &g);
+        for (n, choice) in [
+            (2, 0),
+            (2, 0),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (64, 0),
+            (64, 63),
+            (128, 0),
+            (128, 127),
+            (200, 0),
+            (200, 199),
+        ] {
+            check::<HashToCurve<Sha3_256>>(&mut rng, n, choice, &g);
+            check::<TryAndIncrement<Sha3_256>>(&mut rng, n, choice, &g);
+        }
+
+        // Default hasher is try-and-increment with SHA3-256
+        let (receiver, r) = ROTReceiver::new(&mut StdRng::seed_from_u64(1u64), 3, 1, &g).unwrap();
+        assert_eq!(
+            (receiver.clone(), r.clone()),
+            ROTReceiver::new_with_hasher::<_, TryAndIncrement<Sha3_256>>(
+                &mut StdRng::seed_from_u64(1u64),
+                3,
+                1,
+                &g
+            )
+            .unwrap()
+        );
+        let (sender_keys, m) =
+            ROTSenderKeys::new(&mut StdRng::seed_from_u64(2u64), 3, r.clone(), &g).unwrap();
+        assert_eq!(
+            (sender_keys.clone(), m.clone()),
+            ROTSenderKeys::new_with_hasher::<_, _, TryAndIncrement<Sha3_256>>(
+                &mut StdRng::seed_from_u64(2u64),
+                3,
+                r,
+                &g
+            )
+            .unwrap()
+        );
+        assert_eq!(sender_keys.0[1], receiver.derive_key(m));
     }
 }
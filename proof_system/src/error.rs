@@ -68,6 +68,22 @@ pub enum ProofSystemError {
     IncompatibleR1CSSetupParamAtIndex(usize),
     CircomError(CircomError),
     R1CSInsufficientPrivateInputs(usize, usize),
+    /// The signal is a private wire of the circuit but not among the ones committed by the proof. Contains the
+    /// signal name, its index among the private wires and the number of committed witnesses
+    R1CSSignalNotCommitted(String, usize, usize),
+    /// The verifying key expects a different number of public inputs than given. Contains the statement
+    /// index, the expected count and the given count
+    R1CSPublicInputCountMismatch(usize, usize, usize),
+    /// The batch accumulator statement at the given index is for a different number of elements than the witness or
+    /// proof. Contains the statement index, the number of elements in the statement and the number given
+    AccumulatorBatchSizeMismatch(usize, usize, usize),
+    /// The batch accumulator statement at the given index has no elements
+    AccumulatorBatchEmpty(usize),
+    /// A witness reference points past the last element of the batch accumulator statement. Contains the statement
+    /// index and the witness index
+    AccumulatorBatchWitnessRefOutOfBounds(usize, usize),
+    /// The verifying key of the statement at the given index was not created for the bound check circuit
+    BoundCheckLegoGroth16IncompatibleVerifyingKey(usize),
     InvalidWitnessEquality,
     /// Witness is being used a zero knowledge proof (bound check, accumulator, etc) while also being
     /// revealed. This shouldn't be the case, ever.
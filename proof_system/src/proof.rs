@@ -1,9 +1,13 @@
 use crate::prelude::StatementProof;
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeSet, vec::Vec};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use legogroth16::aggregation;
 use serde::{Deserialize, Serialize};
+use vb_accumulator::proofs_keyed_verification::DelegatedProof;
 
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct AggregatedGroth16<E: Pairing> {
@@ -31,3 +35,23 @@ impl<E: Pairing, G: AffineRepr> PartialEq for Proof<E, G> {
         // TODO: Add remaining
     }
 }
+
+impl<E: Pairing, G: AffineRepr> Proof<E, G> {
+    /// Get the parts of the proofs of keyed-verification statements that need to be verified with the secret key,
+    /// keyed by the statement index. Used when the proof was verified by someone who doesn't know the secret key.
+    pub fn get_delegated_proofs(&self) -> BTreeMap<usize, DelegatedProof<E::G1Affine>> {
+        let mut proofs = BTreeMap::new();
+        for (i, p) in self.statement_proofs.iter().enumerate() {
+            match p {
+                StatementProof::AccumulatorMembershipKV(p) => {
+                    proofs.insert(i, p.to_delegated_proof());
+                }
+                StatementProof::AccumulatorNonMembershipKV(p) => {
+                    proofs.insert(i, p.to_delegated_proof());
+                }
+                _ => (),
+            }
+        }
+        proofs
+    }
+}
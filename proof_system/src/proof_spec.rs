@@ -186,6 +186,63 @@ where
             }
         }
 
+        // Check that the SNARK keys are well-formed and compatible with their statements as they might
+        // have been created by an untrusted party
+        for (i, st) in self.statements.0.iter().enumerate() {
+            match st {
+                Statement::SaverProver(s) => {
+                    s.get_snark_proving_key(&self.setup_params, i)?
+                        .validate(s.chunk_bit_size)?;
+                }
+                Statement::SaverVerifier(s) => {
+                    saver::saver_groth16::validate_verifying_key(
+                        s.get_snark_verifying_key(&self.setup_params, i)?,
+                        s.chunk_bit_size,
+                    )?;
+                }
+                Statement::BoundCheckLegoGroth16Prover(s) => {
+                    let pk = s.get_proving_key(&self.setup_params, i)?;
+                    pk.validate()?;
+                    Self::check_bound_check_lego_vk(&pk.vk, i)?;
+                }
+                Statement::BoundCheckLegoGroth16Verifier(s) => {
+                    let vk = s.get_verifying_key(&self.setup_params, i)?;
+                    vk.validate()?;
+                    Self::check_bound_check_lego_vk(vk, i)?;
+                }
+                Statement::R1CSCircomProver(s) => {
+                    s.get_proving_key(&self.setup_params, i)?.validate()?;
+                }
+                Statement::R1CSCircomVerifier(s) => {
+                    let vk = s.get_verifying_key(&self.setup_params, i)?;
+                    vk.validate()?;
+                    let expected = vk.num_public_inputs() as usize - 1;
+                    let given = s.get_public_inputs(&self.setup_params, i)?.len();
+                    if expected != given {
+                        return Err(ProofSystemError::R1CSPublicInputCountMismatch(
+                            i, expected, given,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // A batch accumulator statement must be for at least 1 element and witness references to it must be within
+        // the batch
+        let mut batch_sizes = BTreeMap::new();
+        for (i, st) in self.statements.0.iter().enumerate() {
+            let num_elements = match st {
+                Statement::AccumulatorBatchMembership(s) => s.num_elements,
+                Statement::AccumulatorBatchNonMembership(s) => s.num_elements,
+                _ => continue,
+            };
+            if num_elements == 0 {
+                return Err(ProofSystemError::AccumulatorBatchEmpty(i));
+            }
+            batch_sizes.insert(i, num_elements);
+        }
+
         // Check that a message signed with BBS+ being revealed does not occur as a witness in any zero
         // knowledge proof
         for (i, st) in self.statements.0.iter().enumerate() {
@@ -214,6 +271,15 @@ where
                         if revealed_wit_refs.contains(r) {
                             return Err(ProofSystemError::WitnessAlreadyBeingRevealed(r.0, r.1));
                         }
+                        if let Some(num_elements) = batch_sizes.get(&r.0) {
+                            if r.1 >= *num_elements {
+                                return Err(
+                                    ProofSystemError::AccumulatorBatchWitnessRefOutOfBounds(
+                                        r.0, r.1,
+                                    ),
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -221,6 +287,17 @@ where
         Ok(())
     }
 
+    /// The bound check circuit has 2 public inputs, `min` and `max`, and commits to 1 witness
+    fn check_bound_check_lego_vk(
+        vk: &LegoVerifyingKey<E>,
+        st_idx: usize,
+    ) -> Result<(), ProofSystemError> {
+        if vk.commit_witness_count != 1 || vk.gamma_abc_g1.len() != 4 {
+            return Err(ProofSystemError::BoundCheckLegoGroth16IncompatibleVerifyingKey(st_idx));
+        }
+        Ok(())
+    }
+
     /// Derive commitment keys for Schnorr protocol from public params. This is done to avoid
     /// creating them if the same public params are used in multiple statements and is effectively a
     /// pre-processing step done for optimization.
@@ -441,6 +518,20 @@ where
                     let pk = s.get_public_key(&self.setup_params, s_idx)?;
                     derived_accum_pk.on_new_statement_idx(pk, s_idx);
                 }
+                Statement::AccumulatorBatchMembership(s) => {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    derived_accum_p.on_new_statement_idx(params, s_idx);
+
+                    let pk = s.get_public_key(&self.setup_params, s_idx)?;
+                    derived_accum_pk.on_new_statement_idx(pk, s_idx);
+                }
+                Statement::AccumulatorBatchNonMembership(s) => {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    derived_accum_p.on_new_statement_idx(params, s_idx);
+
+                    let pk = s.get_public_key(&self.setup_params, s_idx)?;
+                    derived_accum_pk.on_new_statement_idx(pk, s_idx);
+                }
                 Statement::SaverVerifier(s) => {
                     let gens = s.get_encryption_gens(&self.setup_params, s_idx)?;
                     derived_enc_gens.on_new_statement_idx(gens, s_idx);
@@ -21,6 +21,12 @@ use crate::{
     statement_proof::StatementProof,
     sub_protocols::{
         accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol},
+        accumulator_batch::{
+            AccumulatorBatchMembershipSubProtocol, AccumulatorBatchNonMembershipSubProtocol,
+        },
+        accumulator_with_kv::{
+            AccumulatorMembershipKVSubProtocol, AccumulatorNonMembershipKVSubProtocol,
+        },
         bbs_23::PoKBBSSigG1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol as PoKBBSPlusSigG1SubProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
@@ -228,6 +234,9 @@ where
                             pk,
                             prk,
                             s.accumulator_value,
+                        )
+                        .with_prepared_proving_key(
+                            s.get_prepared_proving_key(&proof_spec.setup_params),
                         );
                         sp.init(rng, blinding, w)?;
                         sub_protocols.push(SubProtocol::AccumulatorMembership(sp));
@@ -246,6 +255,9 @@ where
                             pk,
                             prk,
                             s.accumulator_value,
+                        )
+                        .with_prepared_proving_key(
+                            s.get_prepared_proving_key(&proof_spec.setup_params),
                         );
                         sp.init(rng, blinding, w)?;
                         sub_protocols.push(SubProtocol::AccumulatorNonMembership(sp));
@@ -473,6 +485,72 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::AccumulatorMembershipKV(s) => match witness {
+                    Witness::AccumulatorMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let mut sp =
+                            AccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.init(rng, blinding, w)?;
+                        sub_protocols.push(SubProtocol::AccumulatorMembershipKV(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::AccumulatorNonMembershipKV(s) => match witness {
+                    Witness::AccumulatorNonMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = AccumulatorNonMembershipKVSubProtocol::new(
+                            s_idx,
+                            params,
+                            s.accumulator_value,
+                        );
+                        sp.init(rng, blinding, w)?;
+                        sub_protocols.push(SubProtocol::AccumulatorNonMembershipKV(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::AccumulatorBatchMembership(s) => match witness {
+                    Witness::AccumulatorBatchMembership(w) => {
+                        if w.len() != s.num_elements {
+                            return Err(ProofSystemError::AccumulatorBatchSizeMismatch(
+                                s_idx,
+                                s.num_elements,
+                                w.len(),
+                            ));
+                        }
+                        let blindings_for_batch = (0..w.len())
+                            .map(|k| blindings.remove(&(s_idx, k)))
+                            .collect();
+                        let mut sp =
+                            AccumulatorBatchMembershipSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.init(rng, blindings_for_batch, w)?;
+                        sub_protocols.push(SubProtocol::AccumulatorBatchMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::AccumulatorBatchNonMembership(s) => match witness {
+                    Witness::AccumulatorBatchNonMembership(w) => {
+                        if w.len() != s.num_elements {
+                            return Err(ProofSystemError::AccumulatorBatchSizeMismatch(
+                                s_idx,
+                                s.num_elements,
+                                w.len(),
+                            ));
+                        }
+                        let blindings_for_batch = (0..w.len())
+                            .map(|k| blindings.remove(&(s_idx, k)))
+                            .collect();
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = AccumulatorBatchNonMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            s.accumulator_value,
+                        );
+                        sp.init(rng, blindings_for_batch, w)?;
+                        sub_protocols.push(SubProtocol::AccumulatorBatchNonMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
         }
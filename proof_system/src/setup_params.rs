@@ -29,8 +29,8 @@ use schnorr_pok::inequality::CommitmentKey;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use vb_accumulator::prelude::{
-    MembershipProvingKey, NonMembershipProvingKey, PublicKey as AccumPublicKey,
-    SetupParams as AccumParams,
+    MembershipProvingKey, NonMembershipProvingKey, PreparedMembershipProvingKey,
+    PreparedNonMembershipProvingKey, PublicKey as AccumPublicKey, SetupParams as AccumParams,
 };
 
 /// Holds (public) setup parameters of different protocols.
@@ -64,6 +64,8 @@ pub enum SetupParams<E: Pairing, G: AffineRepr> {
         #[serde_as(as = "ArkObjectBytes")] SmcParamsAndCommitmentKeyAndSecretKey<E>,
     ),
     CommitmentKey(#[serde_as(as = "ArkObjectBytes")] CommitmentKey<G>),
+    VbAccumulatorPreparedMemProvingKey(PreparedMembershipProvingKey<E::G1Affine>),
+    VbAccumulatorPreparedNonMemProvingKey(PreparedNonMembershipProvingKey<E::G1Affine>),
 }
 
 macro_rules! delegate {
@@ -93,7 +95,9 @@ macro_rules! delegate {
                 BppSetupParams,
                 SmcParamsAndCommKey,
                 SmcParamsAndCommKeyAndSk,
-                CommitmentKey
+                CommitmentKey,
+                VbAccumulatorPreparedMemProvingKey,
+                VbAccumulatorPreparedNonMemProvingKey
             : $($tt)+
         }
     }};
@@ -126,7 +130,9 @@ macro_rules! delegate_reverse {
                 BppSetupParams,
                 SmcParamsAndCommKey,
                 SmcParamsAndCommKeyAndSk,
-                CommitmentKey
+                CommitmentKey,
+                VbAccumulatorPreparedMemProvingKey,
+                VbAccumulatorPreparedNonMemProvingKey
             : $($tt)+
         }
 
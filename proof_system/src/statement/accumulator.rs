@@ -6,7 +6,8 @@ use dock_crypto_utils::serde_utils::*;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use vb_accumulator::prelude::{
-    MembershipProvingKey, NonMembershipProvingKey, PublicKey, SetupParams as AccumParams,
+    MembershipProvingKey, NonMembershipProvingKey, PreparedMembershipProvingKey,
+    PreparedNonMembershipProvingKey, PublicKey, SetupParams as AccumParams,
 };
 
 /// Public values like setup params, public key, proving key and accumulator for proving membership
@@ -120,6 +121,9 @@ impl<E: Pairing> AccumulatorMembership<E> {
         setup_params: &'a [SetupParams<E, G>],
         st_idx: usize,
     ) -> Result<&'a MembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        if let Some(prk) = self.get_prepared_proving_key(setup_params) {
+            return Ok(prk.proving_key());
+        }
         extract_param!(
             setup_params,
             &self.proving_key,
@@ -129,6 +133,20 @@ impl<E: Pairing> AccumulatorMembership<E> {
             st_idx
         )
     }
+
+    /// Get the prepared membership proving key if the statement refers to one in the given `setup_params`
+    pub fn get_prepared_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+    ) -> Option<&'a PreparedMembershipProvingKey<E::G1Affine>> {
+        if self.proving_key.is_some() {
+            return None;
+        }
+        match self.proving_key_ref.and_then(|idx| setup_params.get(idx)) {
+            Some(SetupParams::VbAccumulatorPreparedMemProvingKey(prk)) => Some(prk),
+            _ => None,
+        }
+    }
 }
 
 impl<E: Pairing> AccumulatorNonMembership<E> {
@@ -201,6 +219,9 @@ impl<E: Pairing> AccumulatorNonMembership<E> {
         setup_params: &'a [SetupParams<E, G>],
         st_idx: usize,
     ) -> Result<&'a NonMembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        if let Some(prk) = self.get_prepared_proving_key(setup_params) {
+            return Ok(prk.proving_key());
+        }
         extract_param!(
             setup_params,
             &self.proving_key,
@@ -210,4 +231,18 @@ impl<E: Pairing> AccumulatorNonMembership<E> {
             st_idx
         )
     }
+
+    /// Get the prepared non-membership proving key if the statement refers to one in the given `setup_params`
+    pub fn get_prepared_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+    ) -> Option<&'a PreparedNonMembershipProvingKey<E::G1Affine>> {
+        if self.proving_key.is_some() {
+            return None;
+        }
+        match self.proving_key_ref.and_then(|idx| setup_params.get(idx)) {
+            Some(SetupParams::VbAccumulatorPreparedNonMemProvingKey(prk)) => Some(prk),
+            _ => None,
+        }
+    }
 }
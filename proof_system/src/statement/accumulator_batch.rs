@@ -0,0 +1,182 @@
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::serde_utils::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use vb_accumulator::prelude::{PublicKey, SetupParams as AccumParams};
+
+/// Public values like setup params, public key and accumulator for proving membership of several elements in
+/// the same positive or universal accumulator. The witness has 1 member per witness index and `num_elements` members
+/// in total.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorBatchMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    /// Number of elements whose (non)membership is proven. A proof for a different number of elements is rejected.
+    pub num_elements: usize,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+}
+
+/// Public values like setup params, public key and accumulator for proving non-membership of several elements in
+/// the same universal accumulator. The witness has 1 non-member per witness index and `num_elements` non-members in
+/// total.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorBatchNonMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    /// Number of elements whose (non)membership is proven. A proof for a different number of elements is rejected.
+    pub num_elements: usize,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> AccumulatorBatchMembership<E> {
+    /// Create a statement by passing the accumulator params and public key directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorBatchMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: Some(params),
+            public_key: Some(public_key),
+            params_ref: None,
+            public_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params and public key in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorBatchMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: None,
+            public_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+        })
+    }
+
+    /// Get accumulator params for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Get public key for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_public_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.public_key,
+            self.public_key_ref,
+            VbAccumulatorPublicKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
+
+impl<E: Pairing> AccumulatorBatchNonMembership<E> {
+    /// Create a statement by passing the accumulator params and public key directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorBatchNonMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: Some(params),
+            public_key: Some(public_key),
+            params_ref: None,
+            public_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params and public key in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorBatchNonMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: None,
+            public_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+        })
+    }
+
+    /// Get accumulator params for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// Get public key for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_public_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.public_key,
+            self.public_key_ref,
+            VbAccumulatorPublicKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::serde_utils::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use vb_accumulator::prelude::{SecretKey, SetupParams as AccumParams};
+
+/// Public values for proving membership in positive and universal accumulator to a verifier knowing the secret
+/// key. Used by the prover and by a verifier that only checks the proof of knowledge and leaves the check requiring
+/// the secret key to the holder of the secret key.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorMembershipKV<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+}
+
+/// Used by the verifier as it knows the secret key. Should not be shared with the prover
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorMembershipKVFullVerifier<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    pub secret_key: SecretKey<E::ScalarField>,
+}
+
+/// Public values for proving non-membership in universal accumulator to a verifier knowing the secret key. Used by
+/// the prover and by a verifier that only checks the proof of knowledge and leaves the check requiring the secret key
+/// to the holder of the secret key.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorNonMembershipKV<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    pub params: Option<AccumParams<E>>,
+    pub params_ref: Option<usize>,
+}
+
+/// Used by the verifier as it knows the secret key. Should not be shared with the prover
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AccumulatorNonMembershipKVFullVerifier<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    pub params: Option<AccumParams<E>>,
+    pub params_ref: Option<usize>,
+    pub secret_key: SecretKey<E::ScalarField>,
+}
+
+impl<E: Pairing> AccumulatorMembershipKV<E> {
+    pub fn new_statement<G: AffineRepr>(accumulator_value: E::G1Affine) -> Statement<E, G> {
+        Statement::AccumulatorMembershipKV(Self { accumulator_value })
+    }
+}
+
+impl<E: Pairing> AccumulatorMembershipKVFullVerifier<E> {
+    pub fn new_statement<G: AffineRepr>(
+        accumulator_value: E::G1Affine,
+        secret_key: SecretKey<E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorMembershipKVFullVerifier(Self {
+            accumulator_value,
+            secret_key,
+        })
+    }
+}
+
+impl<E: Pairing> AccumulatorNonMembershipKV<E> {
+    /// Create a statement by passing the accumulator params directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        accumulator_value: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorNonMembershipKV(Self {
+            accumulator_value,
+            params: Some(params),
+            params_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the index of accumulator params in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        accumulator_value: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorNonMembershipKV(Self {
+            accumulator_value,
+            params: None,
+            params_ref: Some(params_ref),
+        })
+    }
+
+    /// Get accumulator params for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
+
+impl<E: Pairing> AccumulatorNonMembershipKVFullVerifier<E> {
+    /// Create a statement by passing the accumulator params directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        accumulator_value: E::G1Affine,
+        secret_key: SecretKey<E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorNonMembershipKVFullVerifier(Self {
+            accumulator_value,
+            params: Some(params),
+            params_ref: None,
+            secret_key,
+        })
+    }
+
+    /// Create a statement by passing the index of accumulator params in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        accumulator_value: E::G1Affine,
+        secret_key: SecretKey<E::ScalarField>,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorNonMembershipKVFullVerifier(Self {
+            accumulator_value,
+            params: None,
+            params_ref: Some(params_ref),
+            secret_key,
+        })
+    }
+
+    /// Get accumulator params for the statement index `s_idx` either from `self` or from given `setup_params`
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
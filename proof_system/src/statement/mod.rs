@@ -7,6 +7,8 @@ use ark_std::{
 use serde::{Deserialize, Serialize};
 
 pub mod accumulator;
+pub mod accumulator_batch;
+pub mod accumulator_with_kv;
 pub mod bbs_23;
 #[macro_use]
 pub mod bbs_plus;
@@ -60,6 +62,26 @@ pub enum Statement<E: Pairing, G: AffineRepr> {
     BoundCheckSmcWithKVVerifier(bound_check_smc_with_kv::BoundCheckSmcWithKVVerifier<E>),
     /// To prove inequality of a signed message with a public value
     PublicInequality(inequality::PublicInequality<G>),
+    /// Used by the prover for proof of knowledge of an accumulator member and its corresponding witness with keyed
+    /// verification. Also used by the verifier when verification with the secret key is delegated.
+    AccumulatorMembershipKV(accumulator_with_kv::AccumulatorMembershipKV<E>),
+    /// Used by the verifier knowing the secret key for proof of knowledge of an accumulator member and its
+    /// corresponding witness with keyed verification
+    AccumulatorMembershipKVFullVerifier(
+        accumulator_with_kv::AccumulatorMembershipKVFullVerifier<E>,
+    ),
+    /// Used by the prover for proof of knowledge of an accumulator non-member and its corresponding witness with
+    /// keyed verification. Also used by the verifier when verification with the secret key is delegated.
+    AccumulatorNonMembershipKV(accumulator_with_kv::AccumulatorNonMembershipKV<E>),
+    /// Used by the verifier knowing the secret key for proof of knowledge of an accumulator non-member and its
+    /// corresponding witness with keyed verification
+    AccumulatorNonMembershipKVFullVerifier(
+        accumulator_with_kv::AccumulatorNonMembershipKVFullVerifier<E>,
+    ),
+    /// For proof of knowledge of several members of the same accumulator and their corresponding witnesses
+    AccumulatorBatchMembership(accumulator_batch::AccumulatorBatchMembership<E>),
+    /// For proof of knowledge of several non-members of the same accumulator and their corresponding witnesses
+    AccumulatorBatchNonMembership(accumulator_batch::AccumulatorBatchNonMembership<E>),
 }
 
 /// A collection of statements
@@ -115,7 +137,13 @@ macro_rules! delegate {
                 BoundCheckSmc,
                 BoundCheckSmcWithKVProver,
                 BoundCheckSmcWithKVVerifier,
-                PublicInequality
+                PublicInequality,
+                AccumulatorMembershipKV,
+                AccumulatorMembershipKVFullVerifier,
+                AccumulatorNonMembershipKV,
+                AccumulatorNonMembershipKVFullVerifier,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
     }}
@@ -141,7 +169,13 @@ macro_rules! delegate_reverse {
                 BoundCheckSmc,
                 BoundCheckSmcWithKVProver,
                 BoundCheckSmcWithKVVerifier,
-                PublicInequality
+                PublicInequality,
+                AccumulatorMembershipKV,
+                AccumulatorMembershipKVFullVerifier,
+                AccumulatorNonMembershipKV,
+                AccumulatorNonMembershipKVFullVerifier,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
 
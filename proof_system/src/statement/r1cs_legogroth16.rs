@@ -1,12 +1,18 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{string::String, vec::Vec};
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
-pub use legogroth16::{circom::R1CS, PreparedVerifyingKey, ProvingKey, VerifyingKey};
+pub use legogroth16::{
+    circom::{SymbolTable, R1CS},
+    PreparedVerifyingKey, ProvingKey, VerifyingKey,
+};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+use crate::{
+    error::ProofSystemError, meta_statement::WitnessRef, setup_params::SetupParams,
+    statement::Statement,
+};
 
 #[serde_as]
 #[derive(
@@ -95,6 +101,41 @@ impl<E: Pairing> R1CSCircomProver<E> {
         }))
     }
 
+    /// Get the reference to the witness corresponding to the private input signal `name` like `main.age`
+    /// for the statement at index `st_idx`. The returned reference can be used in `EqualWitnesses`. Uses the
+    /// symbol table parsed from the .sym file so that the caller does not need to know the order of wires
+    /// in the compiled circuit. The signal must be among the first `commit_witness_count` private wires.
+    pub fn witness_ref_for_signal(
+        st_idx: usize,
+        r1cs: &R1CS<E>,
+        symbols: &SymbolTable,
+        commit_witness_count: u32,
+        name: &str,
+    ) -> Result<WitnessRef, ProofSystemError> {
+        let idx = symbols.private_input_index(r1cs, name)?;
+        check_committed(name, idx, commit_witness_count)?;
+        Ok((st_idx, idx))
+    }
+
+    /// Same as `Self::witness_ref_for_signal` but for an array signal like `main.in` in `signal input in[3]`.
+    /// Returns a reference for each element of the array.
+    pub fn witness_refs_for_signal(
+        st_idx: usize,
+        r1cs: &R1CS<E>,
+        symbols: &SymbolTable,
+        commit_witness_count: u32,
+        name: &str,
+    ) -> Result<Vec<WitnessRef>, ProofSystemError> {
+        symbols
+            .private_input_indices(r1cs, name)?
+            .into_iter()
+            .map(|idx| {
+                check_committed(name, idx, commit_witness_count)?;
+                Ok((st_idx, idx))
+            })
+            .collect()
+    }
+
     pub fn get_r1cs<'a, G: AffineRepr>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
@@ -196,3 +237,19 @@ impl<E: Pairing> R1CSCircomVerifier<E> {
         )
     }
 }
+
+/// Check that the private wire at index `idx` is among the witnesses committed by the proof
+fn check_committed(
+    name: &str,
+    idx: usize,
+    commit_witness_count: u32,
+) -> Result<(), ProofSystemError> {
+    if idx >= commit_witness_count as usize {
+        return Err(ProofSystemError::R1CSSignalNotCommitted(
+            String::from(name),
+            idx,
+            commit_witness_count as usize,
+        ));
+    }
+    Ok(())
+}
@@ -12,7 +12,11 @@ use saver::encryption::Ciphertext;
 use schnorr_pok::SchnorrResponse;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use vb_accumulator::prelude::{MembershipProof, NonMembershipProof};
+use vb_accumulator::{
+    prelude::{MembershipProof, NonMembershipProof},
+    proofs_batch::{BatchMembershipProof, BatchNonMembershipProof},
+    proofs_keyed_verification::{MembershipProofWithKV, NonMembershipProofWithKV},
+};
 
 use crate::error::ProofSystemError;
 pub use serialization::*;
@@ -37,6 +41,10 @@ pub enum StatementProof<E: Pairing, G: AffineRepr> {
     BoundCheckSmc(BoundCheckSmcProof<E>),
     BoundCheckSmcWithKV(BoundCheckSmcWithKVProof<E>),
     Inequality(InequalityProof<G>),
+    AccumulatorMembershipKV(MembershipProofWithKV<E>),
+    AccumulatorNonMembershipKV(NonMembershipProofWithKV<E>),
+    AccumulatorBatchMembership(BatchMembershipProof<E>),
+    AccumulatorBatchNonMembership(BatchNonMembershipProof<E>),
 }
 
 macro_rules! delegate {
@@ -58,7 +66,11 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                AccumulatorMembershipKV,
+                AccumulatorNonMembershipKV,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
     }};
@@ -83,7 +95,11 @@ macro_rules! delegate_reverse {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                AccumulatorMembershipKV,
+                AccumulatorNonMembershipKV,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
 
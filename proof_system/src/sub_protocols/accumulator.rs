@@ -4,8 +4,9 @@ use ark_std::{io::Write, rand::RngCore};
 use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
 use vb_accumulator::prelude::{
     MembershipProof, MembershipProofProtocol, MembershipProvingKey, NonMembershipProof,
-    NonMembershipProofProtocol, NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams,
-    PublicKey, SetupParams as AccumParams,
+    NonMembershipProofProtocol, NonMembershipProvingKey, PreparedMembershipProvingKey,
+    PreparedNonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams, PublicKey,
+    SetupParams as AccumParams,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -14,6 +15,8 @@ pub struct AccumulatorMembershipSubProtocol<'a, E: Pairing> {
     pub params: &'a AccumParams<E>,
     pub public_key: &'a PublicKey<E>,
     pub proving_key: &'a MembershipProvingKey<E::G1Affine>,
+    /// When set, its tables are used for creating and verifying the proof
+    pub prepared_proving_key: Option<&'a PreparedMembershipProvingKey<E::G1Affine>>,
     pub accumulator_value: E::G1Affine,
     pub protocol: Option<MembershipProofProtocol<E>>,
 }
@@ -24,6 +27,8 @@ pub struct AccumulatorNonMembershipSubProtocol<'a, E: Pairing> {
     pub params: &'a AccumParams<E>,
     pub public_key: &'a PublicKey<E>,
     pub proving_key: &'a NonMembershipProvingKey<E::G1Affine>,
+    /// When set, its tables are used for creating and verifying the proof
+    pub prepared_proving_key: Option<&'a PreparedNonMembershipProvingKey<E::G1Affine>>,
     pub accumulator_value: E::G1Affine,
     pub protocol: Option<NonMembershipProofProtocol<E>>,
 }
@@ -41,11 +46,21 @@ impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
             params,
             public_key,
             proving_key,
+            prepared_proving_key: None,
             accumulator_value,
             protocol: None,
         }
     }
 
+    /// Use the tables of the prepared membership proving key, if given, rather than creating them for the proof
+    pub fn with_prepared_proving_key(
+        mut self,
+        prepared_proving_key: Option<&'a PreparedMembershipProvingKey<E::G1Affine>>,
+    ) -> Self {
+        self.prepared_proving_key = prepared_proving_key;
+        self
+    }
+
     pub fn init<R: RngCore>(
         &mut self,
         rng: &mut R,
@@ -55,15 +70,26 @@ impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
         if self.protocol.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
-        let protocol = MembershipProofProtocol::init(
-            rng,
-            &witness.element,
-            blinding,
-            &witness.witness,
-            self.public_key,
-            self.params,
-            self.proving_key,
-        );
+        let protocol = match self.prepared_proving_key {
+            Some(prk) => MembershipProofProtocol::init_with_prepared_proving_key(
+                rng,
+                &witness.element,
+                blinding,
+                &witness.witness,
+                self.public_key,
+                self.params,
+                prk,
+            ),
+            None => MembershipProofProtocol::init(
+                rng,
+                &witness.element,
+                blinding,
+                &witness.witness,
+                self.public_key,
+                self.params,
+                self.proving_key,
+            ),
+        };
         self.protocol = Some(protocol);
         Ok(())
     }
@@ -106,8 +132,17 @@ impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
         params: impl Into<PreparedSetupParams<E>>,
         pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
     ) -> Result<(), ProofSystemError> {
-        match pairing_checker {
-            Some(c) => proof.verify_with_randomized_pairing_checker(
+        match (pairing_checker, self.prepared_proving_key) {
+            (Some(c), Some(prk)) => proof
+                .verify_with_prepared_proving_key_and_randomized_pairing_checker(
+                    &self.accumulator_value,
+                    challenge,
+                    pk,
+                    params,
+                    prk,
+                    c,
+                )?,
+            (Some(c), None) => proof.verify_with_randomized_pairing_checker(
                 &self.accumulator_value,
                 challenge,
                 pk,
@@ -115,7 +150,14 @@ impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
                 self.proving_key,
                 c,
             )?,
-            None => proof.verify(
+            (None, Some(prk)) => proof.verify_with_prepared_proving_key(
+                &self.accumulator_value,
+                challenge,
+                pk,
+                params,
+                prk,
+            )?,
+            (None, None) => proof.verify(
                 &self.accumulator_value,
                 challenge,
                 pk,
@@ -140,11 +182,21 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
             params,
             public_key,
             proving_key,
+            prepared_proving_key: None,
             accumulator_value,
             protocol: None,
         }
     }
 
+    /// Use the tables of the prepared non-membership proving key, if given, rather than creating them for the proof
+    pub fn with_prepared_proving_key(
+        mut self,
+        prepared_proving_key: Option<&'a PreparedNonMembershipProvingKey<E::G1Affine>>,
+    ) -> Self {
+        self.prepared_proving_key = prepared_proving_key;
+        self
+    }
+
     pub fn init<R: RngCore>(
         &mut self,
         rng: &mut R,
@@ -154,15 +206,26 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
         if self.protocol.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
-        let protocol = NonMembershipProofProtocol::init(
-            rng,
-            &witness.element,
-            blinding,
-            &witness.witness,
-            self.public_key,
-            self.params,
-            self.proving_key,
-        );
+        let protocol = match self.prepared_proving_key {
+            Some(prk) => NonMembershipProofProtocol::init_with_prepared_proving_key(
+                rng,
+                &witness.element,
+                blinding,
+                &witness.witness,
+                self.public_key,
+                self.params,
+                prk,
+            ),
+            None => NonMembershipProofProtocol::init(
+                rng,
+                &witness.element,
+                blinding,
+                &witness.witness,
+                self.public_key,
+                self.params,
+                self.proving_key,
+            ),
+        };
         self.protocol = Some(protocol);
         Ok(())
     }
@@ -205,8 +268,17 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
         params: impl Into<PreparedSetupParams<E>>,
         pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
     ) -> Result<(), ProofSystemError> {
-        match pairing_checker {
-            Some(c) => proof.verify_with_randomized_pairing_checker(
+        match (pairing_checker, self.prepared_proving_key) {
+            (Some(c), Some(prk)) => proof
+                .verify_with_prepared_proving_key_and_randomized_pairing_checker(
+                    &self.accumulator_value,
+                    challenge,
+                    pk,
+                    params,
+                    prk,
+                    c,
+                )?,
+            (Some(c), None) => proof.verify_with_randomized_pairing_checker(
                 &self.accumulator_value,
                 challenge,
                 pk,
@@ -214,7 +286,14 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
                 self.proving_key,
                 c,
             )?,
-            None => proof.verify(
+            (None, Some(prk)) => proof.verify_with_prepared_proving_key(
+                &self.accumulator_value,
+                challenge,
+                pk,
+                params,
+                prk,
+            )?,
+            (None, None) => proof.verify(
                 &self.accumulator_value,
                 challenge,
                 pk,
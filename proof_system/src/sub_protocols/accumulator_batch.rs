@@ -0,0 +1,199 @@
+use crate::{error::ProofSystemError, statement_proof::StatementProof};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::{io::Write, rand::RngCore, vec::Vec};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use vb_accumulator::{
+    prelude::{PreparedPublicKey, PreparedSetupParams, SetupParams as AccumParams},
+    proofs_batch::{
+        BatchMembershipProof, BatchMembershipProofProtocol, BatchNonMembershipProof,
+        BatchNonMembershipProofProtocol,
+    },
+};
+
+/// Proof of membership of several elements in the same accumulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorBatchMembershipSubProtocol<E: Pairing> {
+    pub id: usize,
+    pub accumulator_value: E::G1Affine,
+    pub protocol: Option<BatchMembershipProofProtocol<E>>,
+}
+
+/// Proof of non-membership of several elements in the same accumulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorBatchNonMembershipSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub accumulator_value: E::G1Affine,
+    pub protocol: Option<BatchNonMembershipProofProtocol<E>>,
+}
+
+impl<E: Pairing> AccumulatorBatchMembershipSubProtocol<E> {
+    pub fn new(id: usize, accumulator_value: E::G1Affine) -> Self {
+        Self {
+            id,
+            accumulator_value,
+            protocol: None,
+        }
+    }
+
+    /// `blindings` has the optional blinding for each member in `witness`, in the same order
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: Vec<Option<E::ScalarField>>,
+        witness: Vec<crate::witness::Membership<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let elements = witness.iter().map(|w| w.element).collect::<Vec<_>>();
+        let witnesses = witness
+            .iter()
+            .map(|w| w.witness.clone())
+            .collect::<Vec<_>>();
+        let protocol = BatchMembershipProofProtocol::init(
+            rng,
+            &elements,
+            blindings,
+            &witnesses,
+            &self.accumulator_value,
+        )?;
+        self.protocol = Some(protocol);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&self.accumulator_value, writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let proof = protocol.gen_proof(challenge)?;
+        Ok(StatementProof::AccumulatorBatchMembership(proof))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &BatchMembershipProof<E>,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        match pairing_checker {
+            Some(c) => proof.verify_with_randomized_pairing_checker(
+                &self.accumulator_value,
+                challenge,
+                pk,
+                params,
+                c,
+            )?,
+            None => proof.verify(&self.accumulator_value, challenge, pk, params)?,
+        }
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing> AccumulatorBatchNonMembershipSubProtocol<'a, E> {
+    pub fn new(id: usize, params: &'a AccumParams<E>, accumulator_value: E::G1Affine) -> Self {
+        Self {
+            id,
+            params,
+            accumulator_value,
+            protocol: None,
+        }
+    }
+
+    /// `blindings` has the optional blinding for each non-member in `witness`, in the same order
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: Vec<Option<E::ScalarField>>,
+        witness: Vec<crate::witness::NonMembership<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let elements = witness.iter().map(|w| w.element).collect::<Vec<_>>();
+        let witnesses = witness
+            .iter()
+            .map(|w| w.witness.clone())
+            .collect::<Vec<_>>();
+        let protocol = BatchNonMembershipProofProtocol::init(
+            rng,
+            &elements,
+            blindings,
+            &witnesses,
+            &self.accumulator_value,
+            self.params,
+        )?;
+        self.protocol = Some(protocol);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol.as_ref().unwrap().challenge_contribution(
+            &self.accumulator_value,
+            self.params,
+            writer,
+        )?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let proof = protocol.gen_proof(challenge)?;
+        Ok(StatementProof::AccumulatorBatchNonMembership(proof))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &BatchNonMembershipProof<E>,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        match pairing_checker {
+            Some(c) => proof.verify_with_randomized_pairing_checker(
+                &self.accumulator_value,
+                challenge,
+                pk,
+                params,
+                c,
+            )?,
+            None => proof.verify(&self.accumulator_value, challenge, pk, params)?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{error::ProofSystemError, statement_proof::StatementProof};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::{io::Write, rand::RngCore};
+use vb_accumulator::{
+    prelude::{SecretKey, SetupParams as AccumParams},
+    proofs_keyed_verification::{
+        MembershipProofWithKV, MembershipProofWithKVProtocol, NonMembershipProofWithKV,
+        NonMembershipProofWithKVProtocol,
+    },
+};
+
+/// Proof of membership in an accumulator where the verifier knows the secret key. The verifier without the secret
+/// key only verifies the proof of knowledge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorMembershipKVSubProtocol<E: Pairing> {
+    pub id: usize,
+    pub accumulator_value: E::G1Affine,
+    pub protocol: Option<MembershipProofWithKVProtocol<E>>,
+}
+
+/// Proof of non-membership in an accumulator where the verifier knows the secret key. The verifier without the secret
+/// key only verifies the proofs of knowledge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorNonMembershipKVSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub accumulator_value: E::G1Affine,
+    pub protocol: Option<NonMembershipProofWithKVProtocol<E>>,
+}
+
+impl<E: Pairing> AccumulatorMembershipKVSubProtocol<E> {
+    pub fn new(id: usize, accumulator_value: E::G1Affine) -> Self {
+        Self {
+            id,
+            accumulator_value,
+            protocol: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blinding: Option<E::ScalarField>,
+        witness: crate::witness::Membership<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let protocol = MembershipProofWithKVProtocol::init(
+            rng,
+            witness.element,
+            blinding,
+            &witness.witness,
+            &self.accumulator_value,
+        );
+        self.protocol = Some(protocol);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&self.accumulator_value, writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let proof = protocol.gen_proof(challenge)?;
+        Ok(StatementProof::AccumulatorMembershipKV(proof))
+    }
+
+    /// Verify the proof of knowledge only
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &MembershipProofWithKV<E>,
+    ) -> Result<(), ProofSystemError> {
+        proof.verify_schnorr_proof(&self.accumulator_value, challenge)?;
+        Ok(())
+    }
+
+    /// Verify the proof of knowledge and the proof using the secret key
+    pub fn verify_full_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &MembershipProofWithKV<E>,
+        secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        proof.verify(&self.accumulator_value, challenge, secret_key)?;
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing> AccumulatorNonMembershipKVSubProtocol<'a, E> {
+    pub fn new(id: usize, params: &'a AccumParams<E>, accumulator_value: E::G1Affine) -> Self {
+        Self {
+            id,
+            params,
+            accumulator_value,
+            protocol: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blinding: Option<E::ScalarField>,
+        witness: crate::witness::NonMembership<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let protocol = NonMembershipProofWithKVProtocol::init(
+            rng,
+            witness.element,
+            blinding,
+            &witness.witness,
+            &self.accumulator_value,
+            self.params,
+        );
+        self.protocol = Some(protocol);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol.as_ref().unwrap().challenge_contribution(
+            &self.accumulator_value,
+            self.params,
+            writer,
+        )?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let proof = protocol.gen_proof(challenge)?;
+        Ok(StatementProof::AccumulatorNonMembershipKV(proof))
+    }
+
+    /// Verify the proofs of knowledge only
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &NonMembershipProofWithKV<E>,
+    ) -> Result<(), ProofSystemError> {
+        proof.verify_schnorr_proof(&self.accumulator_value, challenge, self.params)?;
+        Ok(())
+    }
+
+    /// Verify the proofs of knowledge and the proof using the secret key
+    pub fn verify_full_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &NonMembershipProofWithKV<E>,
+        secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        proof.verify(&self.accumulator_value, challenge, secret_key, self.params)?;
+        Ok(())
+    }
+}
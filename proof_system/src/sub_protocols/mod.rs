@@ -1,4 +1,6 @@
 pub mod accumulator;
+pub mod accumulator_batch;
+pub mod accumulator_with_kv;
 #[macro_use]
 pub mod bbs_plus;
 pub mod bbs_23;
@@ -31,6 +33,12 @@ use crate::{
     },
 };
 use accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol};
+use accumulator_batch::{
+    AccumulatorBatchMembershipSubProtocol, AccumulatorBatchNonMembershipSubProtocol,
+};
+use accumulator_with_kv::{
+    AccumulatorMembershipKVSubProtocol, AccumulatorNonMembershipKVSubProtocol,
+};
 
 /// Various sub-protocols that are executed to create a `StatementProof` which are then combined to
 /// form a `Proof`
@@ -57,6 +65,14 @@ pub enum SubProtocol<'a, E: Pairing, G: AffineRepr> {
     BoundCheckSmcWithKV(BoundCheckSmcWithKVProtocol<'a, E>),
     /// To prove inequality of a signed message with a public value
     Inequality(InequalityProtocol<'a, G>),
+    /// For membership in an accumulator with keyed verification
+    AccumulatorMembershipKV(AccumulatorMembershipKVSubProtocol<E>),
+    /// For non-membership in an accumulator with keyed verification
+    AccumulatorNonMembershipKV(AccumulatorNonMembershipKVSubProtocol<'a, E>),
+    /// For membership of several elements in an accumulator
+    AccumulatorBatchMembership(AccumulatorBatchMembershipSubProtocol<E>),
+    /// For non-membership of several elements in an accumulator
+    AccumulatorBatchNonMembership(AccumulatorBatchNonMembershipSubProtocol<'a, E>),
 }
 
 macro_rules! delegate {
@@ -75,7 +91,11 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                AccumulatorMembershipKV,
+                AccumulatorNonMembershipKV,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
     }};
@@ -6,6 +6,12 @@ use crate::{
     statement_proof::StatementProof,
     sub_protocols::{
         accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol},
+        accumulator_batch::{
+            AccumulatorBatchMembershipSubProtocol, AccumulatorBatchNonMembershipSubProtocol,
+        },
+        accumulator_with_kv::{
+            AccumulatorMembershipKVSubProtocol, AccumulatorNonMembershipKVSubProtocol,
+        },
         bbs_23::PoKBBSSigG1SubProtocol as PoKBBSSig23G1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
@@ -592,6 +598,115 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::AccumulatorMembershipKV(_)
+                | Statement::AccumulatorMembershipKVFullVerifier(_) => match proof {
+                    StatementProof::AccumulatorMembershipKV(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
+                        );
+                        let accumulator_value = match statement {
+                            Statement::AccumulatorMembershipKV(s) => &s.accumulator_value,
+                            Statement::AccumulatorMembershipKVFullVerifier(s) => {
+                                &s.accumulator_value
+                            }
+                            _ => panic!("This should never happen"),
+                        };
+                        p.challenge_contribution(accumulator_value, &mut challenge_bytes)?;
+                    }
+                    _ => err_incompat_proof!(s_idx, statement, proof),
+                },
+                Statement::AccumulatorNonMembershipKV(_)
+                | Statement::AccumulatorNonMembershipKVFullVerifier(_) => match proof {
+                    StatementProof::AccumulatorNonMembershipKV(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
+                        );
+                        let (accumulator_value, params) = match statement {
+                            Statement::AccumulatorNonMembershipKV(s) => (
+                                &s.accumulator_value,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            ),
+                            Statement::AccumulatorNonMembershipKVFullVerifier(s) => (
+                                &s.accumulator_value,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            ),
+                            _ => panic!("This should never happen"),
+                        };
+                        p.challenge_contribution(accumulator_value, params, &mut challenge_bytes)?;
+                    }
+                    _ => err_incompat_proof!(s_idx, statement, proof),
+                },
+                Statement::AccumulatorBatchMembership(s) => match proof {
+                    StatementProof::AccumulatorBatchMembership(p) => {
+                        if p.len() != s.num_elements {
+                            return Err(ProofSystemError::AccumulatorBatchSizeMismatch(
+                                s_idx,
+                                s.num_elements,
+                                p.len(),
+                            ));
+                        }
+                        for i in 0..p.len() {
+                            // Check witness equalities for this statement.
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&(s_idx, i)) {
+                                    let r = p.get_schnorr_response_for_element(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
+                        p.challenge_contribution(&s.accumulator_value, &mut challenge_bytes)?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorBatchNonMembership(s) => match proof {
+                    StatementProof::AccumulatorBatchNonMembership(p) => {
+                        if p.len() != s.num_elements {
+                            return Err(ProofSystemError::AccumulatorBatchSizeMismatch(
+                                s_idx,
+                                s.num_elements,
+                                p.len(),
+                            ));
+                        }
+                        for i in 0..p.len() {
+                            // Check witness equalities for this statement.
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&(s_idx, i)) {
+                                    let r = p.get_schnorr_response_for_element(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        p.challenge_contribution(
+                            &s.accumulator_value,
+                            params,
+                            &mut challenge_bytes,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
         }
@@ -674,6 +789,9 @@ where
                             pk,
                             prk,
                             s.accumulator_value,
+                        )
+                        .with_prepared_proving_key(
+                            s.get_prepared_proving_key(&proof_spec.setup_params),
                         );
                         sp.verify_proof_contribution(
                             &challenge,
@@ -696,6 +814,9 @@ where
                             pk,
                             prk,
                             s.accumulator_value,
+                        )
+                        .with_prepared_proving_key(
+                            s.get_prepared_proving_key(&proof_spec.setup_params),
                         );
                         sp.verify_proof_contribution(
                             &challenge,
@@ -918,6 +1039,78 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::AccumulatorMembershipKV(s) => match proof {
+                    StatementProof::AccumulatorMembershipKV(ref p) => {
+                        let sp =
+                            AccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.verify_proof_contribution(&challenge, p)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorMembershipKVFullVerifier(s) => match proof {
+                    StatementProof::AccumulatorMembershipKV(ref p) => {
+                        let sp =
+                            AccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.verify_full_proof_contribution(&challenge, p, &s.secret_key)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorNonMembershipKV(s) => match proof {
+                    StatementProof::AccumulatorNonMembershipKV(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = AccumulatorNonMembershipKVSubProtocol::new(
+                            s_idx,
+                            params,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(&challenge, p)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorNonMembershipKVFullVerifier(s) => match proof {
+                    StatementProof::AccumulatorNonMembershipKV(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = AccumulatorNonMembershipKVSubProtocol::new(
+                            s_idx,
+                            params,
+                            s.accumulator_value,
+                        );
+                        sp.verify_full_proof_contribution(&challenge, p, &s.secret_key)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorBatchMembership(s) => match proof {
+                    StatementProof::AccumulatorBatchMembership(ref p) => {
+                        let sp =
+                            AccumulatorBatchMembershipSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            derived_accum_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::AccumulatorBatchNonMembership(s) => match proof {
+                    StatementProof::AccumulatorBatchNonMembership(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = AccumulatorBatchNonMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            derived_accum_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
         }
@@ -1,11 +1,12 @@
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{cmp, collections::BTreeMap, fmt::Debug, string::String, vec::Vec};
+use ark_std::{cmp, collections::BTreeMap, fmt::Debug, format, string::String, vec::Vec};
 use bbs_plus::{
     signature::SignatureG1 as BBSSignatureG1, signature_23::Signature23G1 as BBSSignature23G1,
 };
 use coconut_crypto::Signature;
 use dock_crypto_utils::serde_utils::*;
+use legogroth16::circom::SymbolTable;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Same};
 use vb_accumulator::witness::{MembershipWitness, NonMembershipWitness};
@@ -35,6 +36,10 @@ pub enum Witness<E: Pairing> {
     BoundCheckSmc(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
     BoundCheckSmcWithKV(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
     PublicInequality(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Members, each with its witness, whose membership is proven together
+    AccumulatorBatchMembership(Vec<Membership<E>>),
+    /// Non-members, each with its witness, whose non-membership is proven together
+    AccumulatorBatchNonMembership(Vec<NonMembership<E>>),
 }
 
 macro_rules! delegate {
@@ -53,7 +58,9 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                PublicInequality
+                PublicInequality,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
     }}
@@ -75,7 +82,9 @@ macro_rules! delegate_reverse {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                PublicInequality
+                PublicInequality,
+                AccumulatorBatchMembership,
+                AccumulatorBatchNonMembership
             : $($tt)+
         }
 
@@ -301,6 +310,19 @@ impl<E: Pairing> Membership<E> {
     ) -> Witness<E> {
         Witness::AccumulatorMembership(Membership { element, witness })
     }
+
+    /// Create a `Witness` variant for proving membership of several members in the same accumulator. The member at
+    /// index `i` has witness index `i` in the statement.
+    pub fn new_batch_as_witness(
+        members: Vec<(E::ScalarField, MembershipWitness<E::G1Affine>)>,
+    ) -> Witness<E> {
+        Witness::AccumulatorBatchMembership(
+            members
+                .into_iter()
+                .map(|(element, witness)| Membership { element, witness })
+                .collect(),
+        )
+    }
 }
 
 impl<E: Pairing> NonMembership<E> {
@@ -311,6 +333,19 @@ impl<E: Pairing> NonMembership<E> {
     ) -> Witness<E> {
         Witness::AccumulatorNonMembership(NonMembership { element, witness })
     }
+
+    /// Create a `Witness` variant for proving non-membership of several non-members in the same accumulator. The
+    /// non-member at index `i` has witness index `i` in the statement.
+    pub fn new_batch_as_witness(
+        non_members: Vec<(E::ScalarField, NonMembershipWitness<E::G1Affine>)>,
+    ) -> Witness<E> {
+        Witness::AccumulatorBatchNonMembership(
+            non_members
+                .into_iter()
+                .map(|(element, witness)| NonMembership { element, witness })
+                .collect(),
+        )
+    }
 }
 
 impl<E: Pairing> R1CSCircomWitness<E> {
@@ -341,6 +376,22 @@ impl<E: Pairing> R1CSCircomWitness<E> {
         self.inputs.insert(name, value);
     }
 
+    /// Reorder the private input signals as they are laid out in the compiled circuit using the symbol table
+    /// parsed from the .sym file. This removes the need to call `Self::set_private` in the order of declaration.
+    /// Input names are as passed to `Self::set_private`, i.e. without the `main.` prefix.
+    pub fn order_private_inputs_using_symbols(
+        &mut self,
+        symbols: &SymbolTable,
+    ) -> Result<(), ProofSystemError> {
+        let mut wires = BTreeMap::new();
+        for name in self.private.iter() {
+            let w = symbols.wire_indices(&format!("main.{}", name))?;
+            wires.insert(name.clone(), w.into_iter().min().unwrap_or(0));
+        }
+        self.private.sort_by_key(|n| wires[n]);
+        Ok(())
+    }
+
     /// Get the 1st `n` private inputs to the circuit. The order is determined by the order in which
     /// `Self::set_private` was called.
     pub fn get_first_n_private_inputs(
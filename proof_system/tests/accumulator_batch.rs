@@ -0,0 +1,371 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use proof_system::{
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSystemError, VerifierConfig, Witness, WitnessRef,
+        Witnesses,
+    },
+    proof_spec::ProofSpec,
+    setup_params::SetupParams,
+    statement::{
+        accumulator_batch::{
+            AccumulatorBatchMembership as AccumulatorBatchMembershipStmt,
+            AccumulatorBatchNonMembership as AccumulatorBatchNonMembershipStmt,
+        },
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        Statements,
+    },
+    statement_proof::StatementProof,
+    witness::{
+        Membership as MembershipWit, NonMembership as NonMembershipWit,
+        PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+    },
+};
+use test_utils::{accumulators::*, bbs::*, test_serialization, ProofG1};
+use vb_accumulator::prelude::Accumulator;
+
+#[test]
+fn pok_of_bbs_plus_sig_and_batch_accumulator_membership() {
+    // Prove knowledge of BBS+ signature and that 3 of the messages are members of an accumulator and 2 of the
+    // messages are non-members of another accumulator
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 8;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (pos_params, pos_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let members = vec![msgs[1], msgs[3], msgs[4]];
+    pos_accumulator = pos_accumulator
+        .add_batch(members.clone(), &pos_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wits = pos_accumulator
+        .get_membership_witnesses_for_batch(&members, &pos_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let (uni_params, uni_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_members = vec![msgs[5], msgs[7]];
+    uni_accumulator = uni_accumulator
+        .add(
+            msgs[6],
+            &uni_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let non_mem_wits = uni_accumulator
+        .get_non_membership_witnesses_for_batch(
+            &non_members,
+            &uni_keypair.secret_key,
+            &uni_state,
+            &uni_params,
+        )
+        .unwrap();
+
+    let all_setup_params = vec![
+        SetupParams::VbAccumulatorParams(uni_params.clone()),
+        SetupParams::VbAccumulatorPublicKey(uni_keypair.public_key.clone()),
+    ];
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(AccumulatorBatchMembershipStmt::new_statement_from_params(
+        pos_params.clone(),
+        pos_keypair.public_key.clone(),
+        *pos_accumulator.value(),
+        members.len(),
+    ));
+    statements.add(
+        AccumulatorBatchNonMembershipStmt::new_statement_from_params_ref(
+            0,
+            1,
+            *uni_accumulator.value(),
+            non_members.len(),
+        ),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+    let mut meta_statements = MetaStatements::new();
+    for (i, j) in [(1, 0), (3, 1), (4, 2)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, i), (1, j)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    for (i, j) in [(5, 0), (7, 1)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, i), (2, j)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+
+    let proof_spec = ProofSpec::new(
+        statements.clone(),
+        meta_statements.clone(),
+        all_setup_params.clone(),
+        None,
+    );
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_batch_as_witness(
+        members
+            .iter()
+            .cloned()
+            .zip(mem_wits.iter().cloned())
+            .collect(),
+    ));
+    witnesses.add(NonMembershipWit::new_batch_as_witness(
+        non_members
+            .iter()
+            .cloned()
+            .zip(non_mem_wits.iter().cloned())
+            .collect(),
+    ));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+            },
+        )
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+            },
+        )
+        .unwrap();
+
+    // Witness equality referring to a member not in the batch fails
+    let mut wrong_meta_statements = meta_statements.clone();
+    wrong_meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (1, 3)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let wrong_proof_spec = ProofSpec::new(
+        statements.clone(),
+        wrong_meta_statements,
+        all_setup_params.clone(),
+        None,
+    );
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            wrong_proof_spec,
+            witnesses.clone(),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::AccumulatorBatchWitnessRefOutOfBounds(
+            1, 3
+        ))
+    ));
+
+    // A proof for fewer elements than the statement is rejected by the verifier
+    for s_idx in [1, 2] {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(proof_spec.statements.0[s_idx].clone());
+        let single_proof_spec = ProofSpec::new(
+            statements,
+            MetaStatements::new(),
+            all_setup_params.clone(),
+            None,
+        );
+        let mut proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            single_proof_spec.clone(),
+            Witnesses(vec![witnesses.0[s_idx].clone()]),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                single_proof_spec.clone(),
+                None,
+                Default::default(),
+            )
+            .unwrap();
+        let expected = match &mut proof.statement_proofs[0] {
+            StatementProof::AccumulatorBatchMembership(p) => {
+                p.0.pop();
+                members.len()
+            }
+            StatementProof::AccumulatorBatchNonMembership(p) => {
+                p.0.pop();
+                non_members.len()
+            }
+            _ => panic!("Expected a batch proof"),
+        };
+        assert!(matches!(
+            proof.verify::<StdRng, Blake2b512>(
+                &mut rng,
+                single_proof_spec,
+                None,
+                Default::default()
+            ),
+            Err(ProofSystemError::AccumulatorBatchSizeMismatch(0, e, g)) if e == expected && g == expected - 1
+        ));
+    }
+
+    // A witness with a different number of elements than the statement is rejected by the prover
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorBatchMembershipStmt::new_statement_from_params(
+        pos_params.clone(),
+        pos_keypair.public_key.clone(),
+        *pos_accumulator.value(),
+        members.len() - 1,
+    ));
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(statements, MetaStatements::new(), vec![], None),
+            Witnesses(vec![witnesses.0[1].clone()]),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::AccumulatorBatchSizeMismatch(0, 2, 3))
+    ));
+
+    // A statement for no elements is invalid
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorBatchMembershipStmt::new_statement_from_params(
+        pos_params.clone(),
+        pos_keypair.public_key.clone(),
+        *pos_accumulator.value(),
+        0,
+    ));
+    assert!(matches!(
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None).validate(),
+        Err(ProofSystemError::AccumulatorBatchEmpty(0))
+    ));
+
+    // Proof fails once a member is removed and a non-member is added
+    pos_accumulator = pos_accumulator
+        .remove(&members[1], &pos_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    uni_accumulator = uni_accumulator
+        .add(
+            non_members[0],
+            &uni_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    for s_idx in [1, 2] {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        let mut witnesses = Witnesses::new();
+        if s_idx == 1 {
+            statements.add(AccumulatorBatchMembershipStmt::new_statement_from_params(
+                pos_params.clone(),
+                pos_keypair.public_key.clone(),
+                *pos_accumulator.value(),
+                members.len(),
+            ));
+            witnesses.add(MembershipWit::new_batch_as_witness(
+                members
+                    .iter()
+                    .cloned()
+                    .zip(mem_wits.iter().cloned())
+                    .collect(),
+            ));
+        } else {
+            statements.add(
+                AccumulatorBatchNonMembershipStmt::new_statement_from_params(
+                    uni_params.clone(),
+                    uni_keypair.public_key.clone(),
+                    *uni_accumulator.value(),
+                    non_members.len(),
+                ),
+            );
+            witnesses.add(NonMembershipWit::new_batch_as_witness(
+                non_members
+                    .iter()
+                    .cloned()
+                    .zip(non_mem_wits.iter().cloned())
+                    .collect(),
+            ));
+        }
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        assert!(proof
+            .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default())
+            .is_err());
+    }
+
+    // Witness of the wrong type is rejected
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorBatchMembershipStmt::new_statement_from_params(
+        pos_params,
+        pos_keypair.public_key.clone(),
+        *pos_accumulator.value(),
+        members.len(),
+    ));
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::AccumulatorMembership(MembershipWit {
+        element: members[0],
+        witness: mem_wits[0].clone(),
+    }));
+    assert!(ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .is_err());
+}
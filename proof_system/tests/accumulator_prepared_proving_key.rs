@@ -0,0 +1,241 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use std::time::Instant;
+use vb_accumulator::prelude::{
+    Accumulator, MembershipProvingKey, NonMembershipProvingKey, PreparedMembershipProvingKey,
+    PreparedNonMembershipProvingKey,
+};
+
+use proof_system::{
+    prelude::{EqualWitnesses, MetaStatements, VerifierConfig, WitnessRef, Witnesses},
+    proof_spec::ProofSpec,
+    setup_params::SetupParams,
+    statement::{
+        accumulator::{
+            AccumulatorMembership as AccumulatorMembershipStmt,
+            AccumulatorNonMembership as AccumulatorNonMembershipStmt,
+        },
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        Statements,
+    },
+    witness::{
+        Membership as MembershipWit, NonMembership as NonMembershipWit,
+        PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+    },
+};
+use test_utils::{accumulators::*, bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_accumulator_membership_with_prepared_proving_keys() {
+    // Prove knowledge of BBS+ signature and that 3 of the messages are members or non-members of accumulators
+    // where the setup params contain the prepared proving keys
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (pos_params, pos_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let pos_member = msgs[1];
+    pos_accumulator = pos_accumulator
+        .add(pos_member, &pos_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let pos_mem_wit = pos_accumulator
+        .get_membership_witness(&pos_member, &pos_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let (uni_params, uni_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    let uni_member = msgs[3];
+    uni_accumulator = uni_accumulator
+        .add(
+            uni_member,
+            &uni_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let uni_mem_wit = uni_accumulator
+        .get_membership_witness(&uni_member, &uni_keypair.secret_key, &uni_state)
+        .unwrap();
+    let non_member = msgs[4];
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_keypair.secret_key,
+            &uni_state,
+            &uni_params,
+        )
+        .unwrap();
+
+    let prepared_non_mem_prk = PreparedNonMembershipProvingKey::new(non_mem_prk, &uni_params);
+    let all_setup_params = vec![
+        SetupParams::VbAccumulatorParams(pos_params),
+        SetupParams::VbAccumulatorPublicKey(pos_keypair.public_key.clone()),
+        SetupParams::VbAccumulatorPreparedMemProvingKey(PreparedMembershipProvingKey::from(
+            mem_prk,
+        )),
+        SetupParams::VbAccumulatorParams(uni_params),
+        SetupParams::VbAccumulatorPublicKey(uni_keypair.public_key.clone()),
+        SetupParams::VbAccumulatorPreparedMemProvingKey(
+            prepared_non_mem_prk.derive_membership_proving_key(),
+        ),
+        SetupParams::VbAccumulatorPreparedNonMemProvingKey(prepared_non_mem_prk),
+    ];
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params_ref(
+        0,
+        1,
+        2,
+        *pos_accumulator.value(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params_ref(
+        3,
+        4,
+        5,
+        *uni_accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params_ref(
+        3,
+        4,
+        6,
+        *uni_accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    for (msg_idx, st_idx) in [(1, 1), (3, 2), (4, 3)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (st_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, all_setup_params, None);
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(pos_member, pos_mem_wit));
+    witnesses.add(MembershipWit::new_as_witness(uni_member, uni_mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let start = Instant::now();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    println!(
+        "Time to create proof with a BBS+ signature and 3 accumulator membership and non-membership checks using prepared proving keys: {:?}",
+        start.elapsed()
+    );
+    test_serialization!(ProofG1, proof);
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .unwrap();
+    println!(
+        "Time to verify proof with a BBS+ signature and 3 accumulator membership and non-membership checks using prepared proving keys: {:?}",
+        start.elapsed()
+    );
+
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn deserialized_prepared_proving_key_has_tables_for_its_key() {
+    // Only the proving key is serialized so bytes of a prepared key from an untrusted party can't carry
+    // tables for another key
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk_1 = MembershipProvingKey::<G1Affine>::generate_using_rng(&mut rng);
+    let prk_2 = MembershipProvingKey::<G1Affine>::generate_using_rng(&mut rng);
+
+    // Bytes of `prk_2` followed by whatever was in the prepared key of `prk_1` after its proving key
+    let mut bytes = vec![];
+    prk_2.serialize_compressed(&mut bytes).unwrap();
+    let mut prepared_bytes = vec![];
+    PreparedMembershipProvingKey::from(prk_1.clone())
+        .serialize_compressed(&mut prepared_bytes)
+        .unwrap();
+    assert_eq!(prepared_bytes.len(), prk_1.compressed_size());
+    let prepared_prk =
+        PreparedMembershipProvingKey::<G1Affine>::deserialize_compressed(&bytes[..]).unwrap();
+    assert_eq!(prepared_prk.proving_key(), &prk_2);
+    assert_eq!(prepared_prk, PreparedMembershipProvingKey::from(prk_2));
+
+    // Proof created and verified with the deserialized prepared key
+    let member = Fr::rand(&mut rng);
+    accumulator = accumulator
+        .add(member, &keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &keypair.secret_key, &state)
+        .unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params_ref(
+        0,
+        1,
+        2,
+        *accumulator.value(),
+    ));
+    let all_setup_params = vec![
+        SetupParams::VbAccumulatorParams(params),
+        SetupParams::VbAccumulatorPublicKey(keypair.public_key.clone()),
+        SetupParams::VbAccumulatorPreparedMemProvingKey(prepared_prk),
+    ];
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), all_setup_params, None);
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default())
+        .unwrap();
+}
@@ -0,0 +1,282 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use vb_accumulator::prelude::Accumulator;
+
+use proof_system::{
+    prelude::{EqualWitnesses, MetaStatements, Witness, WitnessRef, Witnesses},
+    proof_spec::ProofSpec,
+    setup_params::SetupParams,
+    statement::{
+        accumulator_with_kv::{
+            AccumulatorMembershipKV as AccumulatorMembershipKVStmt,
+            AccumulatorMembershipKVFullVerifier as AccumulatorMembershipKVFullVerifierStmt,
+            AccumulatorNonMembershipKV as AccumulatorNonMembershipKVStmt,
+            AccumulatorNonMembershipKVFullVerifier as AccumulatorNonMembershipKVFullVerifierStmt,
+        },
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        Statements,
+    },
+    witness::{
+        Membership as MembershipWit, NonMembership as NonMembershipWit,
+        PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+    },
+};
+use test_utils::{accumulators::*, bbs::*, test_serialization, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_accumulator_membership_with_keyed_verification() {
+    // Prove knowledge of BBS+ signature and that 2 of the messages are a member and a non-member of accumulators
+    // whose manager is the verifier
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (_, pos_keypair, mut pos_accumulator, mut pos_state) = setup_positive_accum(&mut rng);
+    let mem_member = msgs[2];
+    pos_accumulator = pos_accumulator
+        .add(mem_member, &pos_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&mem_member, &pos_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let (uni_params, uni_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_member = msgs[4];
+    uni_accumulator = uni_accumulator
+        .add(
+            msgs[3],
+            &uni_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_keypair.secret_key,
+            &uni_state,
+            &uni_params,
+        )
+        .unwrap();
+
+    let all_setup_params = vec![SetupParams::VbAccumulatorParams(uni_params.clone())];
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipKVStmt::new_statement(
+        *pos_accumulator.value(),
+    ));
+    prover_statements.add(
+        AccumulatorNonMembershipKVStmt::new_statement_from_params_ref(0, *uni_accumulator.value()),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, prover_statements);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 4), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        all_setup_params.clone(),
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(mem_member, mem_wit.clone()));
+    witnesses.add(NonMembershipWit::new_as_witness(
+        non_member,
+        non_mem_wit.clone(),
+    ));
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    // Verifier knowing the secret keys
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipKVFullVerifierStmt::new_statement(
+        *pos_accumulator.value(),
+        pos_keypair.secret_key.clone(),
+    ));
+    verifier_statements.add(
+        AccumulatorNonMembershipKVFullVerifierStmt::new_statement_from_params_ref(
+            0,
+            *uni_accumulator.value(),
+            uni_keypair.secret_key.clone(),
+        ),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+    let proof_spec_verifier = ProofSpec::new(
+        verifier_statements.clone(),
+        meta_statements.clone(),
+        all_setup_params.clone(),
+        None,
+    );
+    proof_spec_verifier.validate().unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_verifier.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Verifier not knowing the secret keys verifies the proofs of knowledge and the manager verifies the
+    // delegated proofs
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_prover.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+    let delegated_proofs = proof.get_delegated_proofs();
+    assert_eq!(delegated_proofs.len(), 2);
+    delegated_proofs
+        .get(&1)
+        .unwrap()
+        .verify(&pos_keypair.secret_key)
+        .unwrap();
+    delegated_proofs
+        .get(&2)
+        .unwrap()
+        .verify(&uni_keypair.secret_key)
+        .unwrap();
+    assert!(delegated_proofs
+        .get(&1)
+        .unwrap()
+        .verify(&uni_keypair.secret_key)
+        .is_err());
+
+    // Proof fails once the member is removed and the non-member is added
+    pos_accumulator = pos_accumulator
+        .remove(&mem_member, &pos_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipKVFullVerifierStmt::new_statement(
+        *pos_accumulator.value(),
+        pos_keypair.secret_key.clone(),
+    ));
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let mut prover_statements = verifier_statements.clone();
+    prover_statements.0[1] = AccumulatorMembershipKVStmt::new_statement(*pos_accumulator.value());
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(mem_member, mem_wit));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(verifier_statements, meta_statements, vec![], None),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    uni_accumulator = uni_accumulator
+        .add(
+            non_member,
+            &uni_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(AccumulatorNonMembershipKVStmt::new_statement_from_params(
+        uni_params.clone(),
+        *uni_accumulator.value(),
+    ));
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::AccumulatorNonMembership(NonMembershipWit {
+        element: non_member,
+        witness: non_mem_wit,
+    }));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(
+        AccumulatorNonMembershipKVFullVerifierStmt::new_statement_from_params(
+            uni_params,
+            *uni_accumulator.value(),
+            uni_keypair.secret_key.clone(),
+        ),
+    );
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None),
+            None,
+            Default::default(),
+        )
+        .is_err());
+}
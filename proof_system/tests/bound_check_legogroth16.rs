@@ -1,4 +1,5 @@
 use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_ec::AffineRepr;
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
@@ -9,6 +10,7 @@ use blake2::Blake2b512;
 use std::time::Instant;
 
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
         EqualWitnesses, MetaStatements, ProofSpec, ProverConfig, VerifierConfig, Witness,
         WitnessRef, Witnesses,
@@ -663,3 +665,69 @@ fn pok_of_bbs_plus_sig_and_message_same_as_bound() {
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
         .unwrap();
 }
+
+#[test]
+fn proof_spec_with_malformed_legogroth16_keys() {
+    // Keys received from an untrusted party are rejected by `ProofSpec::validate`
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let min = 100;
+    let max = 200;
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let prover_spec = |pk| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(BoundCheckProverStmt::new_statement_from_params(min, max, pk).unwrap());
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    let verifier_spec = |vk| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(BoundCheckVerifierStmt::new_statement_from_params(min, max, vk).unwrap());
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    prover_spec(snark_pk.clone()).validate().unwrap();
+    verifier_spec(snark_pk.vk.clone()).validate().unwrap();
+
+    // delta in the proving key differs from the one in the verifying key
+    let mut pk = snark_pk.clone();
+    pk.common.delta_g1 = pk.common.beta_g1;
+    assert!(matches!(
+        prover_spec(pk).validate(),
+        Err(ProofSystemError::LegoGroth16Error(_))
+    ));
+
+    // eta in the proving key differs from the one in the verifying key
+    let mut pk = snark_pk.clone();
+    pk.common.eta_delta_inv_g1 = pk.common.delta_g1;
+    assert!(matches!(
+        prover_spec(pk).validate(),
+        Err(ProofSystemError::LegoGroth16Error(_))
+    ));
+
+    // Identity in the verifying key
+    let mut pk = snark_pk.clone();
+    pk.vk.gamma_abc_g1[1] = G1Affine::zero();
+    assert!(matches!(
+        prover_spec(pk.clone()).validate(),
+        Err(ProofSystemError::LegoGroth16Error(_))
+    ));
+    assert!(matches!(
+        verifier_spec(pk.vk).validate(),
+        Err(ProofSystemError::LegoGroth16Error(_))
+    ));
+
+    // gamma and delta are equal so anyone can forge proofs
+    let mut vk = snark_pk.vk.clone();
+    vk.delta_g2 = vk.gamma_g2;
+    assert!(matches!(
+        verifier_spec(vk).validate(),
+        Err(ProofSystemError::LegoGroth16Error(_))
+    ));
+
+    // Well-formed key but not for the bound check circuit
+    let mut vk = snark_pk.vk.clone();
+    vk.gamma_abc_g1.push(vk.gamma_abc_g1[0]);
+    assert!(matches!(
+        verifier_spec(vk).validate(),
+        Err(ProofSystemError::BoundCheckLegoGroth16IncompatibleVerifyingKey(0))
+    ));
+}
@@ -11,6 +11,7 @@ pub mod mimc_hash;
 pub mod multiple_circuits_in_single_proof;
 pub mod proof_aggregation;
 pub mod set_membership;
+pub mod signal_names;
 pub mod single_circuit_in_a_proof;
 
 /// Given path relative to this crate, return absolute disk path
@@ -0,0 +1,151 @@
+use ark_bls12_381::Bls12_381;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use blake2::Blake2b512;
+use legogroth16::circom::SymbolTable;
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, R1CSCircomWitness, Statements, Witness,
+        Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        r1cs_legogroth16::{
+            R1CSCircomProver as R1CSProverStmt, R1CSCircomVerifier as R1CSVerifierStmt,
+        },
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+use std::collections::BTreeMap;
+
+use crate::r1cs::{abs_path, get_r1cs_and_wasm_bytes};
+use test_utils::{bbs::*, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_multiplication_check_using_signal_names() {
+    // Prove knowledge of a BBS+ signature and product of 2 messages being equal to a public value
+    // where the circuit signals are referred to by their names rather than their positions.
+
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let msgs: Vec<Fr> = (0..msg_count)
+        .map(|i| Fr::from((100 + i) * 10_u64))
+        .collect();
+
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let msg_1_idx = 1;
+    let msg_2_idx = 3;
+    let product = msgs[msg_1_idx] * msgs[msg_2_idx];
+
+    let commit_witness_count = 2;
+    // Circom code for following in tests/r1cs/circom/circuits/multiply2.circom
+    let (snark_pk, r1cs, wasm_bytes) = get_r1cs_and_wasm_bytes(
+        "tests/r1cs/circom/bls12-381/multiply2.r1cs",
+        "tests/r1cs/circom/bls12-381/multiply2.wasm",
+        commit_witness_count,
+        &mut rng,
+    );
+    let symbols =
+        SymbolTable::new_from_file(abs_path("tests/r1cs/circom/bls12-381/multiply2.sym")).unwrap();
+
+    let a_ref =
+        R1CSProverStmt::witness_ref_for_signal(1, &r1cs, &symbols, commit_witness_count, "main.a")
+            .unwrap();
+    let b_ref =
+        R1CSProverStmt::witness_ref_for_signal(1, &r1cs, &symbols, commit_witness_count, "main.b")
+            .unwrap();
+    assert_eq!(a_ref, (1, 0));
+    assert_eq!(b_ref, (1, 1));
+
+    // Signal `b` is committed only when at least 2 witnesses are committed
+    assert!(matches!(
+        R1CSProverStmt::witness_ref_for_signal(1, &r1cs, &symbols, 1, "main.b"),
+        Err(ProofSystemError::R1CSSignalNotCommitted(_, 1, 1))
+    ));
+    // Output is not a private signal
+    assert!(R1CSProverStmt::witness_ref_for_signal(
+        1,
+        &r1cs,
+        &symbols,
+        commit_witness_count,
+        "main.c"
+    )
+    .is_err());
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        R1CSProverStmt::new_statement_from_params(r1cs.clone(), wasm_bytes, snark_pk.clone())
+            .unwrap(),
+    );
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_1_idx), a_ref].into_iter().collect(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_2_idx), b_ref].into_iter().collect(),
+    ));
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    // Private inputs are set in an order different from their declaration and then reordered
+    let mut r1cs_wit = R1CSCircomWitness::<Bls12_381>::new();
+    r1cs_wit.set_private("b".to_string(), vec![msgs[msg_2_idx]]);
+    r1cs_wit.set_private("a".to_string(), vec![msgs[msg_1_idx]]);
+    r1cs_wit
+        .order_private_inputs_using_symbols(&symbols)
+        .unwrap();
+    assert_eq!(r1cs_wit.private, vec!["a".to_string(), "b".to_string()]);
+    witnesses.add(Witness::R1CSLegoGroth16(r1cs_wit));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover.clone(),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // Verifier places the public value as per the position of the output signal
+    let mut public_inputs = vec![Fr::from(0u64); r1cs.num_public as usize - 1];
+    public_inputs[symbols.public_input_index(&r1cs, "main.c").unwrap()] = product;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        R1CSVerifierStmt::new_statement_from_params(public_inputs, snark_pk.vk.clone()).unwrap(),
+    );
+    let verifier_proof_spec = ProofSpec::new(
+        verifier_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    verifier_proof_spec.validate().unwrap();
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
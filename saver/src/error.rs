@@ -1,4 +1,5 @@
 use ark_relations::r1cs::SynthesisError;
+use ark_std::string::String;
 use legogroth16::error::Error as LegoGroth16Error;
 
 #[derive(Clone, Debug)]
@@ -19,6 +20,8 @@ pub enum SaverError {
     CouldNotFindDiscreteLog,
     InvalidPairingPowers,
     PairingCheckFailed,
+    InvalidVerifyingKey(String),
+    InvalidProvingKey(String),
 }
 
 impl From<SynthesisError> for SaverError {
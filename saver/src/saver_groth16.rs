@@ -1,9 +1,10 @@
 //! Using SAVER with Groth16
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group, VariableBaseMSM};
-use ark_ff::{Field, PrimeField};
+use ark_ff::{Field, PrimeField, Zero};
 use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
 use ark_std::{
+    format,
     ops::{AddAssign, Mul},
     rand::{Rng, RngCore},
     string::ToString,
@@ -26,7 +27,7 @@ use dock_crypto_utils::{
     randomized_pairing_check::RandomizedPairingChecker,
 };
 
-use crate::error::SaverError;
+use crate::{error::SaverError, utils::chunks_count};
 use dock_crypto_utils::{serde_utils::*, transcript::Transcript};
 
 use crate::{keygen::EncryptionKey, setup::EncryptionGens};
@@ -44,6 +45,137 @@ pub struct ProvingKey<E: Pairing> {
     pub gamma_g1: E::G1Affine,
 }
 
+impl<E: Pairing> ProvingKey<E> {
+    /// Check that the proving key is well-formed and can be used for SAVER with chunks of
+    /// `chunk_bit_size` bits. This validates the verifying key and checks that `beta`, `gamma` and `delta`
+    /// are consistent between the proving and verifying key. It does not check the queries, use
+    /// [`Self::validate_queries`] for that.
+    pub fn validate(&self, chunk_bit_size: u8) -> crate::Result<()> {
+        validate_verifying_key(&self.pk.vk, chunk_bit_size)
+            .map_err(|e| SaverError::InvalidProvingKey(format!("{:?}", e)))?;
+        for (name, g) in [
+            ("beta_g1", &self.pk.beta_g1),
+            ("delta_g1", &self.pk.delta_g1),
+            ("gamma_g1", &self.gamma_g1),
+        ] {
+            g.check()
+                .map_err(|e| SaverError::InvalidProvingKey(format!("{}: {:?}", name, e)))?;
+            if g.is_zero() {
+                return Err(SaverError::InvalidProvingKey(format!(
+                    "{} is identity",
+                    name
+                )));
+            }
+        }
+        if self.pk.b_g1_query.len() != self.pk.b_g2_query.len() {
+            return Err(SaverError::InvalidProvingKey(format!(
+                "b_g1_query has {} elements but b_g2_query has {}",
+                self.pk.b_g1_query.len(),
+                self.pk.b_g2_query.len()
+            )));
+        }
+        // e(beta_g1, delta_g2) == e(delta_g1, beta_g2)
+        if E::pairing(self.pk.beta_g1, self.pk.vk.delta_g2)
+            != E::pairing(self.pk.delta_g1, self.pk.vk.beta_g2)
+        {
+            return Err(SaverError::InvalidProvingKey(
+                "beta or delta inconsistent with verifying key".to_string(),
+            ));
+        }
+        // e(-gamma * G, delta * H) * e(delta * G, gamma * H) == 1
+        if !E::multi_pairing(
+            [self.gamma_g1, self.pk.delta_g1],
+            [self.pk.vk.delta_g2, self.pk.vk.gamma_g2],
+        )
+        .is_zero()
+        {
+            return Err(SaverError::InvalidProvingKey(
+                "gamma inconsistent with verifying key".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Does the checks of [`Self::validate`] and additionally checks that all elements of the queries are
+    /// valid and that the queries `b_g1_query` and `b_g2_query` have the same discrete logs.
+    pub fn validate_queries<R: RngCore>(
+        &self,
+        chunk_bit_size: u8,
+        rng: &mut R,
+    ) -> crate::Result<()> {
+        self.validate(chunk_bit_size)?;
+        self.pk
+            .check()
+            .map_err(|e| SaverError::InvalidProvingKey(format!("{:?}", e)))?;
+        // e(\sum_i r_i * b_g1_query_i, delta * H) == e(delta * G, \sum_i r_i * b_g2_query_i)
+        let r = (0..self.pk.b_g1_query.len())
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let b_g1 = E::G1::msm_unchecked(&self.pk.b_g1_query, &r);
+        let b_g2 = E::G2::msm_unchecked(&self.pk.b_g2_query, &r);
+        if E::pairing(b_g1.into_affine(), self.pk.vk.delta_g2)
+            != E::pairing(self.pk.delta_g1, b_g2.into_affine())
+        {
+            return Err(SaverError::InvalidProvingKey(
+                "b_g1_query and b_g2_query are inconsistent".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Check that a Groth16 verifying key is well-formed. This should be called on a key received from
+/// an untrusted party before using it. Checks that all elements are valid and in the correct subgroup,
+/// none of them is the identity and that `gamma` and `delta` are different.
+pub fn validate_groth16_verifying_key<E: Pairing>(vk: &VerifyingKey<E>) -> crate::Result<()> {
+    vk.check()
+        .map_err(|e| SaverError::InvalidVerifyingKey(format!("{:?}", e)))?;
+    if vk.alpha_g1.is_zero()
+        || vk.beta_g2.is_zero()
+        || vk.gamma_g2.is_zero()
+        || vk.delta_g2.is_zero()
+    {
+        return Err(SaverError::InvalidVerifyingKey(
+            "Identity element in verifying key".to_string(),
+        ));
+    }
+    if vk.gamma_g2 == vk.delta_g2 {
+        return Err(SaverError::InvalidVerifyingKey(
+            "gamma and delta are equal".to_string(),
+        ));
+    }
+    if vk.gamma_abc_g1.is_empty() {
+        return Err(SaverError::InvalidVerifyingKey(
+            "gamma_abc_g1 is empty".to_string(),
+        ));
+    }
+    if let Some(i) = vk.gamma_abc_g1.iter().position(|g| g.is_zero()) {
+        return Err(SaverError::InvalidVerifyingKey(format!(
+            "Identity element at index {} in gamma_abc_g1",
+            i
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the verifying key is well-formed as per [`validate_groth16_verifying_key`] and has one
+/// public input for each chunk when the chunks are of `chunk_bit_size` bits.
+pub fn validate_verifying_key<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    chunk_bit_size: u8,
+) -> crate::Result<()> {
+    validate_groth16_verifying_key(vk)?;
+    let expected = chunks_count::<E::ScalarField>(chunk_bit_size) as usize + 1;
+    if vk.gamma_abc_g1.len() != expected {
+        return Err(SaverError::InvalidVerifyingKey(format!(
+            "Expected {} elements in gamma_abc_g1 but found {}",
+            expected,
+            vk.gamma_abc_g1.len()
+        )));
+    }
+    Ok(())
+}
+
 /// These parameters are needed for setting up keys for encryption/decryption
 pub fn get_gs_for_encryption<E: Pairing>(vk: &VerifyingKey<E>) -> &[E::G1Affine] {
     &vk.gamma_abc_g1[1..]
@@ -280,7 +412,6 @@ mod tests {
         encryption::{tests::gen_messages, Encryption},
         keygen::keygen,
         setup::setup_for_groth16,
-        utils::chunks_count,
     };
     use ark_bls12_381::Bls12_381;
     use ark_std::rand::{prelude::StdRng, SeedableRng};
@@ -303,6 +434,30 @@ mod tests {
             let circuit = BitsizeCheckCircuit::new(chunk_bit_size, Some(n), None, true);
             let snark_srs = generate_srs::<Bls12_381, _, _>(circuit, &gens, &mut rng).unwrap();
 
+            snark_srs.validate(chunk_bit_size).unwrap();
+            snark_srs
+                .validate_queries(chunk_bit_size, &mut rng)
+                .unwrap();
+            validate_verifying_key(&snark_srs.pk.vk, chunk_bit_size).unwrap();
+            // Key for a different chunk size
+            let other_chunk_bit_size = if chunk_bit_size == 8 { 16 } else { 8 };
+            assert!(validate_verifying_key(&snark_srs.pk.vk, other_chunk_bit_size).is_err());
+            assert!(snark_srs.validate(other_chunk_bit_size).is_err());
+            // Wrong gamma_g1
+            let mut bad_srs = snark_srs.clone();
+            bad_srs.gamma_g1 = (-bad_srs.gamma_g1.into_group()).into_affine();
+            assert!(matches!(
+                bad_srs.validate(chunk_bit_size),
+                Err(SaverError::InvalidProvingKey(_))
+            ));
+            // gamma equal to delta
+            let mut bad_srs = snark_srs.clone();
+            bad_srs.pk.vk.gamma_g2 = bad_srs.pk.vk.delta_g2;
+            assert!(matches!(
+                validate_groth16_verifying_key(&bad_srs.pk.vk),
+                Err(SaverError::InvalidVerifyingKey(_))
+            ));
+
             println!(
                 "For chunk_bit_size {}, Snark SRS has compressed size {} and uncompressed size {}",
                 chunk_bit_size,
@@ -11,8 +11,8 @@ use ark_std::{
     UniformRand,
 };
 use legogroth16::{
-    create_random_proof, generate_parameters_with_qap, verify_qap_proof, LibsnarkReduction,
-    PreparedVerifyingKey, Proof, VerifyingKey,
+    create_random_proof, generate_parameters_with_qap, mpc::MPCParameters, verify_qap_proof,
+    LibsnarkReduction, PreparedVerifyingKey, Proof, VerifyingKey,
 };
 
 use crate::{keygen::EncryptionKey, setup::EncryptionGens};
@@ -25,6 +25,18 @@ pub struct ProvingKey<E: Pairing> {
     pub gamma_g1: E::G1Affine,
 }
 
+/// Get the SNARK SRS from the output of a phase-2 ceremony such that no one knows the secrets. The
+/// ceremony uses the standard generators of the groups so the `EncryptionGens` must have those as `G`
+/// and `H` respectively.
+impl<E: Pairing> From<MPCParameters<E>> for ProvingKey<E> {
+    fn from(params: MPCParameters<E>) -> Self {
+        Self {
+            gamma_g1: (-params.gamma_g1.into_group()).into_affine(),
+            pk: params.params,
+        }
+    }
+}
+
 /// These parameters are needed for setting up keys for encryption/decryption
 pub fn get_gs_for_encryption<E: Pairing>(vk: &VerifyingKey<E>) -> &[E::G1Affine] {
     &vk.gamma_abc_g1[1..]
@@ -197,7 +209,9 @@ mod tests {
     use ark_bls12_381::Bls12_381;
     use ark_ff::Zero;
     use ark_std::rand::{prelude::StdRng, SeedableRng};
-    use legogroth16::{prepare_verifying_key, prover::verify_witness_commitment};
+    use legogroth16::{
+        prepare_verifying_key, prover::verify_witness_commitment, ptau::setup_fake_powers_of_tau,
+    };
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
 
@@ -330,4 +344,73 @@ mod tests {
         check(8);
         check(16);
     }
+
+    #[test]
+    fn snark_srs_from_mpc() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let chunk_bit_size = 16;
+        let n = chunks_count::<Fr>(chunk_bit_size);
+        let gens = EncryptionGens::<Bls12_381> {
+            G: <Bls12_381 as Pairing>::G1Affine::generator(),
+            H: <Bls12_381 as Pairing>::G2Affine::generator(),
+        };
+
+        let powers = setup_fake_powers_of_tau::<Bls12_381, _>(&mut rng, 13);
+        let circuit = BitsizeCheckCircuit::new(chunk_bit_size, Some(n), None, false);
+        let initial = MPCParameters::new(circuit, &powers, n as u32).unwrap();
+        let mut params = initial.clone();
+        params.contribute(&mut rng);
+        initial.verify_contribution(&params, &mut rng).unwrap();
+        let snark_srs = ProvingKey::from(params);
+
+        let msgs = gen_messages(&mut rng, n as u32, chunk_bit_size);
+        let msgs_as_field_elems = msgs.iter().map(|m| Fr::from(*m as u64)).collect::<Vec<_>>();
+        let g_i = get_gs_for_encryption(&snark_srs.pk.vk);
+        let (sk, ek, dk) = keygen(
+            &mut rng,
+            chunk_bit_size,
+            &gens,
+            g_i,
+            &snark_srs.pk.common.delta_g1,
+            &snark_srs.gamma_g1,
+        )
+        .unwrap();
+        let (ct, r) =
+            Encryption::encrypt_decomposed_message(&mut rng, msgs.clone(), &ek, g_i).unwrap();
+        let (m_, _) = Encryption::decrypt_to_chunks(
+            &ct[0],
+            &ct[1..n as usize + 1],
+            &sk,
+            dk,
+            g_i,
+            chunk_bit_size,
+        )
+        .unwrap();
+        assert_eq!(m_, msgs);
+
+        let v = Fr::rand(&mut rng);
+        let circuit = BitsizeCheckCircuit::new(
+            chunk_bit_size,
+            Some(n),
+            Some(msgs_as_field_elems.clone()),
+            false,
+        );
+        let proof = protocol_1::create_proof(circuit, v, &r, &snark_srs, &ek, &mut rng).unwrap();
+        let pvk = prepare_verifying_key::<Bls12_381>(&snark_srs.pk.vk);
+        Encryption::verify_ciphertext_commitment(
+            &ct[0],
+            &ct[1..n as usize + 1],
+            &ct[n as usize + 1],
+            ek,
+            gens,
+        )
+        .unwrap();
+        let ct = Ciphertext {
+            X_r: ct[0],
+            enc_chunks: ct[1..n as usize + 1].to_vec(),
+            commitment: ct[n as usize + 1],
+        };
+        protocol_1::verify_proof(&pvk, &proof, &ct).unwrap();
+        verify_witness_commitment(&pvk.vk, &proof.proof, 0, &msgs_as_field_elems, &v).unwrap();
+    }
 }
@@ -10,7 +10,11 @@ use digest::Digest;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use dock_crypto_utils::{affine_group_element_from_byte_slices, serde_utils::*};
+use dock_crypto_utils::{
+    concat_slices,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
+    serde_utils::*,
+};
 
 /// Create "G" and "H" from the paper.
 #[serde_as]
@@ -38,8 +42,19 @@ pub struct PreparedEncryptionGens<E: Pairing> {
 
 impl<E: Pairing> EncryptionGens<E> {
     pub fn new<D: Digest>(label: &[u8]) -> Self {
-        let G = affine_group_element_from_byte_slices!(label, b" : G");
-        let H = affine_group_element_from_byte_slices!(label, b" : H");
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H>(label: &[u8]) -> Self
+    where
+        H: GroupElemHasher<E::G1Affine> + GroupElemHasher<E::G2Affine>,
+    {
+        let G =
+            <H as GroupElemHasher<E::G1Affine>>::hash_to_affine(&concat_slices!(label, b" : G"));
+        let H =
+            <H as GroupElemHasher<E::G2Affine>>::hash_to_affine(&concat_slices!(label, b" : H"));
         Self { G, H }
     }
 
@@ -73,8 +88,14 @@ pub struct ChunkedCommitmentGens<G: AffineRepr> {
 
 impl<G: AffineRepr> ChunkedCommitmentGens<G> {
     pub fn new<D: Digest>(label: &[u8]) -> Self {
-        let G = affine_group_element_from_byte_slices!(label, b" : G");
-        let H = affine_group_element_from_byte_slices!(label, b" : H");
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H: GroupElemHasher<G>>(label: &[u8]) -> Self {
+        let G = H::hash_to_affine(&concat_slices!(label, b" : G"));
+        let H = H::hash_to_affine(&concat_slices!(label, b" : H"));
         Self { G, H }
     }
 
@@ -152,6 +173,32 @@ pub(crate) mod tests {
         )
     }
 
+    #[test]
+    fn gens_with_hash_to_curve() {
+        use dock_crypto_utils::hashing_utils::HashToCurve;
+
+        let label = [1, 2, 3];
+        let enc_gens =
+            EncryptionGens::<Bls12_381>::new_with_hasher::<HashToCurve<Blake2b512>>(&label);
+        assert_eq!(
+            enc_gens,
+            EncryptionGens::<Bls12_381>::new_with_hasher::<HashToCurve<Blake2b512>>(&label)
+        );
+        assert_ne!(
+            enc_gens,
+            EncryptionGens::<Bls12_381>::new::<Blake2b512>(&label)
+        );
+
+        let comm_gens = ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_with_hasher::<
+            HashToCurve<Blake2b512>,
+        >(&label);
+        assert_ne!(comm_gens.G, comm_gens.H);
+        assert_ne!(
+            comm_gens,
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new::<Blake2b512>(&label)
+        );
+    }
+
     #[test]
     fn setup_for_groth16_works() {
         fn check(chunk_bit_size: u8) {
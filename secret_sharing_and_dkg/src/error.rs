@@ -1,7 +1,8 @@
 use crate::common::{ParticipantId, ShareId};
 use schnorr_pok::error::SchnorrError;
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum SSError {
     InvalidThresholdOrTotal(ShareId, ShareId),
     BelowThreshold(ShareId, ShareId),
@@ -17,6 +18,8 @@ pub enum SSError {
     InvalidProofOfSecretKeyKnowledge,
     DoesNotSupportThreshold(ShareId),
     SchnorrError(SchnorrError),
+    /// The dealer's polynomial for refreshing shares doesn't have 0 as the constant term
+    InvalidZeroSharingFrom(ParticipantId),
 }
 
 impl From<SchnorrError> for SSError {
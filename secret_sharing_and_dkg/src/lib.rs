@@ -9,3 +9,4 @@ pub mod gennaro_dkg;
 pub mod pedersen_dvss;
 pub mod pedersen_vss;
 pub mod shamir_ss;
+pub mod share_refresh;
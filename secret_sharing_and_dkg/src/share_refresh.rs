@@ -0,0 +1,342 @@
+//! Proactive refresh of shares based on the paper [Proactive Secret Sharing Or: How to Cope With Perpetual Leakage](https://link.springer.com/chapter/10.1007/3-540-44750-4_27)
+//!
+//! To refresh the shares of a secret without changing the secret, each participant deals a sharing of 0 using Feldman's
+//! verifiable secret sharing and sends each participant its share. Each participant adds the received shares to its
+//! current share to get the new share. Since all dealt secrets are 0, the shared secret and hence the public key
+//! don't change but the old shares can't be combined with the new shares. The commitments to the coefficients of the
+//! dealt polynomials are public and are used by anyone to refresh the public key shares, i.e. `ck * share`, of the
+//! participants. Feldman commitments are used rather than Pedersen so that the public key shares can be refreshed.
+//!
+//! All participants must receive the shares from the same set of dealers, else their new shares will be inconsistent.
+
+use crate::{
+    common::{CommitmentToCoefficients, ParticipantId, Share, ShareId, Shares},
+    error::SSError,
+    feldman_vss,
+};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::RngCore,
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// Deal shares of 0 to `total` participants. Returns the shares and commitments to the coefficients of the polynomial
+pub fn deal_zero_sharing<'a, R: RngCore, G: AffineRepr>(
+    rng: &mut R,
+    threshold: ShareId,
+    total: ShareId,
+    ck: impl Into<&'a G>,
+) -> Result<(Shares<G::ScalarField>, CommitmentToCoefficients<G>), SSError> {
+    let (shares, comms, _) =
+        feldman_vss::deal_secret(rng, G::ScalarField::zero(), threshold, total, ck)?;
+    Ok((shares, comms))
+}
+
+/// Checks that the commitments are of a polynomial with 0 as the constant term and supporting the threshold.
+pub fn verify_zero_sharing_commitments<G: AffineRepr>(
+    dealer_id: ParticipantId,
+    commitment_coeffs: &CommitmentToCoefficients<G>,
+    threshold: ShareId,
+) -> Result<(), SSError> {
+    if commitment_coeffs.0.is_empty() || !commitment_coeffs.supports_threshold(threshold) {
+        return Err(SSError::DoesNotSupportThreshold(threshold));
+    }
+    if !commitment_coeffs.commitment_to_secret().is_zero() {
+        return Err(SSError::InvalidZeroSharingFrom(dealer_id));
+    }
+    Ok(())
+}
+
+/// Used by a participant to store the received shares of 0 and the commitments to coefficients.
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct RefreshAccumulator<G: AffineRepr> {
+    pub participant_id: ParticipantId,
+    pub threshold: ShareId,
+    pub shares: BTreeMap<ParticipantId, Share<G::ScalarField>>,
+    pub coeff_comms: BTreeMap<ParticipantId, CommitmentToCoefficients<G>>,
+}
+
+impl<G: AffineRepr> Zeroize for RefreshAccumulator<G> {
+    fn zeroize(&mut self) {
+        self.shares.values_mut().for_each(|v| v.zeroize())
+    }
+}
+
+impl<G: AffineRepr> RefreshAccumulator<G> {
+    pub fn new(id: ParticipantId, threshold: ShareId) -> Self {
+        Self {
+            participant_id: id,
+            threshold,
+            shares: Default::default(),
+            coeff_comms: Default::default(),
+        }
+    }
+
+    /// Called by a participant when it deals shares of 0 and keeps its own share
+    pub fn add_self_share(
+        &mut self,
+        share: Share<G::ScalarField>,
+        commitment_coeffs: CommitmentToCoefficients<G>,
+    ) {
+        self.shares.insert(self.participant_id, share);
+        self.coeff_comms
+            .insert(self.participant_id, commitment_coeffs);
+    }
+
+    /// Called by a participant when it receives a share of 0 from another participant
+    pub fn add_received_share<'a>(
+        &mut self,
+        sender_id: ParticipantId,
+        share: Share<G::ScalarField>,
+        commitment_coeffs: CommitmentToCoefficients<G>,
+        ck: impl Into<&'a G>,
+    ) -> Result<(), SSError> {
+        if sender_id == self.participant_id {
+            return Err(SSError::SenderIdSameAsReceiver(
+                sender_id,
+                self.participant_id,
+            ));
+        }
+        if self.shares.contains_key(&sender_id) {
+            return Err(SSError::AlreadyProcessedFromSender(sender_id));
+        }
+        if self.participant_id != share.id {
+            return Err(SSError::UnequalParticipantAndShareId(
+                self.participant_id,
+                share.id,
+            ));
+        }
+        if self.threshold != share.threshold {
+            return Err(SSError::UnequalThresholdInReceivedShare(
+                self.threshold,
+                share.threshold,
+            ));
+        }
+        verify_zero_sharing_commitments(sender_id, &commitment_coeffs, self.threshold)?;
+        share.verify(&commitment_coeffs, ck)?;
+        self.shares.insert(sender_id, share);
+        self.coeff_comms.insert(sender_id, commitment_coeffs);
+        Ok(())
+    }
+
+    /// Called by a participant when it has received shares from all dealers. Returns the new share by adding the
+    /// received shares of 0 to the current share. `dealers` are the participants expected to deal, including this
+    /// participant, and shares must have been received from exactly these.
+    pub fn finalize(
+        self,
+        current_share: &Share<G::ScalarField>,
+        dealers: &BTreeSet<ParticipantId>,
+    ) -> Result<Share<G::ScalarField>, SSError> {
+        if self.participant_id != current_share.id {
+            return Err(SSError::UnequalParticipantAndShareId(
+                self.participant_id,
+                current_share.id,
+            ));
+        }
+        if self.threshold != current_share.threshold {
+            return Err(SSError::UnequalThresholdInReceivedShare(
+                self.threshold,
+                current_share.threshold,
+            ));
+        }
+        if !dealers.contains(&self.participant_id) {
+            return Err(SSError::InvalidParticipantId(self.participant_id));
+        }
+        if let Some(id) = dealers.iter().find(|id| !self.shares.contains_key(id)) {
+            return Err(SSError::MissingSomeParticipants(*id));
+        }
+        if let Some(id) = self.shares.keys().find(|id| !dealers.contains(id)) {
+            return Err(SSError::InvalidParticipantId(*id));
+        }
+        let mut share = current_share.share;
+        for s in self.shares.values() {
+            share += s.share;
+        }
+        Ok(Share {
+            id: self.participant_id,
+            threshold: self.threshold,
+            share,
+        })
+    }
+}
+
+/// Refresh the public key shares, i.e. `ck * share` of each participant, using the commitments to coefficients of the
+/// polynomials dealt by each dealer. The new public key share of participant `i` is `pk_i + \sum_{k} C_k * i^k` where
+/// `C_k` is the sum of commitments to the `k`-th coefficients by all dealers.
+pub fn refresh_public_key_shares<G: AffineRepr>(
+    public_key_shares: &BTreeMap<ParticipantId, G>,
+    coeff_comms: &BTreeMap<ParticipantId, CommitmentToCoefficients<G>>,
+    threshold: ShareId,
+) -> Result<BTreeMap<ParticipantId, G>, SSError> {
+    let mut comm_coeffs = vec![G::Group::zero(); threshold as usize];
+    for (dealer_id, comm) in coeff_comms {
+        verify_zero_sharing_commitments(*dealer_id, comm, threshold)?;
+        for (c, comm_c) in comm_coeffs.iter_mut().zip(comm.0.iter()) {
+            *c += comm_c;
+        }
+    }
+    let ids = public_key_shares.keys().copied().collect::<Vec<_>>();
+    let new_pk_shares = ids
+        .iter()
+        .map(|id| {
+            let x = G::ScalarField::from(*id);
+            // Horner's method
+            let delta = comm_coeffs
+                .iter()
+                .rev()
+                .fold(G::Group::zero(), |acc, c| acc * x + c);
+            delta + public_key_shares[id]
+        })
+        .collect::<Vec<_>>();
+    Ok(ids
+        .into_iter()
+        .zip(G::Group::normalize_batch(&new_pk_shares))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feldman_dvss_dkg::reconstruct_threshold_public_key, feldman_vss::deal_random_secret,
+    };
+    use ark_ff::PrimeField;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use test_utils::{G1, G2};
+
+    #[test]
+    fn refresh_shares() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let g1 = G1::rand(&mut rng);
+        let g2 = G2::rand(&mut rng);
+
+        fn check<G: AffineRepr>(rng: &mut StdRng, g: &G) {
+            for (threshold, total) in [(2, 2), (2, 3), (3, 5), (4, 9), (5, 7)] {
+                let (secret, shares, _, _) =
+                    deal_random_secret::<_, G>(rng, threshold, total, g).unwrap();
+                let pk = g.mul_bigint(secret.into_bigint()).into_affine();
+                let pk_shares = shares
+                    .0
+                    .iter()
+                    .map(|s| (s.id, g.mul_bigint(s.share.into_bigint()).into_affine()))
+                    .collect::<BTreeMap<_, _>>();
+
+                let mut accumulators = (1..=total)
+                    .map(|i| RefreshAccumulator::<G>::new(i, threshold))
+                    .collect::<Vec<_>>();
+                let mut all_comms = BTreeMap::new();
+                for dealer_id in 1..=total {
+                    let (zero_shares, comms) =
+                        deal_zero_sharing::<_, G>(rng, threshold, total, g).unwrap();
+                    assert_eq!(
+                        zero_shares.reconstruct_secret().unwrap(),
+                        G::ScalarField::zero()
+                    );
+                    for receiver_id in 1..=total {
+                        let share = zero_shares.0[receiver_id as usize - 1].clone();
+                        let acc = &mut accumulators[receiver_id as usize - 1];
+                        if dealer_id == receiver_id {
+                            acc.add_self_share(share, comms.clone());
+                            continue;
+                        }
+
+                        // Sharing of a non-zero secret is rejected
+                        let mut wrong_comms = comms.clone();
+                        wrong_comms.0[0] = *g;
+                        assert!(matches!(
+                            acc.add_received_share(dealer_id, share.clone(), wrong_comms, g),
+                            Err(SSError::InvalidZeroSharingFrom(id)) if id == dealer_id
+                        ));
+
+                        let mut wrong_share = share.clone();
+                        wrong_share.share += G::ScalarField::from(1u64);
+                        assert!(acc
+                            .add_received_share(dealer_id, wrong_share, comms.clone(), g)
+                            .is_err());
+
+                        acc.add_received_share(dealer_id, share.clone(), comms.clone(), g)
+                            .unwrap();
+                        assert!(acc
+                            .add_received_share(dealer_id, share, comms.clone(), g)
+                            .is_err());
+                    }
+                    all_comms.insert(dealer_id, comms);
+                }
+
+                let dealers = (1..=total).collect::<BTreeSet<_>>();
+                // Shares from a subset of the dealers or from unexpected dealers are rejected
+                let mut fewer_dealers = dealers.clone();
+                fewer_dealers.remove(&total);
+                let mut more_dealers = dealers.clone();
+                more_dealers.insert(total + 1);
+                let mut missing_dealer = dealers.clone();
+                missing_dealer.remove(&1);
+                assert!(matches!(
+                    accumulators[0].clone().finalize(&shares.0[0], &fewer_dealers),
+                    Err(SSError::InvalidParticipantId(id)) if id == total
+                ));
+                assert!(matches!(
+                    accumulators[0].clone().finalize(&shares.0[0], &more_dealers),
+                    Err(SSError::MissingSomeParticipants(id)) if id == total + 1
+                ));
+                assert!(matches!(
+                    accumulators[1]
+                        .clone()
+                        .finalize(&shares.0[1], &missing_dealer),
+                    Err(SSError::InvalidParticipantId(1))
+                ));
+
+                let new_shares = Shares(
+                    accumulators
+                        .into_iter()
+                        .zip(shares.0.iter())
+                        .map(|(acc, s)| acc.finalize(s, &dealers).unwrap())
+                        .collect(),
+                );
+                // Secret is unchanged but the shares are not
+                assert_eq!(new_shares.reconstruct_secret().unwrap(), secret);
+                for (old, new) in shares.0.iter().zip(new_shares.0.iter()) {
+                    assert_ne!(old.share, new.share);
+                }
+                // Old and new shares can't be mixed
+                if threshold > 1 {
+                    let mut mixed = new_shares.clone();
+                    mixed.0[0] = shares.0[0].clone();
+                    assert_ne!(mixed.reconstruct_secret().unwrap(), secret);
+                }
+
+                let new_pk_shares =
+                    refresh_public_key_shares(&pk_shares, &all_comms, threshold).unwrap();
+                for s in &new_shares.0 {
+                    assert_eq!(
+                        new_pk_shares[&s.id],
+                        g.mul_bigint(s.share.into_bigint()).into_affine()
+                    );
+                }
+                assert_eq!(
+                    reconstruct_threshold_public_key(
+                        new_pk_shares.into_iter().collect(),
+                        threshold
+                    )
+                    .unwrap(),
+                    pk
+                );
+            }
+        }
+
+        check(&mut rng, &g1);
+        check(&mut rng, &g2);
+    }
+}
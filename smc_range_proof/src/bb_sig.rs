@@ -13,7 +13,10 @@ use ark_std::{
 use digest::Digest;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
+use dock_crypto_utils::{
+    concat_slices,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
+};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -46,10 +49,19 @@ pub struct PublicKeyG2<E: Pairing>(pub <E as Pairing>::G2Affine);
 
 impl<E: Pairing> SignatureParams<E> {
     pub fn new<D: Digest>(label: &[u8]) -> Self {
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H>(label: &[u8]) -> Self
+    where
+        H: GroupElemHasher<E::G1Affine> + GroupElemHasher<E::G2Affine>,
+    {
         let g1 =
-            affine_group_elem_from_try_and_incr::<E::G1Affine, D>(&concat_slices![label, b" : g1"]);
+            <H as GroupElemHasher<E::G1Affine>>::hash_to_affine(&concat_slices![label, b" : g1"]);
         let g2 =
-            affine_group_elem_from_try_and_incr::<E::G2Affine, D>(&concat_slices![label, b" : g2"]);
+            <H as GroupElemHasher<E::G2Affine>>::hash_to_affine(&concat_slices![label, b" : g2"]);
         Self { g1, g2 }
     }
 
@@ -190,6 +202,25 @@ mod tests {
         UniformRand,
     };
 
+    #[test]
+    fn params_with_hash_to_curve() {
+        use blake2::Blake2b512;
+        use dock_crypto_utils::hashing_utils::HashToCurve;
+
+        let label = b"test";
+        let params =
+            SignatureParams::<Bls12_381>::new_with_hasher::<HashToCurve<Blake2b512>>(label);
+        assert!(params.is_valid());
+        assert_ne!(
+            params,
+            SignatureParams::<Bls12_381>::new::<Blake2b512>(label)
+        );
+        assert_eq!(
+            SignatureParams::<Bls12_381>::new::<Blake2b512>(label),
+            SignatureParams::<Bls12_381>::new_with_hasher::<TryAndIncrement<Blake2b512>>(label)
+        );
+    }
+
     #[test]
     fn secret_key_validation() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -7,7 +7,7 @@ use digest::Digest;
 use dock_crypto_utils::{
     concat_slices,
     ff::{inner_product, powers},
-    hashing_utils::affine_group_elem_from_try_and_incr,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
     misc::rand,
 };
 
@@ -20,8 +20,14 @@ pub struct MemberCommitmentKey<G: AffineRepr> {
 
 impl<G: AffineRepr> MemberCommitmentKey<G> {
     pub fn new<D: Digest>(label: &[u8]) -> Self {
-        let g = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : G"]);
-        let h = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : H"]);
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H: GroupElemHasher<G>>(label: &[u8]) -> Self {
+        let g = H::hash_to_affine(&concat_slices![label, b" : G"]);
+        let h = H::hash_to_affine(&concat_slices![label, b" : H"]);
         Self { g, h }
     }
 
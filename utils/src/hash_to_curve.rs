@@ -0,0 +1,456 @@
+//! Hashing to fields and elliptic curves as per [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html).
+//!
+//! Unlike the try-and-increment functions in [`hashing_utils`], these take a fixed number of hashing and field
+//! operations for any input and produce the same output as other implementations of the RFC. Supported groups are
+//! BLS12-381 G1 and G2 (using the simplified SWU map of arkworks), with feature `bls12-381`, and BN254 G1 (using the
+//! Shallue-van de Woestijne map), with feature `bn254`.
+//!
+//! These are not constant time. The Shallue-van de Woestijne map selects values arithmetically and inverts using
+//! exponentiation rather than branching on the input, but the arkworks field operations it uses (like computing the
+//! Legendre symbol) and the arkworks simplified SWU map branch on input-dependent values. So these should only be used
+//! for public inputs, like when generating setup parameters.
+//!
+//! [`hashing_utils`]: crate::hashing_utils
+
+#[cfg(feature = "bls12-381")]
+use ark_ec::hashing::curve_maps::wb::WBMap;
+use ark_ec::{
+    hashing::{map_to_curve_hasher::MapToCurve, HashToCurveError},
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_std::{marker::PhantomData, vec, vec::Vec};
+use digest::{core_api::BlockSizeUser, Digest, ExtendableOutput, Update};
+
+/// Security parameter in bits used to decide the number of bytes needed to get a uniformly random field element
+pub const SECURITY_PARAMETER: usize = 128;
+
+/// Expands a message into a uniformly random byte string of the given length using a domain separation tag (DST).
+pub trait ExpandMessage {
+    fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8>;
+}
+
+/// `expand_message_xmd` using hash function `D`
+pub struct ExpandMsgXmd<D>(PhantomData<D>);
+
+/// `expand_message_xof` using extendable output function `D` targeting `K` bits of security
+pub struct ExpandMsgXof<D, const K: usize = SECURITY_PARAMETER>(PhantomData<D>);
+
+impl<D: Digest + BlockSizeUser> ExpandMessage for ExpandMsgXmd<D> {
+    fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        expand_message_xmd::<D>(msg, dst, len_in_bytes)
+    }
+}
+
+impl<D: Default + ExtendableOutput + Update, const K: usize> ExpandMessage for ExpandMsgXof<D, K> {
+    fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        expand_message_xof::<D>(msg, dst, len_in_bytes, K)
+    }
+}
+
+/// `expand_message_xmd` of RFC 9380 section 5.3.1. Panics if `len_in_bytes` is too big for the hash function.
+pub fn expand_message_xmd<D: Digest + BlockSizeUser>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Vec<u8> {
+    let b_in_bytes = <D as Digest>::output_size();
+    let s_in_bytes = <D as BlockSizeUser>::block_size();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    assert!(ell <= 255 && len_in_bytes <= u16::MAX as usize);
+    let dst = if dst.len() > 255 {
+        D::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let dst_prime = [dst.as_slice(), &[dst.len() as u8]].concat();
+
+    let b_0 = D::new()
+        .chain_update(vec![0; s_in_bytes])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut b_i = D::new()
+        .chain_update(&b_0)
+        .chain_update([1])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let xored = b_0
+            .iter()
+            .zip(b_i.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        b_i = D::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// `expand_message_xof` of RFC 9380 section 5.3.2. `k` is the target security level in bits.
+pub fn expand_message_xof<D: Default + ExtendableOutput + Update>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+    k: usize,
+) -> Vec<u8> {
+    assert!(len_in_bytes <= u16::MAX as usize);
+    let dst = if dst.len() > 255 {
+        let mut hasher = D::default();
+        hasher.update(b"H2C-OVERSIZE-DST-");
+        hasher.update(dst);
+        let mut d = vec![0; (2 * k).div_ceil(8)];
+        hasher.finalize_xof_into(&mut d);
+        d
+    } else {
+        dst.to_vec()
+    };
+    let mut hasher = D::default();
+    hasher.update(msg);
+    hasher.update(&(len_in_bytes as u16).to_be_bytes());
+    hasher.update(&dst);
+    hasher.update(&[dst.len() as u8]);
+    let mut uniform_bytes = vec![0; len_in_bytes];
+    hasher.finalize_xof_into(&mut uniform_bytes);
+    uniform_bytes
+}
+
+/// `hash_to_field` of RFC 9380 section 5.2. Hashes `msg` to `count` elements of field `F` which can be an
+/// extension field.
+pub fn hash_to_field<F: Field, X: ExpandMessage>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    let m = F::extension_degree() as usize;
+    let len_per_elem = hash_to_field_len::<F::BasePrimeField>();
+    let uniform_bytes = X::expand_message(msg, dst, count * m * len_per_elem);
+    uniform_bytes
+        .chunks(m * len_per_elem)
+        .map(|e| {
+            F::from_base_prime_field_elems(
+                &e.chunks(len_per_elem)
+                    .map(F::BasePrimeField::from_be_bytes_mod_order)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Number of bytes hashed to get each prime field element, `L = ceil((ceil(log2(p)) + k) / 8)`
+pub fn hash_to_field_len<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize + SECURITY_PARAMETER).div_ceil(8)
+}
+
+/// A group for which RFC 9380 defines (or allows) a mapping from its base field to the curve
+pub trait HashToCurveGroup: AffineRepr {
+    type Map: MapToCurve<Self::Group>;
+}
+
+#[cfg(feature = "bls12-381")]
+impl HashToCurveGroup for Affine<ark_bls12_381::g1::Config> {
+    type Map = WBMap<ark_bls12_381::g1::Config>;
+}
+
+#[cfg(feature = "bls12-381")]
+impl HashToCurveGroup for Affine<ark_bls12_381::g2::Config> {
+    type Map = WBMap<ark_bls12_381::g2::Config>;
+}
+
+#[cfg(feature = "bn254")]
+impl HashToCurveGroup for Affine<ark_bn254::g1::Config> {
+    type Map = SVDWMap<ark_bn254::g1::Config>;
+}
+
+/// `hash_to_curve` of RFC 9380 section 3 which is a random oracle, i.e. the suites ending with `_RO_`
+pub fn hash_to_curve<G: HashToCurveGroup, X: ExpandMessage>(msg: &[u8], dst: &[u8]) -> G {
+    let u = hash_to_field::<<G::Group as CurveGroup>::BaseField, X>(msg, dst, 2);
+    let map = G::Map::new().unwrap();
+    let q0 = map.map_to_curve(u[0]).unwrap();
+    let q1 = map.map_to_curve(u[1]).unwrap();
+    (q0 + q1).into_affine().clear_cofactor()
+}
+
+/// `encode_to_curve` of RFC 9380 section 3 which is not a random oracle (its output is not uniformly distributed),
+/// i.e. the suites ending with `_NU_`
+pub fn encode_to_curve<G: HashToCurveGroup, X: ExpandMessage>(msg: &[u8], dst: &[u8]) -> G {
+    let u = hash_to_field::<<G::Group as CurveGroup>::BaseField, X>(msg, dst, 1);
+    G::Map::new()
+        .unwrap()
+        .map_to_curve(u[0])
+        .unwrap()
+        .clear_cofactor()
+}
+
+/// Curves of the form `y^2 = x^3 + A * x + B` over a prime field for which the Shallue-van de Woestijne map is used.
+pub trait SVDWConfig: SWCurveConfig
+where
+    Self::BaseField: PrimeField,
+{
+    /// The constant `Z` chosen as per RFC 9380 appendix H.1
+    const Z: Self::BaseField;
+}
+
+#[cfg(feature = "bn254")]
+impl SVDWConfig for ark_bn254::g1::Config {
+    const Z: ark_bn254::Fq = ark_bn254::Fq::ONE;
+}
+
+/// The Shallue-van de Woestijne map of RFC 9380 section 6.6.1 which works for any curve of the form
+/// `y^2 = x^3 + A * x + B`. Used for curves like BN254 where `A * B = 0` and no suitable isogeny is known.
+pub struct SVDWMap<P: SVDWConfig>
+where
+    P::BaseField: PrimeField,
+{
+    c1: P::BaseField,
+    c2: P::BaseField,
+    c3: P::BaseField,
+    c4: P::BaseField,
+    /// `p - 2`, the exponent used for inversion
+    p_minus_2: <P::BaseField as PrimeField>::BigInt,
+}
+
+impl<P: SVDWConfig> SVDWMap<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn g(x: P::BaseField) -> P::BaseField {
+        (x.square() + P::COEFF_A) * x + P::COEFF_B
+    }
+
+    /// 1 if `b` is true else 0, to select values without branching
+    fn from_bool(b: bool) -> P::BaseField {
+        P::BaseField::from(b as u64)
+    }
+}
+
+impl<P: SVDWConfig> MapToCurve<Projective<P>> for SVDWMap<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn new() -> Result<Self, HashToCurveError> {
+        let z = P::Z;
+        // c1 = g(Z), c2 = -Z / 2, c3 = sqrt(-g(Z) * (3 * Z^2 + 4 * A)) with sgn0(c3) = 0,
+        // c4 = -4 * g(Z) / (3 * Z^2 + 4 * A)
+        let c1 = Self::g(z);
+        let two = P::BaseField::from(2u64);
+        let c2 = -z / two;
+        let t = P::BaseField::from(3u64) * z.square() + P::BaseField::from(4u64) * P::COEFF_A;
+        if c1.is_zero() || t.is_zero() {
+            return Err(HashToCurveError::MapToCurveError(
+                "Z is invalid for the curve".into(),
+            ));
+        }
+        let mut c3 = (-c1 * t).sqrt().ok_or_else(|| {
+            HashToCurveError::MapToCurveError("Z is invalid for the curve".into())
+        })?;
+        if sgn0(&c3) {
+            c3 = -c3;
+        }
+        let c4 = -P::BaseField::from(4u64) * c1 / t;
+        let mut p_minus_2 = P::BaseField::MODULUS;
+        p_minus_2.sub_with_borrow(&2u64.into());
+        Ok(Self {
+            c1,
+            c2,
+            c3,
+            c4,
+            p_minus_2,
+        })
+    }
+
+    fn map_to_curve(&self, u: P::BaseField) -> Result<Affine<P>, HashToCurveError> {
+        let one = P::BaseField::ONE;
+        let tv1 = u.square() * self.c1;
+        let tv2 = one + tv1;
+        let tv1 = one - tv1;
+        // inv0 as x^(p - 2), i.e. 0 maps to 0
+        let tv3 = (tv1 * tv2).pow(self.p_minus_2);
+        let tv4 = u * tv1 * tv3 * self.c3;
+        let x1 = self.c2 - tv4;
+        let gx1 = Self::g(x1);
+        let e1 = Self::from_bool(!gx1.legendre().is_qnr());
+        let x2 = self.c2 + tv4;
+        let gx2 = Self::g(x2);
+        // e2 is 1 only if gx2 is square and gx1 is not
+        let e2 = Self::from_bool(!gx2.legendre().is_qnr()) * (one - e1);
+        let x3 = (tv2.square() * tv3).square() * self.c4 + P::Z;
+        // x = x1 if gx1 is square else x2 if gx2 is square else x3
+        let x = x3 + e1 * (x1 - x3) + e2 * (x2 - x3);
+        let y = Self::g(x)
+            .sqrt()
+            .ok_or_else(|| HashToCurveError::MapToCurveError("g(x) is not a square".into()))?;
+        // Negate y if its sign differs from u's
+        let e3 = Self::from_bool(sgn0(&u) ^ sgn0(&y));
+        let y = y - e3 * y.double();
+        let point = Affine::<P>::new_unchecked(x, y);
+        debug_assert!(point.is_on_curve());
+        Ok(point)
+    }
+}
+
+/// `sgn0` of RFC 9380 section 4.1 for prime fields, i.e. the parity of the element
+fn sgn0<F: PrimeField>(x: &F) -> bool {
+    x.into_bigint().is_odd()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::Sha256;
+    use sha3::Shake128;
+
+    #[test]
+    fn expand_message_vectors() {
+        // Test vectors from RFC 9380 appendix K
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        assert_eq!(
+            hex::encode(expand_message_xof::<Shake128>(b"", dst, 0x20, 128)),
+            "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2"
+        );
+    }
+
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn bls12_381_vectors() {
+        use ark_bls12_381::{Fq, Fq2};
+        let fq = |hex: &str| Fq::from_be_bytes_mod_order(&hex::decode(hex).unwrap());
+
+        // Test vectors from RFC 9380 appendix J.9
+        let p = hash_to_curve::<ark_bls12_381::G1Affine, ExpandMsgXmd<Sha256>>(
+            b"",
+            b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        );
+        assert_eq!(p.x, fq("052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"));
+        assert_eq!(p.y, fq("08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265"));
+
+        let p = encode_to_curve::<ark_bls12_381::G1Affine, ExpandMsgXmd<Sha256>>(
+            b"",
+            b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_NU_",
+        );
+        assert_eq!(p.x, fq("184bb665c37ff561a89ec2122dd343f20e0f4cbcaec84e3c3052ea81d1834e192c426074b02ed3dca4e7676ce4ce48ba"));
+        assert_eq!(p.y, fq("04407b8d35af4dacc809927071fc0405218f1401a6d15af775810e4e460064bcc9468beeba82fdc751be70476c888bf3"));
+
+        let p = hash_to_curve::<ark_bls12_381::G2Affine, ExpandMsgXmd<Sha256>>(
+            b"",
+            b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_",
+        );
+        assert_eq!(
+            p.x,
+            Fq2::new(
+                fq("0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+                fq("05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d")
+            )
+        );
+        assert_eq!(
+            p.y,
+            Fq2::new(
+                fq("0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92"),
+                fq("12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6")
+            )
+        );
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn bn254_vectors() {
+        // RFC 9380 has no test vectors for BN254 so these were computed with a separate implementation (in Python) of
+        // the steps of section 6.6.1 with Z = 1
+        let fq = |hex: &str| ark_bn254::Fq::from_be_bytes_mod_order(&hex::decode(hex).unwrap());
+        let check = |p: ark_bn254::G1Affine, x: &str, y: &str| {
+            assert_eq!(p.x, fq(x));
+            assert_eq!(p.y, fq(y));
+        };
+
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+        check(
+            hash_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(b"", dst),
+            "0a976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86",
+            "02925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5",
+        );
+        check(
+            hash_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(b"abc", dst),
+            "23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1",
+            "04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d",
+        );
+
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_NU_";
+        check(
+            encode_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(b"", dst),
+            "1bb8810e2ceaf04786d4efd216fc2820ddd9363712efc736ada11049d8af5925",
+            "1efbf8d54c60d865cce08437668ea30f5bf90d287dbd9b5af31da852915e8f11",
+        );
+        check(
+            encode_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(b"abc", dst),
+            "0da4a96147df1f35b0f820bd35c6fac3b80e8e320de7c536b1e054667b22c332",
+            "189bd3fbffe4c8740d6543754d95c790e44cd2d162858e3b733d2b8387983bb7",
+        );
+
+        // Exceptional case where inv0 is applied to 0
+        check(
+            SVDWMap::<ark_bn254::g1::Config>::new()
+                .unwrap()
+                .map_to_curve(ark_bn254::Fq::zero())
+                .unwrap(),
+            "183227397098d014dc2822db40c0ac2ecbc0b548b438e5469e10460b6c3e7ea3",
+            "0a6ea289876b139cfe2cd1f08c065a2ab4aad542eaccb013520ea36934e877b4",
+        );
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn bn254() {
+        use blake2::Blake2b512;
+
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+        let msgs: [&[u8]; 4] = [b"", b"abc", b"abcdef0123456789", &[b'a'; 200]];
+        let points = msgs
+            .iter()
+            .map(|m| hash_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(m, dst))
+            .collect::<Vec<_>>();
+        for (i, p) in points.iter().enumerate() {
+            assert!(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve());
+            assert!(!p.is_zero());
+            for q in &points[i + 1..] {
+                assert_ne!(p, q);
+            }
+            assert_eq!(
+                *p,
+                hash_to_curve::<ark_bn254::G1Affine, ExpandMsgXmd<Sha256>>(msgs[i], dst)
+            );
+        }
+
+        // Every field element is mapped to a point on the curve, including the exceptional case of 0
+        let map = SVDWMap::<ark_bn254::g1::Config>::new().unwrap();
+        for u in [0u64, 1, 2, 3, 100]
+            .into_iter()
+            .map(ark_bn254::Fq::from)
+            .chain(hash_to_field::<ark_bn254::Fq, ExpandMsgXmd<Blake2b512>>(
+                b"test", dst, 20,
+            ))
+        {
+            assert!(map.map_to_curve(u).unwrap().is_on_curve());
+        }
+    }
+}
@@ -1,9 +1,13 @@
 #![allow(non_snake_case)]
 
-use crate::concat_slices;
+use crate::{
+    concat_slices,
+    hash_to_curve::{hash_to_curve, ExpandMsgXmd, HashToCurveGroup},
+};
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
-use digest::Digest;
+use ark_std::marker::PhantomData;
+use digest::{core_api::BlockSizeUser, Digest};
 
 /// Hash bytes to a point on the curve. Returns as Projective coordinates. This is vulnerable to timing attack and is only used when input
 /// is public anyway like when generating setup parameters.
@@ -38,3 +42,36 @@ pub fn field_elem_from_try_and_incr<F: PrimeField, D: Digest>(bytes: &[u8]) -> F
     }
     f.unwrap()
 }
+
+/// Method of deriving group elements from public bytes, like when generating setup parameters. Used by params
+/// constructors to choose between try-and-increment and hashing to curve as per RFC 9380.
+pub trait GroupElemHasher<G: AffineRepr> {
+    fn hash_to_projective(bytes: &[u8]) -> G::Group;
+
+    fn hash_to_affine(bytes: &[u8]) -> G {
+        Self::hash_to_projective(bytes).into_affine()
+    }
+}
+
+/// Derive group elements using [`projective_group_elem_from_try_and_incr`] with hash function `D`. Works for any
+/// group but is not constant time and not interoperable with other libraries.
+pub struct TryAndIncrement<D>(PhantomData<D>);
+
+/// Derive group elements using `hash_to_curve` of RFC 9380 with `expand_message_xmd` using hash function `D`. Only
+/// works for groups implementing [`HashToCurveGroup`].
+pub struct HashToCurve<D>(PhantomData<D>);
+
+/// Domain separation tag used when deriving group elements with [`HashToCurve`]
+pub const PARAMS_HASH_TO_CURVE_DST: &[u8] = b"DOCK-CRYPTO-PARAMS-V01-CS01-with-XMD_RO_";
+
+impl<G: AffineRepr, D: Digest> GroupElemHasher<G> for TryAndIncrement<D> {
+    fn hash_to_projective(bytes: &[u8]) -> G::Group {
+        projective_group_elem_from_try_and_incr::<G, D>(bytes)
+    }
+}
+
+impl<G: HashToCurveGroup, D: Digest + BlockSizeUser> GroupElemHasher<G> for HashToCurve<D> {
+    fn hash_to_projective(bytes: &[u8]) -> G::Group {
+        hash_to_curve::<G, ExpandMsgXmd<D>>(bytes, PARAMS_HASH_TO_CURVE_DST).into_group()
+    }
+}
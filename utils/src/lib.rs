@@ -8,6 +8,7 @@ pub mod extend_some;
 #[macro_use]
 pub mod serde_utils;
 pub mod ff;
+pub mod hash_to_curve;
 pub mod hashing_utils;
 pub mod iter;
 pub mod macros;
@@ -3,7 +3,7 @@ use core::ops::Range;
 use crate::{
     aliases::{DoubleEndedExactSizeIterator, SendIfParallel},
     concat_slices,
-    hashing_utils::projective_group_elem_from_try_and_incr,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
     impl_indexed_iter,
     msm::multiply_field_elems_with_same_group_elem,
     try_iter::{InvalidPair, InvalidPairOrSingle},
@@ -94,9 +94,21 @@ pub fn n_projective_group_elements<'iter, G, D>(
 where
     G: AffineRepr + SendIfParallel,
     D: Digest,
+{
+    n_projective_group_elements_with_hasher::<G, TryAndIncrement<D>>(counter_range, bytes)
+}
+
+/// Same as [`n_projective_group_elements`] but group elements are derived using the given [`GroupElemHasher`]
+pub fn n_projective_group_elements_with_hasher<'iter, G, H>(
+    counter_range: Range<u32>,
+    bytes: &'iter [u8],
+) -> impl_indexed_iter!(<Item = G::Group> + 'iter)
+where
+    G: AffineRepr + SendIfParallel,
+    H: GroupElemHasher<G>,
 {
     le_bytes_iter_from_given_range(counter_range).map(move |ctr_bytes| -> G::Group {
-        projective_group_elem_from_try_and_incr::<G, D>(&concat_slices!(bytes.as_ref(), ctr_bytes))
+        H::hash_to_projective(&concat_slices!(bytes, ctr_bytes))
     })
 }
 
@@ -112,6 +124,18 @@ where
     n_projective_group_elements::<G, D>(n, bytes).map(CurveGroup::into_affine)
 }
 
+/// Same as [`n_affine_group_elements`] but group elements are derived using the given [`GroupElemHasher`]
+pub fn n_affine_group_elements_with_hasher<'iter, G, H>(
+    n: Range<u32>,
+    bytes: &'iter [u8],
+) -> impl_indexed_iter!(<Item = G> + 'iter)
+where
+    G: AffineRepr + SendIfParallel,
+    H: GroupElemHasher<G>,
+{
+    n_projective_group_elements_with_hasher::<G, H>(n, bytes).map(CurveGroup::into_affine)
+}
+
 /// Generates a random using given `rng`.
 pub fn rand<T: UniformRand, R: RngCore>(rng: &mut R) -> T {
     UniformRand::rand(rng)
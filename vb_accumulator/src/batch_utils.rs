@@ -408,6 +408,14 @@ where
 )]
 pub struct Omega<G: AffineRepr>(#[serde_as(as = "Vec<ArkObjectBytes>")] pub Vec<G>);
 
+/// Additions, removals and the corresponding `Omega` for each of several batch updates, in the order
+/// the batches were applied to the accumulator
+pub type UpdatesAndOmegas<'a, G> = Vec<(
+    &'a [<G as AffineRepr>::ScalarField],
+    &'a [<G as AffineRepr>::ScalarField],
+    &'a Omega<G>,
+)>;
+
 impl<G> Omega<G>
 where
     G: AffineRepr,
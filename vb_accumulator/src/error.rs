@@ -3,9 +3,15 @@
 #![allow(non_camel_case_types)]
 
 use ark_serialize::SerializationError;
-use ark_std::fmt::Debug;
+use ark_std::{fmt::Debug, string::String};
+#[cfg(feature = "threshold")]
+use bbs_plus::error::BBSPlusError;
 use dock_crypto_utils::serde_utils::ArkSerializationError;
+#[cfg(feature = "threshold")]
+use oblivious_transfer_protocols::ParticipantId;
 use schnorr_pok::error::SchnorrError;
+#[cfg(feature = "threshold")]
+use secret_sharing_and_dkg::error::SSError;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -20,6 +26,8 @@ pub enum VBAccumulatorError {
     ElementPresent,
     /// Element is already absent in the accumulator
     ElementAbsent,
+    /// Element isn't part of the domain of the accumulator
+    ElementOutsideDomain,
     NewElementSameAsCurrent,
     NeedSameNoOfElementsAndWitnesses,
     CannotBeZero,
@@ -33,6 +41,44 @@ pub enum VBAccumulatorError {
     #[serde(with = "ArkSerializationError")]
     Serialization(SerializationError),
     SchnorrError(SchnorrError),
+    /// No update record for this epoch
+    UnknownEpoch(u64),
+    /// Accumulator doesn't match the one in the last update record
+    AccumulatorMismatchWithUpdateLog,
+    InvalidUpdateProof,
+    /// Keyed-verification proof doesn't verify with the secret key
+    InvalidKeyedProof,
+    /// Error from the storage backend of the accumulator state
+    StorageError(String),
+    /// Record at this byte offset of the storage file is corrupted but isn't the last record so it isn't the result of
+    /// a crash while writing
+    CorruptedStorageRecord(u64),
+    /// Manager isn't expected to take part in the threshold protocol
+    #[cfg(feature = "threshold")]
+    UnexpectedManager(ParticipantId),
+    #[cfg(feature = "threshold")]
+    DuplicateShareFrom(ParticipantId),
+    /// Share of the manager doesn't verify with its public key share
+    #[cfg(feature = "threshold")]
+    InvalidShareFrom(ParticipantId),
+    /// Got fewer shares than the threshold
+    #[cfg(feature = "threshold")]
+    InsufficientShares(usize, usize),
+    /// Batch proof must have at least 1 element
+    EmptyBatch,
+    /// Index isn't less than the batch size
+    IndexOutOfBatch(usize, usize),
+    /// Aggregated inversion shares don't give the expected result
+    #[cfg(feature = "threshold")]
+    InvalidInversionShares,
+    /// None of the tweaks encode the identifier as a member acceptable in the accumulator
+    NoAcceptableEncoding,
+    /// Member is already registered for a different identifier
+    MemberEncodingCollision,
+    #[cfg(feature = "threshold")]
+    BBSPlusError(BBSPlusError),
+    #[cfg(feature = "threshold")]
+    SSError(SSError),
 }
 
 impl From<SchnorrError> for VBAccumulatorError {
@@ -41,8 +87,36 @@ impl From<SchnorrError> for VBAccumulatorError {
     }
 }
 
+#[cfg(feature = "threshold")]
+impl From<BBSPlusError> for VBAccumulatorError {
+    fn from(e: BBSPlusError) -> Self {
+        Self::BBSPlusError(e)
+    }
+}
+
+#[cfg(feature = "threshold")]
+impl From<SSError> for VBAccumulatorError {
+    fn from(e: SSError) -> Self {
+        Self::SSError(e)
+    }
+}
+
 impl From<SerializationError> for VBAccumulatorError {
     fn from(e: SerializationError) -> Self {
         Self::Serialization(e)
     }
 }
+
+#[cfg(feature = "file-store")]
+impl From<std::io::Error> for VBAccumulatorError {
+    fn from(e: std::io::Error) -> Self {
+        Self::StorageError(e.to_string())
+    }
+}
+
+#[cfg(feature = "sled-store")]
+impl From<sled::Error> for VBAccumulatorError {
+    fn from(e: sled::Error) -> Self {
+        Self::StorageError(e.to_string())
+    }
+}
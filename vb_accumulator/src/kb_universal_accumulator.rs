@@ -0,0 +1,905 @@
+#![allow(non_snake_case)]
+
+//! Universal accumulator built from 2 positive accumulators sharing the same secret key, as described in
+//! section 5 of the paper [Efficient Constructions of Pairing Based Accumulators](https://eprint.iacr.org/2021/638).
+//!
+//! The manager fixes a domain of elements that can ever be members of the accumulator. One positive accumulator,
+//! `mem`, accumulates the current members and the other, `non_mem`, accumulates the elements of the domain that
+//! are not members. Adding an element removes it from `non_mem` and adds it to `mem`, removing does the opposite.
+//! A membership witness is a membership witness in `mem` and a non-membership witness is a membership witness in
+//! `non_mem`, so both are computed and updated like the witnesses of [`PositiveAccumulator`] and proved with the
+//! membership proof protocol from [`proofs`].
+//!
+//! Compared to [`UniversalAccumulator`], there are no initial elements to generate at setup, no maximum size and
+//! creating a non-membership witness does not need to iterate over all members. The domain can be extended at any
+//! time with [`KBUniversalAccumulator::extend_domain`]. The costs are 2 accumulated values instead of 1 and that
+//! non-membership can only be proven for elements of the domain.
+//!
+//! ```
+//! use ark_bls12_381::Bls12_381;
+//! use vb_accumulator::setup::{Keypair, SetupParams};
+//! use vb_accumulator::kb_universal_accumulator::KBUniversalAccumulator;
+//! use vb_accumulator::persistence::State;
+//!
+//! let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+//! let keypair = Keypair::<Bls12_381>::generate(&mut rng, &params);
+//!
+//! // `mem_state` and `non_mem_state` should be persistent dbs implementing the trait `State`. `domain` is a
+//! // vector of all elements that can be added to the accumulator
+//! let accumulator = KBUniversalAccumulator::initialize(&params, &keypair.secret_key, domain, &mut non_mem_state).unwrap();
+//!
+//! // `elem` should be in the domain
+//! let accumulator = accumulator
+//!                 .add(elem, &keypair.secret_key, &mut mem_state, &mut non_mem_state)
+//!                 .unwrap();
+//!
+//! // Get the accumulated values (as group elements in G1). Both need to be published.
+//! let mem_value = accumulator.mem_value();
+//! let non_mem_value = accumulator.non_mem_value();
+//!
+//! let m_wit = accumulator
+//!                 .get_membership_witness(&elem, &keypair.secret_key, &mem_state)
+//!                 .unwrap();
+//! accumulator.verify_membership(&elem, &m_wit, &keypair.public_key, &params);
+//!
+//! // `non_member` should be in the domain but not added to the accumulator
+//! let nm_wit = accumulator
+//!                 .get_non_membership_witness(&non_member, &keypair.secret_key, &non_mem_state)
+//!                 .unwrap();
+//! accumulator.verify_non_membership(&non_member, &nm_wit, &keypair.public_key, &params);
+//!
+//! // Or create a new accumulator for verification and verify
+//! let verification_accumulator = KBUniversalAccumulator::<Bls12_381>::from_accumulated(*mem_value, *non_mem_value);
+//! verification_accumulator.verify_non_membership(&non_member, &nm_wit, &keypair.public_key, &params);
+//!
+//! // Before applying a batch of updates, the manager publishes the update info for both kinds of witnesses
+//! let omega_mem = accumulator.generate_omega_for_membership_witnesses(&additions, &removals, &keypair.secret_key);
+//! let omega_non_mem = accumulator.generate_omega_for_non_membership_witnesses(&additions, &removals, &keypair.secret_key);
+//! let accumulator = accumulator
+//!             .batch_updates(additions, &removals, &keypair.secret_key, &mut mem_state, &mut non_mem_state)
+//!             .unwrap();
+//!
+//! // Similar to other accumulators, methods starting with `compute_` don't need access to the states.
+//! ```
+//!
+//! [`PositiveAccumulator`]: crate::positive::PositiveAccumulator
+//! [`UniversalAccumulator`]: crate::universal::UniversalAccumulator
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    batch_utils::Omega,
+    error::VBAccumulatorError,
+    persistence::State,
+    positive::{Accumulator, PositiveAccumulator},
+    setup::{PublicKey, SecretKey, SetupParams},
+};
+
+pub mod proofs;
+pub mod witness;
+
+use witness::{
+    KBUniversalAccumulatorMembershipWitness, KBUniversalAccumulatorNonMembershipWitness,
+};
+
+/// Universal accumulator made of a positive accumulator of the members and a positive accumulator of the
+/// non-members of the domain.
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulator<E: Pairing> {
+    /// Accumulates the members
+    pub mem: PositiveAccumulator<E>,
+    /// Accumulates the elements of the domain that are not members
+    pub non_mem: PositiveAccumulator<E>,
+}
+
+impl<E> KBUniversalAccumulator<E>
+where
+    E: Pairing,
+{
+    /// Create a new accumulator with no members. All elements of `domain` are added to `non_mem_state`.
+    pub fn initialize(
+        params: &SetupParams<E>,
+        sk: &SecretKey<E::ScalarField>,
+        domain: Vec<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        let mem = PositiveAccumulator::initialize(params);
+        let non_mem =
+            PositiveAccumulator::initialize(params).add_batch(domain, sk, non_mem_state)?;
+        Ok(Self { mem, non_mem })
+    }
+
+    /// Compute new accumulator after the domain is extended with `new_elements`. Only `non_mem` changes.
+    pub fn compute_extended(
+        &self,
+        new_elements: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        let non_mem = self.non_mem.compute_new_post_add_batch(new_elements, sk);
+        Self {
+            mem: self.mem.clone(),
+            non_mem: PositiveAccumulator::from_value(non_mem),
+        }
+    }
+
+    /// Extend the domain of the accumulator with `new_elements`. These become non-members and thus
+    /// existing non-membership witnesses need to be updated.
+    pub fn extend_domain(
+        &self,
+        sk: &SecretKey<E::ScalarField>,
+        new_elements: Vec<E::ScalarField>,
+        mem_state: &dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        for element in new_elements.iter() {
+            if mem_state.has(element) {
+                return Err(VBAccumulatorError::ElementPresent);
+            }
+        }
+        let non_mem = self.non_mem.add_batch(new_elements, sk, non_mem_state)?;
+        Ok(Self {
+            mem: self.mem.clone(),
+            non_mem,
+        })
+    }
+
+    /// Compute new accumulator after addition
+    pub fn compute_new_post_add(
+        &self,
+        element: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        Self::from_accumulated(
+            self.mem.compute_new_post_add(element, sk),
+            self.non_mem.compute_new_post_remove(element, sk),
+        )
+    }
+
+    /// Add an element of the domain to the accumulator and move it from `non_mem_state` to `mem_state`
+    pub fn add(
+        &self,
+        element: E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        self.check_before_add(&element, mem_state, non_mem_state)?;
+        let new = self.compute_new_post_add(&element, sk);
+        non_mem_state.remove(&element);
+        mem_state.add(element);
+        Ok(new)
+    }
+
+    /// Compute new accumulator after batch addition
+    pub fn compute_new_post_add_batch(
+        &self,
+        elements: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        Self::from_accumulated(
+            self.mem.compute_new_post_add_batch(elements, sk),
+            self.non_mem.compute_new_post_remove_batch(elements, sk),
+        )
+    }
+
+    /// Add a batch of elements of the domain to the accumulator
+    pub fn add_batch(
+        &self,
+        elements: Vec<E::ScalarField>,
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        for element in elements.iter() {
+            self.check_before_add(element, mem_state, non_mem_state)?;
+        }
+        let new = self.compute_new_post_add_batch(&elements, sk);
+        for element in elements {
+            non_mem_state.remove(&element);
+            mem_state.add(element);
+        }
+        Ok(new)
+    }
+
+    /// Compute new accumulator after removal
+    pub fn compute_new_post_remove(
+        &self,
+        element: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        Self::from_accumulated(
+            self.mem.compute_new_post_remove(element, sk),
+            self.non_mem.compute_new_post_add(element, sk),
+        )
+    }
+
+    /// Remove a member from the accumulator and move it from `mem_state` to `non_mem_state`
+    pub fn remove(
+        &self,
+        element: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        self.check_before_remove(element, mem_state, non_mem_state)?;
+        let new = self.compute_new_post_remove(element, sk);
+        mem_state.remove(element);
+        non_mem_state.add(*element);
+        Ok(new)
+    }
+
+    /// Compute new accumulator after batch removal
+    pub fn compute_new_post_remove_batch(
+        &self,
+        elements: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        Self::from_accumulated(
+            self.mem.compute_new_post_remove_batch(elements, sk),
+            self.non_mem.compute_new_post_add_batch(elements, sk),
+        )
+    }
+
+    /// Remove a batch of members from the accumulator
+    pub fn remove_batch(
+        &self,
+        elements: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        for element in elements {
+            self.check_before_remove(element, mem_state, non_mem_state)?;
+        }
+        let new = self.compute_new_post_remove_batch(elements, sk);
+        for element in elements {
+            mem_state.remove(element);
+            non_mem_state.add(*element);
+        }
+        Ok(new)
+    }
+
+    /// Compute new accumulator after batch additions and removals
+    pub fn compute_new_post_batch_updates(
+        &self,
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        Self::from_accumulated(
+            self.mem
+                .compute_new_post_batch_updates(additions, removals, sk),
+            self.non_mem
+                .compute_new_post_batch_updates(removals, additions, sk),
+        )
+    }
+
+    /// Adding and removing batches of elements from the accumulator
+    pub fn batch_updates(
+        &self,
+        additions: Vec<E::ScalarField>,
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &mut dyn State<E::ScalarField>,
+        non_mem_state: &mut dyn State<E::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        for element in additions.iter() {
+            self.check_before_add(element, mem_state, non_mem_state)?;
+        }
+        for element in removals {
+            self.check_before_remove(element, mem_state, non_mem_state)?;
+        }
+        let new = self.compute_new_post_batch_updates(&additions, removals, sk);
+        for element in additions {
+            non_mem_state.remove(&element);
+            mem_state.add(element);
+        }
+        for element in removals {
+            mem_state.remove(element);
+            non_mem_state.add(*element);
+        }
+        Ok(new)
+    }
+
+    /// Compute membership witness
+    pub fn compute_membership_witness(
+        &self,
+        member: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+    ) -> KBUniversalAccumulatorMembershipWitness<E::G1Affine> {
+        self.mem.compute_membership_witness(member, sk).into()
+    }
+
+    /// Get membership witness for a member of the accumulator
+    pub fn get_membership_witness(
+        &self,
+        member: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<KBUniversalAccumulatorMembershipWitness<E::G1Affine>, VBAccumulatorError> {
+        self.mem
+            .get_membership_witness(member, sk, mem_state)
+            .map(|w| w.into())
+    }
+
+    /// Compute membership witnesses for batch
+    pub fn compute_membership_witnesses_for_batch(
+        &self,
+        members: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Vec<KBUniversalAccumulatorMembershipWitness<E::G1Affine>> {
+        self.mem
+            .compute_membership_witness_for_batch(members, sk)
+            .into_iter()
+            .map(|w| w.into())
+            .collect()
+    }
+
+    /// Get membership witnesses for multiple members of the accumulator. Returns witnesses in the order
+    /// of passed elements.
+    pub fn get_membership_witnesses_for_batch(
+        &self,
+        members: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<Vec<KBUniversalAccumulatorMembershipWitness<E::G1Affine>>, VBAccumulatorError> {
+        Ok(self
+            .mem
+            .get_membership_witnesses_for_batch(members, sk, mem_state)?
+            .into_iter()
+            .map(|w| w.into())
+            .collect())
+    }
+
+    /// Compute non-membership witness
+    pub fn compute_non_membership_witness(
+        &self,
+        non_member: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+    ) -> KBUniversalAccumulatorNonMembershipWitness<E::G1Affine> {
+        self.non_mem
+            .compute_membership_witness(non_member, sk)
+            .into()
+    }
+
+    /// Get non-membership witness for an element of the domain that is not a member. Returns error
+    /// `ElementAbsent` if `non_member` is absent from `non_mem_state`, i.e. it's either a member or not
+    /// in the domain.
+    pub fn get_non_membership_witness(
+        &self,
+        non_member: &E::ScalarField,
+        sk: &SecretKey<E::ScalarField>,
+        non_mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>, VBAccumulatorError> {
+        self.non_mem
+            .get_membership_witness(non_member, sk, non_mem_state)
+            .map(|w| w.into())
+    }
+
+    /// Compute non-membership witnesses for batch
+    pub fn compute_non_membership_witnesses_for_batch(
+        &self,
+        non_members: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Vec<KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>> {
+        self.non_mem
+            .compute_membership_witness_for_batch(non_members, sk)
+            .into_iter()
+            .map(|w| w.into())
+            .collect()
+    }
+
+    /// Get non-membership witnesses for multiple elements of the domain that are not members. Returns
+    /// witnesses in the order of passed elements.
+    pub fn get_non_membership_witnesses_for_batch(
+        &self,
+        non_members: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        non_mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<Vec<KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>>, VBAccumulatorError>
+    {
+        Ok(self
+            .non_mem
+            .get_membership_witnesses_for_batch(non_members, sk, non_mem_state)?
+            .into_iter()
+            .map(|w| w.into())
+            .collect())
+    }
+
+    /// Check if element is a member of the accumulator
+    pub fn verify_membership(
+        &self,
+        member: &E::ScalarField,
+        witness: &KBUniversalAccumulatorMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+    ) -> bool {
+        self.mem.verify_membership(member, &witness.0, pk, params)
+    }
+
+    /// Check if element is not a member of the accumulator
+    pub fn verify_non_membership(
+        &self,
+        non_member: &E::ScalarField,
+        witness: &KBUniversalAccumulatorNonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+    ) -> bool {
+        self.non_mem
+            .verify_membership(non_member, &witness.0, pk, params)
+    }
+
+    /// Create the update info for membership witnesses after `additions` and `removals`. Should be called
+    /// on the accumulator before the updates are applied.
+    pub fn generate_omega_for_membership_witnesses(
+        &self,
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Omega<E::G1Affine> {
+        Omega::new(additions, removals, self.mem.value(), sk)
+    }
+
+    /// Create the update info for non-membership witnesses after `additions` and `removals`. Should be called
+    /// on the accumulator before the updates are applied.
+    pub fn generate_omega_for_non_membership_witnesses(
+        &self,
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Omega<E::G1Affine> {
+        // Additions to the accumulator are removals from `non_mem` and vice versa
+        Omega::new(removals, additions, self.non_mem.value(), sk)
+    }
+
+    /// Create the update info for non-membership witnesses after the domain is extended with `new_elements`.
+    /// Should be called on the accumulator before the domain is extended. Membership witnesses don't change.
+    pub fn generate_omega_for_domain_extension(
+        &self,
+        new_elements: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Omega<E::G1Affine> {
+        Omega::new(new_elements, &[], self.non_mem.value(), sk)
+    }
+
+    /// The accumulated value of the members
+    pub fn mem_value(&self) -> &E::G1Affine {
+        self.mem.value()
+    }
+
+    /// The accumulated value of the elements of the domain that are not members
+    pub fn non_mem_value(&self) -> &E::G1Affine {
+        self.non_mem.value()
+    }
+
+    /// Create an accumulator using the accumulated values. This is used for verification purposes only
+    pub fn from_accumulated(mem: E::G1Affine, non_mem: E::G1Affine) -> Self {
+        Self {
+            mem: PositiveAccumulator::from_accumulated(mem),
+            non_mem: PositiveAccumulator::from_accumulated(non_mem),
+        }
+    }
+
+    /// The element should not be a member and should be in the domain
+    fn check_before_add(
+        &self,
+        element: &E::ScalarField,
+        mem_state: &dyn State<E::ScalarField>,
+        non_mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.mem.check_before_add(element, mem_state)?;
+        if !non_mem_state.has(element) {
+            return Err(VBAccumulatorError::ElementOutsideDomain);
+        }
+        Ok(())
+    }
+
+    fn check_before_remove(
+        &self,
+        element: &E::ScalarField,
+        mem_state: &dyn State<E::ScalarField>,
+        non_mem_state: &dyn State<E::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.mem.check_before_remove(element, mem_state)?;
+        self.non_mem.check_before_add(element, non_mem_state)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{persistence::test::*, setup::Keypair, test_serialization};
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    /// Setup a KB universal accumulator with a random domain of size `domain_size`, its keys, params and states
+    /// for testing.
+    /// Setup params, keypair, accumulator, domain and the states of members and non-members
+    pub type KBUniversalAccumSetup = (
+        SetupParams<Bls12_381>,
+        Keypair<Bls12_381>,
+        KBUniversalAccumulator<Bls12_381>,
+        Vec<Fr>,
+        InMemoryState<Fr>,
+        InMemoryState<Fr>,
+    );
+
+    pub fn setup_kb_universal_accum(rng: &mut StdRng, domain_size: usize) -> KBUniversalAccumSetup {
+        let params = SetupParams::<Bls12_381>::generate_using_rng(rng);
+        let keypair = Keypair::<Bls12_381>::generate_using_rng(rng, &params);
+
+        let domain = (0..domain_size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let mem_state = InMemoryState::new();
+        let mut non_mem_state = InMemoryState::new();
+        let accumulator = KBUniversalAccumulator::initialize(
+            &params,
+            &keypair.secret_key,
+            domain.clone(),
+            &mut non_mem_state,
+        )
+        .unwrap();
+        (
+            params,
+            keypair,
+            accumulator,
+            domain,
+            mem_state,
+            non_mem_state,
+        )
+    }
+
+    #[test]
+    fn membership_non_membership() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 20);
+        let sk = &keypair.secret_key;
+        let pk = &keypair.public_key;
+
+        test_serialization!(KBUniversalAccumulator<Bls12_381>, accumulator);
+        assert_eq!(non_mem_state.size(), 20);
+
+        // Elements outside the domain can't be added and have no non-membership witness
+        let outside = Fr::rand(&mut rng);
+        assert!(matches!(
+            accumulator.add(outside, sk, &mut mem_state, &mut non_mem_state),
+            Err(VBAccumulatorError::ElementOutsideDomain)
+        ));
+        assert!(accumulator
+            .get_non_membership_witness(&outside, sk, &non_mem_state)
+            .is_err());
+
+        // `domain[0]` stays a member and `domain[19]` stays a non-member while the rest are added and removed.
+        // Their witnesses are updated by the holders after each update.
+        accumulator = accumulator
+            .add(domain[0], sk, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        let mut m_wit = accumulator
+            .get_membership_witness(&domain[0], sk, &mem_state)
+            .unwrap();
+        let mut nm_wit = accumulator
+            .get_non_membership_witness(&domain[19], sk, &non_mem_state)
+            .unwrap();
+        assert!(accumulator.verify_membership(&domain[0], &m_wit, pk, &params));
+        assert!(accumulator.verify_non_membership(&domain[19], &nm_wit, pk, &params));
+
+        for element in domain.iter().take(10).skip(1) {
+            assert!(accumulator
+                .get_membership_witness(element, sk, &mem_state)
+                .is_err());
+            let w = accumulator
+                .get_non_membership_witness(element, sk, &non_mem_state)
+                .unwrap();
+            assert!(accumulator.verify_non_membership(element, &w, pk, &params));
+
+            let new_accumulator = accumulator
+                .add(*element, sk, &mut mem_state, &mut non_mem_state)
+                .unwrap();
+            m_wit = m_wit.update_after_addition(&domain[0], element, accumulator.mem_value());
+            nm_wit = nm_wit
+                .update_after_addition(&domain[19], element, new_accumulator.non_mem_value())
+                .unwrap();
+            accumulator = new_accumulator;
+
+            assert!(!accumulator.verify_non_membership(element, &w, pk, &params));
+            let w = accumulator
+                .get_membership_witness(element, sk, &mem_state)
+                .unwrap();
+            assert!(accumulator.verify_membership(element, &w, pk, &params));
+            assert!(accumulator.verify_membership(&domain[0], &m_wit, pk, &params));
+            assert!(accumulator.verify_non_membership(&domain[19], &nm_wit, pk, &params));
+        }
+
+        assert!(matches!(
+            accumulator.add(domain[1], sk, &mut mem_state, &mut non_mem_state),
+            Err(VBAccumulatorError::ElementPresent)
+        ));
+        assert!(matches!(
+            accumulator.remove(&domain[15], sk, &mut mem_state, &mut non_mem_state),
+            Err(VBAccumulatorError::ElementAbsent)
+        ));
+
+        for element in domain.iter().take(10).skip(1) {
+            let new_accumulator = accumulator
+                .remove(element, sk, &mut mem_state, &mut non_mem_state)
+                .unwrap();
+            m_wit = m_wit
+                .update_after_removal(&domain[0], element, new_accumulator.mem_value())
+                .unwrap();
+            nm_wit = nm_wit.update_after_removal(&domain[19], element, accumulator.non_mem_value());
+            accumulator = new_accumulator;
+
+            let w = accumulator
+                .get_non_membership_witness(element, sk, &non_mem_state)
+                .unwrap();
+            assert!(accumulator.verify_non_membership(element, &w, pk, &params));
+            assert!(accumulator.verify_membership(&domain[0], &m_wit, pk, &params));
+            assert!(accumulator.verify_non_membership(&domain[19], &nm_wit, pk, &params));
+        }
+        assert_eq!(mem_state.size(), 1);
+        assert_eq!(non_mem_state.size(), 19);
+
+        // Extend the domain
+        assert!(matches!(
+            accumulator.extend_domain(sk, vec![domain[0]], &mem_state, &mut non_mem_state),
+            Err(VBAccumulatorError::ElementPresent)
+        ));
+        let new_element = Fr::rand(&mut rng);
+        let new_accumulator = accumulator
+            .extend_domain(sk, vec![new_element], &mem_state, &mut non_mem_state)
+            .unwrap();
+        assert_eq!(
+            new_accumulator,
+            accumulator.compute_extended(&[new_element], sk)
+        );
+        assert_eq!(new_accumulator.mem_value(), accumulator.mem_value());
+        nm_wit = nm_wit.update_after_domain_extension(
+            &domain[19],
+            &new_element,
+            accumulator.non_mem_value(),
+        );
+        accumulator = new_accumulator;
+        assert!(accumulator.verify_membership(&domain[0], &m_wit, pk, &params));
+        assert!(accumulator.verify_non_membership(&domain[19], &nm_wit, pk, &params));
+
+        accumulator = accumulator
+            .add(new_element, sk, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        let w = accumulator
+            .get_membership_witness(&new_element, sk, &mem_state)
+            .unwrap();
+        let verification_accumulator = KBUniversalAccumulator::<Bls12_381>::from_accumulated(
+            *accumulator.mem_value(),
+            *accumulator.non_mem_value(),
+        );
+        assert!(verification_accumulator.verify_membership(&new_element, &w, pk, &params));
+
+        // A membership witness doesn't work as a non-membership witness
+        let w = KBUniversalAccumulatorNonMembershipWitness(w.0.clone());
+        assert!(!verification_accumulator.verify_non_membership(&new_element, &w, pk, &params));
+    }
+
+    #[test]
+    fn batch_updates_and_witness_updates() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 40);
+        let sk = &keypair.secret_key;
+        let pk = &keypair.public_key;
+
+        let members = domain[0..5].to_vec();
+        let non_members = domain[35..40].to_vec();
+
+        accumulator = accumulator
+            .add_batch(members.clone(), sk, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        assert_eq!(
+            accumulator,
+            KBUniversalAccumulator::initialize(
+                &params,
+                sk,
+                domain.clone(),
+                &mut InMemoryState::new()
+            )
+            .unwrap()
+            .compute_new_post_add_batch(&members, sk)
+        );
+
+        let m_wits = accumulator
+            .get_membership_witnesses_for_batch(&members, sk, &mem_state)
+            .unwrap();
+        let nm_wits = accumulator
+            .get_non_membership_witnesses_for_batch(&non_members, sk, &non_mem_state)
+            .unwrap();
+        assert!(accumulator
+            .get_non_membership_witnesses_for_batch(&members, sk, &non_mem_state)
+            .is_err());
+        for i in 0..5 {
+            assert_eq!(
+                m_wits[i],
+                accumulator.compute_membership_witness(&members[i], sk)
+            );
+            assert_eq!(
+                nm_wits[i],
+                accumulator.compute_non_membership_witness(&non_members[i], sk)
+            );
+            assert!(accumulator.verify_membership(&members[i], &m_wits[i], pk, &params));
+            assert!(accumulator.verify_non_membership(&non_members[i], &nm_wits[i], pk, &params));
+        }
+
+        // 3 batches of updates, each adding some elements of the domain and removing some members
+        let batches = [
+            (domain[5..10].to_vec(), vec![]),
+            (domain[10..15].to_vec(), domain[5..8].to_vec()),
+            (domain[15..20].to_vec(), domain[8..12].to_vec()),
+        ];
+        let mut m_omegas = vec![];
+        let mut nm_omegas = vec![];
+        let mut m_wits_sk = m_wits.clone();
+        let mut nm_wits_sk = nm_wits.clone();
+        let mut m_wits_pub = m_wits.clone();
+        let mut nm_wits_pub = nm_wits.clone();
+        for (additions, removals) in batches.iter() {
+            let m_omega =
+                accumulator.generate_omega_for_membership_witnesses(additions, removals, sk);
+            let nm_omega =
+                accumulator.generate_omega_for_non_membership_witnesses(additions, removals, sk);
+            let new_accumulator = accumulator
+                .batch_updates(
+                    additions.clone(),
+                    removals,
+                    sk,
+                    &mut mem_state,
+                    &mut non_mem_state,
+                )
+                .unwrap();
+            assert_eq!(
+                new_accumulator,
+                accumulator.compute_new_post_batch_updates(additions, removals, sk)
+            );
+
+            m_wits_sk = KBUniversalAccumulatorMembershipWitness::update_using_secret_key_after_batch_updates(
+                additions,
+                removals,
+                &members,
+                &m_wits_sk,
+                accumulator.mem_value(),
+                sk,
+            )
+            .unwrap();
+            nm_wits_sk = KBUniversalAccumulatorNonMembershipWitness::update_using_secret_key_after_batch_updates(
+                additions,
+                removals,
+                &non_members,
+                &nm_wits_sk,
+                accumulator.non_mem_value(),
+                sk,
+            )
+            .unwrap();
+            accumulator = new_accumulator;
+
+            for i in 0..5 {
+                m_wits_pub[i] = m_wits_pub[i]
+                    .update_using_public_info_after_batch_updates(
+                        additions,
+                        removals,
+                        &m_omega,
+                        &members[i],
+                    )
+                    .unwrap();
+                assert_eq!(m_wits_pub[i], m_wits_sk[i]);
+                assert!(accumulator.verify_membership(&members[i], &m_wits_pub[i], pk, &params));
+                nm_wits_pub[i] = nm_wits_pub[i]
+                    .update_using_public_info_after_batch_updates(
+                        additions,
+                        removals,
+                        &nm_omega,
+                        &non_members[i],
+                    )
+                    .unwrap();
+                assert_eq!(nm_wits_pub[i], nm_wits_sk[i]);
+                assert!(accumulator.verify_non_membership(
+                    &non_members[i],
+                    &nm_wits_pub[i],
+                    pk,
+                    &params
+                ));
+            }
+            m_omegas.push(m_omega);
+            nm_omegas.push(nm_omega);
+        }
+
+        // Update the original witnesses using all batches at once
+        for i in 0..5 {
+            let m_wit = m_wits[i]
+                .update_using_public_info_after_multiple_batch_updates(
+                    batches
+                        .iter()
+                        .zip(m_omegas.iter())
+                        .map(|((a, r), o)| (a.as_slice(), r.as_slice(), o))
+                        .collect(),
+                    &members[i],
+                )
+                .unwrap();
+            assert!(accumulator.verify_membership(&members[i], &m_wit, pk, &params));
+            let nm_wit = nm_wits[i]
+                .update_using_public_info_after_multiple_batch_updates(
+                    batches
+                        .iter()
+                        .zip(nm_omegas.iter())
+                        .map(|((a, r), o)| (a.as_slice(), r.as_slice(), o))
+                        .collect(),
+                    &non_members[i],
+                )
+                .unwrap();
+            assert!(accumulator.verify_non_membership(&non_members[i], &nm_wit, pk, &params));
+        }
+
+        // Remove a batch
+        let removals = domain[12..20].to_vec();
+        let new_accumulator = accumulator
+            .remove_batch(&removals, sk, &mut mem_state, &mut non_mem_state)
+            .unwrap();
+        assert_eq!(
+            new_accumulator,
+            accumulator.compute_new_post_remove_batch(&removals, sk)
+        );
+        accumulator = new_accumulator;
+        assert_eq!(mem_state.size(), 5);
+        assert_eq!(non_mem_state.size(), 35);
+        for member in members.iter() {
+            assert!(accumulator.verify_membership(
+                member,
+                &accumulator.compute_membership_witness(member, sk),
+                pk,
+                &params
+            ));
+        }
+
+        // Extend the domain with a batch of new elements
+        let new_elements = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let omega = accumulator.generate_omega_for_domain_extension(&new_elements, sk);
+        let nm_wits = accumulator.compute_non_membership_witnesses_for_batch(&non_members, sk);
+        let new_accumulator = accumulator
+            .extend_domain(sk, new_elements.clone(), &mem_state, &mut non_mem_state)
+            .unwrap();
+        let nm_wits_sk =
+            KBUniversalAccumulatorNonMembershipWitness::update_using_secret_key_after_domain_extension(
+                &new_elements,
+                &non_members,
+                &nm_wits,
+                accumulator.non_mem_value(),
+                sk,
+            )
+            .unwrap();
+        accumulator = new_accumulator;
+        for i in 0..5 {
+            let nm_wit = nm_wits[i]
+                .update_using_public_info_after_domain_extension(
+                    &new_elements,
+                    &omega,
+                    &non_members[i],
+                )
+                .unwrap();
+            assert_eq!(nm_wit, nm_wits_sk[i]);
+            assert!(accumulator.verify_non_membership(&non_members[i], &nm_wit, pk, &params));
+        }
+        for e in new_elements.iter() {
+            let w = accumulator
+                .get_non_membership_witness(e, sk, &non_mem_state)
+                .unwrap();
+            assert!(accumulator.verify_non_membership(e, &w, pk, &params));
+        }
+    }
+}
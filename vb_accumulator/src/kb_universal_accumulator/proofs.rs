@@ -0,0 +1,368 @@
+//! Zero knowledge proofs of membership and non-membership in [`KBUniversalAccumulator`]. Both are proofs of
+//! membership from [`proofs`], the former in the accumulator of members and the latter in the accumulator of
+//! non-members, so the accumulator value passed to the proof of non-membership must be
+//! [`KBUniversalAccumulator::non_mem_value`]. Both use the same [`MembershipProvingKey`].
+//!
+//! [`KBUniversalAccumulator`]: crate::kb_universal_accumulator::KBUniversalAccumulator
+//! [`KBUniversalAccumulator::non_mem_value`]: crate::kb_universal_accumulator::KBUniversalAccumulator::non_mem_value
+//! [`proofs`]: crate::proofs
+
+use crate::{
+    error::VBAccumulatorError,
+    kb_universal_accumulator::witness::{
+        KBUniversalAccumulatorMembershipWitness, KBUniversalAccumulatorNonMembershipWitness,
+    },
+    proofs::{MembershipProof, MembershipProofProtocol, MembershipProvingKey},
+    setup::{PreparedPublicKey, PreparedSetupParams, PublicKey, SetupParams},
+};
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{io::Write, rand::RngCore, vec::Vec};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// Protocol for proving knowledge of the member and the membership witness
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Zeroize,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorMembershipProofProtocol<E: Pairing>(
+    pub MembershipProofProtocol<E>,
+);
+
+/// Proof of knowledge of the member and the membership witness
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorMembershipProof<E: Pairing>(pub MembershipProof<E>);
+
+/// Protocol for proving knowledge of the non-member and the non-membership witness
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Zeroize,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorNonMembershipProofProtocol<E: Pairing>(
+    pub MembershipProofProtocol<E>,
+);
+
+/// Proof of knowledge of the non-member and the non-membership witness
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorNonMembershipProof<E: Pairing>(pub MembershipProof<E>);
+
+macro_rules! impl_protocol_and_proof {
+    ($protocol: ident, $proof: ident, $witness: ident) => {
+        impl<E: Pairing> $protocol<E> {
+            /// Initialize the protocol. Delegates to [`MembershipProofProtocol::init`]
+            pub fn init<R: RngCore>(
+                rng: &mut R,
+                element: &E::ScalarField,
+                element_blinding: Option<E::ScalarField>,
+                witness: &$witness<E::G1Affine>,
+                pk: &PublicKey<E>,
+                params: &SetupParams<E>,
+                prk: &MembershipProvingKey<E::G1Affine>,
+            ) -> Self {
+                Self(MembershipProofProtocol::init(
+                    rng,
+                    element,
+                    element_blinding,
+                    &witness.0,
+                    pk,
+                    params,
+                    prk,
+                ))
+            }
+
+            /// Contribution of this protocol to the overall challenge (when using this protocol as a sub-protocol).
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                accumulator_value: &E::G1Affine,
+                pk: &PublicKey<E>,
+                params: &SetupParams<E>,
+                prk: &MembershipProvingKey<E::G1Affine>,
+                writer: W,
+            ) -> Result<(), VBAccumulatorError> {
+                self.0
+                    .challenge_contribution(accumulator_value, pk, params, prk, writer)
+            }
+
+            /// Create proof once the overall challenge is ready.
+            pub fn gen_proof(self, challenge: &E::ScalarField) -> $proof<E> {
+                $proof(self.0.gen_proof(challenge))
+            }
+        }
+
+        impl<E: Pairing> $proof<E> {
+            /// Challenge contribution for this proof
+            pub fn challenge_contribution<W: Write>(
+                &self,
+                accumulator_value: &E::G1Affine,
+                pk: &PublicKey<E>,
+                params: &SetupParams<E>,
+                prk: &MembershipProvingKey<E::G1Affine>,
+                writer: W,
+            ) -> Result<(), VBAccumulatorError> {
+                self.0
+                    .challenge_contribution(accumulator_value, pk, params, prk, writer)
+            }
+
+            /// Verify this proof. Delegates to [`MembershipProof::verify`]
+            pub fn verify(
+                &self,
+                accumulator_value: &E::G1Affine,
+                challenge: &E::ScalarField,
+                pk: impl Into<PreparedPublicKey<E>>,
+                params: impl Into<PreparedSetupParams<E>>,
+                prk: &MembershipProvingKey<E::G1Affine>,
+            ) -> Result<(), VBAccumulatorError> {
+                self.0.verify(accumulator_value, challenge, pk, params, prk)
+            }
+
+            pub fn verify_with_randomized_pairing_checker(
+                &self,
+                accumulator_value: &E::G1Affine,
+                challenge: &E::ScalarField,
+                pk: impl Into<PreparedPublicKey<E>>,
+                params: impl Into<PreparedSetupParams<E>>,
+                prk: &MembershipProvingKey<E::G1Affine>,
+                pairing_checker: &mut RandomizedPairingChecker<E>,
+            ) -> Result<(), VBAccumulatorError> {
+                self.0.verify_with_randomized_pairing_checker(
+                    accumulator_value,
+                    challenge,
+                    pk,
+                    params,
+                    prk,
+                    pairing_checker,
+                )
+            }
+
+            /// Get response for Schnorr protocol for the element. This is useful when the element is also used
+            /// in another relation that is proven along this protocol.
+            pub fn get_schnorr_response_for_element(&self) -> &E::ScalarField {
+                self.0.get_schnorr_response_for_element()
+            }
+        }
+    };
+}
+
+impl_protocol_and_proof!(
+    KBUniversalAccumulatorMembershipProofProtocol,
+    KBUniversalAccumulatorMembershipProof,
+    KBUniversalAccumulatorMembershipWitness
+);
+
+impl_protocol_and_proof!(
+    KBUniversalAccumulatorNonMembershipProofProtocol,
+    KBUniversalAccumulatorNonMembershipProof,
+    KBUniversalAccumulatorNonMembershipWitness
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        kb_universal_accumulator::tests::setup_kb_universal_accum, persistence::State,
+        test_serialization,
+    };
+
+    use ark_bls12_381::Bls12_381;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+    use schnorr_pok::compute_random_oracle_challenge;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    #[test]
+    fn membership_and_non_membership_proofs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, domain, mut mem_state, mut non_mem_state) =
+            setup_kb_universal_accum(&mut rng, 20);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+        let prepared_params = PreparedSetupParams::from(params.clone());
+        let prepared_pk = PreparedPublicKey::from(keypair.public_key.clone());
+
+        accumulator = accumulator
+            .add_batch(
+                domain[0..10].to_vec(),
+                &keypair.secret_key,
+                &mut mem_state,
+                &mut non_mem_state,
+            )
+            .unwrap();
+        assert_eq!(mem_state.size(), 10);
+
+        let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+        for i in 0..10 {
+            let member = domain[i];
+            let non_member = domain[10 + i];
+            let m_wit = accumulator
+                .get_membership_witness(&member, &keypair.secret_key, &mem_state)
+                .unwrap();
+            let nm_wit = accumulator
+                .get_non_membership_witness(&non_member, &keypair.secret_key, &non_mem_state)
+                .unwrap();
+
+            let m_protocol = KBUniversalAccumulatorMembershipProofProtocol::init(
+                &mut rng,
+                &member,
+                None,
+                &m_wit,
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+            let nm_protocol = KBUniversalAccumulatorNonMembershipProofProtocol::init(
+                &mut rng,
+                &non_member,
+                None,
+                &nm_wit,
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+
+            let mut chal_bytes_prover = vec![];
+            m_protocol
+                .challenge_contribution(
+                    accumulator.mem_value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_prover,
+                )
+                .unwrap();
+            nm_protocol
+                .challenge_contribution(
+                    accumulator.non_mem_value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_prover,
+                )
+                .unwrap();
+            let challenge_prover =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+
+            let m_proof = m_protocol.gen_proof(&challenge_prover);
+            let nm_proof = nm_protocol.gen_proof(&challenge_prover);
+
+            let mut chal_bytes_verifier = vec![];
+            m_proof
+                .challenge_contribution(
+                    accumulator.mem_value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_verifier,
+                )
+                .unwrap();
+            nm_proof
+                .challenge_contribution(
+                    accumulator.non_mem_value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_verifier,
+                )
+                .unwrap();
+            let challenge_verifier =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            assert_eq!(challenge_prover, challenge_verifier);
+
+            m_proof
+                .verify(
+                    accumulator.mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                )
+                .unwrap();
+            nm_proof
+                .verify(
+                    accumulator.non_mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                )
+                .unwrap();
+            m_proof
+                .verify_with_randomized_pairing_checker(
+                    accumulator.mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                    &mut pairing_checker,
+                )
+                .unwrap();
+            nm_proof
+                .verify_with_randomized_pairing_checker(
+                    accumulator.non_mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                    &mut pairing_checker,
+                )
+                .unwrap();
+
+            // Proofs don't verify against the other accumulated value
+            assert!(m_proof
+                .verify(
+                    accumulator.non_mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                )
+                .is_err());
+            assert!(nm_proof
+                .verify(
+                    accumulator.mem_value(),
+                    &challenge_verifier,
+                    prepared_pk.clone(),
+                    prepared_params.clone(),
+                    &prk,
+                )
+                .is_err());
+
+            assert_eq!(
+                m_proof.get_schnorr_response_for_element(),
+                m_proof.0.get_schnorr_response_for_element()
+            );
+
+            if i == 0 {
+                test_serialization!(KBUniversalAccumulatorMembershipProof<Bls12_381>, m_proof);
+                test_serialization!(
+                    KBUniversalAccumulatorNonMembershipProof<Bls12_381>,
+                    nm_proof
+                );
+            }
+        }
+        assert!(pairing_checker.verify());
+    }
+}
@@ -0,0 +1,312 @@
+//! Membership and non-membership witnesses of [`KBUniversalAccumulator`]. Both are membership witnesses
+//! in one of the 2 positive accumulators and are updated like them. For a non-membership witness, an
+//! addition to the universal accumulator is a removal from the accumulator of non-members and vice versa.
+//!
+//! [`KBUniversalAccumulator`]: crate::kb_universal_accumulator::KBUniversalAccumulator
+
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, fmt::Debug, vec::Vec};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    batch_utils::{Omega, UpdatesAndOmegas},
+    error::VBAccumulatorError,
+    setup::SecretKey,
+    witness::MembershipWitness,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Witness to check membership. It's a membership witness in the accumulator of members.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+    Zeroize,
+    ZeroizeOnDrop,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorMembershipWitness<G: AffineRepr>(pub MembershipWitness<G>);
+
+/// Witness to check non-membership. It's a membership witness in the accumulator of non-members.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+    Zeroize,
+    ZeroizeOnDrop,
+)]
+#[serde(bound = "")]
+pub struct KBUniversalAccumulatorNonMembershipWitness<G: AffineRepr>(pub MembershipWitness<G>);
+
+impl<G: AffineRepr> From<MembershipWitness<G>> for KBUniversalAccumulatorMembershipWitness<G> {
+    fn from(w: MembershipWitness<G>) -> Self {
+        Self(w)
+    }
+}
+
+impl<G: AffineRepr> From<MembershipWitness<G>> for KBUniversalAccumulatorNonMembershipWitness<G> {
+    fn from(w: MembershipWitness<G>) -> Self {
+        Self(w)
+    }
+}
+
+impl<G> KBUniversalAccumulatorMembershipWitness<G>
+where
+    G: AffineRepr,
+{
+    /// Update the witness after an element is added to the accumulator. Needs the accumulated value of
+    /// members before the addition was done.
+    pub fn update_after_addition(
+        &self,
+        member: &G::ScalarField,
+        addition: &G::ScalarField,
+        old_accumulator: &G,
+    ) -> Self {
+        Self(
+            self.0
+                .update_after_addition(member, addition, old_accumulator),
+        )
+    }
+
+    /// Update the witness after an element is removed from the accumulator. Needs the accumulated value
+    /// of members after the removal was done.
+    pub fn update_after_removal(
+        &self,
+        member: &G::ScalarField,
+        removal: &G::ScalarField,
+        new_accumulator: &G,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(self.0.update_after_removal(
+            member,
+            removal,
+            new_accumulator,
+        )?))
+    }
+
+    /// Compute an update to several witnesses after adding and removing batches of elements from the
+    /// accumulator. Expects the accumulated value of members before the update and knowledge of the
+    /// secret key. Intended to be used by the manager
+    pub fn update_using_secret_key_after_batch_updates(
+        additions: &[G::ScalarField],
+        removals: &[G::ScalarField],
+        members: &[G::ScalarField],
+        old_witnesses: &[Self],
+        old_accumulator: &G,
+        sk: &SecretKey<G::ScalarField>,
+    ) -> Result<Vec<Self>, VBAccumulatorError> {
+        let old = inner_witnesses(old_witnesses);
+        let new = MembershipWitness::update_using_secret_key_after_batch_updates(
+            additions,
+            removals,
+            members,
+            &old,
+            old_accumulator,
+            sk,
+        )?;
+        Ok(new.into_iter().map(Self).collect())
+    }
+
+    /// Compute an update to the witness after adding and removing batches of elements from the accumulator.
+    /// Expects the update-info (`Omega`) published by the manager for membership witnesses.
+    pub fn update_using_public_info_after_batch_updates(
+        &self,
+        additions: &[G::ScalarField],
+        removals: &[G::ScalarField],
+        omega: &Omega<G>,
+        member: &G::ScalarField,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(self.0.update_using_public_info_after_batch_updates(
+            additions, removals, omega, member,
+        )?))
+    }
+
+    /// Compute an update to the witness after adding and removing several batches of elements from the
+    /// accumulator. Expects the update-info (`Omega`) published by the manager for membership witnesses
+    /// for each batch.
+    pub fn update_using_public_info_after_multiple_batch_updates(
+        &self,
+        updates_and_omegas: UpdatesAndOmegas<G>,
+        member: &G::ScalarField,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(
+            self.0
+                .update_using_public_info_after_multiple_batch_updates(
+                    updates_and_omegas,
+                    member,
+                )?,
+        ))
+    }
+}
+
+impl<G> KBUniversalAccumulatorNonMembershipWitness<G>
+where
+    G: AffineRepr,
+{
+    /// Update the witness after an element is added to the accumulator. Needs the accumulated value of
+    /// non-members after the addition was done.
+    pub fn update_after_addition(
+        &self,
+        non_member: &G::ScalarField,
+        addition: &G::ScalarField,
+        new_accumulator: &G,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(self.0.update_after_removal(
+            non_member,
+            addition,
+            new_accumulator,
+        )?))
+    }
+
+    /// Update the witness after an element is removed from the accumulator. Needs the accumulated value
+    /// of non-members before the removal was done.
+    pub fn update_after_removal(
+        &self,
+        non_member: &G::ScalarField,
+        removal: &G::ScalarField,
+        old_accumulator: &G,
+    ) -> Self {
+        Self(
+            self.0
+                .update_after_addition(non_member, removal, old_accumulator),
+        )
+    }
+
+    /// Update the witness after the domain is extended with an element. Needs the accumulated value of
+    /// non-members before the extension.
+    pub fn update_after_domain_extension(
+        &self,
+        non_member: &G::ScalarField,
+        new_element: &G::ScalarField,
+        old_accumulator: &G,
+    ) -> Self {
+        Self(
+            self.0
+                .update_after_addition(non_member, new_element, old_accumulator),
+        )
+    }
+
+    /// Compute an update to several witnesses after adding and removing batches of elements from the
+    /// accumulator. Expects the accumulated value of non-members before the update and knowledge of the
+    /// secret key. Intended to be used by the manager
+    pub fn update_using_secret_key_after_batch_updates(
+        additions: &[G::ScalarField],
+        removals: &[G::ScalarField],
+        non_members: &[G::ScalarField],
+        old_witnesses: &[Self],
+        old_accumulator: &G,
+        sk: &SecretKey<G::ScalarField>,
+    ) -> Result<Vec<Self>, VBAccumulatorError> {
+        let old = inner_witnesses(old_witnesses);
+        let new = MembershipWitness::update_using_secret_key_after_batch_updates(
+            removals,
+            additions,
+            non_members,
+            &old,
+            old_accumulator,
+            sk,
+        )?;
+        Ok(new.into_iter().map(Self).collect())
+    }
+
+    /// Compute an update to several witnesses after the domain is extended with `new_elements`. Expects the
+    /// accumulated value of non-members before the extension and knowledge of the secret key. Intended to
+    /// be used by the manager
+    pub fn update_using_secret_key_after_domain_extension(
+        new_elements: &[G::ScalarField],
+        non_members: &[G::ScalarField],
+        old_witnesses: &[Self],
+        old_accumulator: &G,
+        sk: &SecretKey<G::ScalarField>,
+    ) -> Result<Vec<Self>, VBAccumulatorError> {
+        let old = inner_witnesses(old_witnesses);
+        let new = MembershipWitness::update_using_secret_key_after_batch_additions(
+            new_elements,
+            non_members,
+            &old,
+            old_accumulator,
+            sk,
+        )?;
+        Ok(new.into_iter().map(Self).collect())
+    }
+
+    /// Compute an update to the witness after adding and removing batches of elements from the accumulator.
+    /// Expects the update-info (`Omega`) published by the manager for non-membership witnesses.
+    pub fn update_using_public_info_after_batch_updates(
+        &self,
+        additions: &[G::ScalarField],
+        removals: &[G::ScalarField],
+        omega: &Omega<G>,
+        non_member: &G::ScalarField,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(self.0.update_using_public_info_after_batch_updates(
+            removals, additions, omega, non_member,
+        )?))
+    }
+
+    /// Compute an update to the witness after the domain is extended with `new_elements`. Expects the
+    /// update-info (`Omega`) published by the manager for the extension.
+    pub fn update_using_public_info_after_domain_extension(
+        &self,
+        new_elements: &[G::ScalarField],
+        omega: &Omega<G>,
+        non_member: &G::ScalarField,
+    ) -> Result<Self, VBAccumulatorError> {
+        Ok(Self(self.0.update_using_public_info_after_batch_updates(
+            new_elements,
+            &[],
+            omega,
+            non_member,
+        )?))
+    }
+
+    /// Compute an update to the witness after adding and removing several batches of elements from the
+    /// accumulator. Expects the update-info (`Omega`) published by the manager for non-membership witnesses
+    /// for each batch.
+    pub fn update_using_public_info_after_multiple_batch_updates(
+        &self,
+        updates_and_omegas: UpdatesAndOmegas<G>,
+        non_member: &G::ScalarField,
+    ) -> Result<Self, VBAccumulatorError> {
+        let swapped = updates_and_omegas
+            .into_iter()
+            .map(|(additions, removals, omega)| (removals, additions, omega))
+            .collect();
+        Ok(Self(
+            self.0
+                .update_using_public_info_after_multiple_batch_updates(swapped, non_member)?,
+        ))
+    }
+}
+
+fn inner_witnesses<G: AffineRepr, W: AsRef<MembershipWitness<G>> + Sync>(
+    witnesses: &[W],
+) -> Vec<MembershipWitness<G>> {
+    cfg_iter!(witnesses).map(|w| w.as_ref().clone()).collect()
+}
+
+impl<G: AffineRepr> AsRef<MembershipWitness<G>> for KBUniversalAccumulatorMembershipWitness<G> {
+    fn as_ref(&self) -> &MembershipWitness<G> {
+        &self.0
+    }
+}
+
+impl<G: AffineRepr> AsRef<MembershipWitness<G>> for KBUniversalAccumulatorNonMembershipWitness<G> {
+    fn as_ref(&self) -> &MembershipWitness<G> {
+        &self.0
+    }
+}
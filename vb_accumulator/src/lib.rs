@@ -5,7 +5,13 @@
 //! Provides
 //! - a dynamic positive accumulator [`PositiveAccumulator`], that supports membership proofs.
 //! - a dynamic universal accumulator [`UniversalAccumulator`], that supports membership and non-membership proofs.
+//! - a dynamic universal accumulator [`KBUniversalAccumulator`] made of 2 positive accumulators, that supports
+//!   membership and non-membership proofs without a maximum size or generating initial elements at setup.
 //! - a zero knowledge proof of membership and non-membership in the accumulators with [`ProofProtocol`].
+//! - a zero knowledge proof of membership and non-membership verified with the secret key instead of pairings in
+//!   [`proofs_keyed_verification`].
+//! - a zero knowledge proof of membership or non-membership of several elements in the same accumulator in
+//!   [`proofs_batch`] which is smaller and faster to verify than separate proofs.
 //!
 //! Allows
 //! - single and batch updates (additions, removals or both) to the accumulators.
@@ -16,28 +22,52 @@
 //! info published by accumulator manager called [`Omega`].
 //! Most of the update logic is in the trait [`Witness`] which is implemented by both [`MembershipWitness`]
 //! and [`NonMembershipWitness`].
+//! The manager can record each update in an epoch with [`AccumulatorRegistry`] which publishes the update info for
+//! holders.
+//! Each batch update can be accompanied by an [`UpdateProof`] so that anyone with the public key can check that the
+//! new accumulator has exactly the published updates applied.
+//! The manager can re-issue the witnesses of all members after large updates with [`WitnessRefresher`].
+//! The accumulator secret key can be shared among several managers with [`threshold`], with feature `threshold`, so
+//! that a threshold number of them is needed to update the accumulator or create witnesses.
+//! Byte identifiers like credential ids can be encoded as members with [`MemberEncoder`] and the manager can decode
+//! members back to identifiers with [`MemberDecodingRegistry`].
 //! The implementation tries to use the same variable names as the paper and thus violate Rust's naming conventions at places.
 //!
 //! [`Accumulator`]: crate::positive::Accumulator
 //! [`PositiveAccumulator`]: crate::positive::PositiveAccumulator
 //! [`UniversalAccumulator`]: crate::universal::UniversalAccumulator
+//! [`KBUniversalAccumulator`]: crate::kb_universal_accumulator::KBUniversalAccumulator
 //! [`MembershipWitness`]: crate::witness::MembershipWitness
 //! [`NonMembershipWitness`]: crate::witness::NonMembershipWitness
 //! [`Witness`]: crate::witness::Witness
 //! [`Omega`]: crate::batch_utils::Omega
 //! [`ProofProtocol`]: crate::proofs::ProofProtocol
+//! [`AccumulatorRegistry`]: crate::registry::AccumulatorRegistry
+//! [`UpdateProof`]: crate::update_proof::UpdateProof
+//! [`WitnessRefresher`]: crate::witness_refresh::WitnessRefresher
+//! [`MemberEncoder`]: crate::member_encoding::MemberEncoder
+//! [`MemberDecodingRegistry`]: crate::member_encoding::MemberDecodingRegistry
 
 #[macro_use]
 pub mod utils;
 pub mod batch_utils;
 pub mod error;
+pub mod kb_universal_accumulator;
+pub mod member_encoding;
 pub mod persistence;
 pub mod positive;
 pub mod proofs;
+pub mod proofs_batch;
+pub mod proofs_keyed_verification;
+pub mod registry;
 pub mod setup;
+#[cfg(feature = "threshold")]
+pub mod threshold;
 pub mod universal;
 pub mod universal_init_constants;
+pub mod update_proof;
 pub mod witness;
+pub mod witness_refresh;
 
 pub mod prelude {
     pub use crate::{
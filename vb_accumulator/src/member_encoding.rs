@@ -0,0 +1,334 @@
+//! Standard encoding of byte identifiers, like credential ids, as accumulator members.
+//!
+//! [`MemberEncoder`] hashes an identifier to the scalar field using `hash_to_field` of RFC 9380 with a domain
+//! separation tag made of [`MEMBER_ENCODING_DST_PREFIX`] and an application chosen domain, like the accumulator's id,
+//! so that the same identifier gives unrelated members in different applications. A single byte tweak is appended
+//! to the identifier before hashing. It's 0 unless the member isn't acceptable in the accumulator, like when it's
+//! one of the initial elements of the [`UniversalAccumulator`], in which case the next tweak is tried.
+//!
+//! Since members can't be decoded, the accumulator manager keeps the identifier of each encoded member in a
+//! [`MemberDecodingRegistry`] to go from the member back to the identifier.
+//!
+//! ```ignore
+//! let encoder = MemberEncoder::<Blake2b512>::new(b"my-accumulator");
+//! let mut registry = MemberDecodingRegistry::new(encoder, BTreeMap::new());
+//! let member = registry.encode_for_universal_accumulator(b"credential-1", &accumulator, &initial_elements)?;
+//! accumulator = accumulator.add(member, &sk, &initial_elements, &mut state)?;
+//! assert_eq!(registry.decode(&member), Some(b"credential-1".as_slice()));
+//! ```
+
+use crate::{
+    error::VBAccumulatorError, persistence::InitialElementsStore, universal::UniversalAccumulator,
+};
+use ark_ec::pairing::Pairing;
+use ark_ff::{
+    field_hashers::{DefaultFieldHasher, HashToField},
+    PrimeField,
+};
+use ark_std::{collections::BTreeMap, marker::PhantomData, vec::Vec};
+use digest::DynDigest;
+
+/// Prefix of the domain separation tag used when encoding identifiers as members
+pub const MEMBER_ENCODING_DST_PREFIX: &[u8] = b"VB-ACCUMULATOR-MEMBER-ENCODING-V01-";
+
+/// Encodes byte identifiers as accumulator members using `hash_to_field` with hash function `D`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberEncoder<D> {
+    dst: Vec<u8>,
+    _phantom: PhantomData<D>,
+}
+
+/// Storage of the identifiers of encoded members. Only the accumulator manager needs to keep this.
+pub trait MemberIdentifierStore<F> {
+    /// Add the identifier of the member
+    fn add(&mut self, member: F, identifier: Vec<u8>);
+
+    /// Get the identifier of the member
+    fn get(&self, member: &F) -> Option<&[u8]>;
+
+    /// Remove the identifier of the member
+    fn remove(&mut self, member: &F);
+}
+
+impl<F: Ord> MemberIdentifierStore<F> for BTreeMap<F, Vec<u8>> {
+    fn add(&mut self, member: F, identifier: Vec<u8>) {
+        self.insert(member, identifier);
+    }
+
+    fn get(&self, member: &F) -> Option<&[u8]> {
+        BTreeMap::get(self, member).map(|i| i.as_slice())
+    }
+
+    fn remove(&mut self, member: &F) {
+        BTreeMap::remove(self, member);
+    }
+}
+
+/// Encodes identifiers with a [`MemberEncoder`] and keeps their identifiers in the store to decode members
+#[derive(Clone, Debug)]
+pub struct MemberDecodingRegistry<F, D, S: MemberIdentifierStore<F>> {
+    encoder: MemberEncoder<D>,
+    store: S,
+    _phantom: PhantomData<F>,
+}
+
+impl<D: Default + DynDigest + Clone> MemberEncoder<D> {
+    /// Create an encoder for the given domain, like the id of the accumulator. The domain should be short as the
+    /// domain separation tag can't be longer than 255 bytes.
+    pub fn new(domain: &[u8]) -> Self {
+        let mut dst = MEMBER_ENCODING_DST_PREFIX.to_vec();
+        dst.extend_from_slice(domain);
+        Self {
+            dst,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Domain separation tag used for hashing
+    pub fn dst(&self) -> &[u8] {
+        &self.dst
+    }
+
+    /// Encode the identifier with tweak 0. Use this when the accumulator accepts any member like the
+    /// [`PositiveAccumulator`](crate::positive::PositiveAccumulator).
+    pub fn encode<F: PrimeField>(&self, identifier: &[u8]) -> F {
+        self.encode_with_tweak(identifier, 0)
+    }
+
+    /// Encode the identifier with the given tweak
+    pub fn encode_with_tweak<F: PrimeField>(&self, identifier: &[u8], tweak: u8) -> F {
+        let hasher = <DefaultFieldHasher<D> as HashToField<F>>::new(&self.dst);
+        Self::hash(&hasher, identifier, tweak)
+    }
+
+    /// Encode the identifier as a member acceptable in the universal accumulator, i.e. not one of its initial elements,
+    /// by trying tweaks in increasing order. Returns the member and the tweak used.
+    pub fn encode_for_universal_accumulator<E: Pairing>(
+        &self,
+        identifier: &[u8],
+        accumulator: &UniversalAccumulator<E>,
+        initial_elements_store: &dyn InitialElementsStore<E::ScalarField>,
+    ) -> Result<(E::ScalarField, u8), VBAccumulatorError> {
+        let hasher = <DefaultFieldHasher<D> as HashToField<E::ScalarField>>::new(&self.dst);
+        for tweak in 0..=u8::MAX {
+            let member = Self::hash(&hasher, identifier, tweak);
+            if accumulator.is_element_acceptable(&member, initial_elements_store) {
+                return Ok((member, tweak));
+            }
+        }
+        Err(VBAccumulatorError::NoAcceptableEncoding)
+    }
+
+    fn hash<F: PrimeField>(hasher: &DefaultFieldHasher<D>, identifier: &[u8], tweak: u8) -> F {
+        let mut msg = Vec::with_capacity(identifier.len() + 1);
+        msg.extend_from_slice(identifier);
+        msg.push(tweak);
+        hasher.hash_to_field(&msg, 1).pop().unwrap()
+    }
+}
+
+impl<F: PrimeField, D: Default + DynDigest + Clone, S: MemberIdentifierStore<F>>
+    MemberDecodingRegistry<F, D, S>
+{
+    pub fn new(encoder: MemberEncoder<D>, store: S) -> Self {
+        Self {
+            encoder,
+            store,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Encode the identifier with [`MemberEncoder::encode`] and keep it for decoding
+    pub fn encode(&mut self, identifier: &[u8]) -> Result<F, VBAccumulatorError> {
+        let member = self.encoder.encode(identifier);
+        self.register(member, identifier)?;
+        Ok(member)
+    }
+
+    /// Encode the identifier with [`MemberEncoder::encode_for_universal_accumulator`] and keep it for decoding
+    pub fn encode_for_universal_accumulator<E: Pairing<ScalarField = F>>(
+        &mut self,
+        identifier: &[u8],
+        accumulator: &UniversalAccumulator<E>,
+        initial_elements_store: &dyn InitialElementsStore<F>,
+    ) -> Result<F, VBAccumulatorError> {
+        let (member, _) = self.encoder.encode_for_universal_accumulator(
+            identifier,
+            accumulator,
+            initial_elements_store,
+        )?;
+        self.register(member, identifier)?;
+        Ok(member)
+    }
+
+    /// Get the identifier that was encoded as the member
+    pub fn decode(&self, member: &F) -> Option<&[u8]> {
+        self.store.get(member)
+    }
+
+    /// Stop keeping the identifier of the member
+    pub fn remove(&mut self, member: &F) {
+        self.store.remove(member)
+    }
+
+    pub fn encoder(&self) -> &MemberEncoder<D> {
+        &self.encoder
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    fn register(&mut self, member: F, identifier: &[u8]) -> Result<(), VBAccumulatorError> {
+        match self.store.get(&member) {
+            Some(i) if i == identifier => Ok(()),
+            Some(_) => Err(VBAccumulatorError::MemberEncodingCollision),
+            None => {
+                self.store.add(member, identifier.to_vec());
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        persistence::{test::InMemoryInitialElements, State},
+        positive::{tests::setup_positive_accum, Accumulator},
+        universal::tests::setup_universal_accum,
+    };
+
+    use ark_bls12_381::Bls12_381;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    #[test]
+    fn encode_identifiers() {
+        let encoder_1 = MemberEncoder::<Blake2b512>::new(b"accumulator-1");
+        let encoder_2 = MemberEncoder::<Blake2b512>::new(b"accumulator-2");
+        assert_eq!(
+            encoder_1.dst(),
+            b"VB-ACCUMULATOR-MEMBER-ENCODING-V01-accumulator-1"
+        );
+
+        let m = encoder_1.encode::<Fr>(b"credential-1");
+        assert_eq!(m, encoder_1.encode::<Fr>(b"credential-1"));
+        assert_eq!(m, encoder_1.encode_with_tweak::<Fr>(b"credential-1", 0));
+        assert_ne!(m, encoder_1.encode_with_tweak::<Fr>(b"credential-1", 1));
+        assert_ne!(m, encoder_1.encode::<Fr>(b"credential-2"));
+        // Same identifier in a different domain
+        assert_ne!(m, encoder_2.encode::<Fr>(b"credential-1"));
+        // Tweak is not confused with the identifier's bytes
+        assert_ne!(
+            encoder_1.encode_with_tweak::<Fr>(b"credential-1", 1),
+            encoder_1.encode::<Fr>(b"credential-1\x01")
+        );
+        assert_ne!(m, encoder_1.encode::<Fr>(b""));
+    }
+
+    #[test]
+    fn encode_for_universal_accumulator() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, keypair, mut accumulator, mut initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 100);
+
+        let encoder = MemberEncoder::<Blake2b512>::new(b"universal");
+        let (m, tweak) = encoder
+            .encode_for_universal_accumulator(b"credential-1", &accumulator, &initial_elements)
+            .unwrap();
+        assert_eq!(tweak, 0);
+        assert_eq!(m, encoder.encode::<Fr>(b"credential-1"));
+
+        // The encoding with tweak 0 is an initial element so tweak 1 is used
+        initial_elements.add(encoder.encode::<Fr>(b"credential-2"));
+        let (m, tweak) = encoder
+            .encode_for_universal_accumulator(b"credential-2", &accumulator, &initial_elements)
+            .unwrap();
+        assert_eq!(tweak, 1);
+        assert_eq!(m, encoder.encode_with_tweak::<Fr>(b"credential-2", 1));
+        assert!(accumulator.is_element_acceptable(&m, &initial_elements));
+
+        let mut registry = MemberDecodingRegistry::new(encoder.clone(), BTreeMap::new());
+        let mut identifiers = vec![];
+        for i in 0..10 {
+            let identifier = [b"credential-".as_slice(), &[i]].concat();
+            let m = registry
+                .encode_for_universal_accumulator(&identifier, &accumulator, &initial_elements)
+                .unwrap();
+            accumulator = accumulator
+                .add(m, &keypair.secret_key, &initial_elements, &mut state)
+                .unwrap();
+            identifiers.push((m, identifier));
+        }
+        assert_eq!(registry.decode(&m), None);
+        let m_2 = registry
+            .encode_for_universal_accumulator(b"credential-2", &accumulator, &initial_elements)
+            .unwrap();
+        assert_eq!(m_2, m);
+        assert_eq!(registry.decode(&m), Some(b"credential-2".as_slice()));
+        for (m, identifier) in &identifiers {
+            assert!(state.has(m));
+            assert_eq!(registry.decode(m), Some(identifier.as_slice()));
+        }
+
+        // No tweak gives an acceptable member
+        let mut all_initial = InMemoryInitialElements::new();
+        for tweak in 0..=u8::MAX {
+            all_initial.add(encoder.encode_with_tweak::<Fr>(b"credential-3", tweak));
+        }
+        assert!(matches!(
+            encoder.encode_for_universal_accumulator(b"credential-3", &accumulator, &all_initial),
+            Err(VBAccumulatorError::NoAcceptableEncoding)
+        ));
+    }
+
+    #[test]
+    fn decoding_registry() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+
+        let encoder = MemberEncoder::<Blake2b512>::new(b"positive");
+        let mut registry = MemberDecodingRegistry::new(encoder, BTreeMap::new());
+
+        let m_1 = registry.encode(b"credential-1").unwrap();
+        let m_2 = registry.encode(b"credential-2").unwrap();
+        // Encoding again keeps the same identifier
+        assert_eq!(registry.encode(b"credential-1").unwrap(), m_1);
+        assert_eq!(registry.store().len(), 2);
+
+        accumulator = accumulator
+            .add_batch(vec![m_1, m_2], &keypair.secret_key, &mut state)
+            .unwrap();
+        let w = accumulator
+            .get_membership_witness(&m_1, &keypair.secret_key, &state)
+            .unwrap();
+        assert!(accumulator.verify_membership(&m_1, &w, &keypair.public_key, &params));
+
+        assert_eq!(registry.decode(&m_1), Some(b"credential-1".as_slice()));
+        assert_eq!(registry.decode(&m_2), Some(b"credential-2".as_slice()));
+        assert_eq!(registry.decode(&Fr::from(1u64)), None);
+
+        registry.remove(&m_2);
+        assert_eq!(registry.decode(&m_2), None);
+
+        // A member registered for another identifier isn't overwritten
+        let mut store = BTreeMap::new();
+        store.add(
+            registry.encoder().encode::<Fr>(b"credential-3"),
+            b"other".to_vec(),
+        );
+        let mut registry = MemberDecodingRegistry::new(registry.encoder().clone(), store);
+        assert!(matches!(
+            registry.encode(b"credential-3"),
+            Err(VBAccumulatorError::MemberEncodingCollision)
+        ));
+        assert_eq!(
+            registry.decode(&registry.encoder().encode::<Fr>(b"credential-3")),
+            Some(b"other".as_slice())
+        );
+    }
+}
@@ -1,10 +1,21 @@
 //! Interfaces for persistent storage of accumulators
+//!
+//! Durable implementations of these interfaces are in [`durable`] and use an embedded key-value store with the
+//! feature `sled-store` or an append-only file with the feature `file-store`.
 
 use ark_std::iter::Iterator;
 
+#[cfg(any(feature = "sled-store", feature = "file-store"))]
+pub mod durable;
+#[cfg(feature = "file-store")]
+pub mod file_store;
+#[cfg(feature = "sled-store")]
+pub mod sled_store;
+
 /// Database interface implemented for the universal accumulator and holds the accumulator members created during setup.
 /// These members are never added or removed from the accumulator. Only the accumulator manager needs to keep this.
-/// A production implementation of this could be persistent key-value store like LevelDb or Rocksdb.
+/// A production implementation of this could be persistent key-value store like LevelDb or Rocksdb. See [`durable`]
+/// for the implementations in this crate.
 pub trait InitialElementsStore<T> {
     /// Add element
     fn add(&mut self, element: T);
@@ -38,6 +49,16 @@ pub trait UniversalAccumulatorState<'a, T: 'a>: State<T> {
     fn elements(&'a self) -> Self::ElementIterator;
 }
 
+/// Database interface for the state of any accumulator, positive or universal, which can return all elements present.
+/// Used for refreshing the witnesses of all members. A state of the universal accumulator would return the same
+/// elements as [`UniversalAccumulatorState::elements`].
+pub trait IterableState<'a, T: 'a>: State<T> {
+    type MemberIterator: Iterator<Item = &'a T>;
+
+    /// Return an iterator over all elements present.
+    fn members(&'a self) -> Self::MemberIterator;
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
@@ -104,4 +125,12 @@ pub mod test {
             self.db.iter()
         }
     }
+
+    impl<'a, T: Clone + Hash + Eq + Sized + 'a> IterableState<'a, T> for InMemoryState<T> {
+        type MemberIterator = std::collections::hash_set::Iter<'a, T>;
+
+        fn members(&'a self) -> Self::MemberIterator {
+            self.db.iter()
+        }
+    }
 }
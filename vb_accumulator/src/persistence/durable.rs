@@ -0,0 +1,336 @@
+//! Durable accumulator state with transactional updates.
+//!
+//! [`DurableState`] keeps the members in memory and records the changes made through the [`State`] interface, like by
+//! `add_batch` or `remove_batch` of the accumulators, till they are committed along with the updated accumulator by
+//! [`DurableState::commit`]. The [`Backend`] applies the changes and the accumulator atomically so after a crash the
+//! stored members are always the ones accumulated in the stored accumulator (and `f_V` for the universal accumulator).
+//! If an update fails, the changes made by it are discarded by [`DurableState::rollback`].
+//!
+//! The backend only provides durability, membership checks are served from memory. So all members are loaded when the
+//! state is created and the memory used grows linearly with the number of members, which limits this to accumulators
+//! whose members fit in memory. This is needed anyway by the universal accumulator which iterates over all members
+//! when computing non-membership witnesses.
+//!
+//! ```ignore
+//! let new_accumulator = accumulator.add_batch(elements, &sk, &mut state).map_err(|e| {
+//!     state.rollback();
+//!     e
+//! })?;
+//! state.commit(&new_accumulator)?;
+//! ```
+//!
+//! The same types can be used as [`InitialElementsStore`] for the universal accumulator where the initial elements are
+//! committed by [`DurableState::commit_members`] after the accumulator is initialized.
+
+use crate::{
+    error::VBAccumulatorError,
+    persistence::{InitialElementsStore, IterableState, State, UniversalAccumulatorState},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::collections::{btree_map, BTreeMap};
+
+/// Changes to the stored members and accumulator that must be applied atomically. Elements and the accumulator are
+/// in compressed serialized form.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ChangeSet {
+    pub additions: Vec<Vec<u8>>,
+    pub removals: Vec<Vec<u8>>,
+    /// Set when the accumulator is updated
+    pub accumulator: Option<Vec<u8>>,
+}
+
+/// Storage used by [`DurableState`]
+pub trait Backend {
+    /// Return the committed state as the additions of all members and the last committed accumulator
+    fn load(&mut self) -> Result<ChangeSet, VBAccumulatorError>;
+
+    /// Apply the changes and persist them before returning. On crash or error, either all or none of the changes
+    /// should be applied.
+    fn apply(&mut self, changes: &ChangeSet) -> Result<(), VBAccumulatorError>;
+}
+
+/// Accumulator members backed by a durable [`Backend`]
+#[derive(Debug)]
+pub struct DurableState<T, B: Backend> {
+    pub(super) backend: B,
+    /// Members including the uncommitted changes, keyed by the serialized element
+    members: BTreeMap<Vec<u8>, T>,
+    /// Last committed accumulator
+    accumulator: Option<Vec<u8>>,
+    /// For each element changed since the last commit, whether it was a member, and the element if so, before the
+    /// change
+    changed: BTreeMap<Vec<u8>, Option<T>>,
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize, B: Backend> DurableState<T, B> {
+    /// Load the committed state from the backend. All members are deserialized and kept in memory.
+    pub fn new(mut backend: B) -> Result<Self, VBAccumulatorError> {
+        let ChangeSet {
+            additions,
+            accumulator,
+            ..
+        } = backend.load()?;
+        let members = additions
+            .into_iter()
+            .map(|k| {
+                let element = T::deserialize_compressed(k.as_slice())?;
+                Ok((k, element))
+            })
+            .collect::<Result<_, VBAccumulatorError>>()?;
+        Ok(Self {
+            backend,
+            members,
+            accumulator,
+            changed: BTreeMap::new(),
+        })
+    }
+
+    /// The last committed accumulator, if any
+    pub fn accumulator<A: CanonicalDeserialize>(&self) -> Result<Option<A>, VBAccumulatorError> {
+        match &self.accumulator {
+            Some(a) => Ok(Some(A::deserialize_compressed(a.as_slice())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist the changes to the members since the last commit along with the accumulator resulting from those
+    /// changes. On error, the changes remain uncommitted and the commit can be retried or the changes rolled back.
+    pub fn commit<A: CanonicalSerialize>(
+        &mut self,
+        accumulator: &A,
+    ) -> Result<(), VBAccumulatorError> {
+        let mut bytes = vec![];
+        accumulator.serialize_compressed(&mut bytes)?;
+        self.apply(Some(bytes))
+    }
+
+    /// Persist the changes to the members since the last commit without changing the stored accumulator
+    pub fn commit_members(&mut self) -> Result<(), VBAccumulatorError> {
+        self.apply(None)
+    }
+
+    /// Discard the changes to the members since the last commit
+    pub fn rollback(&mut self) {
+        for (key, before) in core::mem::take(&mut self.changed) {
+            match before {
+                Some(element) => self.members.insert(key, element),
+                None => self.members.remove(&key),
+            };
+        }
+    }
+
+    /// Whether there are changes to the members since the last commit
+    pub fn has_uncommitted_changes(&self) -> bool {
+        self.changed
+            .iter()
+            .any(|(k, before)| before.is_some() != self.members.contains_key(k))
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    fn apply(&mut self, accumulator: Option<Vec<u8>>) -> Result<(), VBAccumulatorError> {
+        let mut changes = ChangeSet {
+            accumulator,
+            ..Default::default()
+        };
+        for (key, before) in &self.changed {
+            match (before.is_some(), self.members.contains_key(key)) {
+                (false, true) => changes.additions.push(key.clone()),
+                (true, false) => changes.removals.push(key.clone()),
+                _ => (),
+            }
+        }
+        self.backend.apply(&changes)?;
+        if changes.accumulator.is_some() {
+            self.accumulator = changes.accumulator;
+        }
+        self.changed.clear();
+        Ok(())
+    }
+
+    fn key(element: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        // Serializing to a vector doesn't fail
+        element.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize, B: Backend> State<T> for DurableState<T, B> {
+    fn add(&mut self, element: T) {
+        let key = Self::key(&element);
+        let before = self.members.insert(key.clone(), element);
+        self.changed.entry(key).or_insert(before);
+    }
+
+    fn remove(&mut self, element: &T) {
+        let key = Self::key(element);
+        if let Some(before) = self.members.remove(&key) {
+            self.changed.entry(key).or_insert(Some(before));
+        }
+    }
+
+    fn has(&self, element: &T) -> bool {
+        self.members.contains_key(&Self::key(element))
+    }
+
+    fn size(&self) -> u64 {
+        self.members.len() as u64
+    }
+}
+
+impl<'a, T: CanonicalSerialize + CanonicalDeserialize + 'a, B: Backend>
+    UniversalAccumulatorState<'a, T> for DurableState<T, B>
+{
+    type ElementIterator = btree_map::Values<'a, Vec<u8>, T>;
+
+    fn elements(&'a self) -> Self::ElementIterator {
+        self.members.values()
+    }
+}
+
+impl<'a, T: CanonicalSerialize + CanonicalDeserialize + 'a, B: Backend> IterableState<'a, T>
+    for DurableState<T, B>
+{
+    type MemberIterator = btree_map::Values<'a, Vec<u8>, T>;
+
+    fn members(&'a self) -> Self::MemberIterator {
+        self.members.values()
+    }
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize, B: Backend> InitialElementsStore<T>
+    for DurableState<T, B>
+{
+    fn add(&mut self, element: T) {
+        State::add(self, element)
+    }
+
+    fn has(&self, element: &T) -> bool {
+        State::has(self, element)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::{
+        positive::Accumulator,
+        setup::{Keypair, SetupParams},
+        universal::UniversalAccumulator,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    type Accum = UniversalAccumulator<Bls12_381>;
+
+    /// Check that the members and accumulator survive reopening and uncommitted changes don't. `open` is called with
+    /// the name of the store to open, `initial` or `state`.
+    pub fn check_transactions<B: Backend>(open: impl Fn(&str) -> B) {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let max = 20;
+
+        let mut initial = DurableState::<Fr, _>::new(open("initial")).unwrap();
+        let mut state = DurableState::<Fr, _>::new(open("state")).unwrap();
+        assert_eq!(state.accumulator::<Accum>().unwrap(), None);
+        let accumulator = Accum::initialize_with_all_random(
+            &mut rng,
+            &params,
+            max,
+            &keypair.secret_key,
+            &mut initial,
+        );
+        initial.commit_members().unwrap();
+        state.commit(&accumulator).unwrap();
+
+        let elements = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator = accumulator
+            .add_batch(elements.clone(), &keypair.secret_key, &initial, &mut state)
+            .unwrap();
+        assert!(state.has_uncommitted_changes());
+        state.commit(&accumulator).unwrap();
+        assert!(!state.has_uncommitted_changes());
+        let accumulator = accumulator
+            .remove_batch(&elements[0..3], &keypair.secret_key, &initial, &mut state)
+            .unwrap();
+        state.commit(&accumulator).unwrap();
+
+        // Updates which aren't committed are lost on crash
+        let new_elements = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator
+            .batch_updates(
+                new_elements.clone(),
+                &elements[3..5],
+                &keypair.secret_key,
+                &initial,
+                &mut state,
+            )
+            .unwrap();
+        assert_eq!(state.size(), 10);
+        drop(state);
+        drop(initial);
+
+        let initial = DurableState::<Fr, _>::new(open("initial")).unwrap();
+        let mut state = DurableState::<Fr, _>::new(open("state")).unwrap();
+        assert_eq!(state.accumulator::<Accum>().unwrap().unwrap(), accumulator);
+        assert_eq!(state.size(), 7);
+        for e in &elements[0..3] {
+            assert!(!State::has(&state, e));
+        }
+        for e in elements[3..].iter() {
+            assert!(State::has(&state, e));
+        }
+        for e in &new_elements {
+            assert!(!State::has(&state, e));
+            assert!(accumulator.is_element_acceptable(e, &initial));
+        }
+        assert!(!InitialElementsStore::has(&initial, &elements[3]));
+
+        // Reloaded state can be used for witnesses
+        let wit = accumulator
+            .get_membership_witness(&elements[3], &keypair.secret_key, &state)
+            .unwrap();
+        assert!(accumulator.verify_membership(&elements[3], &wit, &keypair.public_key, &params));
+        let nm_wit = accumulator
+            .get_non_membership_witness(&elements[0], &keypair.secret_key, &state, &params)
+            .unwrap();
+        assert!(accumulator.verify_non_membership(
+            &elements[0],
+            &nm_wit,
+            &keypair.public_key,
+            &params
+        ));
+
+        // Rolled back changes are not committed
+        accumulator
+            .remove_batch(&elements[3..6], &keypair.secret_key, &initial, &mut state)
+            .unwrap();
+        State::add(&mut state, elements[0]);
+        assert!(!State::has(&state, &elements[3]));
+        state.rollback();
+        assert!(!state.has_uncommitted_changes());
+        assert!(State::has(&state, &elements[3]));
+        assert!(!State::has(&state, &elements[0]));
+        // Committing only the members keeps the stored accumulator
+        State::remove(&mut state, &elements[9]);
+        State::add(&mut state, elements[9]);
+        assert!(!state.has_uncommitted_changes());
+        state.commit_members().unwrap();
+        drop(state);
+
+        let state = DurableState::<Fr, _>::new(open("state")).unwrap();
+        assert_eq!(state.accumulator::<Accum>().unwrap().unwrap(), accumulator);
+        let mut members = state.elements().copied().collect::<Vec<_>>();
+        let mut expected = elements[3..].to_vec();
+        members.sort();
+        expected.sort();
+        assert_eq!(members, expected);
+    }
+}
@@ -0,0 +1,295 @@
+//! Accumulator state stored in an append-only file.
+//!
+//! Each commit appends a record `length || checksum || changes` to the file, where `changes` is the serialized
+//! [`ChangeSet`], `length` its byte size as 4-byte little-endian and `checksum` its CRC-32, and syncs the file before
+//! returning. The state is loaded by replaying all records. A record partially written due to a crash fails the length
+//! or checksum check and, as it can only be the last record, is truncated from the file so the loaded state is the one
+//! of the last complete commit. Any other record failing the check, i.e. one whose length doesn't reach the end of the
+//! file or which is followed by a complete record, is a corruption of the file and loading fails without changing the
+//! file. As the file grows with each commit, it can be rewritten with only
+//! the current state by [`FileBackend::compact`].
+
+use crate::{
+    error::VBAccumulatorError,
+    persistence::durable::{Backend, ChangeSet, DurableState},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::{
+    collections::BTreeSet,
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+const HEADER_SIZE: usize = 8;
+
+/// Backend storing the commits in an append-only file
+#[derive(Debug)]
+pub struct FileBackend {
+    path: PathBuf,
+    file: File,
+    /// Size of the file till the end of the last complete record
+    len: u64,
+}
+
+/// Accumulator state or initial elements stored in an append-only file
+pub type FileState<T> = DurableState<T, FileBackend>;
+
+impl FileBackend {
+    /// Open the file at `path`, creating it if it doesn't exist
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, VBAccumulatorError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self { path, file, len })
+    }
+
+    /// Rewrite the file with a single record containing the current state. The new file is written and synced before
+    /// it replaces the old one so a crash during compaction leaves either of them.
+    pub fn compact(&mut self) -> Result<(), VBAccumulatorError> {
+        let changes = self.load()?;
+        let tmp_path = self.path.with_extension("compact");
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(&Self::record(&changes)?)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        if let Some(dir) = self.path.parent() {
+            // Persist the rename. Directories can't be opened on some platforms so failure is ignored
+            if let Ok(d) = File::open(dir) {
+                let _ = d.sync_all();
+            }
+        }
+        *self = Self::open(&self.path)?;
+        self.load()?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn record(changes: &ChangeSet) -> Result<Vec<u8>, VBAccumulatorError> {
+        let mut payload = vec![];
+        changes.serialize_compressed(&mut payload)?;
+        let mut record = Vec::with_capacity(HEADER_SIZE + payload.len());
+        record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        record.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        record.append(&mut payload);
+        Ok(record)
+    }
+
+    /// Whether the record at the start of `bytes`, which failed to parse, can be the record being written during a
+    /// crash, i.e. it extends to the end of `bytes` and no complete record follows it. As the length of the failed
+    /// record can itself be corrupted, a complete record is looked for at every later position.
+    fn is_partial_last_record(bytes: &[u8]) -> bool {
+        if bytes.len() >= HEADER_SIZE {
+            let len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+            if HEADER_SIZE + len < bytes.len() {
+                return false;
+            }
+        }
+        (1..bytes.len()).all(|i| Self::parse_record(&bytes[i..]).is_none())
+    }
+
+    /// Parse the record at the start of `bytes` returning the changes and the record's size, or `None` if the record
+    /// is incomplete or corrupted
+    fn parse_record(bytes: &[u8]) -> Option<(ChangeSet, usize)> {
+        if bytes.len() < HEADER_SIZE {
+            return None;
+        }
+        let len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let checksum = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let payload = bytes.get(HEADER_SIZE..HEADER_SIZE + len)?;
+        if crc32fast::hash(payload) != checksum {
+            return None;
+        }
+        let changes = ChangeSet::deserialize_compressed(payload).ok()?;
+        Some((changes, HEADER_SIZE + len))
+    }
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize> FileState<T> {
+    /// Rewrite the file with only the committed state. See [`FileBackend::compact`]
+    pub fn compact(&mut self) -> Result<(), VBAccumulatorError> {
+        self.backend.compact()
+    }
+}
+
+impl Backend for FileBackend {
+    fn load(&mut self) -> Result<ChangeSet, VBAccumulatorError> {
+        let mut bytes = vec![];
+        File::open(&self.path)?.read_to_end(&mut bytes)?;
+        let mut members = BTreeSet::new();
+        let mut accumulator = None;
+        let mut offset = 0;
+        while let Some((changes, size)) = Self::parse_record(&bytes[offset..]) {
+            for e in changes.removals {
+                members.remove(&e);
+            }
+            members.extend(changes.additions);
+            if changes.accumulator.is_some() {
+                accumulator = changes.accumulator;
+            }
+            offset += size;
+        }
+        // Discard the incomplete record left by a crash
+        if offset < bytes.len() {
+            if !Self::is_partial_last_record(&bytes[offset..]) {
+                return Err(VBAccumulatorError::CorruptedStorageRecord(offset as u64));
+            }
+            self.file.set_len(offset as u64)?;
+            self.file.sync_all()?;
+        }
+        self.len = offset as u64;
+        Ok(ChangeSet {
+            additions: members.into_iter().collect(),
+            removals: vec![],
+            accumulator,
+        })
+    }
+
+    fn apply(&mut self, changes: &ChangeSet) -> Result<(), VBAccumulatorError> {
+        let record = Self::record(changes)?;
+        let result = self
+            .file
+            .write_all(&record)
+            .and_then(|_| self.file.sync_data());
+        if let Err(e) = result {
+            // Remove what was written of the record so that later records aren't appended after it
+            let _ = self.file.set_len(self.len);
+            return Err(e.into());
+        }
+        self.len += record.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{durable::tests::check_transactions, State};
+    use ark_bls12_381::Fr;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    #[test]
+    fn file_state() {
+        let dir = tempfile::tempdir().unwrap();
+        check_transactions(|name| FileBackend::open(dir.path().join(name)).unwrap());
+    }
+
+    #[test]
+    fn crash_recovery_and_compaction() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state");
+        let elements = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let mut state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        for e in &elements[0..5] {
+            state.add(*e);
+        }
+        state.commit(&1u64).unwrap();
+        for e in &elements[5..] {
+            state.add(*e);
+        }
+        state.remove(&elements[0]);
+        state.commit(&2u64).unwrap();
+        drop(state);
+        let committed_len = fs::metadata(&path).unwrap().len();
+
+        // Crash in the middle of writing a record
+        let mut record = FileBackend::record(&ChangeSet {
+            additions: vec![],
+            removals: vec![vec![1, 2, 3]],
+            accumulator: Some(vec![4, 5]),
+        })
+        .unwrap();
+        record.truncate(record.len() - 2);
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&record)
+            .unwrap();
+
+        let mut state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), committed_len);
+        assert_eq!(state.accumulator::<u64>().unwrap(), Some(2));
+        assert_eq!(state.size(), 9);
+        assert!(!state.has(&elements[0]));
+
+        // Commits after recovery are loaded
+        state.remove(&elements[1]);
+        state.commit(&3u64).unwrap();
+        drop(state);
+
+        // Corrupted record is discarded
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        let state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        assert_eq!(state.accumulator::<u64>().unwrap(), Some(2));
+        assert!(state.has(&elements[1]));
+        drop(state);
+
+        // Corrupted record followed by other records fails to load and the file is left unchanged
+        let mut state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        state.remove(&elements[3]);
+        state.commit(&3u64).unwrap();
+        drop(state);
+        let mut bytes = fs::read(&path).unwrap();
+        let valid = bytes.clone();
+        bytes[committed_len as usize - 1] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        let first_record_len =
+            HEADER_SIZE + u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        assert!(matches!(
+            FileState::<Fr>::new(FileBackend::open(&path).unwrap()),
+            Err(VBAccumulatorError::CorruptedStorageRecord(o)) if o == first_record_len as u64
+        ));
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+
+        // Corrupted length of a record making it look like the last record also fails to load
+        let mut bytes = valid.clone();
+        bytes[first_record_len..first_record_len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            FileState::<Fr>::new(FileBackend::open(&path).unwrap()),
+            Err(VBAccumulatorError::CorruptedStorageRecord(o)) if o == first_record_len as u64
+        ));
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        fs::write(&path, &valid).unwrap();
+        let state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        assert_eq!(state.accumulator::<u64>().unwrap(), Some(3));
+        assert!(!state.has(&elements[3]));
+        drop(state);
+        // Drop the last commit to continue with the state of commit 2
+        fs::write(&path, &valid[..committed_len as usize]).unwrap();
+
+        // Length is known without loading so a failed write can't truncate the committed records
+        let mut backend = FileBackend::open(&path).unwrap();
+        assert_eq!(backend.len, committed_len);
+        backend.compact().unwrap();
+        assert!(fs::metadata(&path).unwrap().len() < committed_len);
+        let mut state = FileState::<Fr>::new(backend).unwrap();
+        assert_eq!(state.accumulator::<u64>().unwrap(), Some(2));
+        assert_eq!(state.size(), 9);
+        for e in &elements[1..] {
+            assert!(state.has(e));
+        }
+        state.remove(&elements[2]);
+        state.commit(&4u64).unwrap();
+        drop(state);
+        let state = FileState::<Fr>::new(FileBackend::open(&path).unwrap()).unwrap();
+        assert_eq!(state.accumulator::<u64>().unwrap(), Some(4));
+        assert_eq!(state.size(), 8);
+    }
+}
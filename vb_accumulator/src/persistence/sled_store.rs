@@ -0,0 +1,92 @@
+//! Accumulator state stored in the embedded key-value store [sled](https://docs.rs/sled). Each store is a tree in
+//! the database so the state and initial elements of several accumulators can be kept in a single database.
+
+use crate::{
+    error::VBAccumulatorError,
+    persistence::durable::{Backend, ChangeSet, DurableState},
+};
+use std::path::Path;
+
+/// Prefix of the keys of members
+const MEMBER_PREFIX: u8 = 0;
+const ACCUMULATOR_KEY: [u8; 1] = [1];
+
+/// Backend storing the members and the accumulator in a sled tree. The changes of a commit are applied as a single
+/// atomic batch and the database is flushed before the commit returns.
+#[derive(Clone, Debug)]
+pub struct SledBackend {
+    tree: sled::Tree,
+}
+
+/// Accumulator state or initial elements stored in sled
+pub type SledState<T> = DurableState<T, SledBackend>;
+
+impl SledBackend {
+    /// Use the tree with the given name from the database, creating it if it doesn't exist
+    pub fn new(db: &sled::Db, name: &str) -> Result<Self, VBAccumulatorError> {
+        Ok(Self {
+            tree: db.open_tree(name)?,
+        })
+    }
+
+    /// Open or create the database at `path` and use its default tree
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, VBAccumulatorError> {
+        let db = sled::open(path)?;
+        Ok(Self {
+            tree: (*db).clone(),
+        })
+    }
+
+    fn member_key(element: &[u8]) -> Vec<u8> {
+        let mut key = Vec::with_capacity(element.len() + 1);
+        key.push(MEMBER_PREFIX);
+        key.extend_from_slice(element);
+        key
+    }
+}
+
+impl Backend for SledBackend {
+    fn load(&mut self) -> Result<ChangeSet, VBAccumulatorError> {
+        let additions = self
+            .tree
+            .scan_prefix([MEMBER_PREFIX])
+            .keys()
+            .map(|k| Ok(k?[1..].to_vec()))
+            .collect::<Result<Vec<_>, VBAccumulatorError>>()?;
+        let accumulator = self.tree.get(ACCUMULATOR_KEY)?.map(|a| a.to_vec());
+        Ok(ChangeSet {
+            additions,
+            removals: vec![],
+            accumulator,
+        })
+    }
+
+    fn apply(&mut self, changes: &ChangeSet) -> Result<(), VBAccumulatorError> {
+        let mut batch = sled::Batch::default();
+        for e in &changes.additions {
+            batch.insert(Self::member_key(e), &[]);
+        }
+        for e in &changes.removals {
+            batch.remove(Self::member_key(e));
+        }
+        if let Some(a) = &changes.accumulator {
+            batch.insert(&ACCUMULATOR_KEY, a.as_slice());
+        }
+        self.tree.apply_batch(batch)?;
+        self.tree.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::durable::tests::check_transactions;
+
+    #[test]
+    fn sled_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = sled::open(dir.path()).unwrap();
+        check_transactions(|name| SledBackend::new(&db, name).unwrap());
+    }
+}
@@ -5,6 +5,10 @@
 //! the paper. The paper only describes the non-membership proof but the membership proof is similar
 //! with the relationships involving `d` omitted. See the documentation of relevant objects for more detail.
 //!
+//! When several proofs are created or verified with the same proving key, [`PreparedMembershipProvingKey`]
+//! and [`PreparedNonMembershipProvingKey`] can be created once and passed to the `*_with_prepared_proving_key`
+//! functions so that the tables for the proving key aren't created for each proof.
+//!
 //! # Examples
 //!
 //! ```
@@ -102,11 +106,19 @@ use ark_ec::{
     AffineRepr, CurveGroup, Group,
 };
 use ark_ff::{Field, PrimeField};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{fmt::Debug, io::Write, rand::RngCore, vec::Vec, UniformRand};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    fmt::Debug,
+    io::{Read, Write},
+    rand::RngCore,
+    vec::Vec,
+    UniformRand,
+};
 use digest::Digest;
 use dock_crypto_utils::{
-    affine_group_element_from_byte_slices, hashing_utils::projective_group_elem_from_try_and_incr,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
     serde_utils::*,
 };
 use schnorr_pok::{error::SchnorrError, SchnorrChallengeContributor};
@@ -115,8 +127,8 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 use dock_crypto_utils::{
     concat_slices, msm::WindowTable, randomized_pairing_check::RandomizedPairingChecker,
 };
-use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde::{ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{de::DeserializeAs, ser::SerializeAsWrap, serde_as, Same};
 
 /// The public parameters (in addition to public key, accumulator setup params) used during the proof
 /// of membership and non-membership are called `ProvingKey`. These are mutually agreed upon by the
@@ -175,12 +187,12 @@ where
     }
 
     /// Generate by hashing known strings
-    fn generate_proving_key_using_hash<D: Digest>(label: &[u8]) -> ProvingKey<G> {
+    fn generate_proving_key_using_hash<H: GroupElemHasher<G>>(label: &[u8]) -> ProvingKey<G> {
         // 3 G1 elements
         ProvingKey {
-            X: affine_group_element_from_byte_slices![label, b" : X"],
-            Y: affine_group_element_from_byte_slices![label, b" : Y"],
-            Z: affine_group_element_from_byte_slices![label, b" : Z"],
+            X: H::hash_to_affine(&concat_slices![label, b" : X"]),
+            Y: H::hash_to_affine(&concat_slices![label, b" : Y"]),
+            Z: H::hash_to_affine(&concat_slices![label, b" : Z"]),
         }
     }
 }
@@ -196,7 +208,13 @@ where
 
     /// Generate by hashing known strings
     pub fn new<D: Digest>(label: &[u8]) -> Self {
-        Self(ProvingKey::generate_proving_key_using_hash::<D>(label))
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H: GroupElemHasher<G>>(label: &[u8]) -> Self {
+        Self(ProvingKey::generate_proving_key_using_hash::<H>(label))
     }
 }
 
@@ -215,11 +233,16 @@ where
 
     /// Generate by hashing known strings
     pub fn new<D: Digest>(label: &[u8]) -> Self {
-        let XYZ = ProvingKey::generate_proving_key_using_hash::<D>(label);
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H: GroupElemHasher<G>>(label: &[u8]) -> Self {
+        let XYZ = ProvingKey::generate_proving_key_using_hash::<H>(label);
         Self {
             XYZ,
-            K: projective_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : K"])
-                .into(),
+            K: H::hash_to_affine(&concat_slices![label, b" : K"]),
         }
     }
 
@@ -229,6 +252,224 @@ where
     }
 }
 
+/// Fixed-base tables for `X`, `Y` and `Z` of the `ProvingKey`. Since the proving key is fixed, these can
+/// be created once and used for all the proofs created or verified with that key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProvingKeyTables<G: AffineRepr> {
+    pub X: WindowTable<G::Group>,
+    pub Y: WindowTable<G::Group>,
+    pub Z: WindowTable<G::Group>,
+}
+
+/// Membership proving key along with the tables for its elements. Used by the prover and verifier in
+/// place of [`MembershipProvingKey`] to avoid creating the tables for each proof. Only the proving key
+/// is serialized and the tables are created again on deserialization so they always match the key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PreparedMembershipProvingKey<G: AffineRepr> {
+    prk: MembershipProvingKey<G>,
+    tables: ProvingKeyTables<G>,
+}
+
+/// Non-membership proving key along with the tables for its elements and for `P` of the accumulator's
+/// setup params. Used by the prover and verifier in place of [`NonMembershipProvingKey`] to avoid
+/// creating the tables for each proof. Only the proving key and `P` are serialized and the tables are
+/// created again on deserialization so they always match them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PreparedNonMembershipProvingKey<G: AffineRepr> {
+    prk: NonMembershipProvingKey<G>,
+    tables: ProvingKeyTables<G>,
+    K_table: WindowTable<G::Group>,
+    /// `P` of the setup params for which `P_table` was created
+    P: G,
+    P_table: WindowTable<G::Group>,
+}
+
+impl<G: AffineRepr> ProvingKeyTables<G> {
+    /// There are multiple multiplications with X, Y and Z so create tables for them. 20 multiplications
+    /// is the upper bound
+    pub fn new(prk: &ProvingKey<G>) -> Self {
+        Self {
+            X: WindowTable::new(20, prk.X.into_group()),
+            Y: WindowTable::new(20, prk.Y.into_group()),
+            Z: WindowTable::new(20, prk.Z.into_group()),
+        }
+    }
+}
+
+impl<G: AffineRepr> PreparedMembershipProvingKey<G> {
+    /// The proving key for which the tables were created
+    pub fn proving_key(&self) -> &MembershipProvingKey<G> {
+        &self.prk
+    }
+}
+
+impl<G: AffineRepr> From<MembershipProvingKey<G>> for PreparedMembershipProvingKey<G> {
+    fn from(prk: MembershipProvingKey<G>) -> Self {
+        let tables = ProvingKeyTables::new(&prk.0);
+        Self { prk, tables }
+    }
+}
+
+impl<G: AffineRepr> PreparedNonMembershipProvingKey<G> {
+    /// Create tables for the proving key and for `P` of the given setup params. The prepared key should
+    /// then be used only with those setup params.
+    pub fn new<E: Pairing<G1Affine = G>>(
+        prk: NonMembershipProvingKey<G>,
+        params: &SetupParams<E>,
+    ) -> Self {
+        Self::from_key_and_P(prk, params.P)
+    }
+
+    /// The proving key for which the tables were created
+    pub fn proving_key(&self) -> &NonMembershipProvingKey<G> {
+        &self.prk
+    }
+
+    /// Derive the prepared membership proving key when doing a membership proof with a universal accumulator.
+    pub fn derive_membership_proving_key(&self) -> PreparedMembershipProvingKey<G> {
+        PreparedMembershipProvingKey {
+            prk: self.prk.derive_membership_proving_key(),
+            tables: self.tables.clone(),
+        }
+    }
+
+    fn from_key_and_P(prk: NonMembershipProvingKey<G>, P: G) -> Self {
+        // There are multiple multiplications with P and K so create tables for them. 20 multiplications
+        // is the upper bound
+        let tables = ProvingKeyTables::new(&prk.XYZ);
+        let K_table = WindowTable::new(20, prk.K.into_group());
+        let P_table = WindowTable::new(20, P.into_group());
+        Self {
+            prk,
+            tables,
+            K_table,
+            P,
+            P_table,
+        }
+    }
+}
+
+impl<G: AffineRepr> Valid for PreparedMembershipProvingKey<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.prk.check()
+    }
+}
+
+impl<G: AffineRepr> CanonicalSerialize for PreparedMembershipProvingKey<G> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.prk.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.prk.serialized_size(compress)
+    }
+}
+
+impl<G: AffineRepr> CanonicalDeserialize for PreparedMembershipProvingKey<G> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let prk = MembershipProvingKey::deserialize_with_mode(reader, compress, validate)?;
+        Ok(Self::from(prk))
+    }
+}
+
+impl<G: AffineRepr> Serialize for PreparedMembershipProvingKey<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.prk.serialize(serializer)
+    }
+}
+
+impl<'de, G: AffineRepr> Deserialize<'de> for PreparedMembershipProvingKey<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let prk = MembershipProvingKey::deserialize(deserializer)?;
+        Ok(Self::from(prk))
+    }
+}
+
+impl<G: AffineRepr> Valid for PreparedNonMembershipProvingKey<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.prk.check()?;
+        self.P.check()
+    }
+}
+
+impl<G: AffineRepr> CanonicalSerialize for PreparedNonMembershipProvingKey<G> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.prk.serialize_with_mode(&mut writer, compress)?;
+        self.P.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.prk.serialized_size(compress) + self.P.serialized_size(compress)
+    }
+}
+
+impl<G: AffineRepr> CanonicalDeserialize for PreparedNonMembershipProvingKey<G> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let prk = NonMembershipProvingKey::deserialize_with_mode(&mut reader, compress, validate)?;
+        let P = G::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self::from_key_and_P(prk, P))
+    }
+}
+
+impl<G: AffineRepr> Serialize for PreparedNonMembershipProvingKey<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.prk)?;
+        tuple.serialize_element(&SerializeAsWrap::<G, ArkObjectBytes>::new(&self.P))?;
+        tuple.end()
+    }
+}
+
+impl<'de, G: AffineRepr> Deserialize<'de> for PreparedNonMembershipProvingKey<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (prk, P) = <(Same, ArkObjectBytes) as DeserializeAs<
+            'de,
+            (NonMembershipProvingKey<G>, G),
+        >>::deserialize_as(deserializer)?;
+        Ok(Self::from_key_and_P(prk, P))
+    }
+}
+
+/// A table for multiplying a fixed base by several scalars. Either a wNAF table created during
+/// verification or a window table from a prepared proving key.
+#[derive(Clone, Copy)]
+pub(crate) enum BaseTable<'a, G: CurveGroup> {
+    Wnaf(&'a WnafContext, &'a [G]),
+    Window(&'a WindowTable<G>),
+}
+
+impl<'a, G: CurveGroup> BaseTable<'a, G> {
+    fn multiply(&self, scalar: &G::ScalarField) -> G {
+        match self {
+            Self::Wnaf(context, table) => context.mul_with_table(table, scalar).unwrap(),
+            Self::Window(table) => table.multiply(scalar),
+        }
+    }
+}
+
+/// Proving key used during verification. When prepared, its tables are used rather than creating new ones.
+#[derive(Clone, Copy)]
+pub(crate) enum ProvingKeyForVerification<'a, G: AffineRepr> {
+    Unprepared(&'a ProvingKey<G>),
+    Prepared(&'a ProvingKeyTables<G>),
+}
+
 /// Common elements of the randomized witness between membership and non-membership witness
 #[serde_as]
 #[derive(
@@ -732,18 +973,15 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         pairing_extra: Option<E::G1>,
         pk: &PublicKey<E>,
         params: &SetupParams<E>,
-        prk: &ProvingKey<E::G1Affine>,
+        prk_tables: &ProvingKeyTables<E::G1Affine>,
     ) -> (
         RandomizedWitness<E::G1Affine>,
         SchnorrCommit<E>,
         Blindings<E::ScalarField>,
     ) {
-        // TODO: Since proving key is fixed, these tables can be created just once and stored.
-        // There are multiple multiplications with X, Y and Z so create tables for them. 20 multiplications
-        // is the upper bound
-        let X_table = WindowTable::new(20, prk.X.into_group());
-        let Y_table = WindowTable::new(20, prk.Y.into_group());
-        let Z_table = WindowTable::new(20, prk.Z.into_group());
+        let X_table = &prk_tables.X;
+        let Y_table = &prk_tables.Y;
+        let Z_table = &prk_tables.Z;
 
         // To prove e(witness, element*P_tilde + Q_tilde) == e(accumulated, P_tilde)
         let sigma = E::ScalarField::rand(rng);
@@ -890,7 +1128,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         challenge: &E::ScalarField,
         pk: impl Into<PreparedPublicKey<E>>,
         params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        prk: ProvingKeyForVerification<E::G1Affine>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
             randomized_witness,
@@ -918,7 +1156,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         challenge: &E::ScalarField,
         pk: impl Into<PreparedPublicKey<E>>,
         params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        prk: ProvingKeyForVerification<E::G1Affine>,
         pairing_checker: &mut RandomizedPairingChecker<E>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
@@ -947,17 +1185,40 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         pairing_extra: Option<E::G1>,
         accumulator_value: &E::G1Affine,
         challenge: &E::ScalarField,
-        prk: &ProvingKey<E::G1Affine>,
+        prk: ProvingKeyForVerification<E::G1Affine>,
     ) -> Result<(E::G1Affine, E::G1Affine), VBAccumulatorError> {
-        let (context, X_table, Y_table, Z_table, T_sigma_table, T_rho_table, E_C_table) =
-            Self::get_tables(prk, randomized_witness);
+        // There are multiple multiplications with X, Y, Z, T_sigma, T_rho and E_C which can be done in
+        // variable time so use wNAF unless the proving key is prepared
+        let context = WnafContext::new(4);
+        let (X_wnaf, Y_wnaf, Z_wnaf);
+        let (X_table, Y_table, Z_table) = match prk {
+            ProvingKeyForVerification::Unprepared(prk) => {
+                X_wnaf = context.table(prk.X.into_group());
+                Y_wnaf = context.table(prk.Y.into_group());
+                Z_wnaf = context.table(prk.Z.into_group());
+                (
+                    BaseTable::Wnaf(&context, &X_wnaf),
+                    BaseTable::Wnaf(&context, &Y_wnaf),
+                    BaseTable::Wnaf(&context, &Z_wnaf),
+                )
+            }
+            ProvingKeyForVerification::Prepared(tables) => (
+                BaseTable::Window(&tables.X),
+                BaseTable::Window(&tables.Y),
+                BaseTable::Window(&tables.Z),
+            ),
+        };
+        let T_sigma_table = context.table(randomized_witness.T_sigma.into_group());
+        let T_rho_table = context.table(randomized_witness.T_rho.into_group());
+        let E_C_table = context.table(randomized_witness.E_C.into_group());
+
         Self::verify_schnorr_proofs(
             schnorr_commit,
             schnorr_response,
             challenge,
             &context,
-            &X_table,
-            &Y_table,
+            X_table,
+            Y_table,
             &T_sigma_table,
             &T_rho_table,
         )?;
@@ -969,41 +1230,8 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             challenge,
             &context,
             &E_C_table,
-            &Z_table,
-        ))
-    }
-
-    /// There are multiple multiplications with X, Y and Z which can be done in variable time so use wNAF.
-    fn get_tables(
-        prk: &ProvingKey<E::G1Affine>,
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-    ) -> (
-        WnafContext,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-    ) {
-        let context = WnafContext::new(4);
-        // TODO: Since proving key is fixed, these tables can be created just once and stored.
-        let X_table = context.table(prk.X.into_group());
-        let Y_table = context.table(prk.Y.into_group());
-        let Z_table = context.table(prk.Z.into_group());
-
-        let T_sigma_table = context.table(randomized_witness.T_sigma.into_group());
-        let T_rho_table = context.table(randomized_witness.T_rho.into_group());
-        let E_C_table = context.table(randomized_witness.E_C.into_group());
-        (
-            context,
-            X_table,
-            Y_table,
             Z_table,
-            T_sigma_table,
-            T_rho_table,
-            E_C_table,
-        )
+        ))
     }
 
     /// The verifier recomputes various `R_`s values given the responses from the proof and the challenge
@@ -1013,24 +1241,20 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         schnorr_response: &SchnorrResponse<E::ScalarField>,
         challenge: &E::ScalarField,
         context: &WnafContext,
-        X_table: &[E::G1],
-        Y_table: &[E::G1],
+        X_table: BaseTable<E::G1>,
+        Y_table: BaseTable<E::G1>,
         T_sigma_table: &[E::G1],
         T_rho_table: &[E::G1],
     ) -> Result<(), VBAccumulatorError> {
         // R_sigma = schnorr_response.s_sigma * prk.X - challenge * randomized_witness.T_sigma
-        let mut R_sigma = context
-            .mul_with_table(X_table, &schnorr_response.s_sigma)
-            .unwrap();
+        let mut R_sigma = X_table.multiply(&schnorr_response.s_sigma);
         R_sigma -= context.mul_with_table(T_sigma_table, challenge).unwrap();
         if R_sigma.into_affine() != schnorr_commit.R_sigma {
             return Err(VBAccumulatorError::SigmaResponseInvalid);
         }
 
         // R_rho = schnorr_response.s_rho * prk.Y - challenge * randomized_witness.T_rho;
-        let mut R_rho = context
-            .mul_with_table(Y_table, &schnorr_response.s_rho)
-            .unwrap();
+        let mut R_rho = Y_table.multiply(&schnorr_response.s_rho);
         R_rho -= context.mul_with_table(T_rho_table, challenge).unwrap();
         if R_rho.into_affine() != schnorr_commit.R_rho {
             return Err(VBAccumulatorError::RhoResponseInvalid);
@@ -1040,9 +1264,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         let mut R_delta_sigma = context
             .mul_with_table(T_sigma_table, &schnorr_response.s_y)
             .unwrap();
-        R_delta_sigma -= context
-            .mul_with_table(X_table, &schnorr_response.s_delta_sigma)
-            .unwrap();
+        R_delta_sigma -= X_table.multiply(&schnorr_response.s_delta_sigma);
         if R_delta_sigma.into_affine() != schnorr_commit.R_delta_sigma {
             return Err(VBAccumulatorError::DeltaSigmaResponseInvalid);
         }
@@ -1051,9 +1273,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         let mut R_delta_rho = context
             .mul_with_table(T_rho_table, &schnorr_response.s_y)
             .unwrap();
-        R_delta_rho -= context
-            .mul_with_table(Y_table, &schnorr_response.s_delta_rho)
-            .unwrap();
+        R_delta_rho -= Y_table.multiply(&schnorr_response.s_delta_rho);
         if R_delta_rho.into_affine() != schnorr_commit.R_delta_rho {
             return Err(VBAccumulatorError::DeltaRhoResponseInvalid);
         }
@@ -1067,7 +1287,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         challenge: &E::ScalarField,
         context: &WnafContext,
         E_C_table: &[E::G1],
-        Z_table: &[E::G1],
+        Z_table: BaseTable<E::G1>,
     ) -> (E::G1Affine, E::G1Affine) {
         // R_E = e(E_C, params.P_tilde)^s_y * e(prk.Z, params.P_tilde)^(-s_delta_sigma - s_delta_rho) * e(prk.Z, Q_tilde)^(-s_sigma - s_rho) * e(V, params.P_tilde)^-challenge * e(E_C, Q_tilde)^challenge * pairing_extra
         // Here `pairing_extra` refers to `E_d * -challenge` and `K * -s_v` and is used to for creating the pairings `e(E_d, P_tilde)^challenge` as `e(challenge * E_d, P_tilde)` and `e(K, P_tilde)^{-s_v}` as `e(-s_v * K, P_tilde)`
@@ -1079,12 +1299,8 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             .mul_with_table(E_C_table, &schnorr_response.s_y)
             .unwrap();
         // (s_delta_sigma - s_delta_rho) * Z
-        let z_p = context
-            .mul_with_table(
-                Z_table,
-                &(-schnorr_response.s_delta_sigma - schnorr_response.s_delta_rho),
-            )
-            .unwrap();
+        let z_p =
+            Z_table.multiply(&(-schnorr_response.s_delta_sigma - schnorr_response.s_delta_rho));
         // -challenge * V
         let a = accumulator_value.mul_bigint((-*challenge).into_bigint());
         let mut p = E_C_p + z_p + a;
@@ -1094,12 +1310,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         }
 
         // (s_sigma - s_rho) * Z
-        let z_q = context
-            .mul_with_table(
-                Z_table,
-                &(-schnorr_response.s_sigma - schnorr_response.s_rho),
-            )
-            .unwrap();
+        let z_q = Z_table.multiply(&(-schnorr_response.s_sigma - schnorr_response.s_rho));
         // challenge * E_C
         let E_C_q = context.mul_with_table(E_C_table, challenge).unwrap();
         let q = z_q + E_C_q;
@@ -1124,6 +1335,47 @@ where
         pk: &PublicKey<E>,
         params: &SetupParams<E>,
         prk: &MembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        Self::init_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &ProvingKeyTables::new(&prk.0),
+        )
+    }
+
+    /// Same as [`Self::init`] but uses the tables of the prepared proving key rather than creating them
+    pub fn init_with_prepared_proving_key<R: RngCore>(
+        rng: &mut R,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &PreparedMembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        Self::init_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &prk.tables,
+        )
+    }
+
+    fn init_given_tables<R: RngCore>(
+        rng: &mut R,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk_tables: &ProvingKeyTables<E::G1Affine>,
     ) -> Self {
         let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments(
             rng,
@@ -1133,7 +1385,7 @@ where
             None,
             pk,
             params,
-            &prk.0,
+            prk_tables,
         );
         Self {
             element: *element,
@@ -1198,12 +1450,66 @@ where
         params: &SetupParams<E>,
         prk: &NonMembershipProvingKey<E::G1Affine>,
     ) -> Self {
-        // TODO: Since proving key is fixed, these tables can be created just once and stored.
         // There are multiple multiplications with P and K so create tables for them. 20 multiplications
         // is the upper bound
         let P_table = WindowTable::new(20, params.P.into_group());
         let K_table = WindowTable::new(20, prk.K.into_group());
+        Self::init_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &ProvingKeyTables::new(&prk.XYZ),
+            &K_table,
+            &P_table,
+        )
+    }
 
+    /// Same as [`Self::init`] but uses the tables of the prepared proving key rather than creating them.
+    /// The table for `P` is created if the prepared proving key was created for different setup params.
+    pub fn init_with_prepared_proving_key<R: RngCore>(
+        rng: &mut R,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &PreparedNonMembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        let P_table;
+        let P_table = if prk.P == params.P {
+            &prk.P_table
+        } else {
+            P_table = WindowTable::new(20, params.P.into_group());
+            &P_table
+        };
+        Self::init_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &prk.tables,
+            &prk.K_table,
+            P_table,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn init_given_tables<R: RngCore>(
+        rng: &mut R,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk_tables: &ProvingKeyTables<E::G1Affine>,
+        K_table: &WindowTable<E::G1>,
+        P_table: &WindowTable<E::G1>,
+    ) -> Self {
         // To prove non-zero d of witness
         let tau = E::ScalarField::rand(rng); // blinding in commitment to d
         let pi = E::ScalarField::rand(rng);
@@ -1243,7 +1549,7 @@ where
             Some(K_table.multiply(&-r_v)),
             pk,
             params,
-            &prk.XYZ,
+            prk_tables,
         );
 
         Self {
@@ -1363,7 +1669,29 @@ where
             challenge,
             pk,
             params,
-            &prk.0,
+            ProvingKeyForVerification::Unprepared(&prk.0),
+        )
+    }
+
+    /// Same as [`Self::verify`] but uses the tables of the prepared proving key rather than creating them
+    pub fn verify_with_prepared_proving_key(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &PreparedMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        <MembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof(
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            ProvingKeyForVerification::Prepared(&prk.tables),
         )
     }
 
@@ -1385,7 +1713,30 @@ where
             challenge,
             pk,
             params,
-            &prk.0,
+            ProvingKeyForVerification::Unprepared(&prk.0),
+            pairing_checker
+        )
+    }
+
+    pub fn verify_with_prepared_proving_key_and_randomized_pairing_checker(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &PreparedMembershipProvingKey<E::G1Affine>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        <MembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_randomized_pairing_checker(
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            ProvingKeyForVerification::Prepared(&prk.tables),
             pairing_checker
         )
     }
@@ -1445,7 +1796,33 @@ where
             challenge,
             pk,
             params,
-            &prk.XYZ,
+            ProvingKeyForVerification::Unprepared(&prk.XYZ),
+        )
+    }
+
+    /// Same as [`Self::verify`] but uses the tables of the prepared proving key rather than creating them
+    pub fn verify_with_prepared_proving_key(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &PreparedNonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra =
+            self.verify_except_pairings_with_prepared_proving_key(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof(
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            ProvingKeyForVerification::Prepared(&prk.tables),
         )
     }
 
@@ -1470,7 +1847,34 @@ where
             challenge,
             pk,
             params,
-            &prk.XYZ,
+            ProvingKeyForVerification::Unprepared(&prk.XYZ),
+            pairing_checker
+        )
+    }
+
+    pub fn verify_with_prepared_proving_key_and_randomized_pairing_checker(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &PreparedNonMembershipProvingKey<E::G1Affine>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra =
+            self.verify_except_pairings_with_prepared_proving_key(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_randomized_pairing_checker(
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            ProvingKeyForVerification::Prepared(&prk.tables),
             pairing_checker
         )
     }
@@ -1501,14 +1905,42 @@ where
         K_table: &[E::G1],
         P_table: &[E::G1],
         E_d_table: &[E::G1],
+    ) -> Result<(), VBAccumulatorError> {
+        self._verify_schnorr_proofs(
+            challenge,
+            context,
+            BaseTable::Wnaf(context, K_table),
+            BaseTable::Wnaf(context, P_table),
+            E_d_table,
+        )
+    }
+
+    pub fn get_pairing_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        context: &WnafContext,
+        K_table: &[E::G1],
+        E_d_table: &[E::G1],
+    ) -> E::G1 {
+        self._get_pairing_contribution(
+            challenge,
+            context,
+            BaseTable::Wnaf(context, K_table),
+            E_d_table,
+        )
+    }
+
+    fn _verify_schnorr_proofs(
+        &self,
+        challenge: &E::ScalarField,
+        context: &WnafContext,
+        K_table: BaseTable<E::G1>,
+        P_table: BaseTable<E::G1>,
+        E_d_table: &[E::G1],
     ) -> Result<(), VBAccumulatorError> {
         // R_A = schnorr_response.s_u * params.P + schnorr_response.s_v * prk.K - challenge * randomized_witness.E_d;
-        let mut R_A = context
-            .mul_with_table(P_table, &self.schnorr_response.s_u)
-            .unwrap();
-        R_A += context
-            .mul_with_table(K_table, &self.schnorr_response.s_v)
-            .unwrap();
+        let mut R_A = P_table.multiply(&self.schnorr_response.s_u);
+        R_A += K_table.multiply(&self.schnorr_response.s_v);
         R_A -= context.mul_with_table(E_d_table, challenge).unwrap();
 
         if R_A.into_affine() != self.schnorr_commit.R_A {
@@ -1516,14 +1948,12 @@ where
         }
 
         // R_B = schnorr_response.s_w * prk.K + schnorr_response.s_u * randomized_witness.E_d_inv - challenge * params.P;
-        let mut R_B = context
-            .mul_with_table(K_table, &self.schnorr_response.s_w)
-            .unwrap();
+        let mut R_B = K_table.multiply(&self.schnorr_response.s_w);
         R_B += self
             .randomized_witness
             .E_d_inv
             .mul_bigint(self.schnorr_response.s_u.into_bigint());
-        R_B -= context.mul_with_table(P_table, challenge).unwrap();
+        R_B -= P_table.multiply(challenge);
 
         if R_B.into_affine() != self.schnorr_commit.R_B {
             return Err(VBAccumulatorError::E_d_inv_ResponseInvalid);
@@ -1531,17 +1961,15 @@ where
         Ok(())
     }
 
-    pub fn get_pairing_contribution(
+    fn _get_pairing_contribution(
         &self,
         challenge: &E::ScalarField,
         context: &WnafContext,
-        K_table: &[E::G1],
+        K_table: BaseTable<E::G1>,
         E_d_table: &[E::G1],
     ) -> E::G1 {
         // -schnorr_response.s_v * prk.K + challenge * randomized_witness.E_d
-        context
-            .mul_with_table(K_table, &-self.schnorr_response.s_v)
-            .unwrap()
+        K_table.multiply(&-self.schnorr_response.s_v)
             + context.mul_with_table(E_d_table, challenge).unwrap()
     }
 
@@ -1559,6 +1987,29 @@ where
         self.verify_schnorr_proofs(challenge, &context, &K_table, &P_table, &E_d_table)?;
         Ok(self.get_pairing_contribution(challenge, &context, &K_table, &E_d_table))
     }
+
+    /// Same as [`Self::verify_except_pairings`] but uses the tables of the prepared proving key. The wNAF
+    /// table for `P` is created if the prepared proving key was created for a different `P`.
+    fn verify_except_pairings_with_prepared_proving_key(
+        &self,
+        challenge: &E::ScalarField,
+        P: &E::G1Affine,
+        prk: &PreparedNonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<E::G1, VBAccumulatorError> {
+        let context = WnafContext::new(4);
+        let E_d_table = context.table(self.randomized_witness.E_d.into_group());
+        let P_wnaf;
+        let P_table = if prk.P == *P {
+            BaseTable::Window(&prk.P_table)
+        } else {
+            P_wnaf = context.table(P.into_group());
+            BaseTable::Wnaf(&context, &P_wnaf)
+        };
+        let K_table = BaseTable::Window(&prk.K_table);
+
+        self._verify_schnorr_proofs(challenge, &context, K_table, P_table, &E_d_table)?;
+        Ok(self._get_pairing_contribution(challenge, &context, K_table, &E_d_table))
+    }
 }
 
 #[cfg(test)]
@@ -1924,4 +2375,284 @@ mod tests {
             count, proof_verif__with_prepared_and_rand_pair_check_duration
         );
     }
+
+    #[test]
+    fn proofs_with_prepared_proving_keys() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let count = 10;
+
+        // Membership proofs
+        let (params, keypair, mut pos_accumulator, mut pos_state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+        let prepared_prk = PreparedMembershipProvingKey::from(prk.clone());
+        test_serialization!(
+            PreparedMembershipProvingKey<<Bls12_381 as Pairing>::G1Affine>,
+            prepared_prk
+        );
+        // Only the proving key is serialized, the tables are created on deserialization
+        assert_eq!(prepared_prk.compressed_size(), prk.compressed_size());
+
+        let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+        let mut proof_create_duration = Duration::default();
+        let mut proof_create_with_prepared_duration = Duration::default();
+        let mut proof_verif_duration = Duration::default();
+        let mut proof_verif_with_prepared_duration = Duration::default();
+
+        for _ in 0..count {
+            let elem = Fr::rand(&mut rng);
+            pos_accumulator = pos_accumulator
+                .add(elem, &keypair.secret_key, &mut pos_state)
+                .unwrap();
+            let w = pos_accumulator
+                .get_membership_witness(&elem, &keypair.secret_key, &pos_state)
+                .unwrap();
+
+            let start = Instant::now();
+            MembershipProofProtocol::init(
+                &mut rng,
+                &elem,
+                None,
+                &w,
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+            proof_create_duration += start.elapsed();
+
+            let start = Instant::now();
+            let protocol = MembershipProofProtocol::init_with_prepared_proving_key(
+                &mut rng,
+                &elem,
+                None,
+                &w,
+                &keypair.public_key,
+                &params,
+                &prepared_prk,
+            );
+            proof_create_with_prepared_duration += start.elapsed();
+
+            // Challenge contribution is same for the prepared and the unprepared proving key
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(
+                    pos_accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prepared_prk.prk,
+                    &mut chal_bytes,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            let proof = protocol.gen_proof(&challenge);
+
+            let start = Instant::now();
+            proof
+                .verify(
+                    pos_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prk,
+                )
+                .unwrap();
+            proof_verif_duration += start.elapsed();
+
+            let start = Instant::now();
+            proof
+                .verify_with_prepared_proving_key(
+                    pos_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                )
+                .unwrap();
+            proof_verif_with_prepared_duration += start.elapsed();
+
+            proof
+                .verify_with_prepared_proving_key_and_randomized_pairing_checker(
+                    pos_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                    &mut pairing_checker,
+                )
+                .unwrap();
+
+            assert!(proof
+                .verify_with_prepared_proving_key(
+                    pos_accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                )
+                .is_err());
+        }
+        assert!(pairing_checker.verify());
+
+        println!(
+            "Time to create {} membership proofs without and with prepared proving key is {:?} and {:?}",
+            count, proof_create_duration, proof_create_with_prepared_duration
+        );
+        println!(
+            "Time to verify {} membership proofs without and with prepared proving key is {:?} and {:?}",
+            count, proof_verif_duration, proof_verif_with_prepared_duration
+        );
+
+        // Non-membership proofs
+        let (params, keypair, mut uni_accumulator, initial_elems, mut uni_state) =
+            setup_universal_accum(&mut rng, 100);
+        let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+        let prepared_prk = PreparedNonMembershipProvingKey::new(prk.clone(), &params);
+        test_serialization!(
+            PreparedNonMembershipProvingKey<<Bls12_381 as Pairing>::G1Affine>,
+            prepared_prk
+        );
+        // Only the proving key and `P` are serialized, the tables are created on deserialization
+        assert_eq!(
+            prepared_prk.compressed_size(),
+            prk.compressed_size() + params.P.compressed_size()
+        );
+        assert_eq!(
+            prepared_prk.derive_membership_proving_key(),
+            PreparedMembershipProvingKey::from(prk.derive_membership_proving_key())
+        );
+
+        // Prepared for different setup params so the table for `P` is created when needed
+        let other_params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let prepared_prk_for_other_params =
+            PreparedNonMembershipProvingKey::new(prk.clone(), &other_params);
+
+        for _ in 0..20 {
+            uni_accumulator = uni_accumulator
+                .add(
+                    Fr::rand(&mut rng),
+                    &keypair.secret_key,
+                    &initial_elems,
+                    &mut uni_state,
+                )
+                .unwrap();
+        }
+
+        let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+        let mut proof_create_duration = Duration::default();
+        let mut proof_create_with_prepared_duration = Duration::default();
+        let mut proof_verif_duration = Duration::default();
+        let mut proof_verif_with_prepared_duration = Duration::default();
+
+        for i in 0..count {
+            let elem = Fr::rand(&mut rng);
+            let w = uni_accumulator
+                .get_non_membership_witness(&elem, &keypair.secret_key, &uni_state, &params)
+                .unwrap();
+
+            let start = Instant::now();
+            NonMembershipProofProtocol::init(
+                &mut rng,
+                &elem,
+                None,
+                &w,
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+            proof_create_duration += start.elapsed();
+
+            let start = Instant::now();
+            let protocol = NonMembershipProofProtocol::init_with_prepared_proving_key(
+                &mut rng,
+                &elem,
+                None,
+                &w,
+                &keypair.public_key,
+                &params,
+                if i % 2 == 0 {
+                    &prepared_prk
+                } else {
+                    &prepared_prk_for_other_params
+                },
+            );
+            proof_create_with_prepared_duration += start.elapsed();
+
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(
+                    uni_accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prepared_prk.prk,
+                    &mut chal_bytes,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            let proof = protocol.gen_proof(&challenge);
+
+            let start = Instant::now();
+            proof
+                .verify(
+                    uni_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prk,
+                )
+                .unwrap();
+            proof_verif_duration += start.elapsed();
+
+            let start = Instant::now();
+            proof
+                .verify_with_prepared_proving_key(
+                    uni_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                )
+                .unwrap();
+            proof_verif_with_prepared_duration += start.elapsed();
+
+            proof
+                .verify_with_prepared_proving_key(
+                    uni_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk_for_other_params,
+                )
+                .unwrap();
+
+            proof
+                .verify_with_prepared_proving_key_and_randomized_pairing_checker(
+                    uni_accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                    &mut pairing_checker,
+                )
+                .unwrap();
+
+            assert!(proof
+                .verify_with_prepared_proving_key(
+                    uni_accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prepared_prk,
+                )
+                .is_err());
+        }
+        assert!(pairing_checker.verify());
+
+        println!(
+            "Time to create {} non-membership proofs without and with prepared proving key is {:?} and {:?}",
+            count, proof_create_duration, proof_create_with_prepared_duration
+        );
+        println!(
+            "Time to verify {} non-membership proofs without and with prepared proving key is {:?} and {:?}",
+            count, proof_verif_duration, proof_verif_with_prepared_duration
+        );
+    }
 }
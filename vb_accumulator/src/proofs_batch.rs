@@ -0,0 +1,677 @@
+#![allow(non_snake_case)]
+
+//! Proofs of membership and non-membership of several hidden elements in the same accumulator.
+//!
+//! Each element is proven using the randomization described in [`proofs_keyed_verification`], i.e. the prover
+//! sends `C' = C * r` and `C_bar = C' * alpha` along with a proof of knowledge of `r` and the element, but rather than
+//! checking `C_bar = C' * alpha` with the secret key, the verifier checks `e(C_bar, P_tilde) = e(C', Q)` with the
+//! public key `Q`. All proofs of knowledge share the same challenge `c` and the pairing checks of all elements are
+//! aggregated into one as `e(\sum_i{C_bar_i * c^i}, P_tilde) = e(\sum_i{C'_i * c^i}, Q)` so verification needs only
+//! 2 pairings irrespective of the number of elements. Since `C'` and `C_bar` are part of the challenge, the prover
+//! can't choose them to cancel each other out in the sum.
+//!
+//! Each element adds 3 group elements and 2 field elements (5 and 3 for non-membership) to the proof compared to the
+//! 7 group elements, 1 target group element and 5 field elements of the proof in [`proofs`].
+//!
+//! The randomizer `r` is still different for each element. Using the same `r` would have made
+//! `C'_i * (y_i + alpha) = V * r` for all `i`, which lets anyone knowing `alpha` check if a pair of elements is in the
+//! proof.
+//!
+//! [`proofs_keyed_verification`]: crate::proofs_keyed_verification
+//! [`proofs`]: crate::proofs
+
+use crate::{
+    error::VBAccumulatorError,
+    proofs_keyed_verification::{
+        MembershipProofWithKV, MembershipProofWithKVProtocol, NonMembershipProofWithKV,
+        NonMembershipProofWithKVProtocol,
+    },
+    setup::{PreparedPublicKey, PreparedSetupParams, SetupParams},
+    witness::{MembershipWitness, NonMembershipWitness},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{io::Write, rand::RngCore, vec::Vec};
+use dock_crypto_utils::{ff::powers, randomized_pairing_check::RandomizedPairingChecker};
+use serde::{Deserialize, Serialize};
+
+/// Protocol for proving knowledge of several members and their membership witnesses in the same accumulator
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchMembershipProofProtocol<E: Pairing>(pub Vec<MembershipProofWithKVProtocol<E>>);
+
+/// Proof of knowledge of several members and their membership witnesses in the same accumulator
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchMembershipProof<E: Pairing>(pub Vec<MembershipProofWithKV<E>>);
+
+/// Protocol for proving knowledge of several non-members and their non-membership witnesses in the same accumulator
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchNonMembershipProofProtocol<E: Pairing>(
+    pub Vec<NonMembershipProofWithKVProtocol<E>>,
+);
+
+/// Proof of knowledge of several non-members and their non-membership witnesses in the same accumulator
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchNonMembershipProof<E: Pairing>(pub Vec<NonMembershipProofWithKV<E>>);
+
+impl<E: Pairing> BatchMembershipProofProtocol<E> {
+    /// Initialize the protocol for the given members. `element_blindings` are the optional blindings for the members,
+    /// in the same order, which are used when the members are proven to be equal to some other witness.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        elements: &[E::ScalarField],
+        element_blindings: Vec<Option<E::ScalarField>>,
+        witnesses: &[MembershipWitness<E::G1Affine>],
+        accumulator_value: &E::G1Affine,
+    ) -> Result<Self, VBAccumulatorError> {
+        check_batch_size(elements.len(), witnesses.len(), element_blindings.len())?;
+        Ok(Self(
+            elements
+                .iter()
+                .zip(element_blindings)
+                .zip(witnesses)
+                .map(|((e, b), w)| {
+                    MembershipProofWithKVProtocol::init(rng, *e, b, w, accumulator_value)
+                })
+                .collect(),
+        ))
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        accumulator_value.serialize_compressed(&mut writer)?;
+        for p in &self.0 {
+            p.C_prime.serialize_compressed(&mut writer)?;
+            p.C_bar.serialize_compressed(&mut writer)?;
+            p.sc_comm.t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn gen_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<BatchMembershipProof<E>, VBAccumulatorError> {
+        Ok(BatchMembershipProof(
+            self.0
+                .into_iter()
+                .map(|p| p.gen_proof(challenge))
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+impl<E: Pairing> BatchMembershipProof<E> {
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        accumulator_value.serialize_compressed(&mut writer)?;
+        for p in &self.0 {
+            p.C_prime.serialize_compressed(&mut writer)?;
+            p.C_bar.serialize_compressed(&mut writer)?;
+            p.t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn verify(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), VBAccumulatorError> {
+        let (C_bar, C_prime) = self.verify_except_pairings(accumulator_value, challenge)?;
+        verify_pairing(C_bar, C_prime, pk, params)
+    }
+
+    pub fn verify_with_randomized_pairing_checker(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let (C_bar, C_prime) = self.verify_except_pairings(accumulator_value, challenge)?;
+        pairing_checker.add_sources(&C_bar, params.into().P_tilde, &C_prime, pk.into().0);
+        Ok(())
+    }
+
+    /// Verify the proofs of knowledge and return the aggregated `C_bar` and `C'` for the pairing check
+    pub fn verify_except_pairings(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+    ) -> Result<(E::G1Affine, E::G1Affine), VBAccumulatorError> {
+        if self.0.is_empty() {
+            return Err(VBAccumulatorError::EmptyBatch);
+        }
+        for p in &self.0 {
+            p.verify_schnorr_proof(accumulator_value, challenge)?;
+        }
+        Ok(aggregate::<E>(
+            self.0.iter().map(|p| (p.C_bar, p.C_prime)),
+            challenge,
+        ))
+    }
+
+    /// Number of members in the proof
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get response for Schnorr protocol for the member at index `index` in the batch. This is useful when the member
+    /// is also used in another relation that is proven along this protocol.
+    pub fn get_schnorr_response_for_element(
+        &self,
+        index: usize,
+    ) -> Result<&E::ScalarField, VBAccumulatorError> {
+        self.0
+            .get(index)
+            .map(|p| p.get_schnorr_response_for_element())
+            .ok_or(VBAccumulatorError::IndexOutOfBatch(index, self.0.len()))
+    }
+}
+
+impl<E: Pairing> BatchNonMembershipProofProtocol<E> {
+    /// Initialize the protocol for the given non-members. `element_blindings` are the optional blindings for the
+    /// non-members, in the same order, which are used when the non-members are proven to be equal to some other witness.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        elements: &[E::ScalarField],
+        element_blindings: Vec<Option<E::ScalarField>>,
+        witnesses: &[NonMembershipWitness<E::G1Affine>],
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+    ) -> Result<Self, VBAccumulatorError> {
+        check_batch_size(elements.len(), witnesses.len(), element_blindings.len())?;
+        Ok(Self(
+            elements
+                .iter()
+                .zip(element_blindings)
+                .zip(witnesses)
+                .map(|((e, b), w)| {
+                    NonMembershipProofWithKVProtocol::init(rng, *e, b, w, accumulator_value, params)
+                })
+                .collect(),
+        ))
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        params.P.serialize_compressed(&mut writer)?;
+        accumulator_value.serialize_compressed(&mut writer)?;
+        for p in &self.0 {
+            p.C_prime.serialize_compressed(&mut writer)?;
+            p.C_bar.serialize_compressed(&mut writer)?;
+            p.E_d.serialize_compressed(&mut writer)?;
+            p.sc_comm.t.serialize_compressed(&mut writer)?;
+            p.sc_comm_E_d.t.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn gen_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<BatchNonMembershipProof<E>, VBAccumulatorError> {
+        Ok(BatchNonMembershipProof(
+            self.0
+                .into_iter()
+                .map(|p| p.gen_proof(challenge))
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+impl<E: Pairing> BatchNonMembershipProof<E> {
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        params.P.serialize_compressed(&mut writer)?;
+        accumulator_value.serialize_compressed(&mut writer)?;
+        for p in &self.0 {
+            p.C_prime.serialize_compressed(&mut writer)?;
+            p.C_bar.serialize_compressed(&mut writer)?;
+            p.E_d.serialize_compressed(&mut writer)?;
+            p.t.serialize_compressed(&mut writer)?;
+            p.t_E_d.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn verify(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let (C_bar, C_prime) =
+            self.verify_except_pairings(accumulator_value, challenge, &params.P)?;
+        verify_pairing(C_bar, C_prime, pk, params)
+    }
+
+    pub fn verify_with_randomized_pairing_checker(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let (C_bar, C_prime) =
+            self.verify_except_pairings(accumulator_value, challenge, &params.P)?;
+        pairing_checker.add_sources(&C_bar, params.P_tilde, &C_prime, pk.into().0);
+        Ok(())
+    }
+
+    /// Verify the proofs of knowledge and that `d != 0` for each non-member and return the aggregated `C_bar` and `C'`
+    /// for the pairing check
+    pub fn verify_except_pairings(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        P: &E::G1Affine,
+    ) -> Result<(E::G1Affine, E::G1Affine), VBAccumulatorError> {
+        if self.0.is_empty() {
+            return Err(VBAccumulatorError::EmptyBatch);
+        }
+        for p in &self.0 {
+            p.verify_schnorr_proof_given_P(accumulator_value, challenge, P)?;
+        }
+        Ok(aggregate::<E>(
+            self.0.iter().map(|p| (p.C_bar, p.C_prime)),
+            challenge,
+        ))
+    }
+
+    /// Number of non-members in the proof
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get response for Schnorr protocol for the non-member at index `index` in the batch. This is useful when the
+    /// non-member is also used in another relation that is proven along this protocol.
+    pub fn get_schnorr_response_for_element(
+        &self,
+        index: usize,
+    ) -> Result<&E::ScalarField, VBAccumulatorError> {
+        self.0
+            .get(index)
+            .map(|p| p.get_schnorr_response_for_element())
+            .ok_or(VBAccumulatorError::IndexOutOfBatch(index, self.0.len()))
+    }
+}
+
+fn check_batch_size(
+    elements: usize,
+    witnesses: usize,
+    blindings: usize,
+) -> Result<(), VBAccumulatorError> {
+    if elements == 0 {
+        return Err(VBAccumulatorError::EmptyBatch);
+    }
+    if elements != witnesses || elements != blindings {
+        return Err(VBAccumulatorError::NeedSameNoOfElementsAndWitnesses);
+    }
+    Ok(())
+}
+
+/// Returns `(\sum_i{C_bar_i * c^i}, \sum_i{C'_i * c^i})`
+fn aggregate<E: Pairing>(
+    C_bar_and_C_prime: impl Iterator<Item = (E::G1Affine, E::G1Affine)>,
+    challenge: &E::ScalarField,
+) -> (E::G1Affine, E::G1Affine) {
+    let (C_bar, C_prime): (Vec<_>, Vec<_>) = C_bar_and_C_prime.unzip();
+    let r = powers(challenge, C_bar.len() as u32);
+    (
+        E::G1::msm_unchecked(&C_bar, &r).into_affine(),
+        E::G1::msm_unchecked(&C_prime, &r).into_affine(),
+    )
+}
+
+/// Check `e(C_bar, P_tilde) = e(C', Q)`
+fn verify_pairing<E: Pairing>(
+    C_bar: E::G1Affine,
+    C_prime: E::G1Affine,
+    pk: impl Into<PreparedPublicKey<E>>,
+    params: impl Into<PreparedSetupParams<E>>,
+) -> Result<(), VBAccumulatorError> {
+    let C_prime_neg = (-C_prime.into_group()).into_affine();
+    if !E::multi_pairing([C_bar, C_prime_neg], [params.into().P_tilde, pk.into().0]).is_zero() {
+        return Err(VBAccumulatorError::PairingResponseInvalid);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        persistence::State,
+        positive::{tests::setup_positive_accum, Accumulator},
+        setup::Keypair,
+        test_serialization,
+        universal::tests::setup_universal_accum,
+    };
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+    use schnorr_pok::compute_random_oracle_challenge;
+
+    #[test]
+    fn batch_membership_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let other_keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+
+        let elems = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator = accumulator
+            .add_batch(elems.clone(), &keypair.secret_key, &mut state)
+            .unwrap();
+
+        for batch_size in [1, 2, 5, 10] {
+            let members = &elems[0..batch_size];
+            let witnesses = accumulator
+                .get_membership_witnesses_for_batch(members, &keypair.secret_key, &state)
+                .unwrap();
+
+            let protocol = BatchMembershipProofProtocol::<Bls12_381>::init(
+                &mut rng,
+                members,
+                vec![None; batch_size],
+                &witnesses,
+                accumulator.value(),
+            )
+            .unwrap();
+
+            let mut chal_bytes_prover = vec![];
+            protocol
+                .challenge_contribution(accumulator.value(), &mut chal_bytes_prover)
+                .unwrap();
+            let challenge_prover =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+            let proof = protocol.gen_proof(&challenge_prover).unwrap();
+            test_serialization!(BatchMembershipProof<Bls12_381>, proof);
+            assert_eq!(proof.len(), batch_size);
+
+            let mut chal_bytes_verifier = vec![];
+            proof
+                .challenge_contribution(accumulator.value(), &mut chal_bytes_verifier)
+                .unwrap();
+            let challenge_verifier =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            assert_eq!(challenge_prover, challenge_verifier);
+
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .unwrap();
+
+            let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+            proof
+                .verify_with_randomized_pairing_checker(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &mut pairing_checker,
+                )
+                .unwrap();
+            assert!(pairing_checker.verify());
+
+            for (i, member) in members.iter().enumerate() {
+                assert_ne!(proof.get_schnorr_response_for_element(i).unwrap(), member);
+            }
+            assert!(proof.get_schnorr_response_for_element(batch_size).is_err());
+
+            assert!(proof
+                .verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    other_keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .is_err());
+            assert!(proof
+                .verify(
+                    &G1Affine::rand(&mut rng),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .is_err());
+            assert!(proof
+                .verify(
+                    accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .is_err());
+        }
+
+        // Proof fails if any of the elements has been removed
+        let members = &elems[0..3];
+        let witnesses = accumulator
+            .get_membership_witnesses_for_batch(members, &keypair.secret_key, &state)
+            .unwrap();
+        accumulator = accumulator
+            .remove(&members[1], &keypair.secret_key, &mut state)
+            .unwrap();
+        assert!(!state.has(&members[1]));
+        let protocol = BatchMembershipProofProtocol::<Bls12_381>::init(
+            &mut rng,
+            members,
+            vec![None; 3],
+            &witnesses,
+            accumulator.value(),
+        )
+        .unwrap();
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(accumulator.value(), &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        assert!(matches!(
+            proof.verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+
+        assert!(matches!(
+            BatchMembershipProofProtocol::<Bls12_381>::init(
+                &mut rng,
+                &elems[0..2],
+                vec![None; 2],
+                &witnesses,
+                accumulator.value(),
+            ),
+            Err(VBAccumulatorError::NeedSameNoOfElementsAndWitnesses)
+        ));
+        assert!(matches!(
+            BatchMembershipProofProtocol::<Bls12_381>::init(
+                &mut rng,
+                &[],
+                vec![],
+                &[],
+                accumulator.value(),
+            ),
+            Err(VBAccumulatorError::EmptyBatch)
+        ));
+        assert!(matches!(
+            BatchMembershipProof::<Bls12_381>(vec![]).verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(VBAccumulatorError::EmptyBatch)
+        ));
+    }
+
+    #[test]
+    fn batch_non_membership_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 100);
+
+        let members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator = accumulator
+            .add_batch(members, &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+
+        let non_members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        for batch_size in [1, 2, 5] {
+            let elems = &non_members[0..batch_size];
+            let witnesses = accumulator
+                .get_non_membership_witnesses_for_batch(elems, &keypair.secret_key, &state, &params)
+                .unwrap();
+
+            let protocol = BatchNonMembershipProofProtocol::<Bls12_381>::init(
+                &mut rng,
+                elems,
+                vec![None; batch_size],
+                &witnesses,
+                accumulator.value(),
+                &params,
+            )
+            .unwrap();
+
+            let mut chal_bytes_prover = vec![];
+            protocol
+                .challenge_contribution(accumulator.value(), &params, &mut chal_bytes_prover)
+                .unwrap();
+            let challenge_prover =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+            let proof = protocol.gen_proof(&challenge_prover).unwrap();
+            test_serialization!(BatchNonMembershipProof<Bls12_381>, proof);
+
+            let mut chal_bytes_verifier = vec![];
+            proof
+                .challenge_contribution(accumulator.value(), &params, &mut chal_bytes_verifier)
+                .unwrap();
+            let challenge_verifier =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            assert_eq!(challenge_prover, challenge_verifier);
+
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .unwrap();
+
+            let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, false);
+            proof
+                .verify_with_randomized_pairing_checker(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &mut pairing_checker,
+                )
+                .unwrap();
+            assert!(pairing_checker.verify());
+
+            assert!(proof
+                .verify(
+                    accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    keypair.public_key.clone(),
+                    params.clone(),
+                )
+                .is_err());
+
+            // `d` must not be 0
+            let mut wrong = proof.clone();
+            wrong.0[batch_size - 1].E_d = G1Affine::zero();
+            assert!(matches!(
+                wrong.verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                ),
+                Err(VBAccumulatorError::CannotBeZero)
+            ));
+        }
+
+        // Proof fails if any of the elements has been added
+        let elems = &non_members[0..3];
+        let witnesses = accumulator
+            .get_non_membership_witnesses_for_batch(elems, &keypair.secret_key, &state, &params)
+            .unwrap();
+        accumulator = accumulator
+            .add(elems[2], &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+        let protocol = BatchNonMembershipProofProtocol::<Bls12_381>::init(
+            &mut rng,
+            elems,
+            vec![None; 3],
+            &witnesses,
+            accumulator.value(),
+            &params,
+        )
+        .unwrap();
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(accumulator.value(), &params, &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        assert!(matches!(
+            proof.verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+    }
+}
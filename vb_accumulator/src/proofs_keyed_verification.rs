@@ -0,0 +1,685 @@
+#![allow(non_snake_case)]
+
+//! Proofs of membership and non-membership where the verifier knows the accumulator secret key `alpha` and thus
+//! doesn't need pairings. Useful when the accumulator manager (like the issuer of a credential) is also the verifier.
+//!
+//! For membership, the witness `C` satisfies `C * (y + alpha) = V`. The prover picks a random `r`, and computes
+//! `C' = C * r` and `C_bar = V * r - C' * y` which equals `C' * alpha`. It then sends `C'` and `C_bar` along with
+//! a proof of knowledge of `r` and `y` in `C_bar = V * r - C' * y`. The verifier checks the proof of knowledge and
+//! that `C_bar = C' * alpha`.
+//!
+//! For non-membership, the witness `(C, d)` satisfies `C * (y + alpha) + P * d = V` with `d != 0`. The prover picks
+//! a random `r`, and computes `C' = C * r`, `E_d = P * d * r` and `C_bar = V * r - C' * y - E_d` which equals
+//! `C' * alpha`. It then sends `C'`, `C_bar` and `E_d` along with a proof of knowledge of `r` and `y` in
+//! `C_bar + E_d = V * r - C' * y` and of `d * r` in `E_d = P * d * r`. The verifier checks the proofs of knowledge,
+//! that `E_d` is not the identity element, which means `d != 0`, and that `C_bar = C' * alpha`.
+//!
+//! The verification is split in 2 parts: the proofs of knowledge can be checked by anyone, like a third party to which
+//! the verifier delegates the work, using [`MembershipProofWithKV::verify_schnorr_proof`] or
+//! [`NonMembershipProofWithKV::verify_schnorr_proof`], and the check of `C_bar` needs the secret key and is done by
+//! [`DelegatedProof::verify`] on the proof returned by `to_delegated_proof`.
+
+use crate::{
+    error::VBAccumulatorError,
+    setup::{SecretKey, SetupParams},
+    witness::{MembershipWitness, NonMembershipWitness},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{io::Write, rand::RngCore, vec, vec::Vec, UniformRand};
+use dock_crypto_utils::serde_utils::*;
+use schnorr_pok::{SchnorrCommitment, SchnorrResponse};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Protocol for proving knowledge of the member and the membership witness to a verifier knowing the secret key
+#[derive(
+    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+pub struct MembershipProofWithKVProtocol<E: Pairing> {
+    /// Randomized witness `C * r`
+    #[zeroize(skip)]
+    pub C_prime: E::G1Affine,
+    /// `C' * alpha`
+    #[zeroize(skip)]
+    pub C_bar: E::G1Affine,
+    /// Commitment to the blindings of `r` and the member
+    pub sc_comm: SchnorrCommitment<E::G1Affine>,
+    /// `r` and the member
+    sc_wits: [E::ScalarField; 2],
+}
+
+/// Proof of knowledge of the member and the membership witness that can be verified using the secret key
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MembershipProofWithKV<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_prime: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_bar: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t: E::G1Affine,
+    pub sc_resp: SchnorrResponse<E::G1Affine>,
+}
+
+/// Protocol for proving knowledge of the non-member and the non-membership witness to a verifier knowing the secret key
+#[derive(
+    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+pub struct NonMembershipProofWithKVProtocol<E: Pairing> {
+    /// Randomized witness `C * r`
+    #[zeroize(skip)]
+    pub C_prime: E::G1Affine,
+    /// `C' * alpha`
+    #[zeroize(skip)]
+    pub C_bar: E::G1Affine,
+    /// Randomized `d` as `P * d * r`
+    #[zeroize(skip)]
+    pub E_d: E::G1Affine,
+    /// Commitment to the blindings of `r` and the non-member
+    pub sc_comm: SchnorrCommitment<E::G1Affine>,
+    /// `r` and the non-member
+    sc_wits: [E::ScalarField; 2],
+    /// Commitment to the blinding of `d * r`
+    pub sc_comm_E_d: SchnorrCommitment<E::G1Affine>,
+    /// `d * r`
+    sc_wit_E_d: E::ScalarField,
+}
+
+/// Proof of knowledge of the non-member and the non-membership witness that can be verified using the secret key
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct NonMembershipProofWithKV<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_prime: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_bar: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub E_d: E::G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t: E::G1Affine,
+    pub sc_resp: SchnorrResponse<E::G1Affine>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t_E_d: E::G1Affine,
+    pub sc_resp_E_d: SchnorrResponse<E::G1Affine>,
+}
+
+/// The part of a membership or non-membership proof that is left to be verified with the secret key once the proofs
+/// of knowledge in it have been verified, possibly by someone else.
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct DelegatedProof<G: AffineRepr> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_prime: G,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub C_bar: G,
+}
+
+impl<E: Pairing> MembershipProofWithKVProtocol<E> {
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G1Affine>,
+        accumulator_value: &E::G1Affine,
+    ) -> Self {
+        let r = E::ScalarField::rand(rng);
+        let element_blinding = element_blinding.unwrap_or_else(|| E::ScalarField::rand(rng));
+        let C_prime = witness.0 * r;
+        let C_prime_neg = -C_prime;
+        // C_bar = V * r - C' * y
+        let C_bar = *accumulator_value * r + C_prime_neg * element;
+        let C_prime_neg = C_prime_neg.into_affine();
+        let sc_comm = SchnorrCommitment::new(
+            &[*accumulator_value, C_prime_neg],
+            vec![E::ScalarField::rand(rng), element_blinding],
+        );
+        Self {
+            C_prime: C_prime.into_affine(),
+            C_bar: C_bar.into_affine(),
+            sc_comm,
+            sc_wits: [r, element],
+        }
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        MembershipProofWithKV::<E>::compute_challenge_contribution(
+            &self.C_prime,
+            &self.C_bar,
+            &self.sc_comm.t,
+            accumulator_value,
+            writer,
+        )
+    }
+
+    pub fn gen_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<MembershipProofWithKV<E>, VBAccumulatorError> {
+        let sc_resp = self.sc_comm.response(&self.sc_wits, challenge)?;
+        Ok(MembershipProofWithKV {
+            C_prime: self.C_prime,
+            C_bar: self.C_bar,
+            t: self.sc_comm.t,
+            sc_resp,
+        })
+    }
+}
+
+impl<E: Pairing> MembershipProofWithKV<E> {
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        Self::compute_challenge_contribution(
+            &self.C_prime,
+            &self.C_bar,
+            &self.t,
+            accumulator_value,
+            writer,
+        )
+    }
+
+    /// Verify the proof using the secret key
+    pub fn verify(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.verify_schnorr_proof(accumulator_value, challenge)?;
+        self.to_delegated_proof().verify(secret_key)
+    }
+
+    /// Verify the proof of knowledge. This doesn't need the secret key so can be done by anyone but the proof isn't
+    /// valid unless [`DelegatedProof::verify`] succeeds as well.
+    pub fn verify_schnorr_proof(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+    ) -> Result<(), VBAccumulatorError> {
+        if self.C_prime.is_zero() {
+            return Err(VBAccumulatorError::CannotBeZero);
+        }
+        self.sc_resp.is_valid(
+            &[
+                *accumulator_value,
+                (-self.C_prime.into_group()).into_affine(),
+            ],
+            &self.C_bar,
+            &self.t,
+            challenge,
+        )?;
+        Ok(())
+    }
+
+    pub fn to_delegated_proof(&self) -> DelegatedProof<E::G1Affine> {
+        DelegatedProof {
+            C_prime: self.C_prime,
+            C_bar: self.C_bar,
+        }
+    }
+
+    pub fn get_schnorr_response_for_element(&self) -> &E::ScalarField {
+        &self.sc_resp.0[1]
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        C_prime: &E::G1Affine,
+        C_bar: &E::G1Affine,
+        t: &E::G1Affine,
+        accumulator_value: &E::G1Affine,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        accumulator_value.serialize_compressed(&mut writer)?;
+        C_prime.serialize_compressed(&mut writer)?;
+        C_bar.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl<E: Pairing> NonMembershipProofWithKVProtocol<E> {
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+    ) -> Self {
+        let r = E::ScalarField::rand(rng);
+        let element_blinding = element_blinding.unwrap_or_else(|| E::ScalarField::rand(rng));
+        let C_prime = witness.C * r;
+        let C_prime_neg = -C_prime;
+        let d_r = witness.d * r;
+        let E_d = params.P * d_r;
+        // C_bar = V * r - C' * y - E
+        let C_bar = *accumulator_value * r + C_prime_neg * element - E_d;
+        let C_prime_neg = C_prime_neg.into_affine();
+        let sc_comm = SchnorrCommitment::new(
+            &[*accumulator_value, C_prime_neg],
+            vec![E::ScalarField::rand(rng), element_blinding],
+        );
+        let sc_comm_E_d = SchnorrCommitment::new(&[params.P], vec![E::ScalarField::rand(rng)]);
+        Self {
+            C_prime: C_prime.into_affine(),
+            C_bar: C_bar.into_affine(),
+            E_d: E_d.into_affine(),
+            sc_comm,
+            sc_wits: [r, element],
+            sc_comm_E_d,
+            sc_wit_E_d: d_r,
+        }
+    }
+
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        NonMembershipProofWithKV::<E>::compute_challenge_contribution(
+            &self.C_prime,
+            &self.C_bar,
+            &self.E_d,
+            &self.sc_comm.t,
+            &self.sc_comm_E_d.t,
+            accumulator_value,
+            params,
+            writer,
+        )
+    }
+
+    pub fn gen_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<NonMembershipProofWithKV<E>, VBAccumulatorError> {
+        let sc_resp = self.sc_comm.response(&self.sc_wits, challenge)?;
+        let sc_resp_E_d = self.sc_comm_E_d.response(&[self.sc_wit_E_d], challenge)?;
+        Ok(NonMembershipProofWithKV {
+            C_prime: self.C_prime,
+            C_bar: self.C_bar,
+            E_d: self.E_d,
+            t: self.sc_comm.t,
+            sc_resp,
+            t_E_d: self.sc_comm_E_d.t,
+            sc_resp_E_d,
+        })
+    }
+}
+
+impl<E: Pairing> NonMembershipProofWithKV<E> {
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        Self::compute_challenge_contribution(
+            &self.C_prime,
+            &self.C_bar,
+            &self.E_d,
+            &self.t,
+            &self.t_E_d,
+            accumulator_value,
+            params,
+            writer,
+        )
+    }
+
+    /// Verify the proof using the secret key
+    pub fn verify(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        secret_key: &SecretKey<E::ScalarField>,
+        params: &SetupParams<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.verify_schnorr_proof(accumulator_value, challenge, params)?;
+        self.to_delegated_proof().verify(secret_key)
+    }
+
+    /// Verify the proofs of knowledge and that `d != 0`. This doesn't need the secret key so can be done by anyone
+    /// but the proof isn't valid unless [`DelegatedProof::verify`] succeeds as well.
+    pub fn verify_schnorr_proof(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        params: &SetupParams<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.verify_schnorr_proof_given_P(accumulator_value, challenge, &params.P)
+    }
+
+    pub(crate) fn verify_schnorr_proof_given_P(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        P: &E::G1Affine,
+    ) -> Result<(), VBAccumulatorError> {
+        if self.C_prime.is_zero() || self.E_d.is_zero() {
+            return Err(VBAccumulatorError::CannotBeZero);
+        }
+        self.sc_resp.is_valid(
+            &[
+                *accumulator_value,
+                (-self.C_prime.into_group()).into_affine(),
+            ],
+            &(self.C_bar + self.E_d).into_affine(),
+            &self.t,
+            challenge,
+        )?;
+        self.sc_resp_E_d
+            .is_valid(&[*P], &self.E_d, &self.t_E_d, challenge)?;
+        Ok(())
+    }
+
+    pub fn to_delegated_proof(&self) -> DelegatedProof<E::G1Affine> {
+        DelegatedProof {
+            C_prime: self.C_prime,
+            C_bar: self.C_bar,
+        }
+    }
+
+    pub fn get_schnorr_response_for_element(&self) -> &E::ScalarField {
+        &self.sc_resp.0[1]
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_challenge_contribution<W: Write>(
+        C_prime: &E::G1Affine,
+        C_bar: &E::G1Affine,
+        E_d: &E::G1Affine,
+        t: &E::G1Affine,
+        t_E_d: &E::G1Affine,
+        accumulator_value: &E::G1Affine,
+        params: &SetupParams<E>,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        params.P.serialize_compressed(&mut writer)?;
+        accumulator_value.serialize_compressed(&mut writer)?;
+        C_prime.serialize_compressed(&mut writer)?;
+        C_bar.serialize_compressed(&mut writer)?;
+        E_d.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(&mut writer)?;
+        t_E_d.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl<G: AffineRepr> DelegatedProof<G> {
+    /// Check that `C_bar = C' * alpha`
+    pub fn verify(&self, secret_key: &SecretKey<G::ScalarField>) -> Result<(), VBAccumulatorError> {
+        if self.C_prime.is_zero() {
+            return Err(VBAccumulatorError::CannotBeZero);
+        }
+        if (self.C_prime * secret_key.0).into_affine() != self.C_bar {
+            return Err(VBAccumulatorError::InvalidKeyedProof);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        persistence::State,
+        positive::{tests::setup_positive_accum, Accumulator},
+        setup::Keypair,
+        test_serialization,
+        universal::tests::setup_universal_accum,
+    };
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+    use schnorr_pok::compute_random_oracle_challenge;
+
+    #[test]
+    fn membership_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let other_keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+
+        let elems = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator = accumulator
+            .add_batch(elems.clone(), &keypair.secret_key, &mut state)
+            .unwrap();
+
+        for elem in &elems {
+            let witness = accumulator
+                .get_membership_witness(elem, &keypair.secret_key, &state)
+                .unwrap();
+            let protocol = MembershipProofWithKVProtocol::<Bls12_381>::init(
+                &mut rng,
+                *elem,
+                None,
+                &witness,
+                accumulator.value(),
+            );
+
+            let mut chal_bytes_prover = vec![];
+            protocol
+                .challenge_contribution(accumulator.value(), &mut chal_bytes_prover)
+                .unwrap();
+            let challenge_prover =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+            let proof = protocol.gen_proof(&challenge_prover).unwrap();
+            test_serialization!(MembershipProofWithKV<Bls12_381>, proof);
+
+            let mut chal_bytes_verifier = vec![];
+            proof
+                .challenge_contribution(accumulator.value(), &mut chal_bytes_verifier)
+                .unwrap();
+            let challenge_verifier =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            assert_eq!(challenge_prover, challenge_verifier);
+
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    &keypair.secret_key,
+                )
+                .unwrap();
+
+            // Delegated verification
+            proof
+                .verify_schnorr_proof(accumulator.value(), &challenge_verifier)
+                .unwrap();
+            let delegated = proof.to_delegated_proof();
+            test_serialization!(DelegatedProof<G1Affine>, delegated);
+            delegated.verify(&keypair.secret_key).unwrap();
+
+            // Fails with a different key, accumulator or challenge
+            assert!(matches!(
+                proof.verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    &other_keypair.secret_key
+                ),
+                Err(VBAccumulatorError::InvalidKeyedProof)
+            ));
+            assert!(proof
+                .verify(
+                    &G1Affine::rand(&mut rng),
+                    &challenge_verifier,
+                    &keypair.secret_key
+                )
+                .is_err());
+            assert!(proof
+                .verify(
+                    accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    &keypair.secret_key
+                )
+                .is_err());
+        }
+
+        // Proof for a removed element fails
+        let witness = accumulator
+            .get_membership_witness(&elems[0], &keypair.secret_key, &state)
+            .unwrap();
+        accumulator = accumulator
+            .remove(&elems[0], &keypair.secret_key, &mut state)
+            .unwrap();
+        assert!(!state.has(&elems[0]));
+        let protocol = MembershipProofWithKVProtocol::<Bls12_381>::init(
+            &mut rng,
+            elems[0],
+            None,
+            &witness,
+            accumulator.value(),
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(accumulator.value(), &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify_schnorr_proof(accumulator.value(), &challenge)
+            .unwrap();
+        assert!(matches!(
+            proof.verify(accumulator.value(), &challenge, &keypair.secret_key),
+            Err(VBAccumulatorError::InvalidKeyedProof)
+        ));
+    }
+
+    #[test]
+    fn non_membership_proof() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 100);
+
+        let members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator = accumulator
+            .add_batch(members, &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+
+        for _ in 0..5 {
+            let elem = Fr::rand(&mut rng);
+            let witness = accumulator
+                .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+                .unwrap();
+            let protocol = NonMembershipProofWithKVProtocol::<Bls12_381>::init(
+                &mut rng,
+                elem,
+                None,
+                &witness,
+                accumulator.value(),
+                &params,
+            );
+
+            let mut chal_bytes_prover = vec![];
+            protocol
+                .challenge_contribution(accumulator.value(), &params, &mut chal_bytes_prover)
+                .unwrap();
+            let challenge_prover =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+            let proof = protocol.gen_proof(&challenge_prover).unwrap();
+            test_serialization!(NonMembershipProofWithKV<Bls12_381>, proof);
+
+            let mut chal_bytes_verifier = vec![];
+            proof
+                .challenge_contribution(accumulator.value(), &params, &mut chal_bytes_verifier)
+                .unwrap();
+            let challenge_verifier =
+                compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            assert_eq!(challenge_prover, challenge_verifier);
+
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge_verifier,
+                    &keypair.secret_key,
+                    &params,
+                )
+                .unwrap();
+
+            // Delegated verification
+            proof
+                .verify_schnorr_proof(accumulator.value(), &challenge_verifier, &params)
+                .unwrap();
+            proof
+                .to_delegated_proof()
+                .verify(&keypair.secret_key)
+                .unwrap();
+
+            assert!(proof
+                .verify(
+                    &G1Affine::rand(&mut rng),
+                    &challenge_verifier,
+                    &keypair.secret_key,
+                    &params
+                )
+                .is_err());
+            assert!(proof
+                .verify(
+                    accumulator.value(),
+                    &Fr::rand(&mut rng),
+                    &keypair.secret_key,
+                    &params
+                )
+                .is_err());
+
+            // `d` must not be 0
+            let mut wrong = proof.clone();
+            wrong.E_d = G1Affine::zero();
+            assert!(matches!(
+                wrong.verify_schnorr_proof(accumulator.value(), &challenge_verifier, &params),
+                Err(VBAccumulatorError::CannotBeZero)
+            ));
+        }
+
+        // Proof fails once the element is added
+        let elem = Fr::rand(&mut rng);
+        let witness = accumulator
+            .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+            .unwrap();
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+        let protocol = NonMembershipProofWithKVProtocol::<Bls12_381>::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            accumulator.value(),
+            &params,
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(accumulator.value(), &params, &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify_schnorr_proof(accumulator.value(), &challenge, &params)
+            .unwrap();
+        assert!(matches!(
+            proof.verify(
+                accumulator.value(),
+                &challenge,
+                &keypair.secret_key,
+                &params
+            ),
+            Err(VBAccumulatorError::InvalidKeyedProof)
+        ));
+    }
+}
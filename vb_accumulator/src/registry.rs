@@ -0,0 +1,624 @@
+//! Registry of accumulator updates for publishing the update info needed by holders to update their witnesses.
+//!
+//! The accumulator manager makes updates through [`AccumulatorRegistry`] which assigns each update the next epoch,
+//! starting from 1 with the initial accumulator at epoch 0, and appends an [`UpdateRecord`] containing the epoch, the
+//! new accumulated value, the additions, the removals and the [`Omega`] of the update to an [`UpdateLog`]. A holder
+//! with a witness valid at epoch `i` updates its witness to the latest epoch using a [`CatchUpBundle`] created with
+//! [`AccumulatorRegistry::catch_up_bundle`] which contains the records of epochs `i+1` to the latest.
+//! Each record has an [`UpdateProof`] so the holder, or anyone auditing the log, can check with the public key that the
+//! accumulated values follow from the published additions and removals, see [`CatchUpBundle::verify`].
+//!
+//! ```ignore
+//! let mut registry = AccumulatorRegistry::new(accumulator, vec![])?;
+//! registry.batch_updates(additions, &removals, &sk, &mut state)?;
+//! // Holder with a witness of epoch `epoch`
+//! let bundle = registry.catch_up_bundle(epoch)?;
+//! let new_witness = bundle.update_membership_witness(&witness, &member)?;
+//! ```
+
+use crate::{
+    batch_utils::Omega,
+    error::VBAccumulatorError,
+    persistence::{InitialElementsStore, State},
+    positive::{Accumulator, PositiveAccumulator},
+    setup::{PreparedPublicKey, PreparedSetupParams, SecretKey},
+    universal::UniversalAccumulator,
+    update_proof::UpdateProof,
+    witness::{MembershipWitness, NonMembershipWitness},
+};
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{marker::PhantomData, rand::RngCore, vec::Vec};
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Published by the accumulator manager for each update
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct UpdateRecord<E: Pairing> {
+    pub epoch: u64,
+    /// Accumulated value after the update
+    #[serde_as(as = "ArkObjectBytes")]
+    pub value: E::G1Affine,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub additions: Vec<E::ScalarField>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub removals: Vec<E::ScalarField>,
+    pub omega: Omega<E::G1Affine>,
+    /// Proof that `value` is the previous accumulated value with `additions` and `removals` applied
+    pub proof: UpdateProof<E>,
+}
+
+/// Storage of the update records. Records are appended in increasing order of epochs without gaps starting from 1.
+pub trait UpdateLog<E: Pairing> {
+    /// Append record of the next epoch
+    fn append(&mut self, record: UpdateRecord<E>) -> Result<(), VBAccumulatorError>;
+
+    /// Get the record of the given epoch. Returns [`VBAccumulatorError::UnknownEpoch`] if there is no such record.
+    fn get(&self, epoch: u64) -> Result<UpdateRecord<E>, VBAccumulatorError>;
+
+    /// Epoch of the last record or 0 if there are no records
+    fn latest_epoch(&self) -> u64;
+}
+
+/// In-memory log where the record of epoch `i` is at index `i-1`
+impl<E: Pairing> UpdateLog<E> for Vec<UpdateRecord<E>> {
+    fn append(&mut self, record: UpdateRecord<E>) -> Result<(), VBAccumulatorError> {
+        self.push(record);
+        Ok(())
+    }
+
+    fn get(&self, epoch: u64) -> Result<UpdateRecord<E>, VBAccumulatorError> {
+        if epoch == 0 {
+            return Err(VBAccumulatorError::UnknownEpoch(epoch));
+        }
+        self.as_slice()
+            .get(epoch as usize - 1)
+            .cloned()
+            .ok_or(VBAccumulatorError::UnknownEpoch(epoch))
+    }
+
+    fn latest_epoch(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+/// Records of consecutive epochs used by a holder to update its witness from epoch `from_epoch` to the latest epoch
+/// in the bundle
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CatchUpBundle<E: Pairing> {
+    /// Epoch of the witnesses that can be updated with this bundle
+    pub from_epoch: u64,
+    /// Records of epochs `from_epoch + 1` to `from_epoch + records.len()`
+    pub records: Vec<UpdateRecord<E>>,
+}
+
+/// Tracks the epoch of the accumulator and records its updates in the log
+#[derive(Clone, Debug)]
+pub struct AccumulatorRegistry<E: Pairing, A: Accumulator<E>, L: UpdateLog<E>> {
+    accumulator: A,
+    epoch: u64,
+    log: L,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: Pairing, A: Accumulator<E>, L: UpdateLog<E>> AccumulatorRegistry<E, A, L> {
+    /// Create a registry for `accumulator`. If `log` has records, `accumulator` must be the one after the last record.
+    pub fn new(accumulator: A, log: L) -> Result<Self, VBAccumulatorError> {
+        let epoch = log.latest_epoch();
+        if epoch != 0 && log.get(epoch)?.value != *accumulator.value() {
+            return Err(VBAccumulatorError::AccumulatorMismatchWithUpdateLog);
+        }
+        Ok(Self {
+            accumulator,
+            epoch,
+            log,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Current epoch, i.e. the number of updates done
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Accumulator at the current epoch
+    pub fn accumulator(&self) -> &A {
+        &self.accumulator
+    }
+
+    pub fn log(&self) -> &L {
+        &self.log
+    }
+
+    /// Record of the update that resulted in the given epoch
+    pub fn record(&self, epoch: u64) -> Result<UpdateRecord<E>, VBAccumulatorError> {
+        self.log.get(epoch)
+    }
+
+    /// Create a bundle of the records needed to update a witness of epoch `from_epoch` to the current epoch
+    pub fn catch_up_bundle(&self, from_epoch: u64) -> Result<CatchUpBundle<E>, VBAccumulatorError> {
+        if from_epoch > self.epoch {
+            return Err(VBAccumulatorError::UnknownEpoch(from_epoch));
+        }
+        let records = (from_epoch + 1..=self.epoch)
+            .map(|e| self.record(e))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CatchUpBundle {
+            from_epoch,
+            records,
+        })
+    }
+
+    /// Log the update which changed the accumulator to `new_accumulator` and `state` by adding `additions` and
+    /// removing `removals`. If the log fails to append the record, the changes to `state` are undone so that the
+    /// registry, the log and the state remain at the current epoch.
+    fn record_update(
+        &mut self,
+        new_accumulator: A,
+        additions: Vec<E::ScalarField>,
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        state: &mut dyn State<E::ScalarField>,
+    ) -> Result<UpdateRecord<E>, VBAccumulatorError> {
+        let omega = Omega::new(&additions, removals, self.accumulator.value(), sk);
+        let proof = UpdateProof::new(&additions, removals, self.accumulator.value(), sk);
+        let record = UpdateRecord {
+            epoch: self.epoch + 1,
+            value: *new_accumulator.value(),
+            additions,
+            removals: removals.to_vec(),
+            omega,
+            proof,
+        };
+        if let Err(e) = self.log.append(record.clone()) {
+            for element in removals {
+                state.add(*element);
+            }
+            for element in &record.additions {
+                state.remove(element);
+            }
+            return Err(e);
+        }
+        self.epoch = record.epoch;
+        self.accumulator = new_accumulator;
+        Ok(record)
+    }
+}
+
+impl<E: Pairing, L: UpdateLog<E>> AccumulatorRegistry<E, PositiveAccumulator<E>, L> {
+    /// Add and remove batches of elements from the accumulator and record the update in a new epoch. If recording
+    /// fails, `state` is restored.
+    pub fn batch_updates(
+        &mut self,
+        additions: Vec<E::ScalarField>,
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        state: &mut dyn State<E::ScalarField>,
+    ) -> Result<UpdateRecord<E>, VBAccumulatorError> {
+        let new_accumulator =
+            self.accumulator
+                .batch_updates(additions.clone(), removals, sk, state)?;
+        self.record_update(new_accumulator, additions, removals, sk, state)
+    }
+}
+
+impl<E: Pairing, L: UpdateLog<E>> AccumulatorRegistry<E, UniversalAccumulator<E>, L> {
+    /// Add and remove batches of elements from the accumulator and record the update in a new epoch. If recording
+    /// fails, `state` is restored.
+    pub fn batch_updates(
+        &mut self,
+        additions: Vec<E::ScalarField>,
+        removals: &[E::ScalarField],
+        sk: &SecretKey<E::ScalarField>,
+        initial_elements_store: &dyn InitialElementsStore<E::ScalarField>,
+        state: &mut dyn State<E::ScalarField>,
+    ) -> Result<UpdateRecord<E>, VBAccumulatorError> {
+        let new_accumulator = self.accumulator.batch_updates(
+            additions.clone(),
+            removals,
+            sk,
+            initial_elements_store,
+            state,
+        )?;
+        self.record_update(new_accumulator, additions, removals, sk, state)
+    }
+}
+
+impl<E: Pairing> CatchUpBundle<E> {
+    /// Epoch of the witnesses updated with this bundle
+    pub fn to_epoch(&self) -> u64 {
+        self.from_epoch + self.records.len() as u64
+    }
+
+    /// Accumulated value at the last epoch of the bundle, or `None` if the bundle has no records
+    pub fn value(&self) -> Option<&E::G1Affine> {
+        self.records.last().map(|r| &r.value)
+    }
+
+    /// Verify the update proofs of all records given the accumulated value at epoch `from_epoch`
+    pub fn verify<R: RngCore>(
+        &self,
+        rng: &mut R,
+        from_value: &E::G1Affine,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.check_epochs()?;
+        let pk = pk.into();
+        let params = params.into();
+        let mut old_value = from_value;
+        for r in &self.records {
+            r.proof.verify(
+                rng,
+                &r.additions,
+                &r.removals,
+                old_value,
+                &r.value,
+                pk.clone(),
+                params.clone(),
+            )?;
+            old_value = &r.value;
+        }
+        Ok(())
+    }
+
+    /// Update a membership witness of epoch `from_epoch` to the last epoch of the bundle
+    pub fn update_membership_witness(
+        &self,
+        witness: &MembershipWitness<E::G1Affine>,
+        member: &E::ScalarField,
+    ) -> Result<MembershipWitness<E::G1Affine>, VBAccumulatorError> {
+        if self.records.is_empty() {
+            return Ok(witness.clone());
+        }
+        self.check_epochs()?;
+        witness.update_using_public_info_after_multiple_batch_updates(self.updates(), member)
+    }
+
+    /// Update a non-membership witness of epoch `from_epoch` to the last epoch of the bundle
+    pub fn update_non_membership_witness(
+        &self,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        non_member: &E::ScalarField,
+    ) -> Result<NonMembershipWitness<E::G1Affine>, VBAccumulatorError> {
+        if self.records.is_empty() {
+            return Ok(witness.clone());
+        }
+        self.check_epochs()?;
+        witness.update_using_public_info_after_multiple_batch_updates(self.updates(), non_member)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn updates(&self) -> Vec<(&[E::ScalarField], &[E::ScalarField], &Omega<E::G1Affine>)> {
+        self.records
+            .iter()
+            .map(|r| (r.additions.as_slice(), r.removals.as_slice(), &r.omega))
+            .collect()
+    }
+
+    fn check_epochs(&self) -> Result<(), VBAccumulatorError> {
+        for (i, r) in self.records.iter().enumerate() {
+            if r.epoch != self.from_epoch + 1 + i as u64 {
+                return Err(VBAccumulatorError::UnknownEpoch(r.epoch));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        positive::tests::setup_positive_accum, test_serialization,
+        universal::tests::setup_universal_accum,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    #[test]
+    fn positive_accumulator_registry() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, mut state) = setup_positive_accum(&mut rng);
+        let sk = &keypair.secret_key;
+
+        let initial_value = *accumulator.value();
+        let mut registry = AccumulatorRegistry::new(accumulator, vec![]).unwrap();
+        assert_eq!(registry.epoch(), 0);
+        let members = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        registry
+            .batch_updates(members.clone(), &[], sk, &mut state)
+            .unwrap();
+        assert_eq!(registry.epoch(), 1);
+
+        // Holders get witnesses at epoch 1
+        let witnesses = members
+            .iter()
+            .map(|m| {
+                registry
+                    .accumulator()
+                    .get_membership_witness(m, sk, &state)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..5 {
+            let additions = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let record = registry
+                .batch_updates(additions, &members[i..i + 1], sk, &mut state)
+                .unwrap();
+            assert_eq!(record.epoch, 2 + i as u64);
+            assert_eq!(record.value, *registry.accumulator().value());
+            test_serialization!(UpdateRecord<Bls12_381>, record);
+        }
+        assert_eq!(registry.epoch(), 6);
+        assert_eq!(registry.log().len(), 6);
+
+        // Each record updates the witness by 1 epoch
+        let mut witness = witnesses[9].clone();
+        for e in 2..=6 {
+            let r = registry.record(e).unwrap();
+            witness = witness
+                .update_using_public_info_after_batch_updates(
+                    &r.additions,
+                    &r.removals,
+                    &r.omega,
+                    &members[9],
+                )
+                .unwrap();
+        }
+
+        // Bundle updates the witness over several epochs at once
+        let bundle = registry.catch_up_bundle(1).unwrap();
+        assert_eq!(bundle.to_epoch(), 6);
+        bundle
+            .verify(
+                &mut rng,
+                &registry.record(1).unwrap().value,
+                keypair.public_key.clone(),
+                params.clone(),
+            )
+            .unwrap();
+        // Whole history can be audited
+        registry
+            .catch_up_bundle(0)
+            .unwrap()
+            .verify(
+                &mut rng,
+                &initial_value,
+                keypair.public_key.clone(),
+                params.clone(),
+            )
+            .unwrap();
+        let mut tampered = bundle.clone();
+        tampered.records[2].additions.pop();
+        assert!(matches!(
+            tampered.verify(
+                &mut rng,
+                &registry.record(1).unwrap().value,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(VBAccumulatorError::InvalidUpdateProof)
+        ));
+        test_serialization!(CatchUpBundle<Bls12_381>, bundle);
+        for (member, witness) in members.iter().zip(witnesses.iter()).skip(5) {
+            let new_wit = bundle.update_membership_witness(witness, member).unwrap();
+            assert!(registry.accumulator().verify_membership(
+                member,
+                &new_wit,
+                &keypair.public_key,
+                &params
+            ));
+            assert!(
+                PositiveAccumulator::<Bls12_381>::verify_membership_given_accumulated(
+                    bundle.value().unwrap(),
+                    member,
+                    &new_wit,
+                    &keypair.public_key,
+                    &params
+                )
+            );
+        }
+        assert_eq!(
+            bundle
+                .update_membership_witness(&witnesses[9], &members[9])
+                .unwrap(),
+            witness
+        );
+
+        // Bundle from a later epoch
+        let bundle = registry.catch_up_bundle(4).unwrap();
+        assert_eq!(bundle.records.len(), 2);
+        let mut bundle_1_4 = registry.catch_up_bundle(1).unwrap();
+        bundle_1_4.records.truncate(3);
+        assert_eq!(bundle_1_4.to_epoch(), 4);
+        let wit_4 = bundle_1_4
+            .update_membership_witness(&witnesses[9], &members[9])
+            .unwrap();
+        assert_eq!(
+            bundle
+                .update_membership_witness(&wit_4, &members[9])
+                .unwrap(),
+            witness
+        );
+        assert!(registry.catch_up_bundle(6).unwrap().records.is_empty());
+        assert!(matches!(
+            registry.catch_up_bundle(7),
+            Err(VBAccumulatorError::UnknownEpoch(7))
+        ));
+
+        // Bundle with missing epochs is rejected
+        let mut bundle = registry.catch_up_bundle(1).unwrap();
+        bundle.records.remove(1);
+        assert!(bundle
+            .update_membership_witness(&witnesses[9], &members[9])
+            .is_err());
+
+        // Failed update doesn't create an epoch
+        assert!(registry
+            .batch_updates(vec![], &members[0..1], sk, &mut state)
+            .is_err());
+        assert_eq!(registry.epoch(), 6);
+
+        // Registry can be restored from the log
+        let accumulator = registry.accumulator().clone();
+        let log = registry.log().clone();
+        let registry = AccumulatorRegistry::new(accumulator, log.clone()).unwrap();
+        assert_eq!(registry.epoch(), 6);
+        let (_, _, initial, _) = setup_positive_accum(&mut rng);
+        assert!(matches!(
+            AccumulatorRegistry::new(initial, log),
+            Err(VBAccumulatorError::AccumulatorMismatchWithUpdateLog)
+        ));
+    }
+
+    /// Log that fails to append more than `max_records` records
+    struct BoundedLog {
+        records: Vec<UpdateRecord<Bls12_381>>,
+        max_records: usize,
+    }
+
+    impl UpdateLog<Bls12_381> for BoundedLog {
+        fn append(&mut self, record: UpdateRecord<Bls12_381>) -> Result<(), VBAccumulatorError> {
+            if self.records.len() == self.max_records {
+                return Err(VBAccumulatorError::StorageError("log is full".into()));
+            }
+            UpdateLog::append(&mut self.records, record)
+        }
+
+        fn get(&self, epoch: u64) -> Result<UpdateRecord<Bls12_381>, VBAccumulatorError> {
+            UpdateLog::get(&self.records, epoch)
+        }
+
+        fn latest_epoch(&self) -> u64 {
+            self.records.latest_epoch()
+        }
+    }
+
+    #[test]
+    fn failed_append_restores_state() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, keypair, accumulator, mut state) = setup_positive_accum(&mut rng);
+        let sk = &keypair.secret_key;
+
+        let log = BoundedLog {
+            records: vec![],
+            max_records: 1,
+        };
+        let mut registry = AccumulatorRegistry::new(accumulator, log).unwrap();
+        let members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        registry
+            .batch_updates(members.clone(), &[], sk, &mut state)
+            .unwrap();
+        let accumulator = registry.accumulator().clone();
+
+        let additions = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        assert!(matches!(
+            registry.batch_updates(additions.clone(), &members[0..2], sk, &mut state),
+            Err(VBAccumulatorError::StorageError(_))
+        ));
+        assert_eq!(registry.epoch(), 1);
+        assert_eq!(*registry.accumulator(), accumulator);
+        assert_eq!(state.size(), 5);
+        for m in &members {
+            assert!(state.has(m));
+        }
+        for a in &additions {
+            assert!(!state.has(a));
+        }
+        assert!(matches!(
+            registry.record(2),
+            Err(VBAccumulatorError::UnknownEpoch(2))
+        ));
+        assert!(matches!(
+            registry.record(0),
+            Err(VBAccumulatorError::UnknownEpoch(0))
+        ));
+    }
+
+    #[test]
+    fn universal_accumulator_registry() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 100);
+        let sk = &keypair.secret_key;
+
+        let mut registry = AccumulatorRegistry::new(accumulator, vec![]).unwrap();
+        let members = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let non_members = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        registry
+            .batch_updates(members.clone(), &[], sk, &initial_elements, &mut state)
+            .unwrap();
+        let epoch = registry.epoch();
+        let mem_wit = registry
+            .accumulator()
+            .get_membership_witness(&members[0], sk, &state)
+            .unwrap();
+        let non_mem_wits = non_members
+            .iter()
+            .map(|n| {
+                registry
+                    .accumulator()
+                    .get_non_membership_witness(n, sk, &state, &params)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for i in 1..4 {
+            let additions = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            registry
+                .batch_updates(
+                    additions,
+                    &members[2 * i - 1..2 * i + 1],
+                    sk,
+                    &initial_elements,
+                    &mut state,
+                )
+                .unwrap();
+        }
+        // Too many additions
+        assert!(registry
+            .batch_updates(
+                (0..100).map(|_| Fr::rand(&mut rng)).collect(),
+                &[],
+                sk,
+                &initial_elements,
+                &mut state,
+            )
+            .is_err());
+        assert_eq!(registry.epoch(), 4);
+
+        let bundle = registry.catch_up_bundle(epoch).unwrap();
+        let new_wit = bundle
+            .update_membership_witness(&mem_wit, &members[0])
+            .unwrap();
+        assert!(registry.accumulator().verify_membership(
+            &members[0],
+            &new_wit,
+            &keypair.public_key,
+            &params
+        ));
+        for (n, w) in non_members.iter().zip(non_mem_wits.iter()) {
+            let new_wit = bundle.update_non_membership_witness(w, n).unwrap();
+            assert!(registry.accumulator().verify_non_membership(
+                n,
+                &new_wit,
+                &keypair.public_key,
+                &params
+            ));
+        }
+    }
+}
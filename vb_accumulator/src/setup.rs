@@ -40,7 +40,12 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 use digest::{Digest, DynDigest};
 use schnorr_pok::{error::SchnorrError, impl_proof_of_knowledge_of_discrete_log};
 
-use dock_crypto_utils::{affine_group_element_from_byte_slices, join, serde_utils::*};
+use dock_crypto_utils::{
+    concat_slices,
+    hashing_utils::{GroupElemHasher, TryAndIncrement},
+    join,
+    serde_utils::*,
+};
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -126,9 +131,21 @@ where
     /// attack but since all this is public knowledge, it is fine.
     /// This is useful if people need to be convinced that the discrete log of group elements wrt each other is not known.
     pub fn new<D: Digest>(label: &[u8]) -> Self {
+        Self::new_with_hasher::<TryAndIncrement<D>>(label)
+    }
+
+    /// Same as `Self::new` but group elements are derived using the given hasher, like
+    /// [`HashToCurve`](dock_crypto_utils::hashing_utils::HashToCurve) to hash to curve as per RFC 9380
+    pub fn new_with_hasher<H>(label: &[u8]) -> Self
+    where
+        H: GroupElemHasher<E::G1Affine> + GroupElemHasher<E::G2Affine>,
+    {
         let (P, P_tilde) = join!(
-            affine_group_element_from_byte_slices!(label, b" : P"),
-            affine_group_element_from_byte_slices!(label, b" : P_tilde")
+            <H as GroupElemHasher<E::G1Affine>>::hash_to_affine(&concat_slices!(label, b" : P")),
+            <H as GroupElemHasher<E::G2Affine>>::hash_to_affine(&concat_slices!(
+                label,
+                b" : P_tilde"
+            ))
         );
 
         Self { P, P_tilde }
@@ -286,6 +303,20 @@ mod tests {
         drop(keypair);
     }
 
+    #[test]
+    fn params_with_hash_to_curve() {
+        use dock_crypto_utils::hashing_utils::HashToCurve;
+
+        let label = "test".as_bytes();
+        let params = SetupParams::<Bls12_381>::new_with_hasher::<HashToCurve<Blake2b512>>(label);
+        assert!(params.is_valid());
+        assert_ne!(params, SetupParams::<Bls12_381>::new::<Blake2b512>(label));
+        assert_eq!(
+            SetupParams::<Bls12_381>::new::<Blake2b512>(label),
+            SetupParams::<Bls12_381>::new_with_hasher::<TryAndIncrement<Blake2b512>>(label)
+        );
+    }
+
     #[test]
     fn setup_serialization() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -0,0 +1,843 @@
+//! Accumulator updates and witness generation when the accumulator secret key `alpha` is shared among several managers
+//! using Shamir secret sharing so that no single manager can add or remove members.
+//!
+//! The secret key is generated by the DKG of threshold BBS+, [`ThresholdKey::start_dkg`] and
+//! [`ThresholdKey::finish_dkg`] with `P_tilde` of the accumulator params as `g2`, so that it's never known to anyone.
+//! [`ThresholdKey::signing_key_share`] is the manager's share of `alpha`, and the public key and public key shares of
+//! [`ThresholdKey`] convert to the accumulator's [`PublicKey`]. The shares can
+//! be refreshed with [`ThresholdKey::start_refresh`] and [`ThresholdKey::finish_refresh`] as well. The accumulator
+//! value `V` and the elements are public.
+//!
+//! Adding an element `y` changes `V` to `V * (y + alpha)` which is linear in `alpha` so each of a threshold number of
+//! managers sends an [`AdditionShare`] `V * lambda_i * alpha_i` where `lambda_i` is its Lagrange coefficient and
+//! `alpha_i` is its share of the secret key. Anyone can combine the shares to get `V * y + \sum_i V * lambda_i * alpha_i`.
+//!
+//! Removing an element `y` changes `V` to `V * 1/(y + alpha)` and the membership witness of `y` is `V * 1/(y + alpha)`
+//! as well. This inversion is done like the computation of `A` in threshold BBS+ signing. Each manager picks a random
+//! `r_i` and the managers compute additive shares of `u = r * (y + alpha)` where `r = \sum_i r_i` using the OT based
+//! multiplication from the threshold BBS+ module. Each manager then sends an [`InversionShare`] with its share `u_i` of
+//! `u` and `R_i = V * r_i` and anyone can combine these to get `(\sum_i R_i) * 1/(\sum_i u_i) = V * 1/(y + alpha)`.
+//! The inversion has 2 phases (not communication rounds) like threshold signing,
+//!     1. [`Phase1`] where the managers generate shares of 0 which are used to mask their shares of `alpha` and `r`.
+//!     2. [`Phase2`] where the masked shares are multiplied with each other manager's.
+//!
+//! Neither phase needs the elements or the accumulator value so these can be run proactively for a batch of inversions
+//! and each inversion share is created later by [`InversionShare::new`]. Since removals must be done one after another
+//! as each needs the accumulator value after the previous removal, a batch of removals needs only 1 run of both phases
+//! but a round of [`InversionShare`]s per removal. Also its assumed that managers have done the base OT with each other
+//! using [`BaseOTPhase`] and stored its output.
+//!
+//! The managers keep track of the members in a [`State`] as before. This module only computes the new accumulator
+//! value which can be used to create the accumulator with `Accumulator::from_accumulated`.
+//!
+//! [`State`]: crate::persistence::State
+
+use crate::{
+    error::VBAccumulatorError,
+    positive::{Accumulator, PositiveAccumulator},
+    setup::{PublicKey, SetupParams},
+    witness::MembershipWitness,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::RngCore,
+    vec::Vec,
+};
+use bbs_plus::{
+    setup::PublicKeyG2,
+    threshold::{
+        cointoss::Commitments,
+        utils::{
+            check_participants_across_phases, compute_A_from_shares, compute_R_and_u,
+            compute_masked_arguments_to_multiply,
+        },
+        zero_sharing,
+    },
+};
+use digest::DynDigest;
+use oblivious_transfer_protocols::ParticipantId;
+use secret_sharing_and_dkg::common::lagrange_basis_at_0;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+
+pub use bbs_plus::threshold::{
+    base_ot_phase::{BaseOTPhase, BaseOTPhaseOutput},
+    keygen::{
+        refresh_public_key_shares, verify_public_key_shares, RefreshAccumulator, Round1Msg,
+        Round1State, Round2State, ThresholdKey,
+    },
+    multiplication_phase::{Message1, Message2, Phase2, Phase2Output},
+};
+
+/// Share of the accumulator value after adding an element, created by one manager.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct AdditionShare<G: AffineRepr> {
+    pub id: ParticipantId,
+    /// `V * lambda_i * alpha_i`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub V_i: G,
+}
+
+/// This is the first phase of the inversion protocol where managers generate their shares of `r` and additive shares
+/// of 0 to mask the inputs to the multiplication.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Phase1<F: PrimeField, const SALT_SIZE: usize> {
+    pub id: ParticipantId,
+    /// Number of inversions being done in a single batch.
+    pub batch_size: u32,
+    /// Share of `r` for each inversion
+    pub r: Vec<F>,
+    /// Protocols to generate shares of 0s.
+    pub zero_sharing_protocol: zero_sharing::Party<F, SALT_SIZE>,
+}
+
+/// The length of vectors `r`, `masked_secret_key_shares`, `masked_rs` should be `batch_size` and each item of the
+/// vector corresponds to 1 inversion
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Phase1Output<F: PrimeField> {
+    pub id: ParticipantId,
+    pub batch_size: u32,
+    pub r: Vec<F>,
+    /// Additive shares of the secret key masked by a random value
+    pub masked_secret_key_shares: Vec<F>,
+    /// Additive shares of `r` masked by a random value
+    pub masked_rs: Vec<F>,
+    pub others: Vec<ParticipantId>,
+}
+
+/// Share of `base * 1/(element + alpha)` created by one manager, where `base` is the accumulator value. Used for
+/// removing `element` from the accumulator and for creating the membership witness of `element`
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct InversionShare<G: AffineRepr> {
+    pub id: ParticipantId,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub u: G::ScalarField,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub R: G,
+}
+
+impl<E: Pairing> From<PublicKeyG2<E>> for PublicKey<E> {
+    fn from(pk: PublicKeyG2<E>) -> Self {
+        Self(pk.0)
+    }
+}
+
+impl<G: AffineRepr> AdditionShare<G> {
+    /// Create the share for adding an element to the accumulator with value `accumulator_value`. `managers` are
+    /// the ids of all managers participating in this addition, including this one.
+    pub fn new(
+        accumulator_value: &G,
+        secret_key_share: &G::ScalarField,
+        id: ParticipantId,
+        managers: &[ParticipantId],
+    ) -> Result<Self, VBAccumulatorError> {
+        if !managers.contains(&id) {
+            return Err(VBAccumulatorError::UnexpectedManager(id));
+        }
+        let lambda = lagrange_basis_at_0::<G::ScalarField>(managers, id);
+        Ok(Self {
+            id,
+            V_i: (*accumulator_value * (lambda * secret_key_share)).into_affine(),
+        })
+    }
+
+    /// Combine the shares of a threshold number of managers into the accumulator value after adding `element`, i.e.
+    /// `V * element + \sum_i V_i = V * (element + alpha)`
+    pub fn aggregate(
+        shares: &[Self],
+        element: &G::ScalarField,
+        accumulator_value: &G,
+        threshold: ParticipantId,
+    ) -> Result<G, VBAccumulatorError> {
+        let mut ids = BTreeSet::new();
+        let mut new_value = *accumulator_value * element;
+        for share in shares {
+            if !ids.insert(share.id) {
+                return Err(VBAccumulatorError::DuplicateShareFrom(share.id));
+            }
+            new_value += share.V_i;
+        }
+        if ids.len() < threshold as usize {
+            return Err(VBAccumulatorError::InsufficientShares(
+                ids.len(),
+                threshold as usize,
+            ));
+        }
+        Ok(new_value.into_affine())
+    }
+}
+
+impl<G: AffineRepr> AdditionShare<G> {
+    /// Verify the share against the public key share `Q_i` of its manager by checking
+    /// `e(V_i, P_tilde) == e(V * lambda_i, Q_i)`. Errors name the manager if the share is incorrect.
+    pub fn verify<E: Pairing<G1Affine = G>>(
+        &self,
+        accumulator_value: &G,
+        managers: &[ParticipantId],
+        public_key_share: &PublicKey<E>,
+        params: &SetupParams<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let lambda = lagrange_basis_at_0::<G::ScalarField>(managers, self.id);
+        if !E::multi_pairing(
+            [self.V_i, (*accumulator_value * -lambda).into_affine()],
+            [params.P_tilde, public_key_share.0],
+        )
+        .is_zero()
+        {
+            return Err(VBAccumulatorError::InvalidShareFrom(self.id));
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
+    /// Start phase 1 for `batch_size` inversions with the managers in `others`.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        batch_size: u32,
+        id: ParticipantId,
+        others: BTreeSet<ParticipantId>,
+        protocol_id: Vec<u8>,
+    ) -> Result<(Self, BTreeMap<ParticipantId, Commitments>), VBAccumulatorError> {
+        if others.contains(&id) {
+            return Err(VBAccumulatorError::UnexpectedManager(id));
+        }
+        let r = (0..batch_size).map(|_| F::rand(rng)).collect();
+        // Each inversion will have its own zero-sharing to mask the shares of the secret key and `r`
+        let (zero_sharing_protocol, comm_zero_share) =
+            zero_sharing::Party::init(rng, id, 2 * batch_size, others, protocol_id);
+        Ok((
+            Self {
+                id,
+                batch_size,
+                r,
+                zero_sharing_protocol,
+            },
+            comm_zero_share,
+        ))
+    }
+
+    pub fn get_comm_shares_and_salts_for_zero_sharing_protocol_with_other(
+        &self,
+        other_id: &ParticipantId,
+    ) -> Result<Vec<(F, [u8; SALT_SIZE])>, VBAccumulatorError> {
+        Ok(self
+            .zero_sharing_protocol
+            .cointoss_protocols
+            .get(other_id)
+            .ok_or(VBAccumulatorError::UnexpectedManager(*other_id))?
+            .own_shares_and_salts
+            .clone())
+    }
+
+    /// Process received commitments for the zero sharing protocol
+    pub fn receive_commitment(
+        &mut self,
+        sender_id: ParticipantId,
+        comm_zero_share: Commitments,
+    ) -> Result<(), VBAccumulatorError> {
+        self.zero_sharing_protocol
+            .receive_commitment(sender_id, comm_zero_share)?;
+        Ok(())
+    }
+
+    /// Process received shares for the zero sharing protocol
+    pub fn receive_shares(
+        &mut self,
+        sender_id: ParticipantId,
+        zero_shares: Vec<(F, [u8; SALT_SIZE])>,
+    ) -> Result<(), VBAccumulatorError> {
+        self.zero_sharing_protocol
+            .receive_shares(sender_id, zero_shares)?;
+        Ok(())
+    }
+
+    /// End phase 1 and return the output of this phase
+    pub fn finish<D: Default + DynDigest + Clone>(
+        self,
+        secret_key_share: &F,
+    ) -> Result<Phase1Output<F>, VBAccumulatorError> {
+        let others = self
+            .zero_sharing_protocol
+            .cointoss_protocols
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        let zero_shares = self.zero_sharing_protocol.compute_zero_shares::<D>()?;
+        let (masked_secret_key_shares, masked_rs) = compute_masked_arguments_to_multiply(
+            secret_key_share,
+            self.r.clone(),
+            zero_shares,
+            self.id,
+            &others,
+        );
+        Ok(Phase1Output {
+            id: self.id,
+            batch_size: self.batch_size,
+            r: self.r,
+            masked_secret_key_shares,
+            masked_rs,
+            others,
+        })
+    }
+
+    pub fn ready_to_finish(&self) -> bool {
+        self.zero_sharing_protocol
+            .has_shares_from_all_who_committed()
+    }
+}
+
+impl<G: AffineRepr> InversionShare<G> {
+    /// Create the share of `base * 1/(element + alpha)`. `index_in_batch` is the index of this inversion in the
+    /// batch and also in the Phase1 and Phase2 outputs. For removing `element`, `base` is the current accumulator value.
+    pub fn new(
+        base: &G,
+        element: &G::ScalarField,
+        index_in_batch: usize,
+        phase1: &Phase1Output<G::ScalarField>,
+        phase2: &Phase2Output<G::ScalarField>,
+    ) -> Result<Self, VBAccumulatorError> {
+        if index_in_batch >= phase1.batch_size as usize {
+            return Err(VBAccumulatorError::IndexOutOfBatch(
+                index_in_batch,
+                phase1.batch_size as usize,
+            ));
+        }
+        check_participants_across_phases(&phase1.others, phase2)?;
+        let (R, u) = compute_R_and_u(
+            base.into_group(),
+            &phase1.r[index_in_batch],
+            element,
+            &phase1.masked_rs[index_in_batch],
+            &phase1.masked_secret_key_shares[index_in_batch],
+            index_in_batch as u32,
+            phase2,
+        );
+        Ok(Self {
+            id: phase1.id,
+            u,
+            R,
+        })
+    }
+
+    /// Combine the shares into `base * 1/(element + alpha)`. Errors name the manager who sent more than 1 share or
+    /// a share with 0. Note that an incorrect share is only detected by checking the result as in
+    /// `Self::aggregate_and_verify`.
+    pub fn aggregate(shares: &[Self], threshold: ParticipantId) -> Result<G, VBAccumulatorError> {
+        if shares.len() < threshold as usize {
+            return Err(VBAccumulatorError::InsufficientShares(
+                shares.len(),
+                threshold as usize,
+            ));
+        }
+        Ok(compute_A_from_shares(
+            shares.iter().map(|s| (s.id, &s.R, &s.u)),
+        )?)
+    }
+
+    /// Same as `Self::aggregate` but also checks that the result `C` is correct, i.e.
+    /// `e(C, P_tilde * element + Q) == e(base, P_tilde)`
+    pub fn aggregate_and_verify<E: Pairing<G1Affine = G, ScalarField = G::ScalarField>>(
+        shares: &[Self],
+        threshold: ParticipantId,
+        base: &G,
+        element: &G::ScalarField,
+        public_key: &PublicKey<E>,
+        params: &SetupParams<E>,
+    ) -> Result<G, VBAccumulatorError> {
+        let C = Self::aggregate(shares, threshold)?;
+        if !PositiveAccumulator::<E>::verify_membership_given_accumulated(
+            base,
+            element,
+            &MembershipWitness(C),
+            public_key,
+            params,
+        ) {
+            return Err(VBAccumulatorError::InvalidInversionShares);
+        }
+        Ok(C)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        persistence::test::InMemoryState, persistence::State,
+        positive::tests::setup_positive_accum, setup::SecretKey, test_serialization,
+    };
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+    use oblivious_transfer_protocols::ot_based_multiplication::{
+        dkls18_mul_2p::MultiplicationOTEParams, dkls19_batch_mul_2p::GadgetVector,
+    };
+
+    const BASE_OT_KEY_SIZE: u16 = 128;
+    const KAPPA: u16 = 256;
+    const STATISTICAL_SECURITY_PARAMETER: u16 = 80;
+
+    fn dkg(
+        rng: &mut StdRng,
+        threshold: ParticipantId,
+        total: ParticipantId,
+        params: &SetupParams<Bls12_381>,
+    ) -> (Fr, Vec<ThresholdKey<Bls12_381>>) {
+        let protocol_id = b"test-accumulator-dkg";
+        let mut round1_states = vec![];
+        let mut round1_msgs = vec![];
+        for i in 1..=total {
+            let (state, msg) = ThresholdKey::<Bls12_381>::start_dkg::<_, Blake2b512>(
+                rng,
+                i,
+                threshold,
+                total,
+                protocol_id,
+                &params.P_tilde,
+            )
+            .unwrap();
+            round1_states.push(state);
+            round1_msgs.push(msg);
+        }
+        let sk = round1_states.iter().map(|s| s.secret).sum::<Fr>();
+
+        for (i, state) in round1_states.iter_mut().enumerate() {
+            for (j, msg) in round1_msgs.iter().enumerate() {
+                if i != j {
+                    state
+                        .add_received_message::<Blake2b512>(
+                            msg.clone(),
+                            protocol_id,
+                            &params.P_tilde,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+
+        let mut round2_states = vec![];
+        let mut all_shares = vec![];
+        for state in round1_states {
+            let (round2, shares) = state.finish().unwrap();
+            round2_states.push(round2);
+            all_shares.push(shares);
+        }
+        for (i, state) in round2_states.iter_mut().enumerate() {
+            for (j, shares) in all_shares.iter().enumerate() {
+                if i != j {
+                    state
+                        .add_received_share(
+                            j as ParticipantId + 1,
+                            shares.0[i].clone(),
+                            &params.P_tilde,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+
+        let keys = round2_states
+            .into_iter()
+            .map(|s| ThresholdKey::finish_dkg(s, &params.P_tilde).unwrap())
+            .collect();
+        (sk, keys)
+    }
+
+    fn base_ot(
+        rng: &mut StdRng,
+        num_base_ot: u16,
+        managers: BTreeSet<ParticipantId>,
+    ) -> BTreeMap<ParticipantId, BaseOTPhaseOutput> {
+        let B = G1Affine::rand(rng);
+        let mut base_ots = BTreeMap::new();
+        let mut sender_pks = BTreeMap::new();
+        for i in managers.iter() {
+            let mut others = managers.clone();
+            others.remove(i);
+            let (base_ot, sender_pk_and_proof) =
+                BaseOTPhase::init::<_, Blake2b512>(rng, *i, others, num_base_ot, &B).unwrap();
+            base_ots.insert(*i, base_ot);
+            sender_pks.insert(*i, sender_pk_and_proof);
+        }
+
+        let mut receiver_pks = BTreeMap::new();
+        for (sender_id, pks) in sender_pks {
+            for (id, pk) in pks {
+                let recv_pk = base_ots
+                    .get_mut(&id)
+                    .unwrap()
+                    .receive_sender_pubkey::<_, Blake2b512, BASE_OT_KEY_SIZE>(
+                        rng, sender_id, pk, &B,
+                    )
+                    .unwrap();
+                receiver_pks.insert((id, sender_id), recv_pk);
+            }
+        }
+        let mut challenges = BTreeMap::new();
+        for ((sender, receiver), pk) in receiver_pks {
+            let chal = base_ots
+                .get_mut(&receiver)
+                .unwrap()
+                .receive_receiver_pubkey::<BASE_OT_KEY_SIZE>(sender, pk)
+                .unwrap();
+            challenges.insert((receiver, sender), chal);
+        }
+        let mut responses = BTreeMap::new();
+        for ((sender, receiver), chal) in challenges {
+            let resp = base_ots
+                .get_mut(&receiver)
+                .unwrap()
+                .receive_challenges(sender, chal)
+                .unwrap();
+            responses.insert((receiver, sender), resp);
+        }
+        let mut hashed_keys = BTreeMap::new();
+        for ((sender, receiver), resp) in responses {
+            let hk = base_ots
+                .get_mut(&receiver)
+                .unwrap()
+                .receive_responses(sender, resp)
+                .unwrap();
+            hashed_keys.insert((receiver, sender), hk);
+        }
+        for ((sender, receiver), hk) in hashed_keys {
+            base_ots
+                .get_mut(&receiver)
+                .unwrap()
+                .receive_hashed_keys(sender, hk)
+                .unwrap()
+        }
+        base_ots
+            .into_iter()
+            .map(|(id, b)| (id, b.finish().unwrap()))
+            .collect()
+    }
+
+    /// Run both phases of the inversion protocol among `managers` and return their outputs
+    fn run_phases(
+        rng: &mut StdRng,
+        batch_size: u32,
+        managers: &BTreeSet<ParticipantId>,
+        keys: &[ThresholdKey<Bls12_381>],
+        base_ot_outputs: &BTreeMap<ParticipantId, BaseOTPhaseOutput>,
+        ote_params: MultiplicationOTEParams<KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+        gadget_vector: &GadgetVector<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+    ) -> (
+        BTreeMap<ParticipantId, Phase1Output<Fr>>,
+        BTreeMap<ParticipantId, Phase2Output<Fr>>,
+    ) {
+        let protocol_id = b"test-inversion".to_vec();
+        let mut phase1s = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for i in managers {
+            let mut others = managers.clone();
+            others.remove(i);
+            let (phase1, comm) =
+                Phase1::<Fr, 256>::init(rng, batch_size, *i, others, protocol_id.clone()).unwrap();
+            phase1s.insert(*i, phase1);
+            commitments.insert(*i, comm);
+        }
+        for (sender, comms) in commitments {
+            for (receiver, comm) in comms {
+                phase1s
+                    .get_mut(&receiver)
+                    .unwrap()
+                    .receive_commitment(sender, comm)
+                    .unwrap();
+            }
+        }
+        for i in managers {
+            for j in managers {
+                if i != j {
+                    let shares = phase1s[j]
+                        .get_comm_shares_and_salts_for_zero_sharing_protocol_with_other(i)
+                        .unwrap();
+                    phase1s
+                        .get_mut(i)
+                        .unwrap()
+                        .receive_shares(*j, shares)
+                        .unwrap();
+                }
+            }
+        }
+        let phase1_outputs = phase1s
+            .into_iter()
+            .map(|(id, p)| {
+                assert!(p.ready_to_finish());
+                let out = p
+                    .finish::<Blake2b512>(keys[id as usize - 1].signing_key_share())
+                    .unwrap();
+                (id, out)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut phase2s = BTreeMap::new();
+        let mut all_msg_1s = vec![];
+        for i in managers {
+            let mut others = managers.clone();
+            others.remove(i);
+            let (phase2, msg_1s) = Phase2::init(
+                rng,
+                *i,
+                phase1_outputs[i].masked_secret_key_shares.clone(),
+                phase1_outputs[i].masked_rs.clone(),
+                base_ot_outputs[i].clone(),
+                others,
+                ote_params,
+                gadget_vector,
+            )
+            .unwrap();
+            phase2s.insert(*i, phase2);
+            all_msg_1s.push((*i, msg_1s));
+        }
+        let mut all_msg_2s = vec![];
+        for (sender_id, msg_1s) in all_msg_1s {
+            for (receiver_id, m) in msg_1s {
+                let m2 = phase2s
+                    .get_mut(&receiver_id)
+                    .unwrap()
+                    .receive_message1::<Blake2b512>(sender_id, m, gadget_vector)
+                    .unwrap();
+                all_msg_2s.push((receiver_id, sender_id, m2));
+            }
+        }
+        for (sender_id, receiver_id, m2) in all_msg_2s {
+            phase2s
+                .get_mut(&receiver_id)
+                .unwrap()
+                .receive_message2::<Blake2b512>(sender_id, m2, gadget_vector)
+                .unwrap();
+        }
+        let phase2_outputs = phase2s
+            .into_iter()
+            .map(|(id, p)| (id, p.finish().unwrap()))
+            .collect();
+        (phase1_outputs, phase2_outputs)
+    }
+
+    #[test]
+    fn threshold_dkg() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let (sk, keys) = dkg(&mut rng, 3, 5, &params);
+        let public_key = PublicKey::new_from_secret_key(&SecretKey(sk), &params);
+        for key in &keys {
+            assert_eq!(PublicKey::from(key.public_key.clone()), public_key);
+            assert_eq!(key.public_key_shares, keys[0].public_key_shares);
+        }
+        test_serialization!(ThresholdKey<Bls12_381>, keys[0]);
+        verify_public_key_shares(&keys[0].public_key, &keys[0].public_key_shares, 3).unwrap();
+        let mut wrong_pk_shares = keys[0].public_key_shares.clone();
+        wrong_pk_shares.insert(
+            1,
+            PublicKeyG2(<Bls12_381 as Pairing>::G2Affine::rand(&mut rng)),
+        );
+        assert!(verify_public_key_shares(&keys[0].public_key, &wrong_pk_shares, 3).is_err());
+    }
+
+    #[test]
+    fn threshold_additions_removals_and_witnesses() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let ote_params = MultiplicationOTEParams::<KAPPA, STATISTICAL_SECURITY_PARAMETER> {};
+        let gadget_vector = GadgetVector::<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>::new::<
+            Blake2b512,
+        >(ote_params, b"test-gadget-vector");
+
+        let threshold = 3;
+        let total = 5;
+        let (params, _, _, _) = setup_positive_accum(&mut rng);
+        let (sk, keys) = dkg(&mut rng, threshold, total, &params);
+        let secret_key = SecretKey(sk);
+        let public_key = PublicKey::from(keys[0].public_key.clone());
+
+        // Only some of the managers are active
+        let managers = [1, 3, 4].into_iter().collect::<BTreeSet<ParticipantId>>();
+        let manager_ids = managers.iter().copied().collect::<Vec<_>>();
+        let base_ot_outputs = base_ot(&mut rng, ote_params.num_base_ot(), managers.clone());
+
+        let mut accumulator = PositiveAccumulator::<Bls12_381>::initialize(&params);
+        // Accumulator with a single manager knowing the secret key to compare against
+        let mut expected_accumulator = accumulator.clone();
+        let mut state = InMemoryState::new();
+        let mut expected_state = InMemoryState::new();
+
+        // Additions
+        let elems = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        for elem in &elems {
+            let shares = manager_ids
+                .iter()
+                .map(|id| {
+                    let share = AdditionShare::new(
+                        accumulator.value(),
+                        keys[*id as usize - 1].signing_key_share(),
+                        *id,
+                        &manager_ids,
+                    )
+                    .unwrap();
+                    share
+                        .verify(
+                            accumulator.value(),
+                            &manager_ids,
+                            &PublicKey::from(keys[0].public_key_shares[id].clone()),
+                            &params,
+                        )
+                        .unwrap();
+                    share
+                })
+                .collect::<Vec<_>>();
+            test_serialization!(AdditionShare<G1Affine>, shares[0]);
+
+            // Not enough shares
+            assert!(
+                AdditionShare::aggregate(&shares[0..2], elem, accumulator.value(), threshold)
+                    .is_err()
+            );
+            // A share from a different manager doesn't verify
+            assert!(matches!(
+                shares[0].verify(
+                    accumulator.value(),
+                    &manager_ids,
+                    &PublicKey::from(keys[1].public_key_shares[&3].clone()),
+                    &params,
+                ),
+                Err(VBAccumulatorError::InvalidShareFrom(1))
+            ));
+
+            let new_value =
+                AdditionShare::aggregate(&shares, elem, accumulator.value(), threshold).unwrap();
+            accumulator = PositiveAccumulator::from_accumulated(new_value);
+            state.add(*elem);
+            expected_accumulator = expected_accumulator
+                .add(*elem, &secret_key, &mut expected_state)
+                .unwrap();
+            assert_eq!(accumulator, expected_accumulator);
+        }
+
+        // Phases 1 and 2 are done once for 2 witnesses and 2 removals
+        let batch_size = 4;
+        let (phase1_outputs, phase2_outputs) = run_phases(
+            &mut rng,
+            batch_size,
+            &managers,
+            &keys,
+            &base_ot_outputs,
+            ote_params,
+            &gadget_vector,
+        );
+
+        // Membership witnesses
+        for (k, elem) in elems[0..2].iter().enumerate() {
+            let shares = managers
+                .iter()
+                .map(|id| {
+                    InversionShare::new(
+                        accumulator.value(),
+                        elem,
+                        k,
+                        &phase1_outputs[id],
+                        &phase2_outputs[id],
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            test_serialization!(InversionShare<G1Affine>, shares[0]);
+            let C = InversionShare::aggregate_and_verify(
+                &shares,
+                threshold,
+                accumulator.value(),
+                elem,
+                &public_key,
+                &params,
+            )
+            .unwrap();
+            let witness = MembershipWitness(C);
+            assert_eq!(
+                witness,
+                expected_accumulator
+                    .get_membership_witness(elem, &secret_key, &expected_state)
+                    .unwrap()
+            );
+            assert!(accumulator.verify_membership(elem, &witness, &public_key, &params));
+
+            // Result for a different element doesn't verify
+            assert!(matches!(
+                InversionShare::aggregate_and_verify(
+                    &shares,
+                    threshold,
+                    accumulator.value(),
+                    &elems[3],
+                    &public_key,
+                    &params,
+                ),
+                Err(VBAccumulatorError::InvalidInversionShares)
+            ));
+        }
+
+        // Removals, each uses the accumulator value after the previous removal
+        for (k, elem) in elems[2..4].iter().enumerate() {
+            let k = k + 2;
+            let shares = managers
+                .iter()
+                .map(|id| {
+                    InversionShare::new(
+                        accumulator.value(),
+                        elem,
+                        k,
+                        &phase1_outputs[id],
+                        &phase2_outputs[id],
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert!(InversionShare::aggregate(&shares[0..2], threshold).is_err());
+            let mut duplicate_shares = shares.clone();
+            duplicate_shares[2] = shares[0].clone();
+            assert!(matches!(
+                InversionShare::aggregate(&duplicate_shares, threshold),
+                Err(VBAccumulatorError::BBSPlusError(_))
+            ));
+
+            let new_value = InversionShare::aggregate_and_verify(
+                &shares,
+                threshold,
+                accumulator.value(),
+                elem,
+                &public_key,
+                &params,
+            )
+            .unwrap();
+            accumulator = PositiveAccumulator::from_accumulated(new_value);
+            state.remove(elem);
+            expected_accumulator = expected_accumulator
+                .remove(elem, &secret_key, &mut expected_state)
+                .unwrap();
+            assert_eq!(accumulator, expected_accumulator);
+        }
+        assert_eq!(state.size(), 2);
+
+        assert!(InversionShare::new(
+            accumulator.value(),
+            &elems[0],
+            batch_size as usize,
+            &phase1_outputs[&1],
+            &phase2_outputs[&1],
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,329 @@
+#![allow(non_snake_case)]
+
+//! Proof that an accumulator was updated by exactly the published additions and removals, verifiable by anyone
+//! with the public key.
+//!
+//! After a batch update with additions `y_1, ..., y_a` and removals `y_{a+1}, ..., y_n`, the new accumulated value is
+//! `V' = V * d_A(-alpha) / d_D(-alpha)` where `d_A` and `d_D` are the polynomials [`Poly_d`] of the additions and
+//! removals. The manager publishes the accumulated values after applying each update one by one,
+//! `V_0 = V, V_1, ..., V_n = V'`, where `V_i = V_{i-1} * (y_i + alpha)` for an addition and
+//! `V_i = V_{i-1} / (y_i + alpha)` for a removal. For an addition, `V_{i-1}` is the membership witness of `y_i` in
+//! `V_i` and for a removal, `V_i` is the membership witness of `y_i` in `V_{i-1}`, so each step is checked like a
+//! membership witness as `e(V_i - V_{i-1} * y_i, P_tilde) = e(V_{i-1}, Q_tilde)` for an addition and
+//! `e(V_{i-1} - V_i * y_i, P_tilde) = e(V_i, Q_tilde)` for a removal. The verifier combines the checks of all steps
+//! with random weights so verification needs only 2 pairings. The proof has `n - 1` group elements as `V_0` and `V_n`
+//! are known.
+//!
+//! The proof does not cover the [`Omega`] of the update but a holder who updates its witness using a wrong `Omega`
+//! will notice that the new witness does not verify with the new accumulated value.
+//!
+//! [`Poly_d`]: crate::batch_utils::Poly_d
+//! [`Omega`]: crate::batch_utils::Omega
+
+use crate::{
+    error::VBAccumulatorError,
+    setup::{PreparedPublicKey, PreparedSetupParams, SecretKey},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{rand::RngCore, vec, vec::Vec, UniformRand};
+use dock_crypto_utils::{msm::multiply_field_elems_with_same_group_elem, serde_utils::*};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Proof that a batch update changed the accumulated value from `V` to `V'`
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct UpdateProof<E: Pairing> {
+    /// Accumulated values after applying the additions and then the removals one by one, except the last one which
+    /// is `V'`
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub intermediate: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> UpdateProof<E> {
+    /// Create the proof for `additions` and `removals` applied to the accumulated value `old_accumulator`
+    pub fn new(
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        old_accumulator: &E::G1Affine,
+        sk: &SecretKey<E::ScalarField>,
+    ) -> Self {
+        let n = additions.len() + removals.len();
+        if n < 2 {
+            return Self {
+                intermediate: vec![],
+            };
+        }
+        let mut removal_factors = removals.iter().map(|y| *y + sk.0).collect::<Vec<_>>();
+        batch_inversion(&mut removal_factors);
+        // Product of the first `i` factors for each `i` from 1 to n-1
+        let mut product = E::ScalarField::one();
+        let scalars = additions
+            .iter()
+            .map(|y| *y + sk.0)
+            .chain(removal_factors)
+            .take(n - 1)
+            .map(|f| {
+                product *= f;
+                product
+            })
+            .collect::<Vec<_>>();
+        Self {
+            intermediate: E::G1::normalize_batch(&multiply_field_elems_with_same_group_elem(
+                old_accumulator.into_group(),
+                &scalars,
+            )),
+        }
+    }
+
+    /// Verify that applying `additions` and `removals` to the accumulated value `old_accumulator` results in
+    /// `new_accumulator`
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<R: RngCore>(
+        &self,
+        rng: &mut R,
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        old_accumulator: &E::G1Affine,
+        new_accumulator: &E::G1Affine,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<(), VBAccumulatorError> {
+        let n = additions.len() + removals.len();
+        if n == 0 {
+            return if self.intermediate.is_empty() && old_accumulator == new_accumulator {
+                Ok(())
+            } else {
+                Err(VBAccumulatorError::InvalidUpdateProof)
+            };
+        }
+        if self.intermediate.len() != n - 1 {
+            return Err(VBAccumulatorError::InvalidUpdateProof);
+        }
+        // V_0, V_1, ..., V_n
+        let mut values = Vec::with_capacity(n + 1);
+        values.push(*old_accumulator);
+        values.extend_from_slice(&self.intermediate);
+        values.push(*new_accumulator);
+
+        // Each step's check is `e(L, P_tilde) = e(R, Q_tilde)` and the sum of `L`s and `R`s weighted by random `r`
+        // is checked
+        let mut l_bases = Vec::with_capacity(2 * n);
+        let mut l_scalars = Vec::with_capacity(2 * n);
+        let mut r_scalars = Vec::with_capacity(n);
+        for (i, y) in additions.iter().enumerate() {
+            let r = E::ScalarField::rand(rng);
+            // L = V_i - V_{i-1} * y_i, R = V_{i-1}
+            l_bases.push(values[i + 1]);
+            l_bases.push(values[i]);
+            l_scalars.push(r);
+            l_scalars.push(-(r * y));
+            r_scalars.push(r);
+        }
+        for (j, y) in removals.iter().enumerate() {
+            let i = additions.len() + j;
+            let r = E::ScalarField::rand(rng);
+            // L = V_{i-1} - V_i * y_i, R = V_i
+            l_bases.push(values[i]);
+            l_bases.push(values[i + 1]);
+            l_scalars.push(r);
+            l_scalars.push(-(r * y));
+            r_scalars.push(r);
+        }
+        let r_bases = values[..additions.len()]
+            .iter()
+            .chain(values[additions.len() + 1..].iter())
+            .copied()
+            .collect::<Vec<_>>();
+        let l = E::G1::msm_unchecked(&l_bases, &l_scalars);
+        let r = E::G1::msm_unchecked(&r_bases, &r_scalars);
+        let params = params.into();
+        let pk = pk.into();
+        if !E::multi_pairing(
+            [E::G1Prepared::from(l), E::G1Prepared::from(-r)],
+            [params.P_tilde, pk.0],
+        )
+        .is_zero()
+        {
+            return Err(VBAccumulatorError::InvalidUpdateProof);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        positive::{tests::setup_positive_accum, Accumulator},
+        test_serialization,
+        universal::tests::setup_universal_accum,
+    };
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn update_proofs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let sk = &keypair.secret_key;
+        let pk = &keypair.public_key;
+
+        let mut members = vec![];
+        for (a, d) in [
+            (1, 0),
+            (5, 0),
+            (3, 2),
+            (0, 1),
+            (0, 3),
+            (4, 3),
+            (0, 0),
+            (10, 3),
+        ] {
+            let additions = (0..a).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let removals = members.drain(0..d).collect::<Vec<_>>();
+            let new_accumulator = accumulator
+                .batch_updates(additions.clone(), &removals, sk, &mut state)
+                .unwrap();
+            let proof =
+                UpdateProof::<Bls12_381>::new(&additions, &removals, accumulator.value(), sk);
+            assert_eq!(proof.intermediate.len(), (a + d).max(1) - 1);
+            test_serialization!(UpdateProof<Bls12_381>, proof);
+            proof
+                .verify(
+                    &mut rng,
+                    &additions,
+                    &removals,
+                    accumulator.value(),
+                    new_accumulator.value(),
+                    pk.clone(),
+                    params.clone(),
+                )
+                .unwrap();
+
+            // Proof fails with any other update
+            let other = Fr::rand(&mut rng);
+            let other_value = G1Affine::rand(&mut rng);
+            assert!(proof
+                .verify(
+                    &mut rng,
+                    &additions,
+                    &removals,
+                    accumulator.value(),
+                    &other_value,
+                    pk.clone(),
+                    params.clone(),
+                )
+                .is_err());
+            if a > 0 {
+                let mut wrong = additions.clone();
+                wrong[0] = other;
+                assert!(proof
+                    .verify(
+                        &mut rng,
+                        &wrong,
+                        &removals,
+                        accumulator.value(),
+                        new_accumulator.value(),
+                        pk.clone(),
+                        params.clone(),
+                    )
+                    .is_err());
+            }
+            if d > 0 {
+                let mut wrong = removals.clone();
+                wrong[d - 1] = other;
+                assert!(proof
+                    .verify(
+                        &mut rng,
+                        &additions,
+                        &wrong,
+                        accumulator.value(),
+                        new_accumulator.value(),
+                        pk.clone(),
+                        params.clone(),
+                    )
+                    .is_err());
+            }
+            if a + d > 1 {
+                let mut wrong = proof.clone();
+                wrong.intermediate[0] = other_value;
+                assert!(wrong
+                    .verify(
+                        &mut rng,
+                        &additions,
+                        &removals,
+                        accumulator.value(),
+                        new_accumulator.value(),
+                        pk.clone(),
+                        params.clone(),
+                    )
+                    .is_err());
+                // Dropping an update is detected
+                let (dropped_additions, dropped_removals) = if d > 0 {
+                    (&additions[..], &removals[..d - 1])
+                } else {
+                    (&additions[1..], &removals[..])
+                };
+                assert!(proof
+                    .verify(
+                        &mut rng,
+                        dropped_additions,
+                        dropped_removals,
+                        accumulator.value(),
+                        new_accumulator.value(),
+                        pk.clone(),
+                        params.clone(),
+                    )
+                    .is_err());
+            }
+
+            members.extend(additions);
+            accumulator = new_accumulator;
+        }
+
+        // Universal accumulator
+        let (params, keypair, accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 100);
+        let additions = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator_1 = accumulator
+            .add_batch(
+                additions.clone(),
+                &keypair.secret_key,
+                &initial_elements,
+                &mut state,
+            )
+            .unwrap();
+        let more = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator_2 = accumulator_1
+            .batch_updates(
+                more.clone(),
+                &additions[2..8],
+                &keypair.secret_key,
+                &initial_elements,
+                &mut state,
+            )
+            .unwrap();
+        UpdateProof::<Bls12_381>::new(
+            &more,
+            &additions[2..8],
+            accumulator_1.value(),
+            &keypair.secret_key,
+        )
+        .verify(
+            &mut rng,
+            &more,
+            &additions[2..8],
+            accumulator_1.value(),
+            accumulator_2.value(),
+            keypair.public_key.clone(),
+            params.clone(),
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,417 @@
+//! Bulk refresh of membership witnesses by the accumulator manager.
+//!
+//! After a large update, the manager can re-issue the witnesses of all members rather than each member updating its
+//! witness using [`Omega`]. Since the manager knows the secret key `alpha`, the updated witness of member `y` is
+//! `V * 1/(y + alpha)` for the new accumulated value `V`. This doesn't depend on the old witness or the size of the
+//! update unlike [`MembershipWitness::update_using_secret_key_after_batch_updates`] which evaluates the update
+//! polynomials for each member.
+//!
+//! [`WitnessRefresher`] computes the new accumulated value and a window table for it only once and then takes members
+//! from any iterator, like the one returned by [`IterableState::members`] for the state of a positive or universal
+//! accumulator, in chunks of fixed size so that all members never need to be in memory. Each chunk needs a single
+//! batch inversion and a fixed-base multi-scalar multiplication, both of which run in parallel with feature
+//! `parallel`, and is emitted as a [`WitnessRefreshBatch`] that can be serialized and sent to the members. Chunks are
+//! processed one after another since the work within a chunk already uses all threads, processing several chunks at
+//! once would only need more memory. A larger `chunk_size` gives the threads more work per chunk.
+//!
+//! ```
+//! use vb_accumulator::witness_refresh::WitnessRefresher;
+//!
+//! // `accumulator` is the accumulator before the updates and `state` has its members after the updates
+//! let refresher = WitnessRefresher::new_after_batch_updates(&accumulator, &additions, &removals, &keypair.secret_key, 1000).unwrap();
+//! for batch in refresher.refresh_members_of_state(&state) {
+//!     // Store or send `batch` to the members
+//! }
+//! ```
+//!
+//! [`Omega`]: crate::batch_utils::Omega
+//! [`MembershipWitness::update_using_secret_key_after_batch_updates`]: crate::witness::MembershipWitness::update_using_secret_key_after_batch_updates
+//! [`IterableState::members`]: crate::persistence::IterableState::members
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::batch_inversion;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, cfg_iter_mut, fmt::Debug, iter::Copied, vec::Vec};
+use dock_crypto_utils::{msm::WindowTable, serde_utils::*};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroize;
+
+use crate::{
+    error::VBAccumulatorError, persistence::IterableState, positive::Accumulator, setup::SecretKey,
+    witness::MembershipWitness,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Membership witnesses of a chunk of members, created by [`WitnessRefresher`]
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct WitnessRefreshBatch<G: AffineRepr> {
+    /// Accumulated value the witnesses are valid for
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator: G,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub members: Vec<G::ScalarField>,
+    /// Witness of each member, in the same order as `members`
+    pub witnesses: Vec<MembershipWitness<G>>,
+}
+
+/// Creates membership witnesses for all members of an accumulator in chunks. Intended to be used by the manager
+pub struct WitnessRefresher<'a, E: Pairing> {
+    accumulator: E::G1Affine,
+    sk: &'a SecretKey<E::ScalarField>,
+    table: WindowTable<E::G1>,
+    chunk_size: usize,
+}
+
+/// Iterator over the batches of witnesses, created by [`WitnessRefresher::refresh`]
+pub struct WitnessRefreshIter<'b, 'a, E: Pairing, I: Iterator<Item = E::ScalarField>> {
+    refresher: &'b WitnessRefresher<'a, E>,
+    members: I,
+}
+
+impl<G: AffineRepr> WitnessRefreshBatch<G> {
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Iterate over the members and their witnesses
+    pub fn iter(&self) -> impl Iterator<Item = (&G::ScalarField, &MembershipWitness<G>)> + '_ {
+        self.members.iter().zip(self.witnesses.iter())
+    }
+}
+
+impl<'a, E: Pairing> WitnessRefresher<'a, E> {
+    /// Create a refresher for the witnesses of the members of `accumulator`. Each batch of witnesses
+    /// will have at most `chunk_size` members.
+    pub fn new(
+        accumulator: &impl Accumulator<E>,
+        sk: &'a SecretKey<E::ScalarField>,
+        chunk_size: usize,
+    ) -> Result<Self, VBAccumulatorError> {
+        Self::new_for_accumulated(*accumulator.value(), sk, chunk_size)
+    }
+
+    /// Create a refresher for the witnesses of the members of the accumulator that results from applying
+    /// `additions` and `removals` to `old_accumulator`. The new accumulated value is computed only once for all
+    /// members.
+    pub fn new_after_batch_updates(
+        old_accumulator: &impl Accumulator<E>,
+        additions: &[E::ScalarField],
+        removals: &[E::ScalarField],
+        sk: &'a SecretKey<E::ScalarField>,
+        chunk_size: usize,
+    ) -> Result<Self, VBAccumulatorError> {
+        let (_, new_accumulated) =
+            old_accumulator._compute_new_post_batch_updates(additions, removals, sk);
+        Self::new_for_accumulated(new_accumulated, sk, chunk_size)
+    }
+
+    /// Create a refresher given the accumulated value
+    pub fn new_for_accumulated(
+        accumulated: E::G1Affine,
+        sk: &'a SecretKey<E::ScalarField>,
+        chunk_size: usize,
+    ) -> Result<Self, VBAccumulatorError> {
+        if chunk_size == 0 {
+            return Err(VBAccumulatorError::CannotBeZero);
+        }
+        // The table is created once and used for all chunks
+        let table = WindowTable::new(chunk_size, accumulated.into_group());
+        Ok(Self {
+            accumulator: accumulated,
+            sk,
+            table,
+            chunk_size,
+        })
+    }
+
+    /// The accumulated value the witnesses are created for
+    pub fn accumulator(&self) -> &E::G1Affine {
+        &self.accumulator
+    }
+
+    /// Create witnesses for the given members. Doesn't check that they are members so the caller
+    /// should only pass members of the accumulator.
+    pub fn refresh_chunk(&self, members: Vec<E::ScalarField>) -> WitnessRefreshBatch<E::G1Affine> {
+        // 1/(member + sk) * V for each member
+        let mut y_plus_alpha_inv: Vec<E::ScalarField> =
+            cfg_iter!(members).map(|y| *y + self.sk.0).collect();
+        batch_inversion(&mut y_plus_alpha_inv);
+        let wits = self.table.multiply_many(&y_plus_alpha_inv);
+        cfg_iter_mut!(y_plus_alpha_inv).for_each(|y| y.zeroize());
+        WitnessRefreshBatch {
+            accumulator: self.accumulator,
+            members,
+            witnesses: MembershipWitness::projective_points_to_membership_witnesses(wits),
+        }
+    }
+
+    /// Create witnesses for the members returned by `members`, `chunk_size` members at a time. Doesn't check
+    /// that they are members so the caller should only pass members of the accumulator.
+    pub fn refresh<I: IntoIterator<Item = E::ScalarField>>(
+        &self,
+        members: I,
+    ) -> WitnessRefreshIter<'_, 'a, E, I::IntoIter> {
+        WitnessRefreshIter {
+            refresher: self,
+            members: members.into_iter(),
+        }
+    }
+
+    /// Create witnesses for all members in `state`, `chunk_size` members at a time. `state` can be of a positive
+    /// or a universal accumulator.
+    pub fn refresh_members_of_state<'s, S: IterableState<'s, E::ScalarField>>(
+        &self,
+        state: &'s S,
+    ) -> WitnessRefreshIter<'_, 'a, E, Copied<S::MemberIterator>> {
+        self.refresh(state.members().copied())
+    }
+}
+
+impl<'b, 'a, E: Pairing, I: Iterator<Item = E::ScalarField>> Iterator
+    for WitnessRefreshIter<'b, 'a, E, I>
+{
+    type Item = WitnessRefreshBatch<E::G1Affine>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self
+            .members
+            .by_ref()
+            .take(self.refresher.chunk_size)
+            .collect::<Vec<_>>();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(self.refresher.refresh_chunk(chunk))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        persistence::State,
+        positive::{tests::setup_positive_accum, PositiveAccumulator},
+        test_serialization,
+        universal::tests::setup_universal_accum,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use std::time::Instant;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type G1 = <Bls12_381 as Pairing>::G1Affine;
+
+    #[test]
+    fn refresh_witnesses() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, mut state) = setup_positive_accum(&mut rng);
+
+        let members = (0..105).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator = accumulator
+            .add_batch(members.clone(), &keypair.secret_key, &mut state)
+            .unwrap();
+
+        assert!(WitnessRefresher::new(&accumulator, &keypair.secret_key, 0).is_err());
+
+        let refresher = WitnessRefresher::new(&accumulator, &keypair.secret_key, 20).unwrap();
+        assert_eq!(refresher.accumulator(), accumulator.value());
+        let start = Instant::now();
+        let batches = refresher
+            .refresh_members_of_state(&state)
+            .collect::<Vec<_>>();
+        println!(
+            "Time to refresh {} witnesses in chunks of 20: {:?}",
+            members.len(),
+            start.elapsed()
+        );
+
+        assert_eq!(batches.len(), 6);
+        assert_eq!(batches[5].len(), 5);
+        let mut count = 0;
+        for batch in batches.iter() {
+            assert_eq!(batch.accumulator, *accumulator.value());
+            assert_eq!(batch.members.len(), batch.witnesses.len());
+            for (member, witness) in batch.iter() {
+                assert!(state.has(member));
+                assert!(accumulator.verify_membership(
+                    member,
+                    witness,
+                    &keypair.public_key,
+                    &params
+                ));
+                count += 1;
+            }
+        }
+        assert_eq!(count, members.len());
+        test_serialization!(WitnessRefreshBatch<G1>, batches[0]);
+
+        // Same as computing witnesses in one call
+        let batches = refresher.refresh(members.clone()).collect::<Vec<_>>();
+        let expected =
+            accumulator.compute_membership_witness_for_batch(&members, &keypair.secret_key);
+        assert_eq!(
+            batches
+                .into_iter()
+                .flat_map(|b| b.witnesses)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(refresher.refresh(vec![]).count(), 0);
+    }
+
+    #[test]
+    fn refresh_witnesses_after_batch_updates() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, 200);
+
+        let members = (0..50).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &keypair.secret_key,
+                &initial_elements,
+                &mut state,
+            )
+            .unwrap();
+        let old_witnesses = accumulator
+            .get_membership_witnesses_for_batch(&members, &keypair.secret_key, &state)
+            .unwrap();
+
+        let additions = (0..30).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let removals = members[0..20].to_vec();
+        let refresher = WitnessRefresher::new_after_batch_updates(
+            &accumulator,
+            &additions,
+            &removals,
+            &keypair.secret_key,
+            16,
+        )
+        .unwrap();
+        let new_accumulator = accumulator
+            .batch_updates(
+                additions.clone(),
+                &removals,
+                &keypair.secret_key,
+                &initial_elements,
+                &mut state,
+            )
+            .unwrap();
+        assert_eq!(refresher.accumulator(), new_accumulator.value());
+
+        // Witnesses of the remaining members match the ones updated with the secret key
+        let remaining = members[20..].to_vec();
+        let updated = MembershipWitness::update_using_secret_key_after_batch_updates(
+            &additions,
+            &removals,
+            &remaining,
+            &old_witnesses[20..],
+            accumulator.value(),
+            &keypair.secret_key,
+        )
+        .unwrap();
+        let refreshed = refresher
+            .refresh(remaining.clone())
+            .flat_map(|b| b.witnesses)
+            .collect::<Vec<_>>();
+        assert_eq!(refreshed, updated);
+
+        // All members of the new accumulator
+        let mut count = 0;
+        for batch in refresher.refresh_members_of_state(&state) {
+            for (member, witness) in batch.iter() {
+                assert!(new_accumulator.verify_membership(
+                    member,
+                    witness,
+                    &keypair.public_key,
+                    &params
+                ));
+                count += 1;
+            }
+        }
+        assert_eq!(count, 60);
+
+        // Works for a positive accumulator built from the same value
+        let positive = PositiveAccumulator::<Bls12_381>::from_value(*new_accumulator.value());
+        let refresher = WitnessRefresher::new(&positive, &keypair.secret_key, 16).unwrap();
+        assert_eq!(
+            refresher
+                .refresh(remaining.clone())
+                .flat_map(|b| b.witnesses)
+                .collect::<Vec<_>>(),
+            updated
+        );
+    }
+
+    /// State of a positive accumulator which can list its members
+    struct MemberList(Vec<Fr>);
+
+    impl State<Fr> for MemberList {
+        fn add(&mut self, element: Fr) {
+            self.0.push(element)
+        }
+
+        fn remove(&mut self, element: &Fr) {
+            self.0.retain(|e| e != element)
+        }
+
+        fn has(&self, element: &Fr) -> bool {
+            self.0.contains(element)
+        }
+
+        fn size(&self) -> u64 {
+            self.0.len() as u64
+        }
+    }
+
+    impl<'a> IterableState<'a, Fr> for MemberList {
+        type MemberIterator = core::slice::Iter<'a, Fr>;
+
+        fn members(&'a self) -> Self::MemberIterator {
+            self.0.iter()
+        }
+    }
+
+    #[test]
+    fn refresh_members_of_positive_accumulator_state() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (params, keypair, accumulator, _) = setup_positive_accum(&mut rng);
+        let mut state = MemberList(vec![]);
+
+        let members = (0..25).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let accumulator = accumulator
+            .add_batch(members.clone(), &keypair.secret_key, &mut state)
+            .unwrap();
+        let accumulator = accumulator
+            .remove(&members[3], &keypair.secret_key, &mut state)
+            .unwrap();
+
+        let refresher = WitnessRefresher::new(&accumulator, &keypair.secret_key, 10).unwrap();
+        let batches = refresher
+            .refresh_members_of_state(&state)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+            vec![10, 10, 4]
+        );
+        for (member, witness) in batches.iter().flat_map(|b| b.iter()) {
+            assert_ne!(*member, members[3]);
+            assert!(accumulator.verify_membership(member, witness, &keypair.public_key, &params));
+        }
+    }
+}